@@ -154,6 +154,7 @@ pub async fn get_deposits(
     status: models::Status,
     next_token: Option<&str>,
     page_size: Option<i32>,
+    min_height: Option<u64>,
 ) -> Result<models::GetDepositsResponse, Error<GetDepositsError>> {
     let local_var_configuration = configuration;
 
@@ -172,6 +173,10 @@ pub async fn get_deposits(
         local_var_req_builder =
             local_var_req_builder.query(&[("pageSize", &local_var_str.to_string())]);
     }
+    if let Some(ref local_var_str) = min_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("minHeight", &local_var_str.to_string())]);
+    }
     if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
         local_var_req_builder =
             local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
@@ -144,7 +144,8 @@ pub async fn get_deposits_for_transaction(
     params(
         ("status" = Status, Query, description = "the status to search by when getting all deposits."),
         ("nextToken" = Option<String>, Query, description = "the next token value from the previous return of this api call."),
-        ("pageSize" = Option<i32>, Query, description = "the maximum number of items in the response list.")
+        ("pageSize" = Option<i32>, Query, description = "the maximum number of items in the response list."),
+        ("minHeight" = Option<u64>, Query, description = "only return deposits last updated at or after this stacks block height.")
     ),
     tag = "deposit",
     responses(
@@ -167,13 +168,27 @@ pub async fn get_deposits(
         query: GetDepositsQuery,
     ) -> Result<impl warp::reply::Reply, Error> {
         // Deserialize next token into the exclusive start key if present/
-        let (entries, next_token) = accessors::get_deposit_entries(
-            &context,
-            &query.status,
-            query.next_token,
-            query.page_size,
-        )
-        .await?;
+        let (entries, next_token) = match query.min_height {
+            Some(min_height) => {
+                accessors::get_deposit_entries_modified_from_height(
+                    &context,
+                    &query.status,
+                    min_height,
+                    query.next_token,
+                    query.page_size,
+                )
+                .await?
+            }
+            None => {
+                accessors::get_deposit_entries(
+                    &context,
+                    &query.status,
+                    query.next_token,
+                    query.page_size,
+                )
+                .await?
+            }
+        };
         // Convert data into resource types.
         let deposits: Vec<DepositInfo> = entries.into_iter().map(|entry| entry.into()).collect();
         // Create response.
@@ -29,6 +29,9 @@ pub struct GetDepositsQuery {
     /// Maximum number of results to show.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_size: Option<i32>,
+    /// Only show deposits last updated at or after this Stacks block height.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_height: Option<u64>,
 }
 
 /// Request structure for create deposit request.
@@ -77,6 +77,26 @@ pub async fn get_deposit_entries(
     .await
 }
 
+/// Get deposit entries with the given status that were modified at or
+/// after the given height.
+pub async fn get_deposit_entries_modified_from_height(
+    context: &EmilyContext,
+    status: &Status,
+    minimum_height: u64,
+    maybe_next_token: Option<String>,
+    maybe_page_size: Option<i32>,
+) -> Result<(Vec<DepositInfoEntry>, Option<String>), Error> {
+    query_with_partition_and_sort_key::<DepositTableSecondaryIndex>(
+        context,
+        status,
+        &minimum_height,
+        ">=",
+        maybe_next_token,
+        maybe_page_size,
+    )
+    .await
+}
+
 /// Hacky exhasutive list of all statuses that we will iterate over in order to
 /// get every deposit present.
 const ALL_STATUSES: &[Status] = &[
@@ -797,6 +817,26 @@ async fn query_with_partition_key<T: TableIndexTrait>(
     .await
 }
 
+async fn query_with_partition_and_sort_key<T: TableIndexTrait>(
+    context: &EmilyContext,
+    parition_key: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey,
+    sort_key: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::SortKey,
+    sort_key_operator: &str,
+    maybe_next_token: Option<String>,
+    maybe_page_size: Option<i32>,
+) -> Result<(Vec<<T as TableIndexTrait>::Entry>, Option<String>), Error> {
+    <T as TableIndexTrait>::query_with_partition_and_sort_key(
+        &context.dynamodb_client,
+        &context.settings,
+        parition_key,
+        sort_key,
+        sort_key_operator,
+        maybe_next_token,
+        maybe_page_size,
+    )
+    .await
+}
+
 async fn query_all_with_partition_and_sort_key<T: TableIndexTrait>(
     context: &EmilyContext,
     parition_key: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey,
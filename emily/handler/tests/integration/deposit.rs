@@ -329,6 +329,7 @@ async fn get_deposits() {
             status,
             next_token.as_ref().and_then(|o| o.as_deref()),
             Some(chunksize),
+            None,
         )
         .await
         .expect("Received an error after making a valid get deposits api call.");
@@ -360,6 +361,48 @@ async fn get_deposits() {
     assert_eq!(expected_deposit_infos, gotten_deposit_infos);
 }
 
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn get_deposits_modified_from_height() {
+    let configuration = clean_setup().await;
+
+    // Arrange.
+    // --------
+    let DepositTxnData {
+        reclaim_script, deposit_script, ..
+    } = DepositTxnData::new(DEPOSIT_LOCK_TIME, DEPOSIT_MAX_FEE, DEPOSIT_AMOUNT_SATS);
+
+    let create_requests: Vec<CreateDepositRequestBody> = (0..3)
+        .map(|bitcoin_tx_output_index| CreateDepositRequestBody {
+            bitcoin_tx_output_index,
+            bitcoin_txid: "bitcoin_txid".into(),
+            deposit_script: deposit_script.clone(),
+            reclaim_script: reclaim_script.clone(),
+        })
+        .collect();
+
+    // Act.
+    // ----
+    batch_create_deposits(&configuration, create_requests).await;
+
+    let status = emily_client::models::Status::Pending;
+    let from_creation_height =
+        apis::deposit_api::get_deposits(&configuration, status, None, None, Some(BLOCK_HEIGHT))
+            .await
+            .expect("Received an error after making a valid get deposits api call.");
+    let from_later_height =
+        apis::deposit_api::get_deposits(&configuration, status, None, None, Some(BLOCK_HEIGHT + 1))
+            .await
+            .expect("Received an error after making a valid get deposits api call.");
+
+    // Assert.
+    // -------
+    // All deposits were created at `BLOCK_HEIGHT`, so none of them have
+    // been modified since.
+    assert_eq!(from_creation_height.deposits.len(), 3);
+    assert!(from_later_height.deposits.is_empty());
+}
+
 #[cfg_attr(not(feature = "integration-tests"), ignore)]
 #[tokio::test]
 async fn update_deposits() {
@@ -353,15 +353,15 @@ impl DepositScriptInputs {
 /// standard locktime in bitcoin-core. We do not verify whether the
 /// user-supplied script is correct and standard.
 ///
-/// Currently, we only accept locktimes denominated in Bitcoin blocks. This
-/// implies that bit (1 << 22) in the locktime, must be zero.
+/// Locktimes may be denominated in Bitcoin blocks or in units of 512
+/// seconds, where the latter is measured against the median-time-past of
+/// bitcoin blocks. A user sets bit (1 << 22) in the locktime to indicate
+/// that the locktime value is time based, as described in BIP-68.
 ///
 /// Note that locktimes used as `OP_CSV` inputs in the reclaim script only
 /// use the 16 least significant bits for the value of the locktime. All
-/// other bits in the 32-bit locktime must be zero or the deposit
-/// transaction will fail validation. When we support time-based locktimes,
-/// a user may set bit (1 << 22) to indicate that the locktime value is
-/// time based, as described in BIP-68.
+/// other bits in the 32-bit locktime, apart from the type flag, must be
+/// zero or the deposit transaction will fail validation.
 ///
 /// <https://github.com/bitcoin/bips/blob/17c04f9fa1ecae173d6864b65717e13dfc1880af/bip-0068.mediawiki#specification>
 /// <https://github.com/bitcoin/bips/blob/812907c2b00b92ee31e2b638622a4fe14a428aee/bip-0112.mediawiki#summary>
@@ -387,14 +387,6 @@ impl ReclaimScriptInputs {
         // <https://github.com/bitcoin/bitcoin/blob/v27.1/src/script/interpreter.cpp#L560-L592>
         let lock_time = LockTime::from_consensus(lock_time).map_err(Error::DisabledLockTime)?;
 
-        // For now, we only accept locktimes denominated in bitcoin block
-        // units.
-        if matches!(lock_time, LockTime::Time(_)) {
-            return Err(Error::UnsupportedLockTimeUnits(
-                lock_time.to_consensus_u32(),
-            ));
-        }
-
         Ok(Self { lock_time, script })
     }
 
@@ -403,6 +395,13 @@ impl ReclaimScriptInputs {
         self.lock_time.to_consensus_u32()
     }
 
+    /// Get the lock time in the reclaim script as a relative lock time.
+    /// This tells whether the lock time is denominated in bitcoin blocks
+    /// or in units of 512 seconds.
+    pub fn relative_lock_time(&self) -> LockTime {
+        self.lock_time
+    }
+
     /// Return the user supplied part of the script.
     ///
     /// The full reclaim script has the form:
@@ -799,13 +798,24 @@ mod tests {
 
     #[test]
     fn lock_time_as_time() {
-        // We do not support time based lock times. Check that
-        let lock_time = LockTime::from_seconds_ceil(20000)
-            .unwrap()
-            .to_consensus_u32();
-        let reclaim = ReclaimScriptInputs::try_new(lock_time, ScriptBuf::new()).unwrap_err();
+        // Time based lock times are accepted, and we can tell them apart
+        // from height based ones.
+        let lock_time = LockTime::from_seconds_ceil(20000).unwrap();
+        let reclaim =
+            ReclaimScriptInputs::try_new(lock_time.to_consensus_u32(), ScriptBuf::new()).unwrap();
+
+        assert_eq!(reclaim.relative_lock_time(), lock_time);
+        assert!(matches!(reclaim.relative_lock_time(), LockTime::Time(_)));
+
+        // The lock time survives a round trip through the reclaim script.
+        let reclaim_script = reclaim.reclaim_script();
+        assert_eq!(
+            ReclaimScriptInputs::parse(&reclaim_script).unwrap(),
+            reclaim
+        );
 
-        assert!(matches!(reclaim, Error::UnsupportedLockTimeUnits(_)));
+        let reclaim = ReclaimScriptInputs::try_new(150, ScriptBuf::new()).unwrap();
+        assert!(matches!(reclaim.relative_lock_time(), LockTime::Blocks(_)));
     }
 
     #[test]
@@ -44,6 +44,7 @@ pub enum Error {
     ParseStacksAddress(#[source] stacks_common::codec::Error),
     /// This happens when the lock-time is given in time units instead of
     /// block units.
+    #[deprecated(note = "lock-times in time units are supported, so this error is never returned")]
     #[error("lock-time given in time units, but only block units are supported: {0}")]
     UnsupportedLockTimeUnits(u32),
     /// Failed to extract the outpoint from the bitcoin::Transaction.
@@ -0,0 +1,10 @@
+-- The magic bytes that the signers have used in the OP_RETURN output of
+-- their sweep transactions. The magic bytes are configurable, so we keep
+-- track of all of them in order to decode older sweep transactions after
+-- the configuration changes.
+CREATE TABLE sbtc_signer.sweep_magic_bytes (
+    -- The two byte prefix of the sBTC data in the OP_RETURN output.
+    magic_bytes BYTEA PRIMARY KEY,
+    -- a timestamp of when this record was created in the database.
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
@@ -0,0 +1,7 @@
+-- The median-time-past of the block, as a unix timestamp in seconds. Time
+-- based relative locktimes in deposit reclaim scripts are measured against
+-- it. Blocks that were written before this column existed get a value of
+-- zero, which makes deposits with time based locktimes that were
+-- confirmed in them look too old to sweep.
+ALTER TABLE sbtc_signer.bitcoin_blocks
+    ADD COLUMN median_time_past BIGINT NOT NULL DEFAULT 0;
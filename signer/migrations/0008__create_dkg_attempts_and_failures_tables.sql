@@ -0,0 +1,45 @@
+CREATE TYPE sbtc_signer.dkg_attempt_status AS ENUM (
+    'pending',
+    'success',
+    'failed',
+    'timed_out'
+);
+
+CREATE TYPE sbtc_signer.dkg_failure_reason AS ENUM (
+    'bad_state',
+    'missing_public_shares',
+    'bad_public_shares',
+    'missing_private_shares',
+    'bad_private_shares',
+    'bad_signature'
+);
+
+-- The rounds of distributed key generation (DKG) that this signer has
+-- taken part in, and how each of them ended.
+CREATE TABLE sbtc_signer.dkg_attempts (
+    -- The 32-byte identifier of the DKG round. WSTS messages carry it in
+    -- their `txid` field.
+    dkg_id BYTEA PRIMARY KEY,
+    -- The bitcoin chain tip when the DKG round was started.
+    bitcoin_chain_tip BYTEA NOT NULL,
+    -- The public keys of the signers that were asked to take part.
+    signer_set_public_keys BYTEA[] NOT NULL,
+    status sbtc_signer.dkg_attempt_status NOT NULL,
+    -- The aggregate key, set only when the round succeeded.
+    aggregate_key BYTEA,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- The signers that were blamed for a failed DKG round, who blamed them,
+-- and why.
+CREATE TABLE sbtc_signer.dkg_failures (
+    dkg_id BYTEA NOT NULL,
+    -- The public key of the signer that is blamed for the failure.
+    signer_public_key BYTEA NOT NULL,
+    -- The public key of the signer that reported the failure.
+    reported_by BYTEA NOT NULL,
+    reason sbtc_signer.dkg_failure_reason NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (dkg_id, signer_public_key, reported_by, reason)
+);
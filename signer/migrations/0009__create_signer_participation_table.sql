@@ -0,0 +1,34 @@
+CREATE TYPE sbtc_signer.participation_round_type AS ENUM (
+    'dkg',
+    'nonce',
+    'signature_share',
+    'stacks_signature'
+);
+
+CREATE TYPE sbtc_signer.participation_outcome AS ENUM (
+    'responded',
+    'invalid',
+    'missing'
+);
+
+-- How each signer took part in the DKG, WSTS signing and Stacks multi-sig
+-- rounds that this signer coordinated.
+CREATE TABLE sbtc_signer.signer_participation (
+    id BIGSERIAL PRIMARY KEY,
+    -- The 32-byte identifier of the round. This is the `txid` field of
+    -- the WSTS messages for DKG and WSTS signing rounds, and the
+    -- transaction ID of the Stacks transaction for Stacks multi-sig
+    -- rounds. The same identifier may be used by more than one round.
+    round_id BYTEA NOT NULL,
+    round_type sbtc_signer.participation_round_type NOT NULL,
+    -- The bitcoin chain tip when the round was run.
+    bitcoin_chain_tip BYTEA NOT NULL,
+    signer_public_key BYTEA NOT NULL,
+    outcome sbtc_signer.participation_outcome NOT NULL,
+    -- The number of milliseconds between the start of the round and the
+    -- signer's response. This is NULL when the signer did not respond.
+    response_time_ms BIGINT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX ix_signer_participation_signer_public_key ON sbtc_signer.signer_participation(signer_public_key, id DESC);
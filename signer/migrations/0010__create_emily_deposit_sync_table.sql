@@ -0,0 +1,24 @@
+-- Where the block observer left off when syncing pending deposits from
+-- Emily, so that it only fetches the deposits that are new or changed.
+CREATE TABLE sbtc_signer.emily_deposit_sync (
+    -- There is only ever one row in this table.
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    -- The Stacks block height, as reported by Emily, from which the next
+    -- sync fetches deposits.
+    min_height BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- Deposit requests fetched from Emily that could not be stored yet,
+-- because their transaction is not confirmed or because validating them
+-- hit a transient error. The sync height moves past them, and they are
+-- validated again on every sync until they are stored or fail
+-- validation.
+CREATE TABLE sbtc_signer.emily_deposit_sync_retries (
+    txid BYTEA NOT NULL,
+    output_index INTEGER NOT NULL,
+    spend_script BYTEA NOT NULL,
+    reclaim_script BYTEA NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (txid, output_index)
+);
@@ -0,0 +1,280 @@
+//! This module contains the handler for the `POST /deposits` endpoint,
+//! which lets operators submit deposit requests directly to this signer,
+//! for example when Emily has lost track of them.
+//!
+//! The endpoint is meant for the operator of the signer and should not be
+//! exposed publicly, since each request makes the signer reach out to
+//! bitcoin-core. The signer limits the size of the request bodies and the
+//! number of requests that it handles at the same time, see
+//! [`MAX_BODY_SIZE`] and [`MAX_CONCURRENT_REQUESTS`].
+
+use std::str::FromStr as _;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use bitcoin::OutPoint;
+use bitcoin::ScriptBuf;
+use bitcoin::Txid;
+use emily_client::models::CreateDepositRequestBody;
+use sbtc::deposits::CreateDepositRequest;
+use sbtc::deposits::DepositInfo;
+
+use crate::block_observer::validate_and_store_deposit_request;
+use crate::context::Context;
+use crate::context::SignerEvent;
+use crate::error::Error;
+use crate::message::NewDepositRequest;
+use crate::storage::model;
+use crate::storage::DbRead as _;
+
+use super::ApiState;
+
+/// The maximum size, in bytes, of the body of a `POST /deposits` request.
+/// The deposit and reclaim scripts in the body are small, so this is
+/// plenty.
+pub const MAX_BODY_SIZE: usize = 16 * 1024;
+
+/// The maximum number of `POST /deposits` requests that the signer
+/// handles at the same time. Other requests wait until one of these has
+/// been handled.
+pub const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// The deposit information parsed from a submitted deposit request.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DepositResponse {
+    /// The txid of the deposit transaction.
+    pub bitcoin_txid: String,
+    /// The index of the deposit output in the transaction.
+    pub bitcoin_tx_output_index: u32,
+    /// The amount of sats in the deposit UTXO.
+    pub amount: u64,
+    /// The max fee, in sats, that the depositor is willing to pay.
+    pub max_fee: u64,
+    /// The stacks address that the sBTC will be minted to.
+    pub recipient: String,
+    /// The relative lock time in the reclaim script.
+    pub lock_time: u32,
+}
+
+impl From<&DepositInfo> for DepositResponse {
+    fn from(info: &DepositInfo) -> Self {
+        DepositResponse {
+            bitcoin_txid: info.outpoint.txid.to_string(),
+            bitcoin_tx_output_index: info.outpoint.vout,
+            amount: info.amount,
+            max_fee: info.max_fee,
+            recipient: info.recipient.to_string(),
+            lock_time: info.lock_time.to_consensus_u32(),
+        }
+    }
+}
+
+/// A handler of `POST /deposits` requests.
+///
+/// The body has the same shape as the body of Emily's create deposit
+/// endpoint. The deposit request is validated against bitcoin-core and,
+/// if it passes validation, it is stored in the database and shared with
+/// the other signers.
+#[tracing::instrument(skip_all, name = "deposits")]
+pub async fn deposit_handler(
+    state: State<ApiState<impl Context>>,
+    Json(body): Json<CreateDepositRequestBody>,
+) -> Result<Json<DepositResponse>, (StatusCode, String)> {
+    let request = parse_deposit_request(&body)
+        .map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))?;
+
+    let outpoint = request.outpoint;
+    tracing::info!(%outpoint, "received a deposit request");
+
+    match submit_deposit(&state.0.ctx, request).await {
+        Ok(Some(info)) => Ok(Json(DepositResponse::from(&info))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            "the deposit transaction has not been confirmed".to_string(),
+        )),
+        Err(error @ Error::SbtcLib(_)) => Err((StatusCode::BAD_REQUEST, error.to_string())),
+        Err(error) => {
+            tracing::error!(%error, %outpoint, "could not store the deposit request");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "could not store the deposit request".to_string(),
+            ))
+        }
+    }
+}
+
+/// Parse the hex encoded fields of the request body.
+fn parse_deposit_request(body: &CreateDepositRequestBody) -> Result<CreateDepositRequest, Error> {
+    Ok(CreateDepositRequest {
+        outpoint: OutPoint {
+            txid: Txid::from_str(&body.bitcoin_txid).map_err(Error::DecodeHexTxid)?,
+            vout: body.bitcoin_tx_output_index,
+        },
+        reclaim_script: ScriptBuf::from_hex(&body.reclaim_script)
+            .map_err(Error::DecodeHexScript)?,
+        deposit_script: ScriptBuf::from_hex(&body.deposit_script)
+            .map_err(Error::DecodeHexScript)?,
+    })
+}
+
+/// Validate the deposit request and store it in the database, the same
+/// way that the block observer does for deposit requests from Emily.
+///
+/// Returns `None` if the deposit transaction has not been confirmed yet.
+/// Deposit requests that we did not know about are signaled so that they
+/// get gossiped to the other signers.
+pub async fn submit_deposit(
+    ctx: &impl Context,
+    request: CreateDepositRequest,
+) -> Result<Option<DepositInfo>, Error> {
+    let txid = model::BitcoinTxId::from(request.outpoint.txid);
+    let is_new = !ctx
+        .get_storage()
+        .deposit_request_exists(&txid, request.outpoint.vout)
+        .await?;
+
+    let Some(info) = validate_and_store_deposit_request(ctx, &request).await? else {
+        return Ok(None);
+    };
+
+    if is_new {
+        let event = SignerEvent::NewDepositRequestsObserved(vec![NewDepositRequest::from(request)]);
+        ctx.signal(event.into())?;
+    }
+
+    Ok(Some(info))
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hashes::Hash as _;
+    use rand::SeedableRng as _;
+
+    use crate::bitcoin::rpc::GetTxResponse;
+    use crate::context::SignerSignal;
+    use crate::storage;
+    use crate::testing::block_observer::TestHarness;
+    use crate::testing::context::*;
+
+    use super::*;
+
+    fn request_body(request: &CreateDepositRequest) -> CreateDepositRequestBody {
+        CreateDepositRequestBody {
+            bitcoin_tx_output_index: request.outpoint.vout,
+            bitcoin_txid: request.outpoint.txid.to_string(),
+            deposit_script: request.deposit_script.to_hex_string(),
+            reclaim_script: request.reclaim_script.to_hex_string(),
+        }
+    }
+
+    #[test]
+    fn parse_deposit_request_round_trips_the_body() {
+        let setup = sbtc::testing::deposits::tx_setup(150, 32000, 500_000);
+        let request = CreateDepositRequest {
+            outpoint: OutPoint {
+                txid: setup.tx.compute_txid(),
+                vout: 0,
+            },
+            deposit_script: setup.deposit.deposit_script(),
+            reclaim_script: setup.reclaim.reclaim_script(),
+        };
+
+        let parsed = parse_deposit_request(&request_body(&request)).unwrap();
+        assert_eq!(parsed.outpoint, request.outpoint);
+        assert_eq!(parsed.deposit_script, request.deposit_script);
+        assert_eq!(parsed.reclaim_script, request.reclaim_script);
+    }
+
+    #[test]
+    fn parse_deposit_request_rejects_bad_hex() {
+        let body = CreateDepositRequestBody {
+            bitcoin_tx_output_index: 0,
+            bitcoin_txid: "not-a-txid".to_string(),
+            deposit_script: String::new(),
+            reclaim_script: String::new(),
+        };
+        assert!(parse_deposit_request(&body).is_err());
+    }
+
+    #[tokio::test]
+    async fn confirmed_deposits_are_stored_and_signaled() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+        let mut test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+        let block_hash = test_harness
+            .bitcoin_blocks()
+            .first()
+            .map(|block| block.block_hash());
+
+        let setup = sbtc::testing::deposits::tx_setup(150, 32000, 500_000);
+        let request = CreateDepositRequest {
+            outpoint: OutPoint {
+                txid: setup.tx.compute_txid(),
+                vout: 0,
+            },
+            deposit_script: setup.deposit.deposit_script(),
+            reclaim_script: setup.reclaim.reclaim_script(),
+        };
+        let response = GetTxResponse {
+            tx: setup.tx.clone(),
+            block_hash,
+            confirmations: None,
+            block_time: None,
+        };
+        test_harness.add_deposit(setup.tx.compute_txid(), response);
+
+        let storage = storage::in_memory::Store::new_shared();
+        let ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_stacks_client(test_harness.clone())
+            .with_emily_client(test_harness.clone())
+            .with_bitcoin_client(test_harness.clone())
+            .build();
+        let mut signal_rx = ctx.get_signal_receiver();
+
+        let state = State(ApiState { ctx: ctx.clone() });
+        let Json(deposit) = deposit_handler(state, Json(request_body(&request)))
+            .await
+            .unwrap();
+
+        assert_eq!(deposit.bitcoin_txid, request.outpoint.txid.to_string());
+        assert_eq!(deposit.amount, 500_000);
+        assert_eq!(deposit.max_fee, 32000);
+        assert_eq!(deposit.lock_time, 150);
+
+        let db_outpoint = (request.outpoint.txid.into(), request.outpoint.vout);
+        assert!(storage
+            .lock()
+            .await
+            .deposit_requests
+            .contains_key(&db_outpoint));
+
+        let signal = signal_rx.try_recv().unwrap();
+        let SignerSignal::Event(SignerEvent::NewDepositRequestsObserved(requests)) = signal else {
+            panic!("expected a new deposit requests signal, got {signal:?}");
+        };
+        assert_eq!(requests, vec![NewDepositRequest::from(request)]);
+    }
+
+    #[tokio::test]
+    async fn unknown_deposit_transactions_are_not_found() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+        let test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_stacks_client(test_harness.clone())
+            .with_emily_client(test_harness.clone())
+            .with_bitcoin_client(test_harness.clone())
+            .build();
+
+        let body = CreateDepositRequestBody {
+            bitcoin_tx_output_index: 0,
+            bitcoin_txid: Txid::all_zeros().to_string(),
+            deposit_script: String::new(),
+            reclaim_script: String::new(),
+        };
+        let state = State(ApiState { ctx });
+        let (status, _) = deposit_handler(state, Json(body)).await.unwrap_err();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
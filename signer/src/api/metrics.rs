@@ -0,0 +1,22 @@
+//! This module is for the `GET /metrics` endpoint, which serves the
+//! signer's metrics to Prometheus.
+
+use axum::http::header;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+
+/// The content type of the Prometheus text format.
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// A handler of `GET /metrics` that responds with the signer's metrics in
+/// the Prometheus text format.
+pub async fn metrics_handler() -> Response {
+    match crate::metrics::metrics().encode() {
+        Ok(body) => ([(header::CONTENT_TYPE, CONTENT_TYPE)], body).into_response(),
+        Err(error) => {
+            tracing::error!(%error, "could not encode the metrics");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
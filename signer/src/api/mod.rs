@@ -1,10 +1,16 @@
 //! This module contains functions and structs for the Signer API.
 //!
 
+pub mod deposits;
+pub mod metrics;
 pub mod new_block;
 pub mod status;
 
+pub use deposits::deposit_handler;
+pub use metrics::metrics_handler;
 pub use new_block::new_block_handler;
+pub use status::readiness_handler;
+pub use status::signer_status_handler;
 pub use status::status_handler;
 
 /// A struct with state data necessary for runtime operation.
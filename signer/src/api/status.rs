@@ -1,8 +1,525 @@
-//! This module is for the `GET /` endpoint, which just returns the status.
+//! This module is for the `GET /` endpoint, which just returns the status,
+//! and for the `GET /status` and `GET /health/ready` endpoints, which
+//! report the state of the signer as JSON.
 
+use axum::extract::State;
 use axum::http::StatusCode;
+use axum::Json;
+
+use crate::bitcoin::BitcoinInteract;
+use crate::context::Context;
+use crate::emily_client::EmilyInteract;
+use crate::error::Error;
+use crate::keys::PublicKey;
+use crate::stacks::api::GetNakamotoStartHeight as _;
+use crate::stacks::api::StacksInteract;
+use crate::storage::model::BitcoinBlock;
+use crate::storage::model::BitcoinBlockHash;
+use crate::storage::model::StacksBlock;
+use crate::storage::DbRead;
+
+use super::ApiState;
+
+/// The number of bitcoin blocks back from the chain tip that are searched
+/// for requests. This matches the window used by the event loops.
+const CONTEXT_WINDOW: u16 = 10000;
 
 /// A basic handler that responds with 200 OK
 pub async fn status_handler() -> StatusCode {
     StatusCode::OK
 }
+
+/// A chain tip as seen by the signer.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ChainTipStatus {
+    /// The hash of the block at the chain tip.
+    pub block_hash: String,
+    /// The height of the block at the chain tip.
+    pub block_height: u64,
+    /// The number of seconds since the bitcoin block was mined, according
+    /// to its header. For a stacks block this is the age of the bitcoin
+    /// block that anchors it. This is `None` if bitcoin-core could not be
+    /// asked for the header.
+    pub age_seconds: Option<u64>,
+}
+
+/// The latest round of distributed key generation known to the signer.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DkgStatus {
+    /// The identifier of the DKG round.
+    pub dkg_id: String,
+    /// The bitcoin chain tip when the DKG round was started.
+    pub bitcoin_chain_tip: String,
+    /// How far the DKG round has gotten.
+    pub status: String,
+    /// The aggregate key generated by the round, if it succeeded.
+    pub aggregate_key: Option<String>,
+    /// The public keys of the signers blamed for the failure of the round.
+    pub blamed_signers: Vec<String>,
+}
+
+/// The number of deposit and withdrawal requests that are waiting on the
+/// signers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct RequestCounts {
+    /// Deposit requests that this signer has not voted on yet.
+    pub pending_deposits: usize,
+    /// Deposit requests that enough signers have accepted to be swept in.
+    pub accepted_deposits: usize,
+    /// Withdrawal requests that this signer has not voted on yet.
+    pub pending_withdrawals: usize,
+    /// Withdrawal requests that enough signers have accepted to be swept
+    /// out.
+    pub accepted_withdrawals: usize,
+}
+
+/// Whether the services that the signer depends on responded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct Reachability {
+    /// Whether bitcoin-core responded.
+    pub bitcoin: bool,
+    /// Whether the stacks node responded.
+    pub stacks: bool,
+    /// Whether Emily responded.
+    pub emily: bool,
+    /// Whether the database responded.
+    pub postgres: bool,
+}
+
+/// The response body of the `GET /status` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SignerStatus {
+    /// The public key of this signer.
+    pub public_key: String,
+    /// The canonical bitcoin chain tip, if the signer has seen any blocks.
+    pub bitcoin_chain_tip: Option<ChainTipStatus>,
+    /// The stacks chain tip anchored to the canonical bitcoin chain, if
+    /// the signer has seen any blocks.
+    pub stacks_chain_tip: Option<ChainTipStatus>,
+    /// Whether the stacks node is in epoch 3 or later. This is `None` if
+    /// the stacks node could not be asked.
+    pub is_epoch3: Option<bool>,
+    /// The public keys of the current signer set.
+    pub signer_set: Vec<String>,
+    /// The aggregate key of the latest DKG shares.
+    pub aggregate_key: Option<String>,
+    /// The number of signatures required by the latest DKG shares.
+    pub signature_threshold: Option<u16>,
+    /// The latest round of distributed key generation.
+    pub dkg: Option<DkgStatus>,
+    /// The peer IDs of the signers that we are connected to.
+    pub connected_peers: Vec<String>,
+    /// The number of requests that are waiting on the signers.
+    pub requests: RequestCounts,
+    /// The transaction ID of the latest sweep transaction.
+    pub last_sweep_txid: Option<String>,
+    /// Whether the services that the signer depends on responded.
+    pub reachability: Reachability,
+}
+
+/// The parts of the signer state that decide whether the signer is ready.
+/// Unlike the [`SignerStatus`], these are cheap to gather, so that the
+/// `GET /health/ready` endpoint can be polled often.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadinessStatus {
+    /// The public key of this signer.
+    pub public_key: String,
+    /// Whether the signer has seen any bitcoin blocks.
+    pub has_bitcoin_chain_tip: bool,
+    /// The public keys of the current signer set.
+    pub signer_set: Vec<String>,
+    /// The aggregate key of the latest DKG shares.
+    pub aggregate_key: Option<String>,
+    /// The number of signatures required by the latest DKG shares.
+    pub signature_threshold: Option<u16>,
+    /// The number of signers that we are connected to.
+    pub connected_peers: usize,
+    /// Whether bitcoin-core responded.
+    pub bitcoin_reachable: bool,
+    /// Whether the stacks node responded.
+    pub stacks_reachable: bool,
+    /// Whether the database responded.
+    pub postgres_reachable: bool,
+}
+
+/// The response body of the `GET /health/ready` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Readiness {
+    /// Whether the signer can take part in signing.
+    pub ready: bool,
+    /// Why the signer cannot take part in signing, if it can't.
+    pub reasons: Vec<String>,
+}
+
+impl From<&ReadinessStatus> for Readiness {
+    fn from(status: &ReadinessStatus) -> Self {
+        let mut reasons = Vec::new();
+        if !status.postgres_reachable {
+            reasons.push("the database is unreachable".to_string());
+        }
+        if !status.bitcoin_reachable {
+            reasons.push("bitcoin-core is unreachable".to_string());
+        }
+        if !status.stacks_reachable {
+            reasons.push("the stacks node is unreachable".to_string());
+        }
+        if !status.has_bitcoin_chain_tip {
+            reasons.push("no bitcoin blocks have been observed".to_string());
+        }
+        if !status.signer_set.contains(&status.public_key) {
+            reasons.push("this signer is not in the current signer set".to_string());
+        }
+        if status.aggregate_key.is_none() {
+            reasons.push("there is no aggregate key from a successful DKG round".to_string());
+        }
+        // We need signatures from `threshold - 1` other signers to sign
+        // anything, so we need to be connected to at least that many.
+        if let Some(threshold) = status.signature_threshold {
+            let required_peers = usize::from(threshold.saturating_sub(1));
+            if status.connected_peers < required_peers {
+                reasons.push(format!(
+                    "connected to {} signers but {required_peers} are needed to sign",
+                    status.connected_peers
+                ));
+            }
+        }
+        // Emily is deliberately left out, the signers learn about
+        // requests from it but do not need it to sign for them, so it is
+        // not even asked.
+
+        Readiness {
+            ready: reasons.is_empty(),
+            reasons,
+        }
+    }
+}
+
+/// A handler of `GET /status` that reports the state of the signer.
+#[tracing::instrument(skip_all, name = "status")]
+pub async fn signer_status_handler(
+    state: State<ApiState<impl Context>>,
+) -> Result<Json<SignerStatus>, StatusCode> {
+    signer_status(&state.0.ctx)
+        .await
+        .map(Json)
+        .map_err(|error| {
+            tracing::error!(%error, "could not gather the signer status");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// A handler of `GET /health/ready` that responds with 200 OK if the
+/// signer can take part in signing, and with 503 Service Unavailable
+/// otherwise.
+#[tracing::instrument(skip_all, name = "readiness")]
+pub async fn readiness_handler(
+    state: State<ApiState<impl Context>>,
+) -> Result<(StatusCode, Json<Readiness>), StatusCode> {
+    let status = readiness_status(&state.0.ctx).await.map_err(|error| {
+        tracing::error!(%error, "could not gather the signer readiness status");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let readiness = Readiness::from(&status);
+    let status_code = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok((status_code, Json(readiness)))
+}
+
+/// Gather the state of the signer. Failures to reach one of the services
+/// that the signer depends on are logged and reported in the status
+/// rather than failing the request. This only returns an error if the
+/// configuration does not have the signer's key.
+pub async fn signer_status(ctx: &impl Context) -> Result<SignerStatus, Error> {
+    let public_key = ctx.config().signer.public_key()?;
+    let bitcoin_client = ctx.get_bitcoin_client();
+    let stacks_client = ctx.get_stacks_client();
+    let emily_client = ctx.get_emily_client();
+
+    let (bitcoin, pox_info, emily) = tokio::join!(
+        bitcoin_client.get_best_block_hash(),
+        stacks_client.get_pox_info(),
+        emily_client.get_limits(),
+    );
+    log_unreachable("bitcoin-core", &bitcoin);
+    log_unreachable("stacks node", &pox_info);
+    log_unreachable("emily", &emily);
+
+    let is_epoch3 = pox_info.as_ref().ok().map(|pox_info| {
+        pox_info
+            .nakamoto_start_height()
+            .is_some_and(|start_height| pox_info.current_burnchain_block_height > start_height)
+    });
+
+    let database = database_status(ctx, &public_key).await;
+    log_unreachable("database", &database);
+    let database = database.unwrap_or_default();
+
+    let mut bitcoin_chain_tip = None;
+    let mut stacks_chain_tip = None;
+    if let Some(block) = database.bitcoin_chain_tip {
+        bitcoin_chain_tip = Some(ChainTipStatus {
+            block_hash: block.block_hash.to_string(),
+            block_height: block.block_height,
+            age_seconds: block_age(&bitcoin_client, &block.block_hash).await,
+        });
+    }
+    if let Some(block) = database.stacks_chain_tip {
+        stacks_chain_tip = Some(ChainTipStatus {
+            block_hash: block.block_hash.to_string(),
+            block_height: block.block_height,
+            age_seconds: block_age(&bitcoin_client, &block.bitcoin_anchor).await,
+        });
+    }
+
+    let signer_set = current_signer_set(ctx);
+
+    let mut connected_peers: Vec<String> = ctx
+        .state()
+        .connected_peers()
+        .iter()
+        .map(|peer_id| peer_id.to_string())
+        .collect();
+    connected_peers.sort();
+
+    Ok(SignerStatus {
+        public_key: public_key.to_string(),
+        bitcoin_chain_tip,
+        stacks_chain_tip,
+        is_epoch3,
+        signer_set,
+        aggregate_key: database.aggregate_key,
+        signature_threshold: database.signature_threshold,
+        dkg: database.dkg,
+        connected_peers,
+        requests: database.requests,
+        last_sweep_txid: database.last_sweep_txid,
+        reachability: Reachability {
+            bitcoin: bitcoin.is_ok(),
+            stacks: pox_info.is_ok(),
+            emily: emily.is_ok(),
+            postgres: database.reachable,
+        },
+    })
+}
+
+/// Gather the state of the signer that decides whether it is ready. This
+/// only makes one request to each of bitcoin-core and the stacks node, and
+/// two small queries to the database. Like [`signer_status`], this only
+/// returns an error if the configuration does not have the signer's key.
+pub async fn readiness_status(ctx: &impl Context) -> Result<ReadinessStatus, Error> {
+    let public_key = ctx.config().signer.public_key()?;
+    let bitcoin_client = ctx.get_bitcoin_client();
+    let stacks_client = ctx.get_stacks_client();
+    let db = ctx.get_storage();
+
+    let (bitcoin, pox_info, chain_tip, shares) = tokio::join!(
+        bitcoin_client.get_best_block_hash(),
+        stacks_client.get_pox_info(),
+        db.get_bitcoin_canonical_chain_tip(),
+        db.get_latest_encrypted_dkg_shares(),
+    );
+    log_unreachable("bitcoin-core", &bitcoin);
+    log_unreachable("stacks node", &pox_info);
+    log_unreachable("database", &chain_tip);
+    log_unreachable("database", &shares);
+
+    let postgres_reachable = chain_tip.is_ok() && shares.is_ok();
+    let shares = shares.ok().flatten();
+
+    Ok(ReadinessStatus {
+        public_key: public_key.to_string(),
+        has_bitcoin_chain_tip: chain_tip.is_ok_and(|chain_tip| chain_tip.is_some()),
+        signer_set: current_signer_set(ctx),
+        aggregate_key: shares
+            .as_ref()
+            .map(|shares| shares.aggregate_key.to_string()),
+        signature_threshold: shares.map(|shares| shares.signature_share_threshold),
+        connected_peers: ctx.state().connected_peers().len(),
+        bitcoin_reachable: bitcoin.is_ok(),
+        stacks_reachable: pox_info.is_ok(),
+        postgres_reachable,
+    })
+}
+
+/// The sorted public keys of the current signer set.
+fn current_signer_set(ctx: &impl Context) -> Vec<String> {
+    let mut signer_set: Vec<String> = ctx
+        .state()
+        .current_signer_set()
+        .get_signers()
+        .iter()
+        .map(|signer| signer.public_key().to_string())
+        .collect();
+    signer_set.sort();
+    signer_set
+}
+
+/// The parts of the status that are read from the database.
+#[derive(Debug, Default)]
+struct DatabaseStatus {
+    reachable: bool,
+    bitcoin_chain_tip: Option<BitcoinBlock>,
+    stacks_chain_tip: Option<StacksBlock>,
+    aggregate_key: Option<String>,
+    signature_threshold: Option<u16>,
+    dkg: Option<DkgStatus>,
+    requests: RequestCounts,
+    last_sweep_txid: Option<String>,
+}
+
+async fn database_status(
+    ctx: &impl Context,
+    public_key: &PublicKey,
+) -> Result<DatabaseStatus, Error> {
+    let db = ctx.get_storage();
+    let threshold = ctx.config().signer.bootstrap_signatures_required;
+
+    let mut status = DatabaseStatus {
+        reachable: true,
+        ..Default::default()
+    };
+
+    if let Some(shares) = db.get_latest_encrypted_dkg_shares().await? {
+        status.aggregate_key = Some(shares.aggregate_key.to_string());
+        status.signature_threshold = Some(shares.signature_share_threshold);
+    }
+
+    if let Some(attempt) = db.get_latest_dkg_attempt().await? {
+        let failures = db.get_dkg_failures(&attempt.dkg_id).await?;
+        status.dkg = Some(DkgStatus {
+            dkg_id: attempt.dkg_id.to_string(),
+            bitcoin_chain_tip: attempt.bitcoin_chain_tip.to_string(),
+            status: attempt.status.to_string(),
+            aggregate_key: attempt.aggregate_key.map(|key| key.to_string()),
+            blamed_signers: failures
+                .iter()
+                .map(|failure| failure.signer_public_key.to_string())
+                .collect(),
+        });
+    }
+
+    let Some(chain_tip) = db.get_bitcoin_canonical_chain_tip().await? else {
+        return Ok(status);
+    };
+
+    status.bitcoin_chain_tip = db.get_bitcoin_block(&chain_tip).await?;
+    status.stacks_chain_tip = db.get_stacks_chain_tip(&chain_tip).await?;
+    status.requests = RequestCounts {
+        pending_deposits: db
+            .get_pending_deposit_requests(&chain_tip, CONTEXT_WINDOW, public_key)
+            .await?
+            .len(),
+        accepted_deposits: db
+            .get_pending_accepted_deposit_requests(&chain_tip, CONTEXT_WINDOW, threshold)
+            .await?
+            .len(),
+        pending_withdrawals: db
+            .get_pending_withdrawal_requests(&chain_tip, CONTEXT_WINDOW, public_key)
+            .await?
+            .len(),
+        accepted_withdrawals: db
+            .get_pending_accepted_withdrawal_requests(&chain_tip, CONTEXT_WINDOW, threshold)
+            .await?
+            .len(),
+    };
+    status.last_sweep_txid = db
+        .get_latest_sweep_transaction(&chain_tip, CONTEXT_WINDOW)
+        .await?
+        .map(|sweep| sweep.txid.to_string());
+
+    Ok(status)
+}
+
+/// The number of seconds since the given bitcoin block was mined,
+/// according to the time in its header.
+async fn block_age(
+    bitcoin_client: &impl BitcoinInteract,
+    block_hash: &BitcoinBlockHash,
+) -> Option<u64> {
+    let header = bitcoin_client.get_block_header(block_hash).await.ok()??;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    Some(now.as_secs().saturating_sub(header.time as u64))
+}
+
+fn log_unreachable<T>(service: &str, result: &Result<T, Error>) {
+    if let Err(error) = result {
+        tracing::warn!(%error, service, "could not reach a service while gathering the signer status");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(public_key: &str, signer_set: &[&str]) -> ReadinessStatus {
+        ReadinessStatus {
+            public_key: public_key.to_string(),
+            has_bitcoin_chain_tip: true,
+            signer_set: signer_set.iter().map(ToString::to_string).collect(),
+            aggregate_key: Some("aggregate-key".to_string()),
+            signature_threshold: Some(2),
+            connected_peers: 1,
+            bitcoin_reachable: true,
+            stacks_reachable: true,
+            postgres_reachable: true,
+        }
+    }
+
+    #[test]
+    fn ready_when_in_the_signer_set_and_dependencies_are_up() {
+        let readiness = Readiness::from(&status("a", &["a", "b"]));
+        assert!(readiness.ready);
+        assert!(readiness.reasons.is_empty());
+    }
+
+    #[test]
+    fn not_ready_without_an_aggregate_key() {
+        let mut status = status("a", &["a", "b"]);
+        status.aggregate_key = None;
+        status.signature_threshold = None;
+
+        let readiness = Readiness::from(&status);
+        assert!(!readiness.ready);
+        assert_eq!(readiness.reasons.len(), 1);
+    }
+
+    #[test]
+    fn not_ready_when_connected_to_too_few_signers() {
+        let mut status = status("a", &["a", "b", "c"]);
+        status.signature_threshold = Some(3);
+        status.connected_peers = 1;
+
+        let readiness = Readiness::from(&status);
+        assert!(!readiness.ready);
+        assert_eq!(readiness.reasons.len(), 1);
+
+        // Connecting to one more signer is enough, since we count
+        // ourselves towards the threshold.
+        status.connected_peers = 2;
+        assert!(Readiness::from(&status).ready);
+    }
+
+    #[test]
+    fn not_ready_when_outside_the_signer_set() {
+        let readiness = Readiness::from(&status("c", &["a", "b"]));
+        assert!(!readiness.ready);
+        assert_eq!(readiness.reasons.len(), 1);
+    }
+
+    #[test]
+    fn not_ready_when_a_dependency_is_down() {
+        let mut status = status("a", &["a", "b"]);
+        status.postgres_reachable = false;
+        status.stacks_reachable = false;
+        status.has_bitcoin_chain_tip = false;
+
+        let readiness = Readiness::from(&status);
+        assert!(!readiness.ready);
+        assert_eq!(readiness.reasons.len(), 3);
+    }
+}
@@ -14,13 +14,17 @@
 //! - Example when trying to get a block that doesn't exist:
 //!   JsonRpc(Rpc(RpcError { code: -5, message: "Block not found", data: None }))
 
+use std::sync::Arc;
+
 use bitcoin::BlockHash;
 use bitcoin::Txid;
+use bitcoincore_rpc_json::GetBlockHeaderResult;
 use bitcoincore_rpc_json::GetTxOutResult;
 use url::Url;
 
-use crate::{error::Error, util::ApiFallbackClient};
+use crate::{config::BitcoinConfig, error::Error, util::ApiFallbackClient};
 
+use super::fees::FeeEstimator;
 use super::rpc::BitcoinCoreClient;
 use super::rpc::BitcoinTxInfo;
 use super::rpc::GetTxResponse;
@@ -41,7 +45,39 @@ impl TryFrom<&[Url]> for ApiFallbackClient<BitcoinCoreClient> {
     }
 }
 
+/// Implement the [`TryFrom`] trait for the [`BitcoinConfig`] so that all
+/// of the bitcoin-core clients share the configured fee estimator.
+impl TryFrom<&BitcoinConfig> for ApiFallbackClient<BitcoinCoreClient> {
+    type Error = Error;
+    fn try_from(config: &BitcoinConfig) -> Result<Self, Self::Error> {
+        let fee_estimator = Arc::new(FeeEstimator::new(config.fees.clone()));
+        let clients = config
+            .rpc_endpoints
+            .iter()
+            .map(|url| {
+                BitcoinCoreClient::try_from(url)
+                    .map(|client| client.with_fee_estimator(fee_estimator.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::new(clients).map_err(Into::into)
+    }
+}
+
 impl BitcoinInteract for ApiFallbackClient<BitcoinCoreClient> {
+    async fn get_best_block_hash(&self) -> Result<BlockHash, Error> {
+        self.exec(|client, _| async { client.get_best_block_hash() })
+            .await
+    }
+
+    async fn get_block_header(
+        &self,
+        block_hash: &BlockHash,
+    ) -> Result<Option<GetBlockHeaderResult>, Error> {
+        self.exec(|client, _| async { client.get_block_header(block_hash) })
+            .await
+    }
+
     async fn get_block(
         &self,
         block_hash: &bitcoin::BlockHash,
@@ -65,7 +101,6 @@ impl BitcoinInteract for ApiFallbackClient<BitcoinCoreClient> {
     }
 
     async fn estimate_fee_rate(&self) -> Result<f64, Error> {
-        // TODO(542)
         self.exec(|client, _| BitcoinInteract::estimate_fee_rate(client))
             .await
     }
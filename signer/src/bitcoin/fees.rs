@@ -1,60 +1,154 @@
 //! Fee rate estimation module
+//!
+//! The signer estimates the fee rate of its transactions by asking a
+//! configurable list of sources, which may include bitcoin-core and APIs
+//! compatible with mempool.space or bitcoiner.live. The estimates that
+//! come back are combined using the median or a trimmed mean, and the
+//! result is clamped to the configured floor and ceiling.
 
 use std::future::Future;
 use std::time::Duration;
 
 use serde::Deserialize;
 
+use crate::bitcoin::rpc::BitcoinCoreClient;
 use crate::bitcoin::rpc::FeeEstimate;
+use crate::config::FeeAggregation;
+use crate::config::FeeEstimationConfig;
+use crate::config::FeeSourceConfig;
 use crate::error::Error;
 
-const FIVE_MINUTES_SECONDS: i64 = 300;
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
 
-/// Compute the current market fee rate by averaging the recommended price
-/// estimates from various sources.
-pub async fn estimate_fee_rate(client: &reqwest::Client) -> Result<FeeEstimate, Error> {
-    let sources: [FeeSource; 2] = [
-        FeeSource::MempoolSpace(MempoolSpace {
-            base_url: "https://mempool.space".to_string(),
-            client: client.clone(),
-        }),
-        FeeSource::BitcoinerLive(BitcoinerLive {
-            base_url: "https://bitcoiner.live".to_string(),
-            client: client.clone(),
-        }),
-    ];
+/// Estimates the current market fee rate using the sources in the
+/// `[bitcoin.fees]` configuration.
+#[derive(Debug, Clone)]
+pub struct FeeEstimator {
+    config: FeeEstimationConfig,
+    client: reqwest::Client,
+}
 
-    estimate_fee_rate_impl(&sources).await
+impl Default for FeeEstimator {
+    fn default() -> Self {
+        Self::new(FeeEstimationConfig::default())
+    }
 }
 
-/// Used to compute the average price of the fee estimates from the given
-/// sources.
-async fn estimate_fee_rate_impl<T>(sources: &[T]) -> Result<FeeEstimate, Error>
+impl FeeEstimator {
+    /// Create a new fee estimator from the given configuration.
+    pub fn new(config: FeeEstimationConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Compute the current market fee rate from the configured sources.
+    /// The given bitcoin-core client is used for the `bitcoind` sources.
+    pub async fn estimate_fee_rate(
+        &self,
+        bitcoin_core: &BitcoinCoreClient,
+    ) -> Result<FeeEstimate, Error> {
+        let max_age = self.config.max_age;
+        let sources: Vec<FeeSource> = self
+            .config
+            .sources
+            .iter()
+            .map(|source| match source {
+                FeeSourceConfig::Bitcoind { target_blocks } => {
+                    FeeSource::BitcoinCore(BitcoinCore {
+                        client: bitcoin_core.clone(),
+                        target_blocks: *target_blocks,
+                    })
+                }
+                FeeSourceConfig::MempoolSpace { url } => FeeSource::MempoolSpace(MempoolSpace {
+                    base_url: url.as_str().trim_end_matches('/').to_string(),
+                    client: self.client.clone(),
+                }),
+                FeeSourceConfig::BitcoinerLive { url } => FeeSource::BitcoinerLive(BitcoinerLive {
+                    base_url: url.as_str().trim_end_matches('/').to_string(),
+                    client: self.client.clone(),
+                    max_age,
+                }),
+            })
+            .collect();
+
+        estimate_fee_rate_impl(&sources, &self.config).await
+    }
+}
+
+/// Ask all of the given sources for a fee rate estimate and combine the
+/// good ones according to the configuration.
+async fn estimate_fee_rate_impl<T>(
+    sources: &[T],
+    config: &FeeEstimationConfig,
+) -> Result<FeeEstimate, Error>
 where
     T: EstimateFees,
 {
     let futures_iter = sources
         .iter()
         .map(|source| async move { source.estimate_fee_rate().await });
-    let mut responses = futures::future::join_all(futures_iter).await;
+    let responses = futures::future::join_all(futures_iter).await;
 
-    if responses.iter().all(Result::is_err) {
+    let mut estimates: Vec<f64> = responses
+        .into_iter()
+        .filter_map(|response| match response {
+            Ok(estimate) => Some(estimate.sats_per_vbyte),
+            Err(error) => {
+                tracing::warn!(%error, "could not get a fee estimate from a source");
+                None
+            }
+        })
+        .filter(|sats_per_vbyte| sats_per_vbyte.is_finite() && *sats_per_vbyte >= 0.0)
+        .collect();
+
+    if estimates.is_empty() {
         return Err(Error::NoGoodFeeEstimates);
     }
 
-    responses.retain(Result::is_ok);
-    let num_responses = responses.len();
-    let sum_sats_per_vbyte = responses
-        .into_iter()
-        .filter_map(Result::ok)
-        .map(|x| x.sats_per_vbyte)
-        .sum::<f64>();
+    estimates.sort_by(f64::total_cmp);
+    let sats_per_vbyte = match config.aggregation {
+        FeeAggregation::Median => median(&estimates),
+        FeeAggregation::TrimmedMean => trimmed_mean(&estimates, config.trim_fraction),
+    };
+
+    let sats_per_vbyte = sats_per_vbyte.max(config.floor_sats_per_vbyte);
+    let sats_per_vbyte = match config.ceiling_sats_per_vbyte {
+        Some(ceiling) => sats_per_vbyte.min(ceiling),
+        None => sats_per_vbyte,
+    };
 
-    let sats_per_vbyte = sum_sats_per_vbyte / num_responses as f64;
     Ok(FeeEstimate { sats_per_vbyte })
 }
 
+/// Return the median of the given sorted, non-empty, slice.
+fn median(sorted: &[f64]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Return the mean of the given sorted, non-empty, slice after dropping
+/// the `trim_fraction` smallest and largest values. The trim fraction
+/// must be less than one half, so at least one value is always kept.
+fn trimmed_mean(sorted: &[f64], trim_fraction: f64) -> f64 {
+    let trim = (sorted.len() as f64 * trim_fraction).floor() as usize;
+    let kept = &sorted[trim..sorted.len() - trim];
+    kept.iter().sum::<f64>() / kept.len() as f64
+}
+
+/// A struct representing fee estimate requests to bitcoin-core using the
+/// `estimatesmartfee` RPC.
+#[derive(Debug, Clone)]
+struct BitcoinCore {
+    client: BitcoinCoreClient,
+    target_blocks: u16,
+}
+
 /// A struct representing requests to https://bitcoiner.live
 ///
 /// The docs for this API can be found at https://bitcoiner.live/doc/api
@@ -62,6 +156,8 @@ where
 struct BitcoinerLive {
     base_url: String,
     client: reqwest::Client,
+    /// Responses computed longer ago than this are rejected.
+    max_age: Duration,
 }
 
 #[derive(Debug, Deserialize)]
@@ -141,7 +237,7 @@ impl EstimateFees for BitcoinerLive {
             .await?;
 
         let now = time::OffsetDateTime::now_utc().unix_timestamp();
-        if now - resp.timestamp > FIVE_MINUTES_SECONDS {
+        if now - resp.timestamp > self.max_age.as_secs() as i64 {
             return Err(Error::OldFeeEstimate);
         }
 
@@ -151,6 +247,14 @@ impl EstimateFees for BitcoinerLive {
     }
 }
 
+impl EstimateFees for BitcoinCore {
+    /// Fetch the fee estimate from bitcoin-core for the configured
+    /// confirmation target.
+    async fn estimate_fee_rate(&self) -> Result<FeeEstimate, Error> {
+        self.client.estimate_fee_rate(self.target_blocks)
+    }
+}
+
 const MEMPOOL_SPACE_PATH: &str = "/api/v1/fees/recommended";
 
 impl EstimateFees for MempoolSpace {
@@ -177,6 +281,7 @@ impl EstimateFees for MempoolSpace {
 
 #[derive(Debug)]
 enum FeeSource {
+    BitcoinCore(BitcoinCore),
     BitcoinerLive(BitcoinerLive),
     MempoolSpace(MempoolSpace),
 }
@@ -184,6 +289,7 @@ enum FeeSource {
 impl EstimateFees for FeeSource {
     async fn estimate_fee_rate(&self) -> Result<FeeEstimate, Error> {
         match self {
+            Self::BitcoinCore(core) => core.estimate_fee_rate().await,
             Self::BitcoinerLive(btclive) => btclive.estimate_fee_rate().await,
             Self::MempoolSpace(mempool) => mempool.estimate_fee_rate().await,
         }
@@ -192,6 +298,8 @@ impl EstimateFees for FeeSource {
 
 #[cfg(test)]
 mod tests {
+    use test_case::test_case;
+
     use super::*;
 
     struct KnownFeeEstimator(f64);
@@ -211,17 +319,79 @@ mod tests {
             KnownFeeEstimator(7.),
             KnownFeeEstimator(9.),
         ];
-        let est = estimate_fee_rate_impl(&sources).await.unwrap();
+        let est = estimate_fee_rate_impl(&sources, &FeeEstimationConfig::default())
+            .await
+            .unwrap();
 
         assert_eq!(est.sats_per_vbyte, 5.);
     }
 
+    #[test_case(FeeAggregation::Median, 0.25, 5.5; "median-ignores-outlier")]
+    #[test_case(FeeAggregation::TrimmedMean, 0.25, 5.5; "trimmed-mean-drops-outlier")]
+    #[test_case(FeeAggregation::TrimmedMean, 0.0, 28.5; "untrimmed-mean-keeps-outlier")]
+    #[tokio::test]
+    async fn fee_estimates_aggregation(
+        aggregation: FeeAggregation,
+        trim_fraction: f64,
+        expected: f64,
+    ) {
+        let sources = [
+            KnownFeeEstimator(100.),
+            KnownFeeEstimator(3.),
+            KnownFeeEstimator(6.),
+            KnownFeeEstimator(5.),
+        ];
+        let config = FeeEstimationConfig {
+            aggregation,
+            trim_fraction,
+            ..FeeEstimationConfig::default()
+        };
+        let est = estimate_fee_rate_impl(&sources, &config).await.unwrap();
+
+        assert_eq!(est.sats_per_vbyte, expected);
+    }
+
+    #[tokio::test]
+    async fn fee_estimates_are_clamped() {
+        let config = FeeEstimationConfig {
+            floor_sats_per_vbyte: 2.,
+            ceiling_sats_per_vbyte: Some(50.),
+            ..FeeEstimationConfig::default()
+        };
+
+        let sources = [KnownFeeEstimator(0.5)];
+        let est = estimate_fee_rate_impl(&sources, &config).await.unwrap();
+        assert_eq!(est.sats_per_vbyte, 2.);
+
+        let sources = [KnownFeeEstimator(500.)];
+        let est = estimate_fee_rate_impl(&sources, &config).await.unwrap();
+        assert_eq!(est.sats_per_vbyte, 50.);
+
+        // Bogus estimates are ignored rather than clamped.
+        let sources = [KnownFeeEstimator(f64::NAN), KnownFeeEstimator(-1.)];
+        let est = estimate_fee_rate_impl(&sources, &config).await;
+        assert!(est.is_err());
+    }
+
     #[tokio::test]
     #[cfg_attr(not(feature = "integration-tests"), ignore)]
     async fn prod_estimate_fee_rate_works() {
         let client = reqwest::Client::new();
 
-        let ans = estimate_fee_rate(&client).await.unwrap();
+        let fee_sources: [FeeSource; 2] = [
+            FeeSource::MempoolSpace(MempoolSpace {
+                base_url: "https://mempool.space".to_string(),
+                client: client.clone(),
+            }),
+            FeeSource::BitcoinerLive(BitcoinerLive {
+                base_url: "https://bitcoiner.live".to_string(),
+                client: client.clone(),
+                max_age: Duration::from_secs(300),
+            }),
+        ];
+
+        let config = FeeEstimationConfig::default();
+        let ans = estimate_fee_rate_impl(&fee_sources, &config).await.unwrap();
         more_asserts::assert_gt!(ans.sats_per_vbyte, 0.0);
 
         // It's not obvious from the docs that mempool.space returns a fee
@@ -310,10 +480,13 @@ mod tests {
             FeeSource::BitcoinerLive(BitcoinerLive {
                 base_url: bitcoiner_server.url(),
                 client,
+                max_age: Duration::from_secs(300),
             }),
         ];
 
-        let actual_estimate = estimate_fee_rate_impl(&fee_sources).await.unwrap();
+        let actual_estimate = estimate_fee_rate_impl(&fee_sources, &FeeEstimationConfig::default())
+            .await
+            .unwrap();
 
         // The expected response here is (15 + 13) / 2 = 14.
         let expected_estimate = 14.0;
@@ -358,10 +531,13 @@ mod tests {
             FeeSource::BitcoinerLive(BitcoinerLive {
                 base_url: bitcoiner_server.url(),
                 client,
+                max_age: Duration::from_secs(300),
             }),
         ];
 
-        let actual_estimate = estimate_fee_rate_impl(&fee_sources).await.unwrap();
+        let actual_estimate = estimate_fee_rate_impl(&fee_sources, &FeeEstimationConfig::default())
+            .await
+            .unwrap();
 
         // Only mempool responded, so only its response is used to compute
         // the average.
@@ -406,10 +582,11 @@ mod tests {
             FeeSource::BitcoinerLive(BitcoinerLive {
                 base_url: bitcoiner_server.url(),
                 client,
+                max_age: Duration::from_secs(300),
             }),
         ];
 
-        let response = estimate_fee_rate_impl(&fee_sources).await;
+        let response = estimate_fee_rate_impl(&fee_sources, &FeeEstimationConfig::default()).await;
         assert!(response.is_err());
 
         mempool_mock.assert();
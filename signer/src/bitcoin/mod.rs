@@ -5,6 +5,7 @@ use std::future::Future;
 use bitcoin::BlockHash;
 use bitcoin::Txid;
 
+use bitcoincore_rpc_json::GetBlockHeaderResult;
 use bitcoincore_rpc_json::GetMempoolEntryResult;
 use bitcoincore_rpc_json::GetTxOutResult;
 use rpc::BitcoinTxInfo;
@@ -15,6 +16,7 @@ use crate::error::Error;
 pub mod client;
 pub mod fees;
 pub mod packaging;
+pub mod poller;
 pub mod rpc;
 pub mod utxo;
 pub mod validation;
@@ -46,6 +48,17 @@ pub enum TransactionLookupHint {
 /// Represents the ability to interact with the bitcoin blockchain
 #[cfg_attr(any(test, feature = "testing"), mockall::automock())]
 pub trait BitcoinInteract: Sync + Send {
+    /// Get the block hash of the current chain tip.
+    fn get_best_block_hash(&self) -> impl Future<Output = Result<BlockHash, Error>> + Send;
+
+    /// Get the header of the block with the given block hash, along with
+    /// information about where it sits in the blockchain. Returns `None`
+    /// if bitcoin-core does not know about the block.
+    fn get_block_header(
+        &self,
+        block_hash: &BlockHash,
+    ) -> impl Future<Output = Result<Option<GetBlockHeaderResult>, Error>> + Send;
+
     /// Get block
     fn get_block(
         &self,
@@ -65,8 +78,8 @@ pub trait BitcoinInteract: Sync + Send {
         block_hash: &BlockHash,
     ) -> impl Future<Output = Result<Option<BitcoinTxInfo>, Error>> + Send;
 
-    /// Estimate fee rate
-    // This should be implemented with the help of the `fees::EstimateFees` trait
+    /// Estimate the current market fee rate, in sats per vbyte, using the
+    /// sources configured in `[bitcoin.fees]`.
     fn estimate_fee_rate(&self) -> impl std::future::Future<Output = Result<f64, Error>> + Send;
 
     /// Broadcast transaction
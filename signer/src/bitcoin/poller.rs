@@ -0,0 +1,203 @@
+//! This module provides a stream of bitcoin block hashes that is driven by
+//! polling bitcoin-core's RPC interface. It is an alternative to the
+//! ZeroMQ streams in the [`zmq`](super::zmq) module for deployments where
+//! bitcoin-core's ZeroMQ interface is not reachable.
+//!
+//! The stream calls `getbestblockhash` at a fixed interval and emits the
+//! block hash of the chain tip whenever it changes. When the previous
+//! chain tip is no longer on the best chain, which we learn through
+//! `getblockheader`, we note the reorg. The block observer walks back from
+//! the new chain tip to the blocks that it has already processed, so a
+//! reorg does not need any special handling beyond emitting the new chain
+//! tip.
+
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use bitcoin::BlockHash;
+use futures::stream::Stream;
+use futures::stream::StreamExt as _;
+use tokio::sync::mpsc::Sender;
+use tokio::time::MissedTickBehavior;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::bitcoin::BitcoinInteract;
+use crate::error::Error;
+
+/// The capacity of the channel between the polling task and the stream.
+/// The chain tip rarely changes, so this does not need to be large.
+const BLOCK_HASH_CHANNEL_CAPACITY: usize = 32;
+
+/// A stream of bitcoin block hashes that is driven by polling
+/// bitcoin-core for its chain tip.
+///
+/// The first item is the chain tip at the time of the first poll, and
+/// after that there is an item each time the chain tip changes.
+pub struct PollingBlockHashStream {
+    /// The receiving end of the channel that the background task sends
+    /// block hashes over.
+    inner: ReceiverStream<Result<BlockHash, Error>>,
+}
+
+impl PollingBlockHashStream {
+    /// Create a new `PollingBlockHashStream` and spawn the background
+    /// task that polls bitcoin-core for the chain tip every
+    /// `poll_interval`.
+    ///
+    /// The background task stops when this stream is dropped.
+    pub fn new<B>(bitcoin_client: B, poll_interval: Duration) -> Self
+    where
+        B: BitcoinInteract + 'static,
+    {
+        let (sender, receiver) = tokio::sync::mpsc::channel(BLOCK_HASH_CHANNEL_CAPACITY);
+        let task = PollingTask {
+            bitcoin_client,
+            poll_interval,
+            chain_tip: None,
+            sender,
+        };
+        tokio::spawn(task.run());
+
+        Self {
+            inner: ReceiverStream::new(receiver),
+        }
+    }
+}
+
+impl Stream for PollingBlockHashStream {
+    type Item = Result<BlockHash, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// The background task behind a [`PollingBlockHashStream`].
+struct PollingTask<B> {
+    /// The client used to fetch the chain tip.
+    bitcoin_client: B,
+    /// How long to wait between polls.
+    poll_interval: Duration,
+    /// The most recent chain tip that we have sent over the channel.
+    chain_tip: Option<BlockHash>,
+    /// The sending end of the channel backing the stream.
+    sender: Sender<Result<BlockHash, Error>>,
+}
+
+impl<B: BitcoinInteract> PollingTask<B> {
+    /// Poll bitcoin-core until the receiving end of the channel is
+    /// dropped.
+    async fn run(mut self) {
+        let mut interval = tokio::time::interval(self.poll_interval);
+        // If a poll takes longer than the interval, we do not want to
+        // make up for the missed ticks with a burst of requests.
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            if self.sender.is_closed() {
+                break;
+            }
+
+            let block_hash = match self.bitcoin_client.get_best_block_hash().await {
+                Ok(block_hash) => block_hash,
+                Err(error) => {
+                    tracing::warn!(%error, "could not fetch the bitcoin chain tip");
+                    continue;
+                }
+            };
+
+            if self.chain_tip == Some(block_hash) {
+                continue;
+            }
+
+            if let Some(previous_tip) = self.chain_tip {
+                self.check_for_reorg(previous_tip, block_hash).await;
+            }
+
+            tracing::debug!(%block_hash, "observed new bitcoin chain tip");
+            self.chain_tip = Some(block_hash);
+            if self.sender.send(Ok(block_hash)).await.is_err() {
+                break;
+            }
+        }
+
+        tracing::info!("block hash poller has stopped");
+    }
+
+    /// Check whether the previous chain tip is still on the best chain,
+    /// logging a reorg if it is not.
+    async fn check_for_reorg(&self, previous_tip: BlockHash, chain_tip: BlockHash) {
+        match self.bitcoin_client.get_block_header(&previous_tip).await {
+            // Bitcoin-core reports -1 confirmations for blocks that are
+            // not on the best chain.
+            Ok(Some(header)) if header.confirmations < 0 => {
+                tracing::warn!(
+                    %previous_tip,
+                    %chain_tip,
+                    previous_height = header.height,
+                    "bitcoin reorg detected, previous chain tip is no longer on the best chain"
+                );
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                tracing::warn!(%previous_tip, "previous bitcoin chain tip is unknown to bitcoin-core");
+            }
+            Err(error) => {
+                tracing::warn!(%error, %previous_tip, "could not fetch the previous chain tip header");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use bitcoin::hashes::Hash as _;
+
+    use crate::bitcoin::MockBitcoinInteract;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn polling_stream_emits_chain_tip_changes() {
+        let hash = |byte| BlockHash::from_byte_array([byte; 32]);
+        // The chain tip stays the same for a few polls before changing,
+        // and the stream should only emit the changes.
+        let tips = [hash(1), hash(1), hash(2), hash(2), hash(2), hash(3)];
+        let polls = Arc::new(AtomicUsize::new(0));
+
+        let mut client = MockBitcoinInteract::new();
+        let counter = polls.clone();
+        client.expect_get_best_block_hash().returning(move || {
+            let index = counter.fetch_add(1, Ordering::SeqCst);
+            let block_hash = tips[index.min(tips.len() - 1)];
+            Box::pin(std::future::ready(Ok(block_hash)))
+        });
+        client
+            .expect_get_block_header()
+            .returning(|_| Box::pin(std::future::ready(Ok(None))));
+
+        let mut stream = PollingBlockHashStream::new(client, Duration::from_millis(5));
+
+        for expected in [hash(1), hash(2), hash(3)] {
+            let item = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert_eq!(item, expected);
+        }
+
+        // There are no more changes to the chain tip, so nothing else
+        // should come out of the stream even though we keep polling.
+        let item = tokio::time::timeout(Duration::from_millis(50), stream.next()).await;
+        assert!(item.is_err());
+        assert!(polls.load(Ordering::SeqCst) > tips.len());
+    }
+}
@@ -17,6 +17,7 @@ use bitcoincore_rpc::jsonrpc::error::RpcError;
 use bitcoincore_rpc::Auth;
 use bitcoincore_rpc::Error as BtcRpcError;
 use bitcoincore_rpc::RpcApi as _;
+use bitcoincore_rpc_json::GetBlockHeaderResult;
 use bitcoincore_rpc_json::GetMempoolEntryResult;
 use bitcoincore_rpc_json::GetRawTransactionResultVin;
 use bitcoincore_rpc_json::GetRawTransactionResultVout as BitcoinTxInfoVout;
@@ -24,6 +25,7 @@ use bitcoincore_rpc_json::GetTxOutResult;
 use serde::Deserialize;
 use url::Url;
 
+use crate::bitcoin::fees::FeeEstimator;
 use crate::bitcoin::BitcoinInteract;
 use crate::error::Error;
 
@@ -244,6 +246,9 @@ pub struct FeeEstimate {
 pub struct BitcoinCoreClient {
     /// The underlying bitcoin-core client
     inner: Arc<bitcoincore_rpc::Client>,
+    /// Used to estimate fee rates from bitcoin-core and any other
+    /// configured fee sources.
+    fee_estimator: Arc<FeeEstimator>,
 }
 
 /// Implement TryFrom for Url to allow for easy conversion from a URL to a
@@ -277,7 +282,17 @@ impl BitcoinCoreClient {
             .map(Arc::new)
             .map_err(|err| Error::BitcoinCoreRpcClient(err, url.to_string()))?;
 
-        Ok(Self { inner: client })
+        Ok(Self {
+            inner: client,
+            fee_estimator: Arc::new(FeeEstimator::default()),
+        })
+    }
+
+    /// Use the given fee estimator when estimating fee rates through
+    /// [`BitcoinInteract::estimate_fee_rate`].
+    pub fn with_fee_estimator(mut self, fee_estimator: Arc<FeeEstimator>) -> Self {
+        self.fee_estimator = fee_estimator;
+        self
     }
 
     /// Return a reference to the inner bitcoin-core RPC client.
@@ -285,6 +300,28 @@ impl BitcoinCoreClient {
         &self.inner
     }
 
+    /// Fetch the block hash of the tip of the most-work fully-validated
+    /// chain.
+    pub fn get_best_block_hash(&self) -> Result<BlockHash, Error> {
+        self.inner
+            .get_best_block_hash()
+            .map_err(Error::BitcoinCoreRpc)
+    }
+
+    /// Fetch the header of the block identified by the given block hash,
+    /// along with its height and number of confirmations. The number of
+    /// confirmations is -1 if the block is not on the best chain.
+    pub fn get_block_header(
+        &self,
+        block_hash: &BlockHash,
+    ) -> Result<Option<GetBlockHeaderResult>, Error> {
+        match self.inner.get_block_header_info(block_hash) {
+            Ok(header) => Ok(Some(header)),
+            Err(BtcRpcError::JsonRpc(JsonRpcError::Rpc(RpcError { code: -5, .. }))) => Ok(None),
+            Err(error) => Err(Error::BitcoinCoreGetBlockHeader(error, *block_hash)),
+        }
+    }
+
     /// Fetch the block identified by the given block hash.
     pub fn get_block(&self, block_hash: &BlockHash) -> Result<Option<Block>, Error> {
         match self.inner.get_block(block_hash) {
@@ -540,6 +577,17 @@ impl BitcoinInteract for BitcoinCoreClient {
             .map(|_| ())
     }
 
+    async fn get_best_block_hash(&self) -> Result<BlockHash, Error> {
+        self.get_best_block_hash()
+    }
+
+    async fn get_block_header(
+        &self,
+        block_hash: &BlockHash,
+    ) -> Result<Option<GetBlockHeaderResult>, Error> {
+        self.get_block_header(block_hash)
+    }
+
     async fn get_block(&self, block_hash: &BlockHash) -> Result<Option<Block>, Error> {
         self.get_block(block_hash)
     }
@@ -557,10 +605,9 @@ impl BitcoinInteract for BitcoinCoreClient {
     }
 
     async fn estimate_fee_rate(&self) -> Result<f64, Error> {
-        // TODO(542): This function is supposed to incorporate other fee
-        // estimation methods, in particular the ones in the
-        // src/bitcoin/fees.rs module.
-        self.estimate_fee_rate(1)
+        self.fee_estimator
+            .estimate_fee_rate(self)
+            .await
             .map(|estimate| estimate.sats_per_vbyte)
     }
 
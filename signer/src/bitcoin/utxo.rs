@@ -120,6 +120,55 @@ pub struct SignerBtcState {
     pub magic_bytes: [u8; 2],
 }
 
+/// The sBTC data in the `OP_RETURN` output of a sweep transaction.
+///
+/// The layout of the data is described in the documentation of
+/// [`UnsignedTransaction::new_op_return_output`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SbtcOpReturn {
+    /// Two byte prefix for BTC transactions that are related to the Stacks
+    /// blockchain.
+    pub magic_bytes: [u8; 2],
+    /// The version of the data layout.
+    pub version: u8,
+    /// The number of deposits swept in by the transaction.
+    pub num_deposits: u16,
+    /// The aggregated bitmap of how the signers voted for the requests
+    /// serviced by the transaction.
+    pub signer_bitmap: [u8; 16],
+    /// The merkle root of the withdrawal requests serviced by the
+    /// transaction, if there are any.
+    pub withdrawal_merkle_root: Option<[u8; 20]>,
+}
+
+impl SbtcOpReturn {
+    /// Decode the sBTC data from the `scriptPubKey` of an `OP_RETURN`
+    /// output. `None` is returned if the script does not follow the
+    /// layout used by the signers.
+    pub fn from_script_pubkey(script_pubkey: &bitcoin::Script) -> Option<Self> {
+        const OP_RETURN: u8 = bitcoin::opcodes::all::OP_RETURN.to_u8();
+        const OP_PUSHBYTES_21: u8 = bitcoin::opcodes::all::OP_PUSHBYTES_21.to_u8();
+        const OP_PUSHBYTES_41: u8 = bitcoin::opcodes::all::OP_PUSHBYTES_41.to_u8();
+
+        let (sbtc_data, merkle_root) = match script_pubkey.as_bytes() {
+            [OP_RETURN, OP_PUSHBYTES_21, data @ ..] if data.len() == 21 => (data, None),
+            [OP_RETURN, OP_PUSHBYTES_41, data @ ..] if data.len() == 41 => {
+                let merkle_root: [u8; 20] = data[21..].try_into().ok()?;
+                (&data[..21], Some(merkle_root))
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            magic_bytes: [sbtc_data[0], sbtc_data[1]],
+            version: sbtc_data[2],
+            num_deposits: u16::from_be_bytes([sbtc_data[3], sbtc_data[4]]),
+            signer_bitmap: sbtc_data[5..21].try_into().ok()?,
+            withdrawal_merkle_root: merkle_root,
+        })
+    }
+}
+
 /// The set of sBTC requests with additional relevant
 /// information used to construct the next transaction package.
 #[derive(Debug)]
@@ -907,7 +956,8 @@ impl<'a> UnsignedTransaction<'a> {
     ///    magic   op   N_d   bitmap
     /// ```
     ///
-    /// In the above layout, magic is the UTF-8 encoded string "ST", op is
+    /// In the above layout, magic is the two byte prefix from
+    /// [`SignerBtcState::magic_bytes`], op is
     /// the version byte, and N_d is the of deposits in this transaction
     /// encoded as a big-endian two byte unsigned integer.
     ///
@@ -942,7 +992,8 @@ impl<'a> UnsignedTransaction<'a> {
     ///    magic   op   N_d   signer  bitmap
     /// ```
     ///
-    /// In the above layout, magic is the UTF-8 encoded string "ST", op is
+    /// In the above layout, magic is the two byte prefix from
+    /// [`SignerBtcState::magic_bytes`], op is
     /// the version byte, and N_d is the of deposits in this transaction
     /// encoded as a big-endian two byte unsigned integer.
     ///
@@ -1269,13 +1320,7 @@ pub trait TxDeconstructor: BitcoinInputsOutputs {
         // Note that these cannot be deposits because deposits aren't
         // key-path spendable by the signers.
         if !self.is_signer_created(signer_script_pubkeys) {
-            return self
-                .outputs()
-                .iter()
-                .enumerate()
-                .filter(|(_, tx_out)| signer_script_pubkeys.contains(&tx_out.script_pubkey))
-                .filter_map(|(index, _)| self.vout_to_output(index, TxOutputType::Donation))
-                .collect();
+            return self.to_donations(signer_script_pubkeys);
         }
 
         self.outputs()
@@ -1289,6 +1334,17 @@ pub trait TxDeconstructor: BitcoinInputsOutputs {
             .collect()
     }
 
+    /// Return the outputs in this transaction that the signers control,
+    /// as donations.
+    fn to_donations(&self, signer_script_pubkeys: &HashSet<ScriptBuf>) -> Vec<TxOutput> {
+        self.outputs()
+            .iter()
+            .enumerate()
+            .filter(|(_, tx_out)| signer_script_pubkeys.contains(&tx_out.script_pubkey))
+            .filter_map(|(index, _)| self.vout_to_output(index, TxOutputType::Donation))
+            .collect()
+    }
+
     /// Take an output index and the known output type and return the
     /// output.
     fn vout_to_output(&self, index: usize, output_type: TxOutputType) -> Option<TxOutput> {
@@ -1315,6 +1371,30 @@ pub trait TxDeconstructor: BitcoinInputsOutputs {
         })
     }
 
+    /// Decode the sBTC data in the `OP_RETURN` output of a transaction
+    /// created by the signers, and check that it starts with one of the
+    /// given magic bytes.
+    ///
+    /// The `OP_RETURN` output is the second output of the transaction, see
+    /// the description of `UnsignedTransaction` for the full layout.
+    fn sbtc_op_return(&self, known_magic_bytes: &[[u8; 2]]) -> Result<SbtcOpReturn, Error> {
+        let txid = self.tx_ref().compute_txid();
+        let op_return = self
+            .outputs()
+            .get(1)
+            .and_then(|tx_out| SbtcOpReturn::from_script_pubkey(&tx_out.script_pubkey))
+            .ok_or(Error::SweepOpReturnMalformed(txid))?;
+
+        if !known_magic_bytes.contains(&op_return.magic_bytes) {
+            return Err(Error::SweepOpReturnMagicBytes {
+                txid,
+                magic_bytes: op_return.magic_bytes,
+            });
+        }
+
+        Ok(op_return)
+    }
+
     /// Whether this transaction was created by the signers given the
     /// possible scriptPubKeys.
     ///
@@ -1930,6 +2010,50 @@ mod tests {
         }
     }
 
+    #[test_case(Vec::new(), [b'T', b'3']; "no withdrawals, regtest magic bytes")]
+    #[test_case(vec![create_withdrawal(1000, 0, 0)], [b'X', b'2']; "one withdrawal, mainnet magic bytes")]
+    fn sbtc_op_return_decodes_constructed_output(
+        withdrawals: Vec<WithdrawalRequest>,
+        magic_bytes: [u8; 2],
+    ) {
+        let mut requests = SbtcRequests {
+            deposits: vec![create_deposit(123456, 0, 0)],
+            withdrawals,
+            signer_state: SignerBtcState {
+                utxo: SignerUtxo {
+                    outpoint: generate_outpoint(500_000_000_000, 0),
+                    amount: 500_000_000_000,
+                    public_key: generate_x_only_public_key(),
+                },
+                fee_rate: 0.0,
+                public_key: generate_x_only_public_key(),
+                last_fees: None,
+                magic_bytes,
+            },
+            num_signers: 10,
+            accept_threshold: 0,
+            sbtc_limits: SbtcLimits::default(),
+        };
+
+        let mut transactions = requests.construct_transactions().unwrap();
+        assert_eq!(transactions.len(), 1);
+        let unsigned_tx = transactions.pop().unwrap();
+
+        let script_pubkey = &unsigned_tx.tx.output[1].script_pubkey;
+        let op_return = SbtcOpReturn::from_script_pubkey(script_pubkey).unwrap();
+
+        assert_eq!(op_return.magic_bytes, magic_bytes);
+        assert_eq!(op_return.version, OP_RETURN_VERSION);
+        assert_eq!(op_return.num_deposits, 1);
+
+        let expected_merkle_root = calculate_merkle_root(&mut requests.withdrawals);
+        assert_eq!(op_return.withdrawal_merkle_root, expected_merkle_root);
+
+        // The signers' UTXO output is not an OP_RETURN output at all.
+        let script_pubkey = &unsigned_tx.tx.output[0].script_pubkey;
+        assert!(SbtcOpReturn::from_script_pubkey(script_pubkey).is_none());
+    }
+
     /// Deposit requests add to the signers' UTXO.
     #[test]
     fn deposits_with_low_amount_and_high_max_fee() {
@@ -24,6 +24,9 @@ use crate::storage::model::QualifiedRequestId;
 use crate::storage::model::SignerVotes;
 use crate::storage::DbRead;
 use crate::DEPOSIT_LOCKTIME_BLOCK_BUFFER;
+use crate::DEPOSIT_LOCKTIME_TIME_BUFFER;
+use crate::ROTATION_SWEEP_MAX_FEE;
+use crate::WITHDRAWAL_BLOCKS_EXPIRY;
 
 use super::utxo::DepositRequest;
 use super::utxo::RequestRef;
@@ -223,7 +226,7 @@ impl BitcoinPreSignRequest {
             utxo: signer_utxo,
             public_key: bitcoin::XOnlyPublicKey::from(btc_ctx.aggregate_key),
             last_fees: self.last_fees,
-            magic_bytes: [b'T', b'3'], //TODO(#472): Use the correct magic bytes.
+            magic_bytes: ctx.config().signer.magic_bytes(),
         };
         let mut outputs = Vec::new();
 
@@ -297,6 +300,12 @@ impl BitcoinPreSignRequest {
         // network.
         signer_state.last_fees = None;
         let sbtc_limits = ctx.state().get_current_limits();
+        let chain_tip_median_time_past = ctx
+            .get_storage()
+            .get_bitcoin_block(&btc_ctx.chain_tip)
+            .await?
+            .ok_or(Error::MissingBitcoinBlock(btc_ctx.chain_tip))?
+            .median_time_past;
         let out = BitcoinTxValidationData {
             signer_sighash: sighashes.signer_sighash(),
             deposit_sighashes: sighashes.deposit_sighashes(),
@@ -305,6 +314,7 @@ impl BitcoinPreSignRequest {
             tx_fee: Amount::from_sat(tx.tx_fee),
             reports,
             chain_tip_height: btc_ctx.chain_tip_height,
+            chain_tip_median_time_past,
             // If the cap is None, then we assume that it is unlimited.
             max_deposit_amount: sbtc_limits.per_deposit_cap(),
             max_withdrawal_amount: sbtc_limits.per_withdrawal_cap(),
@@ -317,6 +327,7 @@ impl BitcoinPreSignRequest {
 /// An intermediate struct to aid in computing validation of deposits and
 /// withdrawals and transforming the computed sighash into a
 /// [`BitcoinTxSigHash`].
+#[derive(Debug)]
 pub struct BitcoinTxValidationData {
     /// The sighash of the signers' prevout
     pub signer_sighash: SignatureHash,
@@ -333,6 +344,8 @@ pub struct BitcoinTxValidationData {
     pub tx_fee: Amount,
     /// the chain tip height.
     pub chain_tip_height: u64,
+    /// The median-time-past of the chain tip.
+    pub chain_tip_median_time_past: u64,
     /// Maximum amount of BTC allowed to be pegged-in per transaction.
     pub max_deposit_amount: Amount,
     /// Maximum amount of BTC allowed to be pegged-out per transaction.
@@ -363,6 +376,7 @@ impl BitcoinTxValidationData {
         let validation_results = self.reports.deposits.iter().map(|(_, report)| {
             report.validate(
                 self.chain_tip_height,
+                self.chain_tip_median_time_past,
                 &self.tx,
                 self.tx_fee,
                 self.max_deposit_amount,
@@ -414,24 +428,154 @@ impl BitcoinTxValidationData {
             .withdrawals
             .iter()
             .enumerate()
-            .map(|(output_index, (_, report))| BitcoinWithdrawalOutput {
-                bitcoin_txid,
-                bitcoin_chain_tip: self.chain_tip,
-                output_index: output_index as u32,
-                request_id: report.id.request_id,
-                stacks_txid: report.id.txid,
-                stacks_block_hash: report.id.block_hash,
-                validation_result: report.validate(
-                    self.chain_tip_height,
-                    &self.tx,
-                    self.tx_fee,
-                    self.max_withdrawal_amount,
-                ),
-                is_valid_tx,
+            .map(|(index, (_, report))| {
+                let output_index = withdrawal_output_index(index);
+                BitcoinWithdrawalOutput {
+                    bitcoin_txid,
+                    bitcoin_chain_tip: self.chain_tip,
+                    output_index: output_index as u32,
+                    request_id: report.id.request_id,
+                    stacks_txid: report.id.txid,
+                    stacks_block_hash: report.id.block_hash,
+                    validation_result: report.validate(
+                        self.chain_tip_height,
+                        output_index,
+                        &self.tx,
+                        self.tx_fee,
+                        self.max_withdrawal_amount,
+                    ),
+                    is_valid_tx,
+                }
             })
             .collect()
     }
 
+    /// Check that the given transaction, sent to us by the coordinator in
+    /// a [`BitcoinTransactionSignRequest`](crate::message::BitcoinTransactionSignRequest),
+    /// is the transaction that we reconstructed and validated when we
+    /// acked the [`BitcoinPreSignRequest`].
+    ///
+    /// The first input must spend the signers' UTXO and every other input
+    /// must be a deposit request that passes validation. The first two
+    /// outputs must be the signers' new UTXO and the OP_RETURN output, and
+    /// every other output must fulfill a withdrawal request that passes
+    /// validation. The fees paid by the given transaction are assessed
+    /// against the `max_fee` of each request during validation. Lastly,
+    /// the transaction must be identical to the one that we constructed.
+    pub fn validate_sign_request(
+        &self,
+        tx: &bitcoin::Transaction,
+        aggregate_key: &PublicKey,
+    ) -> Result<(), BitcoinSignRequestError> {
+        let txid = tx.compute_txid();
+        let signer_state = &self.reports.signer_state;
+
+        if XOnlyPublicKey::from(aggregate_key) != signer_state.public_key {
+            return Err(BitcoinSignRequestError::AggregateKeyMismatch(txid));
+        }
+
+        // The first input is always the signers' UTXO.
+        let Some((signer_input, deposit_inputs)) = tx.input.split_first() else {
+            return Err(BitcoinSignRequestError::SignerInputMismatch(txid));
+        };
+        if signer_input.previous_output != signer_state.utxo.outpoint {
+            return Err(BitcoinSignRequestError::SignerInputMismatch(txid));
+        }
+
+        // Every other input must be a deposit request that we know about.
+        let mut input_amount = signer_state.utxo.amount;
+        let mut deposit_reports = Vec::with_capacity(deposit_inputs.len());
+        for tx_in in deposit_inputs {
+            let outpoint = tx_in.previous_output;
+            let report = self
+                .reports
+                .deposits
+                .iter()
+                .map(|(_, report)| report)
+                .find(|report| report.outpoint == outpoint)
+                .ok_or(BitcoinSignRequestError::UnexpectedInput(outpoint))?;
+
+            input_amount = input_amount.saturating_add(report.amount);
+            deposit_reports.push(report);
+        }
+
+        // The first two outputs are always the signers' new UTXO and the
+        // OP_RETURN output.
+        let Some([signer_output, op_return_output]) = tx.output.get(..2) else {
+            return Err(BitcoinSignRequestError::SignerOutputMismatch(txid));
+        };
+        if signer_output.script_pubkey != self.tx.output[0].script_pubkey {
+            return Err(BitcoinSignRequestError::SignerOutputMismatch(txid));
+        }
+        if op_return_output != &self.tx.output[1] {
+            return Err(BitcoinSignRequestError::OpReturnMismatch(txid));
+        }
+
+        // The fee is whatever is not spent to the outputs. The fee that
+        // each request pays is assessed against its `max_fee` when we
+        // validate it below.
+        let output_amount = tx
+            .output
+            .iter()
+            .map(|tx_out| tx_out.value.to_sat())
+            .fold(0u64, u64::saturating_add);
+        let tx_fee = input_amount
+            .checked_sub(output_amount)
+            .map(Amount::from_sat)
+            .ok_or(BitcoinSignRequestError::OutputsExceedInputs(txid))?;
+
+        for report in deposit_reports {
+            let result = report.validate(
+                self.chain_tip_height,
+                self.chain_tip_median_time_past,
+                tx,
+                tx_fee,
+                self.max_deposit_amount,
+            );
+            if !matches!(
+                result,
+                InputValidationResult::Ok | InputValidationResult::CannotSignUtxo
+            ) {
+                return Err(BitcoinSignRequestError::InvalidDeposit {
+                    outpoint: report.outpoint,
+                    result,
+                });
+            }
+        }
+
+        // Every other output must fulfill a withdrawal request, and they
+        // are in the same order as the requests in our reports.
+        let num_withdrawal_outputs = tx.output.len() - 2;
+        if num_withdrawal_outputs > self.reports.withdrawals.len() {
+            return Err(BitcoinSignRequestError::UnexpectedOutput {
+                txid,
+                output_index: withdrawal_output_index(self.reports.withdrawals.len()),
+            });
+        }
+        for (index, (_, report)) in self.reports.withdrawals.iter().enumerate() {
+            let result = report.validate(
+                self.chain_tip_height,
+                withdrawal_output_index(index),
+                tx,
+                tx_fee,
+                self.max_withdrawal_amount,
+            );
+            if result != WithdrawalValidationResult::Ok {
+                return Err(BitcoinSignRequestError::InvalidWithdrawal { id: report.id, result });
+            }
+        }
+
+        // Everything checks out, but the transaction must be the exact
+        // one that we reconstructed, since those are the sighashes that
+        // we have agreed to sign.
+        let expected = self.tx.compute_txid();
+        if txid != expected {
+            return Err(BitcoinSignRequestError::PackageMismatch { expected, actual: txid });
+        }
+
+        Ok(())
+    }
+
     /// Check whether the transaction is valid. This determines whether
     /// this signer will sign any of the sighashes for the transaction
     ///
@@ -439,11 +583,20 @@ impl BitcoinTxValidationData {
     /// that the transaction can still pass validation if this signer is
     /// not a part of the signing set locking one or more deposits. In such
     /// a case, it will just sign for the deposits that it can.
+    ///
+    /// A transaction without deposits or withdrawals only hands the
+    /// signers' UTXO over to a new aggregate key. Its fee comes out of the
+    /// signers' UTXO, so it must not exceed [`ROTATION_SWEEP_MAX_FEE`].
     pub fn is_valid_tx(&self) -> bool {
+        if self.reports.deposits.is_empty() && self.reports.withdrawals.is_empty() {
+            return self.tx_fee.to_sat() <= ROTATION_SWEEP_MAX_FEE;
+        }
+
         let deposit_validation_results = self.reports.deposits.iter().all(|(_, report)| {
             matches!(
                 report.validate(
                     self.chain_tip_height,
+                    self.chain_tip_median_time_past,
                     &self.tx,
                     self.tx_fee,
                     self.max_deposit_amount
@@ -452,23 +605,38 @@ impl BitcoinTxValidationData {
             )
         });
 
-        let withdrawal_validation_results = self.reports.withdrawals.iter().all(|(_, report)| {
-            match report.validate(
-                self.chain_tip_height,
-                &self.tx,
-                self.tx_fee,
-                self.max_withdrawal_amount,
-            ) {
-                WithdrawalValidationResult::Unsupported
-                | WithdrawalValidationResult::Unknown
-                | WithdrawalValidationResult::AmountTooHigh => false,
-            }
-        });
+        let withdrawal_validation_results =
+            self.reports
+                .withdrawals
+                .iter()
+                .enumerate()
+                .all(|(index, (_, report))| {
+                    matches!(
+                        report.validate(
+                            self.chain_tip_height,
+                            withdrawal_output_index(index),
+                            &self.tx,
+                            self.tx_fee,
+                            self.max_withdrawal_amount,
+                        ),
+                        WithdrawalValidationResult::Ok
+                    )
+                });
 
         deposit_validation_results && withdrawal_validation_results
     }
 }
 
+/// Return the index of the transaction output for the withdrawal request
+/// at the given index in the [`SbtcReports::withdrawals`] vector.
+///
+/// The first two outputs of a sweep transaction are always the signers'
+/// UTXO and the OP_RETURN output. The withdrawal outputs follow in the
+/// same order as the withdrawal requests.
+fn withdrawal_output_index(index: usize) -> usize {
+    index + 2
+}
+
 /// The set of sBTC requests with additional relevant
 /// information used to construct the next transaction package.
 #[derive(Debug)]
@@ -529,6 +697,12 @@ pub enum InputValidationResult {
     /// Given the current time and block height, it would be imprudent to
     /// attempt to sweep in a deposit request with the given lock-time.
     LockTimeExpiry,
+    /// The deposit request has a time based lock-time, and given the
+    /// median-time-past of the chain tip and of the parent of the block
+    /// confirming the deposit, the depositor will be able to reclaim it
+    /// too soon for us to safely sweep it in. This is also returned when
+    /// either median-time-past is unknown.
+    TimeBasedLockTimeExpiry,
     /// The signer does not have a record of their vote on the deposit
     /// request in their database.
     NoVote,
@@ -538,8 +712,9 @@ pub enum InputValidationResult {
     /// database.
     Unknown,
     /// The locktime in the reclaim script is in time units and that is not
-    /// supported. This shouldn't happen, since we will not put it in our
-    /// database is this is the case.
+    /// supported. Time based locktimes are now supported, so this is no
+    /// longer returned, but validation results written by older versions
+    /// of the signer may still have it.
     UnsupportedLockTime,
 }
 
@@ -558,14 +733,39 @@ impl InputValidationResult {
 #[sqlx(type_name = "TEXT", rename_all = "snake_case")]
 #[cfg_attr(feature = "testing", derive(fake::Dummy))]
 pub enum WithdrawalValidationResult {
+    /// The withdrawal request passed validation
+    Ok,
     /// The withdrawal request amount exceeds the allowed per-withdrawal cap
     AmountTooHigh,
+    /// The assessed fee exceeds the max-fee in the withdrawal request.
+    FeeTooHigh,
+    /// The amount in the transaction output does not match the amount in
+    /// the withdrawal request.
+    InvalidAmount,
+    /// The scriptPubKey of the transaction output does not match the
+    /// recipient in the withdrawal request.
+    InvalidRecipient,
+    /// The signer does not have a record of their vote on the withdrawal
+    /// request in their database.
+    NoVote,
+    /// The signer has rejected the withdrawal request.
+    RejectedRequest,
+    /// The withdrawal request has expired, so it should be rejected
+    /// instead of fulfilled.
+    RequestExpired,
+    /// The withdrawal request has already been fulfilled by a sweep
+    /// transaction that has been confirmed on the canonical bitcoin
+    /// blockchain.
+    RequestFulfilled,
+    /// The withdrawal request has already been rejected on the canonical
+    /// stacks blockchain.
+    RequestRejected,
+    /// The withdrawal request transaction is not on the canonical stacks
+    /// blockchain.
+    TxNotOnBestChain,
     /// The signer does not have a record of the withdrawal request in
     /// their database.
     Unknown,
-    /// We do not support withdrawals at the moment so this is always
-    /// returned.
-    Unsupported,
 }
 
 impl WithdrawalValidationResult {
@@ -614,6 +814,73 @@ impl std::error::Error for BitcoinValidationError {
     }
 }
 
+/// The reasons why a signer will not ack a
+/// [`BitcoinTransactionSignRequest`](crate::message::BitcoinTransactionSignRequest).
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
+pub enum BitcoinSignRequestError {
+    /// The transaction does not correspond to any transaction in the
+    /// package that we acked in the most recent [`BitcoinPreSignRequest`].
+    #[error("transaction {0} is not part of the acked pre-sign package")]
+    NotInAckedPackage(Txid),
+    /// The transaction is to be signed with a different aggregate key
+    /// than the one locking the signers' UTXO.
+    #[error("transaction {0} is not signed by the aggregate key of the signers' UTXO")]
+    AggregateKeyMismatch(Txid),
+    /// The first input of the transaction does not spend the signers'
+    /// UTXO.
+    #[error("the first input of transaction {0} does not spend the signers' UTXO")]
+    SignerInputMismatch(Txid),
+    /// An input of the transaction is neither the signers' UTXO nor a
+    /// deposit request in the acked package.
+    #[error("input {0} is not a deposit request in the acked pre-sign package")]
+    UnexpectedInput(OutPoint),
+    /// A deposit request swept in by the transaction failed validation.
+    #[error("deposit request {outpoint} failed validation: {result:?}")]
+    InvalidDeposit {
+        /// The outpoint of the deposit request.
+        outpoint: OutPoint,
+        /// Why the deposit request failed validation.
+        result: InputValidationResult,
+    },
+    /// The first output of the transaction is not the signers' new UTXO.
+    #[error("the first output of transaction {0} does not pay to the signers")]
+    SignerOutputMismatch(Txid),
+    /// The second output of the transaction is not the expected OP_RETURN
+    /// output.
+    #[error("the OP_RETURN output of transaction {0} does not match the expected one")]
+    OpReturnMismatch(Txid),
+    /// The transaction spends more than it takes in.
+    #[error("the outputs of transaction {0} exceed its inputs")]
+    OutputsExceedInputs(Txid),
+    /// An output of the transaction does not fulfill any withdrawal
+    /// request in the acked package.
+    #[error("output {output_index} of transaction {txid} is not an accepted withdrawal")]
+    UnexpectedOutput {
+        /// The ID of the transaction.
+        txid: Txid,
+        /// The index of the unexpected output.
+        output_index: usize,
+    },
+    /// A withdrawal request fulfilled by the transaction failed
+    /// validation.
+    #[error("withdrawal request {id:?} failed validation: {result:?}")]
+    InvalidWithdrawal {
+        /// The ID of the withdrawal request.
+        id: QualifiedRequestId,
+        /// Why the withdrawal request failed validation.
+        result: WithdrawalValidationResult,
+    },
+    /// The transaction passed validation but it is not the transaction
+    /// that we reconstructed for the pre-sign request.
+    #[error("transaction {actual} does not match the expected transaction {expected}")]
+    PackageMismatch {
+        /// The ID of the transaction that we reconstructed.
+        expected: Txid,
+        /// The ID of the transaction in the sign request.
+        actual: Txid,
+    },
+}
+
 /// An enum for the confirmation status of a deposit request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DepositConfirmationStatus {
@@ -657,6 +924,13 @@ pub struct DepositRequestReport {
     /// or not. This should only be `None` if we do not have a record of
     /// the deposit request.
     pub can_accept: Option<bool>,
+    /// The median-time-past of the parent of the block that confirmed
+    /// the deposit request transaction, which is where BIP-68 starts
+    /// counting time based lock-times from. This is `None` if the deposit
+    /// request transaction has not been confirmed on the canonical
+    /// bitcoin blockchain, or if we do not know the median-time-past of
+    /// the parent block.
+    pub parent_median_time_past: Option<u64>,
     /// The deposit amount
     pub amount: u64,
     /// The max fee embedded in the deposit request.
@@ -676,6 +950,7 @@ impl DepositRequestReport {
     fn validate<F>(
         &self,
         chain_tip_height: u64,
+        chain_tip_median_time_past: u64,
         tx: &F,
         tx_fee: Amount,
         max_deposit_amount: Amount,
@@ -706,19 +981,41 @@ impl DepositRequestReport {
             return InputValidationResult::AmountTooHigh;
         }
 
-        // We only sweep a deposit if the depositor cannot reclaim the
-        // deposit within the next DEPOSIT_LOCKTIME_BLOCK_BUFFER blocks.
-        let deposit_age = chain_tip_height.saturating_sub(confirmed_block_height);
-
         match self.lock_time {
+            // We only sweep a deposit if the depositor cannot reclaim the
+            // deposit within the next DEPOSIT_LOCKTIME_BLOCK_BUFFER blocks.
             LockTime::Blocks(height) => {
+                let deposit_age = chain_tip_height.saturating_sub(confirmed_block_height);
                 let max_age = height.value().saturating_sub(DEPOSIT_LOCKTIME_BLOCK_BUFFER) as u64;
                 if deposit_age >= max_age {
                     return InputValidationResult::LockTimeExpiry;
                 }
             }
-            LockTime::Time(_) => {
-                return InputValidationResult::UnsupportedLockTime;
+            // Time based lock-times are measured in units of 512 seconds
+            // against the median-time-past, so we only sweep a deposit if
+            // the depositor cannot reclaim it within the next
+            // DEPOSIT_LOCKTIME_TIME_BUFFER seconds of median-time-past.
+            //
+            // Blocks written before we started recording the
+            // median-time-past have it set to zero. We cannot tell how old
+            // the deposit is in that case, so we do not sweep it.
+            LockTime::Time(time) => {
+                let parent_median_time_past = self
+                    .parent_median_time_past
+                    .filter(|&median_time_past| median_time_past > 0);
+                let Some(parent_median_time_past) = parent_median_time_past else {
+                    return InputValidationResult::TimeBasedLockTimeExpiry;
+                };
+                if chain_tip_median_time_past == 0 {
+                    return InputValidationResult::TimeBasedLockTimeExpiry;
+                }
+                let deposit_age =
+                    chain_tip_median_time_past.saturating_sub(parent_median_time_past);
+                let max_age =
+                    (time.value() as u64 * 512).saturating_sub(DEPOSIT_LOCKTIME_TIME_BUFFER);
+                if deposit_age >= max_age {
+                    return InputValidationResult::TimeBasedLockTimeExpiry;
+                }
             }
         }
 
@@ -783,6 +1080,10 @@ pub enum WithdrawalRequestStatus {
     /// output in another bitcoin transaction that has been confirmed on
     /// the canonical bitcoin blockchain.
     Fulfilled(BitcoinTxId),
+    /// We have a record of the withdrawal request being rejected by a
+    /// `reject-withdrawal-request` contract call that has been confirmed
+    /// on the canonical Stacks blockchain.
+    Rejected,
     /// We have a record of the withdrawal request transaction, and it has
     /// not been confirmed on the canonical Stacks blockchain.
     ///
@@ -814,21 +1115,92 @@ pub struct WithdrawalRequestReport {
     pub max_fee: u64,
     /// The script_pubkey of the output.
     pub script_pubkey: ScriptBuf,
+    /// Whether this signer accepted the withdrawal request or not. This
+    /// should only be `None` if we do not have a record of the signer's
+    /// vote on the withdrawal request.
+    pub is_accepted: Option<bool>,
 }
 
 impl WithdrawalRequestReport {
     /// Validate that the withdrawal request is okay given the report.
+    ///
+    /// The `output_index` is the index of the output in the transaction
+    /// that is supposed to fulfill the withdrawal request.
     pub fn validate<F>(
         &self,
-        _: u64,
-        _: &F,
-        _: Amount,
-        _max_withdrawal_amount: Amount,
+        chain_tip_height: u64,
+        output_index: usize,
+        tx: &F,
+        tx_fee: Amount,
+        max_withdrawal_amount: Amount,
     ) -> WithdrawalValidationResult
     where
         F: FeeAssessment,
     {
-        WithdrawalValidationResult::Unsupported
+        let confirmed_block_height = match self.status {
+            // Withdrawal requests are only written to the database after
+            // they have been confirmed, so this means that we have a
+            // record of the request, but it has not been confirmed on the
+            // canonical Stacks blockchain.
+            WithdrawalRequestStatus::Unconfirmed => {
+                return WithdrawalValidationResult::TxNotOnBestChain;
+            }
+            // This means that we have a record of the withdrawal request
+            // being fulfilled by a sweep transaction that has been
+            // confirmed on the canonical bitcoin blockchain.
+            WithdrawalRequestStatus::Fulfilled(_) => {
+                return WithdrawalValidationResult::RequestFulfilled;
+            }
+            // The signers have already rejected the request on the
+            // canonical Stacks blockchain, and the user's sBTC has been
+            // returned to them.
+            WithdrawalRequestStatus::Rejected => {
+                return WithdrawalValidationResult::RequestRejected;
+            }
+            // The withdrawal request has been confirmed on the canonical
+            // Stacks blockchain and remains unfulfilled.
+            WithdrawalRequestStatus::Confirmed(block_height, _) => block_height,
+        };
+
+        if self.amount > max_withdrawal_amount.to_sat() {
+            return WithdrawalValidationResult::AmountTooHigh;
+        }
+
+        // Once a withdrawal request expires the signers will reject it,
+        // so we should not attempt to fulfill it.
+        let request_age = chain_tip_height.saturating_sub(confirmed_block_height);
+        if request_age >= WITHDRAWAL_BLOCKS_EXPIRY {
+            return WithdrawalValidationResult::RequestExpired;
+        }
+
+        let Some(tx_out) = tx.outputs().get(output_index) else {
+            return WithdrawalValidationResult::Unknown;
+        };
+
+        if tx_out.script_pubkey != self.script_pubkey {
+            return WithdrawalValidationResult::InvalidRecipient;
+        }
+
+        if tx_out.value.to_sat() != self.amount {
+            return WithdrawalValidationResult::InvalidAmount;
+        }
+
+        let Some(assessed_fee) = tx.assess_output_fee(output_index, tx_fee) else {
+            return WithdrawalValidationResult::Unknown;
+        };
+
+        if assessed_fee.to_sat() > self.max_fee {
+            return WithdrawalValidationResult::FeeTooHigh;
+        }
+
+        // Let's check whether we rejected this withdrawal.
+        match self.is_accepted {
+            Some(true) => (),
+            None => return WithdrawalValidationResult::NoVote,
+            Some(false) => return WithdrawalValidationResult::RejectedRequest,
+        }
+
+        WithdrawalValidationResult::Ok
     }
 
     fn to_withdrawal_request(&self, votes: &SignerVotes) -> WithdrawalRequest {
@@ -850,11 +1222,14 @@ mod tests {
     use bitcoin::ScriptBuf;
     use bitcoin::Sequence;
     use bitcoin::TxIn;
+    use bitcoin::TxOut;
     use bitcoin::Txid;
     use bitcoin::Witness;
     use test_case::test_case;
 
+    use crate::bitcoin::utxo::SignerUtxo;
     use crate::context::SbtcLimits;
+    use crate::keys::PrivateKey;
     use crate::storage::model::StacksBlockHash;
     use crate::storage::model::StacksTxId;
     use crate::testing::context::TestContext;
@@ -869,13 +1244,30 @@ mod tests {
         chain_tip_height: u64,
     }
 
+    /// A helper struct to aid in testing of withdrawal validation.
+    #[derive(Debug)]
+    struct WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport,
+        status: WithdrawalValidationResult,
+        chain_tip_height: u64,
+    }
+
     const TX_FEE: Amount = Amount::from_sat(10000);
 
+    /// The median-time-past of the chain tip used in the deposit report
+    /// validation tests.
+    const CHAIN_TIP_MEDIAN_TIME_PAST: u64 = 1_700_000_000;
+
+    fn withdrawal_script() -> ScriptBuf {
+        ScriptBuf::new_p2wpkh(&bitcoin::WPubkeyHash::from_byte_array([1; 20]))
+    }
+
     #[test_case(DepositReportErrorMapping {
         report: DepositRequestReport {
             status: DepositConfirmationStatus::Unconfirmed,
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: None,
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_height(u16::MAX),
@@ -892,6 +1284,7 @@ mod tests {
             status: DepositConfirmationStatus::Spent(BitcoinTxId::from([1; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: None,
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_height(u16::MAX),
@@ -908,6 +1301,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: None,
             can_accept: None,
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_height(u16::MAX),
@@ -924,6 +1318,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(false),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_height(u16::MAX),
@@ -940,6 +1335,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(false),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_height(u16::MAX),
@@ -956,6 +1352,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_height(DEPOSIT_LOCKTIME_BLOCK_BUFFER + 1),
@@ -972,6 +1369,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_height(DEPOSIT_LOCKTIME_BLOCK_BUFFER + 2),
@@ -988,6 +1386,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_512_second_intervals(u16::MAX),
@@ -996,14 +1395,87 @@ mod tests {
             reclaim_script: ScriptBuf::new(),
             signers_public_key: *sbtc::UNSPENDABLE_TAPROOT_KEY,
         },
-        status: InputValidationResult::UnsupportedLockTime,
+        status: InputValidationResult::Ok,
         chain_tip_height: 2,
-    } ; "lock-time-in-time-units-2")]
+    } ; "lock-time-in-time-units")]
     #[test_case(DepositReportErrorMapping {
         report: DepositRequestReport {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST - 512),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            lock_time: LockTime::from_512_second_intervals(
+                (DEPOSIT_LOCKTIME_TIME_BUFFER / 512) as u16 + 3
+            ),
+            outpoint: OutPoint::null(),
+            deposit_script: ScriptBuf::new(),
+            reclaim_script: ScriptBuf::new(),
+            signers_public_key: *sbtc::UNSPENDABLE_TAPROOT_KEY,
+        },
+        status: InputValidationResult::Ok,
+        chain_tip_height: 2,
+    } ; "lock-time-in-time-units-just-outside-buffer")]
+    #[test_case(DepositReportErrorMapping {
+        report: DepositRequestReport {
+            status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            can_sign: Some(true),
+            can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST - 1024 - DEPOSIT_LOCKTIME_TIME_BUFFER % 512),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            lock_time: LockTime::from_512_second_intervals(
+                (DEPOSIT_LOCKTIME_TIME_BUFFER / 512) as u16 + 3
+            ),
+            outpoint: OutPoint::null(),
+            deposit_script: ScriptBuf::new(),
+            reclaim_script: ScriptBuf::new(),
+            signers_public_key: *sbtc::UNSPENDABLE_TAPROOT_KEY,
+        },
+        status: InputValidationResult::TimeBasedLockTimeExpiry,
+        chain_tip_height: 2,
+    } ; "lock-time-in-time-units-expires-soon")]
+    #[test_case(DepositReportErrorMapping {
+        report: DepositRequestReport {
+            status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            can_sign: Some(true),
+            can_accept: Some(true),
+            parent_median_time_past: None,
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            lock_time: LockTime::from_512_second_intervals(u16::MAX),
+            outpoint: OutPoint::null(),
+            deposit_script: ScriptBuf::new(),
+            reclaim_script: ScriptBuf::new(),
+            signers_public_key: *sbtc::UNSPENDABLE_TAPROOT_KEY,
+        },
+        status: InputValidationResult::TimeBasedLockTimeExpiry,
+        chain_tip_height: 2,
+    } ; "lock-time-in-time-units-missing-median-time-past")]
+    #[test_case(DepositReportErrorMapping {
+        report: DepositRequestReport {
+            status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            can_sign: Some(true),
+            can_accept: Some(true),
+            parent_median_time_past: Some(0),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            lock_time: LockTime::from_512_second_intervals(u16::MAX),
+            outpoint: OutPoint::null(),
+            deposit_script: ScriptBuf::new(),
+            reclaim_script: ScriptBuf::new(),
+            signers_public_key: *sbtc::UNSPENDABLE_TAPROOT_KEY,
+        },
+        status: InputValidationResult::TimeBasedLockTimeExpiry,
+        chain_tip_height: 2,
+    } ; "lock-time-in-time-units-unknown-median-time-past")]
+    #[test_case(DepositReportErrorMapping {
+        report: DepositRequestReport {
+            status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            can_sign: Some(true),
+            can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: u64::MAX,
             lock_time: LockTime::from_height(DEPOSIT_LOCKTIME_BLOCK_BUFFER + 3),
@@ -1020,6 +1492,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: TX_FEE.to_sat(),
             lock_time: LockTime::from_height(DEPOSIT_LOCKTIME_BLOCK_BUFFER + 3),
@@ -1036,6 +1509,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: TX_FEE.to_sat(),
             lock_time: LockTime::from_height(DEPOSIT_LOCKTIME_BLOCK_BUFFER + 3),
@@ -1052,6 +1526,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: TX_FEE.to_sat() - 1,
             max_fee: TX_FEE.to_sat(),
             lock_time: LockTime::from_height(DEPOSIT_LOCKTIME_BLOCK_BUFFER + 3),
@@ -1068,6 +1543,7 @@ mod tests {
             status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
             can_sign: Some(true),
             can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
             amount: 100_000_000,
             max_fee: TX_FEE.to_sat() - 1,
             lock_time: LockTime::from_height(DEPOSIT_LOCKTIME_BLOCK_BUFFER + 3),
@@ -1088,14 +1564,284 @@ mod tests {
             witness: Witness::new(),
         });
 
+        let status = mapping.report.validate(
+            mapping.chain_tip_height,
+            CHAIN_TIP_MEDIAN_TIME_PAST,
+            &tx,
+            TX_FEE,
+            Amount::MAX_MONEY,
+        );
+
+        assert_eq!(status, mapping.status);
+    }
+
+    /// A chain tip written before we started recording the
+    /// median-time-past has it set to zero, and then we cannot tell how
+    /// close a time based lock-time is to expiring.
+    #[test]
+    fn deposit_report_validation_unknown_chain_tip_median_time_past() {
+        let mut tx = crate::testing::btc::base_signer_transaction();
+        tx.input.push(TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        });
+
+        let report = DepositRequestReport {
+            status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            can_sign: Some(true),
+            can_accept: Some(true),
+            parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            lock_time: LockTime::from_512_second_intervals(u16::MAX),
+            outpoint: OutPoint::null(),
+            deposit_script: ScriptBuf::new(),
+            reclaim_script: ScriptBuf::new(),
+            signers_public_key: *sbtc::UNSPENDABLE_TAPROOT_KEY,
+        };
+
+        let status = report.validate(
+            2,
+            CHAIN_TIP_MEDIAN_TIME_PAST,
+            &tx,
+            TX_FEE,
+            Amount::MAX_MONEY,
+        );
+        assert_eq!(status, InputValidationResult::Ok);
+
+        let status = report.validate(2, 0, &tx, TX_FEE, Amount::MAX_MONEY);
+        assert_eq!(status, InputValidationResult::TimeBasedLockTimeExpiry);
+    }
+
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Unconfirmed,
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::TxNotOnBestChain,
+        chain_tip_height: 2,
+    } ; "unconfirmed-withdrawal")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Fulfilled(BitcoinTxId::from([1; 32])),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::RequestFulfilled,
+        chain_tip_height: 2,
+    } ; "fulfilled-withdrawal")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Rejected,
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::RequestRejected,
+        chain_tip_height: 2,
+    } ; "rejected-on-stacks-withdrawal")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_001,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::AmountTooHigh,
+        chain_tip_height: 2,
+    } ; "amount-too-high-withdrawal")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::RequestExpired,
+        chain_tip_height: WITHDRAWAL_BLOCKS_EXPIRY,
+    } ; "expired-withdrawal")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: ScriptBuf::new(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::InvalidRecipient,
+        chain_tip_height: 2,
+    } ; "recipient-mismatch-withdrawal")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 99_999_999,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::InvalidAmount,
+        chain_tip_height: 2,
+    } ; "amount-mismatch-withdrawal")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: None,
+        },
+        status: WithdrawalValidationResult::NoVote,
+        chain_tip_height: 2,
+    } ; "withdrawal-no-vote")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(false),
+        },
+        status: WithdrawalValidationResult::RejectedRequest,
+        chain_tip_height: 2,
+    } ; "signer-rejected-withdrawal")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_000,
+            max_fee: TX_FEE.to_sat(),
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::Ok,
+        chain_tip_height: 2,
+    } ; "withdrawal-fee-at-the-border")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_000,
+            max_fee: TX_FEE.to_sat() - 1,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::FeeTooHigh,
+        chain_tip_height: 2,
+    } ; "withdrawal-one-sat-too-high-fee")]
+    #[test_case(WithdrawalReportErrorMapping {
+        report: WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        },
+        status: WithdrawalValidationResult::Ok,
+        chain_tip_height: WITHDRAWAL_BLOCKS_EXPIRY - 1,
+    } ; "withdrawal-happy-path")]
+    fn withdrawal_report_validation(mapping: WithdrawalReportErrorMapping) {
+        let mut tx = crate::testing::btc::base_signer_transaction();
+        tx.output.push(TxOut {
+            value: Amount::from_sat(100_000_000),
+            script_pubkey: withdrawal_script(),
+        });
+
         let status =
             mapping
                 .report
-                .validate(mapping.chain_tip_height, &tx, TX_FEE, Amount::MAX_MONEY);
+                .validate(mapping.chain_tip_height, 2, &tx, TX_FEE, Amount::ONE_BTC);
 
         assert_eq!(status, mapping.status);
     }
 
+    #[test]
+    fn withdrawal_report_validation_missing_output() {
+        let tx = crate::testing::btc::base_signer_transaction();
+        let report = WithdrawalRequestReport {
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            status: WithdrawalRequestStatus::Confirmed(0, BitcoinBlockHash::from([0; 32])),
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            script_pubkey: withdrawal_script(),
+            is_accepted: Some(true),
+        };
+
+        let status = report.validate(2, 2, &tx, TX_FEE, Amount::MAX_MONEY);
+        assert_eq!(status, WithdrawalValidationResult::Unknown);
+    }
+
     #[test_case(
         vec![TxRequestIds {
             deposits: vec![
@@ -1185,6 +1931,7 @@ mod tests {
                 status: DepositConfirmationStatus::Confirmed(0, BitcoinBlockHash::from([idx; 32])),
                 can_sign: Some(true),
                 can_accept: Some(true),
+                parent_median_time_past: Some(CHAIN_TIP_MEDIAN_TIME_PAST),
                 amount,
                 max_fee: 1000,
                 lock_time: LockTime::from_height(100),
@@ -1297,4 +2044,147 @@ mod tests {
             (result, expected) => panic!("Expected {:?} but got {:?}", expected, result),
         };
     }
+
+    /// Construct the validation data for a transaction sweeping in one
+    /// deposit, as if we had handled a pre-sign request for it.
+    fn presign_validation_data(aggregate_key: &PublicKey) -> BitcoinTxValidationData {
+        let (report, votes) = create_test_report(1, 100_000);
+        let signer_state = SignerBtcState {
+            utxo: SignerUtxo {
+                outpoint: OutPoint::new(Txid::from_byte_array([2; 32]), 0),
+                amount: 1_000_000,
+                public_key: aggregate_key.into(),
+            },
+            fee_rate: 1.0,
+            public_key: aggregate_key.into(),
+            last_fees: None,
+            magic_bytes: [b'T', b'3'],
+        };
+        let reports = SbtcReports {
+            deposits: vec![(report.to_deposit_request(&votes), report)],
+            withdrawals: Vec::new(),
+            signer_state,
+        };
+        let unsigned = reports.create_transaction().unwrap();
+        let sighashes = unsigned.construct_digests().unwrap();
+
+        BitcoinTxValidationData {
+            signer_sighash: sighashes.signer_sighash(),
+            deposit_sighashes: sighashes.deposit_sighashes(),
+            chain_tip: BitcoinBlockHash::from([0; 32]),
+            tx: unsigned.tx.clone(),
+            tx_fee: Amount::from_sat(unsigned.tx_fee),
+            reports,
+            chain_tip_height: 2,
+            chain_tip_median_time_past: CHAIN_TIP_MEDIAN_TIME_PAST,
+            max_deposit_amount: Amount::MAX_MONEY,
+            max_withdrawal_amount: Amount::MAX_MONEY,
+        }
+    }
+
+    #[test_case(ROTATION_SWEEP_MAX_FEE, true; "fee-at-the-cap")]
+    #[test_case(ROTATION_SWEEP_MAX_FEE + 1, false; "fee-above-the-cap")]
+    fn rotation_sweep_fee_is_capped(tx_fee: u64, is_valid: bool) {
+        let aggregate_key = PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng));
+        let mut data = presign_validation_data(&aggregate_key);
+        data.reports.deposits.clear();
+        data.deposit_sighashes.clear();
+        data.tx_fee = Amount::from_sat(tx_fee);
+
+        assert_eq!(data.is_valid_tx(), is_valid);
+        let rows = data.to_input_rows();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].will_sign, is_valid);
+    }
+
+    #[test]
+    fn sign_request_for_presigned_tx_passes_validation() {
+        let aggregate_key = PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng));
+        let data = presign_validation_data(&aggregate_key);
+
+        data.validate_sign_request(&data.tx, &aggregate_key)
+            .unwrap();
+    }
+
+    #[test]
+    fn sign_request_with_unexpected_contents_fails_validation() {
+        let aggregate_key = PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng));
+        let data = presign_validation_data(&aggregate_key);
+
+        let other_key = PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng));
+        let error = data
+            .validate_sign_request(&data.tx, &other_key)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            BitcoinSignRequestError::AggregateKeyMismatch(_)
+        ));
+
+        let mut tx = data.tx.clone();
+        tx.input.swap(0, 1);
+        let error = data.validate_sign_request(&tx, &aggregate_key).unwrap_err();
+        assert!(matches!(
+            error,
+            BitcoinSignRequestError::SignerInputMismatch(_)
+        ));
+
+        let mut tx = data.tx.clone();
+        let outpoint = OutPoint::new(Txid::from_byte_array([3; 32]), 0);
+        tx.input.push(TxIn {
+            previous_output: outpoint,
+            ..tx.input[1].clone()
+        });
+        let error = data.validate_sign_request(&tx, &aggregate_key).unwrap_err();
+        assert_eq!(error, BitcoinSignRequestError::UnexpectedInput(outpoint));
+
+        // Paying way more in fees than the deposit allows.
+        let mut tx = data.tx.clone();
+        tx.output[0].value -= Amount::from_sat(10_000);
+        let error = data.validate_sign_request(&tx, &aggregate_key).unwrap_err();
+        assert!(matches!(
+            error,
+            BitcoinSignRequestError::InvalidDeposit {
+                result: InputValidationResult::FeeTooHigh,
+                ..
+            }
+        ));
+
+        let mut tx = data.tx.clone();
+        tx.output[0].script_pubkey = withdrawal_script();
+        let error = data.validate_sign_request(&tx, &aggregate_key).unwrap_err();
+        assert!(matches!(
+            error,
+            BitcoinSignRequestError::SignerOutputMismatch(_)
+        ));
+
+        let mut tx = data.tx.clone();
+        tx.output[1].script_pubkey = ScriptBuf::new_op_return([0; 21]);
+        let error = data.validate_sign_request(&tx, &aggregate_key).unwrap_err();
+        assert!(matches!(
+            error,
+            BitcoinSignRequestError::OpReturnMismatch(_)
+        ));
+
+        let mut tx = data.tx.clone();
+        tx.output.push(TxOut {
+            value: Amount::from_sat(1_000),
+            script_pubkey: withdrawal_script(),
+        });
+        tx.output[0].value -= Amount::from_sat(1_000);
+        let error = data.validate_sign_request(&tx, &aggregate_key).unwrap_err();
+        assert!(matches!(
+            error,
+            BitcoinSignRequestError::UnexpectedOutput { output_index: 2, .. }
+        ));
+
+        // Everything is fine except that this is not the transaction that
+        // we constructed.
+        let mut tx = data.tx.clone();
+        tx.lock_time = bitcoin::absolute::LockTime::from_consensus(1);
+        let error = data.validate_sign_request(&tx, &aggregate_key).unwrap_err();
+        assert!(matches!(
+            error,
+            BitcoinSignRequestError::PackageMismatch { .. }
+        ));
+    }
 }
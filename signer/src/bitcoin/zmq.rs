@@ -26,24 +26,53 @@
 //!
 //! [^1]: https://github.com/bitcoin/bitcoin/blob/870447fd585e5926b4ce4e83db31c59b1be45a50/doc/zmq.md
 
+use std::collections::VecDeque;
 use std::future::ready;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
+use backoff::backoff::Backoff as _;
+use backoff::ExponentialBackoff;
+use backoff::ExponentialBackoffBuilder;
 use bitcoin::consensus::Decodable as _;
 use bitcoin::hashes::Hash as _;
 use bitcoin::Block;
 use bitcoin::BlockHash;
+use futures::channel::mpsc::Receiver;
 use futures::stream::Stream;
 use futures::stream::StreamExt as _;
+use tokio::sync::mpsc::Sender;
+use tokio_stream::wrappers::ReceiverStream;
+use url::Url;
 use zeromq::Socket as _;
+use zeromq::SocketEvent;
 use zeromq::SocketRecv as _;
 use zeromq::SubSocket;
 use zeromq::ZmqMessage;
 
+use crate::bitcoin::BitcoinInteract;
 use crate::error::Error;
 
+/// The maximum amount of time that we wait for a connection to a
+/// bitcoin-core ZeroMQ endpoint before moving on to the next one. The
+/// zeromq library retries refused connections indefinitely, so we need to
+/// bound it ourselves.
+const ZMQ_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The initial amount of time to wait before reconnecting after a
+/// connection to a bitcoin-core ZeroMQ endpoint has failed.
+const ZMQ_RECONNECT_INITIAL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The maximum amount of time to wait before reconnecting after a
+/// connection to a bitcoin-core ZeroMQ endpoint has failed.
+const ZMQ_RECONNECT_MAX_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The number of recently observed block hashes that the
+/// [`FailoverBlockHashStream`] remembers when de-duplicating block hashes.
+const RECENT_BLOCK_HASHES_CAPACITY: usize = 128;
+
 /// These are the types of messages that we can get from bitcoin-core by
 /// listing to its zeromq socket subscriptions.
 ///
@@ -208,3 +237,258 @@ impl Stream for BitcoinCoreMessageStream {
         self.inner.poll_next_unpin(cx)
     }
 }
+
+/// A stream of bitcoin block hashes that fails over between all of the
+/// given bitcoin-core ZeroMQ endpoints.
+///
+/// The stream listens for `hashblock` notifications on one endpoint at a
+/// time. Whenever the connection to that endpoint fails, it moves on to
+/// the next endpoint in the list, waiting with an exponential backoff
+/// between attempts.
+///
+/// Each time a connection is established, the stream fetches the current
+/// chain tip over bitcoin-core's RPC interface and emits its block hash.
+/// This way the block observer, which walks back from each new block to
+/// the blocks that it has not processed yet, picks up any blocks that were
+/// announced while we were disconnected. Block hashes are de-duplicated, so a block
+/// hash is only emitted once even if we see it from several nodes.
+pub struct FailoverBlockHashStream {
+    /// The receiving end of the channel that the background task sends
+    /// block hashes over.
+    inner: ReceiverStream<Result<BlockHash, Error>>,
+}
+
+impl FailoverBlockHashStream {
+    /// Create a new `FailoverBlockHashStream` and spawn the background
+    /// task that manages the connections to the given endpoints. The
+    /// bitcoin client is used to fetch the chain tip after each
+    /// (re)connection.
+    ///
+    /// The background task stops when this stream is dropped.
+    pub fn new<B>(endpoints: Vec<Url>, bitcoin_client: B) -> Result<Self, Error>
+    where
+        B: BitcoinInteract + 'static,
+    {
+        if endpoints.is_empty() {
+            return Err(Error::NoBlockHashStreamEndpoints);
+        }
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(RECENT_BLOCK_HASHES_CAPACITY);
+        let task = FailoverTask {
+            endpoints,
+            bitcoin_client,
+            recent_block_hashes: RecentBlockHashes::new(RECENT_BLOCK_HASHES_CAPACITY),
+            sender,
+        };
+        tokio::spawn(task.run());
+
+        Ok(Self {
+            inner: ReceiverStream::new(receiver),
+        })
+    }
+}
+
+impl Stream for FailoverBlockHashStream {
+    type Item = Result<BlockHash, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// The background task behind a [`FailoverBlockHashStream`].
+struct FailoverTask<B> {
+    /// The bitcoin-core ZeroMQ endpoints to rotate through.
+    endpoints: Vec<Url>,
+    /// The client used to fetch the chain tip after connecting.
+    bitcoin_client: B,
+    /// The block hashes that we have recently sent over the channel.
+    recent_block_hashes: RecentBlockHashes,
+    /// The sending end of the channel backing the stream.
+    sender: Sender<Result<BlockHash, Error>>,
+}
+
+impl<B: BitcoinInteract> FailoverTask<B> {
+    /// Rotate through the endpoints until the receiving end of the
+    /// channel is dropped.
+    async fn run(mut self) {
+        let mut backoff = reconnect_backoff();
+
+        for endpoint in self.endpoints.clone().iter().cycle() {
+            if self.sender.is_closed() {
+                break;
+            }
+
+            match connect(endpoint).await {
+                Ok((stream, monitor)) => {
+                    tracing::info!(%endpoint, "connected to bitcoin-core ZeroMQ endpoint");
+                    backoff.reset();
+                    if !self.stream_block_hashes(stream, monitor).await {
+                        break;
+                    }
+                    tracing::warn!(%endpoint, "lost connection to bitcoin-core ZeroMQ endpoint");
+                }
+                Err(error) => {
+                    tracing::warn!(%error, %endpoint, "could not connect to bitcoin-core ZeroMQ endpoint");
+                }
+            }
+
+            let delay = backoff.next_backoff().unwrap_or(ZMQ_RECONNECT_MAX_INTERVAL);
+            tokio::time::sleep(delay).await;
+        }
+
+        tracing::info!("block hash stream has stopped");
+    }
+
+    /// Forward block hashes from the given stream until the connection
+    /// fails. Returns `false` if the receiving end of the channel has been
+    /// dropped and `true` otherwise.
+    async fn stream_block_hashes(
+        &mut self,
+        mut stream: BitcoinCoreMessageStream,
+        mut monitor: Receiver<SocketEvent>,
+    ) -> bool {
+        // We may have missed blocks while we were disconnected, so we
+        // send the current chain tip. The block observer will fetch any
+        // of its ancestors that it has not processed yet.
+        match self.bitcoin_client.get_best_block_hash().await {
+            Ok(block_hash) => {
+                if !self.send(Ok(block_hash)).await {
+                    return false;
+                }
+            }
+            Err(error) => tracing::warn!(%error, "could not fetch the bitcoin chain tip"),
+        }
+
+        loop {
+            tokio::select! {
+                message = stream.next() => match message {
+                    Some(Ok(BitcoinCoreMessage::HashBlock(block_hash, _))) => {
+                        if !self.send(Ok(block_hash)).await {
+                            return false;
+                        }
+                    }
+                    Some(Ok(BitcoinCoreMessage::RawBlock(..))) => {}
+                    Some(Err(error @ Error::ZmqReceive(_))) => {
+                        tracing::warn!(%error, "error receiving message over ZeroMQ");
+                        return true;
+                    }
+                    Some(Err(error)) => {
+                        if !self.send(Err(error)).await {
+                            return false;
+                        }
+                    }
+                    None => return true,
+                },
+                event = monitor.next() => match event {
+                    Some(SocketEvent::Disconnected(_)) | None => return true,
+                    Some(_) => {}
+                },
+            }
+        }
+    }
+
+    /// Send the item over the channel, skipping block hashes that we have
+    /// recently sent. Returns `false` if the receiving end of the channel
+    /// has been dropped.
+    async fn send(&mut self, item: Result<BlockHash, Error>) -> bool {
+        if let Ok(block_hash) = &item {
+            if !self.recent_block_hashes.insert(*block_hash) {
+                tracing::debug!(%block_hash, "skipping duplicate block hash");
+                return true;
+            }
+        }
+        self.sender.send(item).await.is_ok()
+    }
+}
+
+/// Connect to the given bitcoin-core ZeroMQ endpoint and subscribe to
+/// `hashblock` notifications. The returned receiver notifies us of
+/// changes to the state of the connection.
+async fn connect(
+    endpoint: &Url,
+) -> Result<(BitcoinCoreMessageStream, Receiver<SocketEvent>), Error> {
+    let mut socket = SubSocket::new();
+    // We need to set up the monitor before connecting, otherwise we may
+    // miss events.
+    let monitor = socket.monitor();
+
+    tokio::time::timeout(ZMQ_CONNECT_TIMEOUT, socket.connect(endpoint.as_str()))
+        .await
+        .map_err(|_| Error::ZmqConnectTimeout(endpoint.clone()))?
+        .map_err(Error::ZmqConnect)?;
+
+    socket
+        .subscribe("hashblock")
+        .await
+        .map_err(Error::ZmqSubscribe)?;
+
+    Ok((BitcoinCoreMessageStream::new_from_socket(socket), monitor))
+}
+
+/// The backoff policy used between connection attempts. It never gives
+/// up.
+fn reconnect_backoff() -> ExponentialBackoff {
+    ExponentialBackoffBuilder::new()
+        .with_initial_interval(ZMQ_RECONNECT_INITIAL_INTERVAL)
+        .with_max_interval(ZMQ_RECONNECT_MAX_INTERVAL)
+        .with_max_elapsed_time(None)
+        .build()
+}
+
+/// A bounded set of recently observed block hashes.
+#[derive(Debug)]
+struct RecentBlockHashes {
+    /// The block hashes, oldest first.
+    block_hashes: VecDeque<BlockHash>,
+    /// The maximum number of block hashes to remember.
+    capacity: usize,
+}
+
+impl RecentBlockHashes {
+    /// Create a new empty set that remembers at most `capacity` block
+    /// hashes.
+    fn new(capacity: usize) -> Self {
+        Self {
+            block_hashes: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Add the block hash to the set, evicting the oldest block hash if
+    /// the set is full. Returns `false` if the block hash was already in
+    /// the set.
+    fn insert(&mut self, block_hash: BlockHash) -> bool {
+        if self.block_hashes.contains(&block_hash) {
+            return false;
+        }
+        if self.block_hashes.len() >= self.capacity {
+            self.block_hashes.pop_front();
+        }
+        self.block_hashes.push_back(block_hash);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_block_hashes_skips_duplicates_and_evicts_oldest() {
+        let hash = |byte| BlockHash::from_byte_array([byte; 32]);
+        let mut recent = RecentBlockHashes::new(2);
+
+        assert!(recent.insert(hash(1)));
+        assert!(!recent.insert(hash(1)));
+        assert!(recent.insert(hash(2)));
+        assert!(!recent.insert(hash(1)));
+
+        // The set is full, so inserting a new block hash evicts the
+        // oldest one.
+        assert!(recent.insert(hash(3)));
+        assert!(!recent.insert(hash(2)));
+        assert!(!recent.insert(hash(3)));
+        assert!(recent.insert(hash(1)));
+    }
+}
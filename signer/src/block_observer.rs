@@ -16,7 +16,13 @@
 //! - Withdraw reject transactions
 //! - Update signer set transactions
 //! - Set aggregate key transactions
+//!
+//! It also keeps the signer set in the [`SignerState`](crate::context::SignerState),
+//! which doubles as the P2P allow-list, in sync with the `sbtc-registry`
+//! contract.
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::future::Future;
 use std::time::Duration;
 
@@ -24,16 +30,23 @@ use crate::bitcoin::rpc::BitcoinTxInfo;
 use crate::bitcoin::utxo::TxDeconstructor as _;
 use crate::bitcoin::BitcoinInteract;
 use crate::context::Context;
+use crate::context::RegistrySignerSet;
 use crate::context::SbtcLimits;
 use crate::context::SignerEvent;
 use crate::emily_client::EmilyInteract;
 use crate::error::Error;
+use crate::keys::PublicKey;
+use crate::keys::SignerScriptPubKey as _;
+use crate::message::NewDepositRequest;
+use crate::metrics::metrics;
+use crate::metrics::OutcomeLabels;
 use crate::stacks::api::StacksInteract;
 use crate::stacks::api::TenureBlocks;
 use crate::storage;
 use crate::storage::model;
 use crate::storage::DbRead;
 use crate::storage::DbWrite;
+use crate::ROTATION_SWEEP_MIN_CONFIRMATIONS;
 use bitcoin::hashes::Hash as _;
 use bitcoin::Amount;
 use bitcoin::BlockHash;
@@ -45,6 +58,15 @@ use sbtc::deposits::CreateDepositRequest;
 use sbtc::deposits::DepositInfo;
 use std::collections::HashSet;
 
+/// How far back the signer looks for bitcoin blocks that it is missing
+/// when it stores a deposit request that was confirmed in one of them.
+const DEPOSIT_BLOCK_HORIZON: u32 = 20;
+
+/// How far back the block observer looks for the signers' UTXO when it
+/// checks whether the signers can retire an old aggregate key. This
+/// matches the context window of the transaction coordinator.
+const SIGNER_UTXO_CONTEXT_WINDOW: u16 = 10_000;
+
 /// Block observer
 #[derive(Debug)]
 pub struct BlockObserver<Context, BlockHashStream> {
@@ -137,8 +159,15 @@ where
             match poll.await {
                 Ok(Some(Ok(block_hash))) => {
                     tracing::info!("observed new bitcoin block from stream");
+                    // Record when we first saw this block, the coordinator
+                    // failover timeouts are measured from this instant.
+                    self.context
+                        .state()
+                        .bitcoin_chain_tip_observed_at(&block_hash.into());
 
-                    let next_blocks = match self.next_blocks_to_process(block_hash).await {
+                    let next_blocks =
+                        next_blocks_to_process(&self.context, block_hash, self.horizon);
+                    let next_blocks = match next_blocks.await {
                         Ok(blocks) => blocks,
                         Err(error) => {
                             tracing::warn!(%error, %block_hash, "could not get next blocks to process");
@@ -152,6 +181,10 @@ where
                         }
                     }
 
+                    if let Err(error) = update_signer_set(&self.context).await {
+                        tracing::warn!(%error, "could not update the signer set");
+                    }
+
                     if let Err(error) = self.update_sbtc_limits().await {
                         tracing::warn!(%error, "could not update sBTC limits");
                         continue;
@@ -182,89 +215,108 @@ where
 impl<C: Context, B> BlockObserver<C, B> {
     /// Fetch deposit requests from Emily and store the ones that pass
     /// validation into the database.
-    #[tracing::instrument(skip_all)]
-    async fn load_latest_deposit_requests(&self) -> Result<(), Error> {
-        let requests = self.context.get_emily_client().get_deposits().await?;
-        self.load_requests(&requests).await
-    }
-
-    /// Validate the given deposit requests and store the ones that pass
-    /// validation into the database.
     ///
-    /// There are three types of errors that can happen during validation
-    /// 1. The transaction fails primary validation. This means the deposit
-    ///    script itself does not align with what we expect. If probably
-    ///    does not follow our protocol.
-    /// 2. The transaction passes step (1), but we don't recognize the
-    ///    x-only public key in the deposit script.
-    /// 3. We cannot find the associated transaction confirmed on a bitcoin
-    ///    block, or when we encountered some unexpected error when
-    ///    reaching out to bitcoin-core or our database.
+    /// Only the deposits that Emily updated since the last sync are
+    /// fetched, and the ones that we already have a record of are not
+    /// validated again. Deposits that are not confirmed yet, or that we
+    /// could not validate because of a transient error, are kept in the
+    /// retry set in the database and validated again on every sync until
+    /// they are stored or fail validation. Since every deposit that Emily
+    /// returned is either stored, rejected or kept for a retry, the sync
+    /// height moves to the latest update from Emily.
+    ///
+    /// Deposit requests that we did not know about before and that pass
+    /// validation are signaled so that they can be gossiped to the other
+    /// signers, in case their Emily is lagging behind or is unreachable.
     #[tracing::instrument(skip_all)]
-    pub async fn load_requests(&self, requests: &[CreateDepositRequest]) -> Result<(), Error> {
-        let mut deposit_requests = Vec::new();
-        for request in requests {
-            let deposit = request
-                .validate(&self.context.get_bitcoin_client())
-                .await
-                .inspect_err(|error| tracing::warn!(%error, "could not validate deposit request"));
+    async fn load_latest_deposit_requests(&self) -> Result<(), Error> {
+        let db = self.context.get_storage_mut();
+        let min_height = db.get_emily_deposit_sync_height().await?.unwrap_or(0);
+        let deposits = self
+            .context
+            .get_emily_client()
+            .get_deposits_updated_since(min_height)
+            .await?;
 
-            // We log the error above, so we just need to extract the
-            // deposit now.
-            if let Ok(Some(deposit)) = deposit {
-                deposit_requests.push(deposit);
-            }
+        let max_height = deposits
+            .iter()
+            .map(|deposit| deposit.last_update_height)
+            .fold(min_height, u64::max);
+
+        // The deposits that we could not store during an earlier sync are
+        // validated again, along with the ones that Emily updated since.
+        let retries = db.get_emily_deposit_sync_retries().await?;
+        let fetched = deposits.len();
+        let retried = retries.len();
+
+        let mut requests = BTreeMap::new();
+        for request in retries.into_iter().map(CreateDepositRequest::from) {
+            requests.insert(request.outpoint, (request, true));
+        }
+        for deposit in deposits {
+            let outpoint = deposit.request.outpoint;
+            let is_retry = requests.contains_key(&outpoint);
+            requests.insert(outpoint, (deposit.request, is_retry));
         }
 
-        self.store_deposit_requests(deposit_requests).await?;
-
-        tracing::debug!("finished processing deposit requests");
-        Ok(())
-    }
+        tracing::debug!(
+            %min_height,
+            %fetched,
+            %retried,
+            "fetched pending deposit requests from Emily"
+        );
 
-    /// Find the parent blocks from the given block that are also missing from our database
-    #[tracing::instrument(skip_all, fields(%block_hash))]
-    async fn next_blocks_to_process(
-        &self,
-        mut block_hash: bitcoin::BlockHash,
-    ) -> Result<Vec<bitcoin::Block>, Error> {
-        let mut blocks = Vec::new();
+        let mut new_requests = Vec::new();
+        for (outpoint, (request, is_retry)) in requests {
+            let txid = model::BitcoinTxId::from(outpoint.txid);
+            let resolved = if db.deposit_request_exists(&txid, outpoint.vout).await? {
+                true
+            } else {
+                match validate_and_store_deposit_request(&self.context, &request).await {
+                    Ok(Some(_)) => {
+                        new_requests.push(NewDepositRequest::from(request.clone()));
+                        true
+                    }
+                    // The deposit request will never pass validation, so
+                    // there is no point in trying again.
+                    Err(Error::SbtcLib(error)) => {
+                        tracing::warn!(%error, %outpoint, "deposit request failed validation");
+                        true
+                    }
+                    Ok(None) => {
+                        tracing::debug!(%outpoint, "deposit transaction is not confirmed yet");
+                        false
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, %outpoint, "could not load deposit request");
+                        false
+                    }
+                }
+            };
 
-        for _ in 0..self.horizon {
-            if self.have_already_processed_block(&block_hash).await? {
-                tracing::debug!(%block_hash, "already processed block");
-                break;
+            match (resolved, is_retry) {
+                (true, true) => {
+                    db.delete_emily_deposit_sync_retry(&txid, outpoint.vout)
+                        .await?
+                }
+                (false, false) => {
+                    let retry = model::EmilyDepositSyncRetry::from(&request);
+                    db.write_emily_deposit_sync_retry(&retry).await?;
+                }
+                _ => {}
             }
+        }
 
-            let block = self
-                .context
-                .get_bitcoin_client()
-                .get_block(&block_hash)
-                .await?
-                .ok_or(Error::MissingBitcoinBlock(block_hash.into()))?;
+        // The deposits that we have to validate again are in the retry
+        // set now, so the next sync can start at the latest update.
+        db.set_emily_deposit_sync_height(max_height).await?;
 
-            block_hash = block.header.prev_blockhash;
-            blocks.push(block);
+        if !new_requests.is_empty() {
+            self.context
+                .signal(SignerEvent::NewDepositRequestsObserved(new_requests).into())?;
         }
 
-        // Make order chronological
-        blocks.reverse();
-        Ok(blocks)
-    }
-
-    /// Check whether we have already processed this block in our
-    /// database.
-    #[tracing::instrument(skip(self))]
-    async fn have_already_processed_block(
-        &self,
-        block_hash: &bitcoin::BlockHash,
-    ) -> Result<bool, Error> {
-        Ok(self
-            .context
-            .get_storage()
-            .get_bitcoin_block(&block_hash.to_byte_array().into())
-            .await?
-            .is_some())
+        Ok(())
     }
 
     /// Process the bitcoin block. Also process all recent stacks blocks.
@@ -285,82 +337,11 @@ impl<C: Context, B> BlockObserver<C, B> {
         self.write_stacks_blocks(&stacks_blocks).await?;
         self.write_bitcoin_block(&block).await?;
 
+        metrics().bitcoin_blocks_processed.inc();
         tracing::debug!("finished processing bitcoin block");
         Ok(())
     }
 
-    /// For each of the deposit requests, persist the corresponding
-    /// transaction and the parsed deposit info into the database.
-    ///
-    /// This function does three things:
-    /// 1. For all deposit requests, check to see if there are bitcoin
-    ///    blocks that we do not have in our database.
-    /// 2. If we do not have a record of the bitcoin block then write it
-    ///    to the database.
-    /// 3. Write the deposit transaction and the extracted deposit info
-    ///    into the database.
-    async fn store_deposit_requests(&self, requests: Vec<Deposit>) -> Result<(), Error> {
-        // We need to check to see if we have a record of the bitcoin block
-        // that contains the deposit request in our database. If we don't
-        // then write them to our database.
-        self.store_deposit_request_blocks(&requests).await?;
-
-        // Okay now we write the deposit requests and the transactions to
-        // the database.
-        let (deposit_requests, deposit_request_txs) = requests
-            .into_iter()
-            .map(|deposit| {
-                let tx = model::Transaction {
-                    txid: deposit.tx_info.txid.to_byte_array(),
-                    tx: bitcoin::consensus::serialize(&deposit.tx_info.tx),
-                    tx_type: model::TransactionType::DepositRequest,
-                    block_hash: deposit.tx_info.block_hash.to_byte_array(),
-                };
-
-                (model::DepositRequest::from(deposit), tx)
-            })
-            .collect::<Vec<_>>()
-            .into_iter()
-            .unzip();
-
-        let db = self.context.get_storage_mut();
-        db.write_bitcoin_transactions(deposit_request_txs).await?;
-        db.write_deposit_requests(deposit_requests).await?;
-
-        Ok(())
-    }
-
-    /// This function does two things:
-    /// 1. For all deposit requests, check to see if there are bitcoin
-    ///    blocks that we do not have in our database.
-    /// 2. If we do not have a record of the bitcoin block then write it
-    ///    to the database.
-    async fn store_deposit_request_blocks(&self, requests: &[Deposit]) -> Result<(), Error> {
-        let db = self.context.get_storage_mut();
-        let mut deposit_blocks = Vec::new();
-
-        // We need to check to see if we have a record of the bitcoin block
-        // that contains the deposit request in our database.
-        for deposit in requests.iter() {
-            let blocks = self
-                .next_blocks_to_process(deposit.tx_info.block_hash)
-                .await?
-                .into_iter()
-                .map(model::BitcoinBlock::from);
-            deposit_blocks.extend(blocks);
-        }
-
-        // We now get the distinct blocks and write them to the database.
-        deposit_blocks.sort_by_key(|block| (block.block_height, block.block_hash));
-        deposit_blocks.dedup();
-
-        for block in deposit_blocks {
-            db.write_bitcoin_block(&block).await?;
-        }
-
-        Ok(())
-    }
-
     /// Extract all BTC transactions from the block where one of the UTXOs
     /// can be spent by the signers.
     ///
@@ -384,6 +365,13 @@ impl<C: Context, B> BlockObserver<C, B> {
             .map(ScriptBuf::from_bytes)
             .collect();
 
+        // Sweep transactions that were created before a change in the
+        // configured magic bytes use magic bytes that we have stored, so
+        // we accept those along with the current ones.
+        let magic_bytes = self.context.config().signer.magic_bytes();
+        let mut known_magic_bytes = db.get_sweep_magic_bytes().await?;
+        known_magic_bytes.push(magic_bytes);
+
         let btc_rpc = self.context.get_bitcoin_client();
         // Look through all the UTXOs in the given transaction slice and
         // keep the transactions where a UTXO is locked with a
@@ -412,8 +400,23 @@ impl<C: Context, B> BlockObserver<C, B> {
                 .await?
                 .ok_or(Error::BitcoinTxMissing(txid, None))?;
 
-            // sBTC transactions have as first txin a signers spendable output
-            let tx_type = if tx_info.is_signer_created(&signer_script_pubkeys) {
+            // sBTC transactions have as first txin a signers spendable
+            // output and an OP_RETURN output with known magic bytes.
+            let is_signer_created = tx_info.is_signer_created(&signer_script_pubkeys);
+            let is_sweep = is_signer_created
+                && match tx_info.sbtc_op_return(&known_magic_bytes) {
+                    Ok(op_return) => {
+                        if op_return.magic_bytes == magic_bytes {
+                            db.write_sweep_magic_bytes(magic_bytes).await?;
+                        }
+                        true
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, "transaction spends the signers' UTXO but is not a valid sweep");
+                        false
+                    }
+                };
+            let tx_type = if is_sweep {
                 model::TransactionType::SbtcTransaction
             } else {
                 model::TransactionType::Donation
@@ -427,11 +430,26 @@ impl<C: Context, B> BlockObserver<C, B> {
                 block_hash: block_hash.to_byte_array(),
             });
 
-            for prevout in tx_info.to_inputs(&signer_script_pubkeys) {
+            // A transaction that spends the signers' UTXO without being
+            // a valid sweep still spends that UTXO, but it does not sweep
+            // in any deposits or fulfill any withdrawals. The outputs that
+            // the signers control are kept as donations.
+            let prevouts = tx_info
+                .to_inputs(&signer_script_pubkeys)
+                .into_iter()
+                .filter(|prevout| {
+                    is_sweep || prevout.prevout_type == model::TxPrevoutType::SignersInput
+                });
+            for prevout in prevouts {
                 db.write_tx_prevout(&prevout).await?;
             }
 
-            for output in tx_info.to_outputs(&signer_script_pubkeys) {
+            let outputs = if is_sweep {
+                tx_info.to_outputs(&signer_script_pubkeys)
+            } else {
+                tx_info.to_donations(&signer_script_pubkeys)
+            };
+            for output in outputs {
                 db.write_tx_output(&output).await?;
             }
         }
@@ -446,7 +464,8 @@ impl<C: Context, B> BlockObserver<C, B> {
     /// This function also extracts sBTC Stacks transactions from the given
     /// blocks and stores them into the database.
     async fn write_stacks_blocks(&self, tenures: &[TenureBlocks]) -> Result<(), Error> {
-        let deployer = &self.context.config().signer.deployer;
+        let config = self.context.config();
+        let deployer = &config.signer.deployer;
         let txs = tenures
             .iter()
             .flat_map(|tenure| {
@@ -468,7 +487,7 @@ impl<C: Context, B> BlockObserver<C, B> {
     /// Write the bitcoin block to the database. We also write any
     /// transactions that are spend to any of the signers `scriptPubKey`s
     async fn write_bitcoin_block(&self, block: &bitcoin::Block) -> Result<(), Error> {
-        let db_block = model::BitcoinBlock::from(block);
+        let db_block = to_db_bitcoin_block(&self.context, block).await?;
 
         self.context
             .get_storage_mut()
@@ -515,11 +534,319 @@ impl<C: Context, B> BlockObserver<C, B> {
     }
 }
 
+/// Validate the given deposit request and store it into the database if
+/// it passes validation.
+///
+/// Returns `None` if the deposit transaction has not been confirmed yet.
+/// Validation errors are returned to the caller; they are
+/// [`Error::SbtcLib`] errors when the deposit request itself is invalid,
+/// and any other error means that we could not reach bitcoin-core or our
+/// database.
+///
+/// The block observer uses this function for deposit requests fetched
+/// from Emily, the request decider uses it for deposit requests gossiped
+/// by other signers, and the API uses it for deposit requests submitted
+/// to the signer directly.
+#[tracing::instrument(skip_all, fields(outpoint = %request.outpoint))]
+pub async fn validate_and_store_deposit_request<C: Context>(
+    ctx: &C,
+    request: &CreateDepositRequest,
+) -> Result<Option<DepositInfo>, Error> {
+    let deposit = request.validate(&ctx.get_bitcoin_client()).await;
+
+    let outcome = match &deposit {
+        Ok(Some(_)) => "validated",
+        Ok(None) => "unconfirmed",
+        Err(_) => "rejected",
+    };
+    metrics()
+        .deposit_requests_validated
+        .get_or_create(&OutcomeLabels { outcome })
+        .inc();
+
+    let Some(deposit) = deposit? else {
+        return Ok(None);
+    };
+
+    let info = deposit.info.clone();
+    store_deposit_request(ctx, deposit).await?;
+    Ok(Some(info))
+}
+
+/// Persist the deposit transaction and the parsed deposit info into the
+/// database.
+///
+/// This function does three things:
+/// 1. Check to see if there are bitcoin blocks leading up to the block
+///    that confirmed the deposit that we do not have in our database.
+/// 2. If we do not have a record of those bitcoin blocks then write them
+///    to the database.
+/// 3. Write the deposit transaction and the extracted deposit info into
+///    the database.
+async fn store_deposit_request<C: Context>(ctx: &C, deposit: Deposit) -> Result<(), Error> {
+    let db = ctx.get_storage_mut();
+
+    // We need to check to see if we have a record of the bitcoin block
+    // that contains the deposit request in our database. If we don't
+    // then write them to our database.
+    let block_hash = deposit.tx_info.block_hash;
+    for block in next_blocks_to_process(ctx, block_hash, DEPOSIT_BLOCK_HORIZON).await? {
+        db.write_bitcoin_block(&to_db_bitcoin_block(ctx, &block).await?)
+            .await?;
+    }
+
+    // Okay now we write the deposit request and the transaction to the
+    // database.
+    let tx = model::Transaction {
+        txid: deposit.tx_info.txid.to_byte_array(),
+        tx: bitcoin::consensus::serialize(&deposit.tx_info.tx),
+        tx_type: model::TransactionType::DepositRequest,
+        block_hash: deposit.tx_info.block_hash.to_byte_array(),
+    };
+
+    db.write_bitcoin_transactions(vec![tx]).await?;
+    db.write_deposit_requests(vec![model::DepositRequest::from(deposit)])
+        .await?;
+
+    Ok(())
+}
+
+/// Find the parent blocks from the given block that are also missing from
+/// our database, going back at most `horizon` blocks.
+#[tracing::instrument(skip(ctx))]
+async fn next_blocks_to_process<C: Context>(
+    ctx: &C,
+    mut block_hash: bitcoin::BlockHash,
+    horizon: u32,
+) -> Result<Vec<bitcoin::Block>, Error> {
+    let mut blocks = Vec::new();
+
+    for _ in 0..horizon {
+        if have_already_processed_block(ctx, &block_hash).await? {
+            tracing::debug!(%block_hash, "already processed block");
+            break;
+        }
+
+        let block = ctx
+            .get_bitcoin_client()
+            .get_block(&block_hash)
+            .await?
+            .ok_or(Error::MissingBitcoinBlock(block_hash.into()))?;
+
+        block_hash = block.header.prev_blockhash;
+        blocks.push(block);
+    }
+
+    // Make order chronological
+    blocks.reverse();
+    Ok(blocks)
+}
+
+/// Check whether we have already processed this block in our database.
+#[tracing::instrument(skip(ctx))]
+async fn have_already_processed_block<C: Context>(
+    ctx: &C,
+    block_hash: &bitcoin::BlockHash,
+) -> Result<bool, Error> {
+    Ok(ctx
+        .get_storage()
+        .get_bitcoin_block(&block_hash.to_byte_array().into())
+        .await?
+        .is_some())
+}
+
+/// Convert the bitcoin block into the database representation. The
+/// median-time-past is not part of the block itself, so we fetch it from
+/// bitcoin-core using the block header.
+async fn to_db_bitcoin_block<C: Context>(
+    ctx: &C,
+    block: &bitcoin::Block,
+) -> Result<model::BitcoinBlock, Error> {
+    let block_hash = block.block_hash();
+    let median_time_past = ctx
+        .get_bitcoin_client()
+        .get_block_header(&block_hash)
+        .await?
+        .and_then(|header| header.median_time)
+        .ok_or(Error::MissingMedianTimePast(block_hash))?;
+
+    Ok(model::BitcoinBlock::new(block, median_time_past as u64))
+}
+
+/// Update the signer set in the signer state, which is also the P2P
+/// allow-list, from the `sbtc-registry` contract.
+///
+/// The allow-list includes the signers that took part in the latest DKG
+/// round along with the current signer set and the signer set approved by
+/// governance, since these differ while the signers are rotating keys and
+/// all of them need to be reachable.
+pub async fn update_signer_set<C: Context>(ctx: &C) -> Result<(), Error> {
+    // If we cannot reach the contract then we keep using the signer set
+    // that we read from it last.
+    match fetch_registry_signer_set(ctx).await {
+        Ok(registry_signer_set) => ctx.state().update_registry_signer_set(registry_signer_set),
+        Err(error) => tracing::warn!(%error, "could not fetch the signer set from the registry"),
+    }
+
+    let (mut signer_set, signatures_required) = load_current_signer_set(ctx).await?;
+
+    let db = ctx.get_storage();
+    let latest_dkg_shares = db.get_latest_encrypted_dkg_shares().await?;
+    if let Some(shares) = latest_dkg_shares {
+        signer_set.extend(shares.signer_set_public_keys);
+    }
+    // The signers of the DKG round whose aggregate key still locks the
+    // signers' UTXO need to be reachable to sign for it.
+    if let Some(chain_tip) = db.get_bitcoin_canonical_chain_tip().await? {
+        let unretired = unretired_dkg_shares(ctx, &chain_tip, SIGNER_UTXO_CONTEXT_WINDOW).await?;
+        if let Some(shares) = unretired {
+            signer_set.extend(shares.signer_set_public_keys);
+        }
+    }
+    if let Some(target) = ctx.config().signer.signer_set.as_ref() {
+        signer_set.extend(target.public_keys());
+    }
+
+    if ctx
+        .state()
+        .current_signer_set()
+        .replace_signers(&signer_set)
+    {
+        tracing::info!(
+            num_signers = signer_set.len(),
+            %signatures_required,
+            "updated the signer set"
+        );
+    } else {
+        tracing::trace!("signer set has not changed");
+    }
+    Ok(())
+}
+
+/// Return the DKG shares of an earlier aggregate key that the signers
+/// cannot retire yet, given the chain tip.
+///
+/// After the signers rotate keys, their UTXO stays locked by the old
+/// aggregate key until a sweep transaction hands it over to the new one.
+/// We keep the old aggregate key until that transaction has
+/// [`ROTATION_SWEEP_MIN_CONFIRMATIONS`] confirmations, so we look at the
+/// signers' UTXO as of the block that many confirmations deep, and return
+/// the DKG shares locking it if they are not the latest ones.
+pub async fn unretired_dkg_shares<C: Context>(
+    ctx: &C,
+    chain_tip: &model::BitcoinBlockHash,
+    context_window: u16,
+) -> Result<Option<model::EncryptedDkgShares>, Error> {
+    let db = ctx.get_storage();
+    let Some(latest_shares) = db.get_latest_encrypted_dkg_shares().await? else {
+        return Ok(None);
+    };
+
+    // The chain tip has one confirmation, so we go back one block less
+    // than the number of confirmations. We stop early if we do not have
+    // the parent of a block, which only happens at the start of the
+    // blockchain data in our database.
+    let mut block_hash = *chain_tip;
+    for _ in 1..ROTATION_SWEEP_MIN_CONFIRMATIONS {
+        let parent_hash = match db.get_bitcoin_block(&block_hash).await? {
+            Some(block) => block.parent_hash,
+            None => break,
+        };
+        if db.get_bitcoin_block(&parent_hash).await?.is_none() {
+            break;
+        }
+        block_hash = parent_hash;
+    }
+
+    let Some(utxo) = db.get_signer_utxo(&block_hash, context_window).await? else {
+        return Ok(None);
+    };
+    if utxo.public_key == bitcoin::XOnlyPublicKey::from(&latest_shares.aggregate_key) {
+        return Ok(None);
+    }
+
+    let script_pubkey = utxo.public_key.signers_script_pubkey().into();
+    db.get_encrypted_dkg_shares_by_script_pubkey(&script_pubkey)
+        .await
+}
+
+/// Fetch the signer set and signature threshold from the `sbtc-registry`
+/// contract. Returns `None` if they have not been set in the contract
+/// yet, or if they are not consistent with each other.
+async fn fetch_registry_signer_set<C: Context>(
+    ctx: &C,
+) -> Result<Option<RegistrySignerSet>, Error> {
+    let config = ctx.config();
+    let deployer = &config.signer.deployer;
+    let stacks = ctx.get_stacks_client();
+
+    let signer_set = stacks.get_current_signer_set(deployer).await?;
+    let Some(signatures_required) = stacks.get_current_signature_threshold(deployer).await? else {
+        return Ok(None);
+    };
+
+    if signer_set.is_empty() || usize::from(signatures_required) > signer_set.len() {
+        return Ok(None);
+    }
+
+    Ok(Some(RegistrySignerSet {
+        signer_set: signer_set.into_iter().collect(),
+        signatures_required,
+    }))
+}
+
+/// Return the current signer set along with the number of signatures
+/// required by the signers' multi-sig wallet.
+///
+/// The `sbtc-registry` contract is the source of truth for this, and the
+/// last signer set read from it is kept in the signer state. If the
+/// signers have not rotated keys yet, then we use the last key rotation
+/// confirmed on the canonical chain, and failing that the bootstrap signer
+/// set from the config.
+pub async fn load_current_signer_set<C>(ctx: &C) -> Result<(BTreeSet<PublicKey>, u16), Error>
+where
+    C: Context,
+{
+    if let Some(registry) = ctx.state().registry_signer_set() {
+        return Ok((registry.signer_set, registry.signatures_required));
+    }
+
+    let db = ctx.get_storage();
+    if let Some(chain_tip) = db.get_bitcoin_canonical_chain_tip().await? {
+        if let Some(rotation) = db.get_last_key_rotation(&chain_tip).await? {
+            let signer_set = rotation.signer_set.into_iter().collect();
+            return Ok((signer_set, rotation.signatures_required));
+        }
+    }
+
+    let config = ctx.config();
+    Ok((
+        config.signer.bootstrap_signing_set(),
+        config.signer.bootstrap_signatures_required,
+    ))
+}
+
+/// Return the signer set that DKG should be run for, along with the
+/// number of signatures that it requires.
+///
+/// This is the signer set approved by governance in the config, if there
+/// is one, and the current signer set otherwise.
+pub async fn target_signer_set<C>(ctx: &C) -> Result<(BTreeSet<PublicKey>, u16), Error>
+where
+    C: Context,
+{
+    match ctx.config().signer.signer_set.as_ref() {
+        Some(target) => Ok((target.public_keys(), target.signatures_required)),
+        None => load_current_signer_set(ctx).await,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bitcoin::Amount;
     use bitcoin::BlockHash;
     use bitcoin::TxOut;
+    use bitcoin::Txid;
     use fake::Dummy;
     use fake::Fake;
     use model::BitcoinTxId;
@@ -527,8 +854,12 @@ mod tests {
     use rand::SeedableRng;
     use test_log::test;
 
+    use crate::bitcoin::rpc::BitcoinTxVin;
+    use crate::bitcoin::rpc::BitcoinTxVinPrevout;
     use crate::bitcoin::rpc::GetTxResponse;
+    use crate::bitcoin::rpc::PrevoutScriptPubKey;
     use crate::context::SignerSignal;
+    use crate::emily_client::PendingDeposit;
     use crate::keys::PublicKey;
     use crate::keys::SignerScriptPubKey as _;
     use crate::storage;
@@ -686,6 +1017,9 @@ mod tests {
             .with_bitcoin_client(test_harness.clone())
             .build();
 
+        // The block observer signals the deposit requests that it
+        // stored, so there must be a signal receiver alive.
+        let mut signal_rx = ctx.get_signal_receiver();
         let block_observer = BlockObserver {
             context: ctx,
             bitcoin_blocks: (),
@@ -709,6 +1043,251 @@ mod tests {
         };
 
         assert_eq!(deposit.outpoint(), req0.outpoint);
+
+        // Only the newly stored deposit request is shared with the other
+        // signers.
+        let signal = signal_rx.try_recv().unwrap();
+        let SignerSignal::Event(SignerEvent::NewDepositRequestsObserved(requests)) = signal else {
+            panic!("expected a new deposit requests signal, got {signal:?}");
+        };
+        assert_eq!(requests, vec![NewDepositRequest::from(req0.clone())]);
+
+        // Loading the same requests again does not signal them again
+        // since we already know about them.
+        block_observer.load_latest_deposit_requests().await.unwrap();
+        assert!(signal_rx.try_recv().is_err());
+    }
+
+    /// Test that `BlockObserver::load_latest_deposit_requests` moves the
+    /// Emily sync height past unconfirmed deposits, and stores them once
+    /// they are confirmed even though Emily does not return them again.
+    #[tokio::test]
+    async fn unconfirmed_deposits_are_retried_until_they_are_confirmed() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+        let mut test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+        let block_hash = test_harness
+            .bitcoin_blocks()
+            .first()
+            .map(|block| block.block_hash());
+
+        let make_request = |setup: &sbtc::testing::deposits::TxSetup| CreateDepositRequest {
+            outpoint: bitcoin::OutPoint {
+                txid: setup.tx.compute_txid(),
+                vout: 0,
+            },
+            deposit_script: setup.deposit.deposit_script(),
+            reclaim_script: setup.reclaim.reclaim_script(),
+        };
+        let get_tx_response =
+            |setup: &sbtc::testing::deposits::TxSetup, block_hash| GetTxResponse {
+                tx: setup.tx.clone(),
+                block_hash,
+                confirmations: None,
+                block_time: None,
+            };
+
+        // The first deposit is confirmed while the second one is still
+        // in the mempool.
+        let confirmed = sbtc::testing::deposits::tx_setup(150, 32000, 500_000);
+        let unconfirmed = sbtc::testing::deposits::tx_setup(300, 2000, 500_000);
+        let mut later_harness = test_harness.clone();
+        test_harness.add_deposits(&[
+            (
+                confirmed.tx.compute_txid(),
+                get_tx_response(&confirmed, block_hash),
+            ),
+            (
+                unconfirmed.tx.compute_txid(),
+                get_tx_response(&unconfirmed, None),
+            ),
+        ]);
+        // By the next sync the second deposit has been confirmed too.
+        later_harness.add_deposits(&[
+            (
+                confirmed.tx.compute_txid(),
+                get_tx_response(&confirmed, block_hash),
+            ),
+            (
+                unconfirmed.tx.compute_txid(),
+                get_tx_response(&unconfirmed, block_hash),
+            ),
+        ]);
+
+        let confirmed_deposit = PendingDeposit {
+            request: make_request(&confirmed),
+            last_update_height: 5,
+        };
+        let unconfirmed_deposit = PendingDeposit {
+            request: make_request(&unconfirmed),
+            last_update_height: 3,
+        };
+
+        let storage = storage::in_memory::Store::new_shared();
+        let mut ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_stacks_client(test_harness.clone())
+            .with_mocked_emily_client()
+            .with_bitcoin_client(test_harness.clone())
+            .build();
+        let _signal_rx = ctx.get_signal_receiver();
+
+        ctx.with_emily_client(|client| {
+            let response = vec![confirmed_deposit.clone(), unconfirmed_deposit.clone()];
+            client
+                .expect_get_deposits_updated_since()
+                .withf(|min_height| *min_height == 0)
+                .times(1)
+                .returning(move |_| Box::pin(std::future::ready(Ok(response.clone()))));
+        })
+        .await;
+
+        let block_observer = BlockObserver {
+            context: ctx,
+            bitcoin_blocks: (),
+            horizon: 1,
+        };
+
+        block_observer.load_latest_deposit_requests().await.unwrap();
+        let unconfirmed_outpoint = (unconfirmed.tx.compute_txid().into(), 0);
+        {
+            let db = storage.lock().await;
+            assert_eq!(db.deposit_requests.len(), 1);
+            assert_eq!(db.emily_deposit_sync_height, Some(5));
+            let retries: Vec<_> = db.emily_deposit_sync_retries.keys().collect();
+            assert_eq!(retries, vec![&unconfirmed_outpoint]);
+        }
+
+        // Emily has nothing new for us, but the unconfirmed deposit is
+        // validated again from the retry set.
+        let mut ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_stacks_client(later_harness.clone())
+            .with_mocked_emily_client()
+            .with_bitcoin_client(later_harness.clone())
+            .build();
+        let _signal_rx = ctx.get_signal_receiver();
+
+        ctx.with_emily_client(|client| {
+            client
+                .expect_get_deposits_updated_since()
+                .withf(|min_height| *min_height == 5)
+                .times(1)
+                .returning(|_| Box::pin(std::future::ready(Ok(Vec::new()))));
+        })
+        .await;
+
+        let block_observer = BlockObserver {
+            context: ctx,
+            bitcoin_blocks: (),
+            horizon: 1,
+        };
+
+        block_observer.load_latest_deposit_requests().await.unwrap();
+        let db = storage.lock().await;
+        assert_eq!(db.deposit_requests.len(), 2);
+        assert!(db.deposit_requests.contains_key(&unconfirmed_outpoint));
+        assert!(db.emily_deposit_sync_retries.is_empty());
+        assert_eq!(db.emily_deposit_sync_height, Some(5));
+    }
+
+    /// Test that `BlockObserver::load_latest_deposit_requests` does not
+    /// retry deposits that fail validation, and keeps retrying unconfirmed
+    /// deposits no matter how far behind the latest update they are.
+    #[tokio::test]
+    async fn deposit_sync_retries_unconfirmed_deposits_but_not_invalid_ones() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+        let mut test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+        let block_hash = test_harness
+            .bitcoin_blocks()
+            .first()
+            .map(|block| block.block_hash());
+
+        let make_request = |setup: &sbtc::testing::deposits::TxSetup| CreateDepositRequest {
+            outpoint: bitcoin::OutPoint {
+                txid: setup.tx.compute_txid(),
+                vout: 0,
+            },
+            deposit_script: setup.deposit.deposit_script(),
+            reclaim_script: setup.reclaim.reclaim_script(),
+        };
+
+        // The first deposit is confirmed but the request does not match
+        // the transaction, the second one has been in the mempool for a
+        // long time, and the third one is confirmed and valid.
+        let invalid = sbtc::testing::deposits::tx_setup(150, 32000, 500_000);
+        let stale = sbtc::testing::deposits::tx_setup(300, 2000, 500_000);
+        let confirmed = sbtc::testing::deposits::tx_setup(450, 4000, 500_000);
+        let get_tx_response =
+            |setup: &sbtc::testing::deposits::TxSetup, block_hash| GetTxResponse {
+                tx: setup.tx.clone(),
+                block_hash,
+                confirmations: None,
+                block_time: None,
+            };
+        test_harness.add_deposits(&[
+            (
+                invalid.tx.compute_txid(),
+                get_tx_response(&invalid, block_hash),
+            ),
+            (stale.tx.compute_txid(), get_tx_response(&stale, None)),
+            (
+                confirmed.tx.compute_txid(),
+                get_tx_response(&confirmed, block_hash),
+            ),
+        ]);
+
+        let latest_height = 100_000;
+        let invalid_deposit = PendingDeposit {
+            request: CreateDepositRequest {
+                deposit_script: stale.deposit.deposit_script(),
+                ..make_request(&invalid)
+            },
+            last_update_height: latest_height - 1,
+        };
+        let stale_deposit = PendingDeposit {
+            request: make_request(&stale),
+            last_update_height: 9,
+        };
+        let confirmed_deposit = PendingDeposit {
+            request: make_request(&confirmed),
+            last_update_height: latest_height,
+        };
+
+        let storage = storage::in_memory::Store::new_shared();
+        let mut ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_stacks_client(test_harness.clone())
+            .with_mocked_emily_client()
+            .with_bitcoin_client(test_harness.clone())
+            .build();
+        let _signal_rx = ctx.get_signal_receiver();
+
+        ctx.with_emily_client(|client| {
+            let response = vec![invalid_deposit, stale_deposit, confirmed_deposit];
+            client
+                .expect_get_deposits_updated_since()
+                .withf(|min_height| *min_height == 0)
+                .times(1)
+                .returning(move |_| Box::pin(std::future::ready(Ok(response.clone()))));
+        })
+        .await;
+
+        let block_observer = BlockObserver {
+            context: ctx,
+            bitcoin_blocks: (),
+            horizon: 1,
+        };
+
+        block_observer.load_latest_deposit_requests().await.unwrap();
+        let db = storage.lock().await;
+        assert_eq!(db.deposit_requests.len(), 1);
+        let db_outpoint = (confirmed.tx.compute_txid().into(), 0);
+        assert!(db.deposit_requests.contains_key(&db_outpoint));
+        assert_eq!(db.emily_deposit_sync_height, Some(latest_height));
+
+        let stale_outpoint = (stale.tx.compute_txid().into(), 0);
+        let retries: Vec<_> = db.emily_deposit_sync_retries.keys().collect();
+        assert_eq!(retries, vec![&stale_outpoint]);
     }
 
     /// Test that `BlockObserver::extract_deposit_requests` after
@@ -770,6 +1349,7 @@ mod tests {
             .with_bitcoin_client(test_harness.clone())
             .build();
 
+        let _signal_rx = ctx.get_signal_receiver();
         let block_observer = BlockObserver {
             context: ctx,
             bitcoin_blocks: (),
@@ -916,4 +1496,214 @@ mod tests {
         assert_eq!(tx_ids.len(), 1);
         assert_eq!(tx_ids[0], expected_tx_id);
     }
+
+    /// Test that `BlockObserver::extract_sbtc_transactions` does not
+    /// treat a transaction that spends the signers' UTXO as a sweep when
+    /// its OP_RETURN output has unknown magic bytes. The signers' UTXO is
+    /// still spent, but the other inputs are not swept deposits and the
+    /// outputs that the signers control are donations.
+    #[tokio::test]
+    async fn signer_transactions_with_unknown_magic_bytes_are_not_sweeps() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+        let mut test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+        let block_hash = BlockHash::from_byte_array([1u8; 32]);
+
+        let signers_script_pubkey: ScriptPubKey = fake::Faker.fake_with_rng(&mut rng);
+        let storage = storage::in_memory::Store::new_shared();
+        let aggregate_key = PublicKey::dummy_with_rng(&fake::Faker, &mut rng);
+        let shares = model::EncryptedDkgShares {
+            aggregate_key,
+            tweaked_aggregate_key: aggregate_key.signers_tweaked_pubkey().unwrap(),
+            script_pubkey: signers_script_pubkey.clone(),
+            encrypted_private_shares: Vec::new(),
+            public_shares: Vec::new(),
+            signer_set_public_keys: vec![aggregate_key],
+            signature_share_threshold: 1,
+        };
+        storage.write_encrypted_dkg_shares(&shares).await.unwrap();
+
+        // The OP_RETURN output follows the layout used by the signers,
+        // except that the magic bytes are not ones that we know about.
+        let mut op_return = vec![
+            bitcoin::opcodes::all::OP_RETURN.to_u8(),
+            bitcoin::opcodes::all::OP_PUSHBYTES_21.to_u8(),
+            b'Z',
+            b'Z',
+        ];
+        op_return.extend_from_slice(&[0; 19]);
+
+        // The first input spends the signers' UTXO and the second one
+        // looks like a deposit.
+        let signer_outpoint = bitcoin::OutPoint::new(Txid::from_byte_array([2; 32]), 0);
+        let deposit_outpoint = bitcoin::OutPoint::new(Txid::from_byte_array([3; 32]), 0);
+        let tx = Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![
+                bitcoin::TxIn {
+                    previous_output: signer_outpoint,
+                    ..Default::default()
+                },
+                bitcoin::TxIn {
+                    previous_output: deposit_outpoint,
+                    ..Default::default()
+                },
+            ],
+            output: vec![
+                TxOut {
+                    value: Amount::ONE_BTC,
+                    script_pubkey: signers_script_pubkey.clone().into(),
+                },
+                TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: ScriptBuf::from_bytes(op_return),
+                },
+            ],
+        };
+
+        let vin = |outpoint: bitcoin::OutPoint, script: ScriptBuf| BitcoinTxVin {
+            details: bitcoincore_rpc_json::GetRawTransactionResultVin {
+                sequence: u32::MAX,
+                coinbase: None,
+                txid: Some(outpoint.txid),
+                vout: Some(outpoint.vout),
+                script_sig: None,
+                txinwitness: None,
+            },
+            prevout: BitcoinTxVinPrevout {
+                generated: false,
+                height: 0,
+                value: Amount::ONE_BTC,
+                script_pub_key: PrevoutScriptPubKey { script },
+            },
+        };
+        let txid = tx.compute_txid();
+        test_harness.add_tx_info(BitcoinTxInfo {
+            in_active_chain: true,
+            fee: Amount::from_sat(1000),
+            txid,
+            hash: tx.compute_wtxid(),
+            size: tx.total_size() as u64,
+            vsize: tx.vsize() as u64,
+            vin: vec![
+                vin(signer_outpoint, signers_script_pubkey.clone().into()),
+                vin(deposit_outpoint, ScriptBuf::new()),
+            ],
+            vout: Vec::new(),
+            block_hash,
+            confirmations: 1,
+            block_time: 0,
+            tx: tx.clone(),
+        });
+
+        let ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_stacks_client(test_harness.clone())
+            .with_emily_client(test_harness.clone())
+            .with_bitcoin_client(test_harness.clone())
+            .build();
+
+        let block_observer = BlockObserver {
+            context: ctx,
+            bitcoin_blocks: (),
+            horizon: 1,
+        };
+
+        block_observer
+            .extract_sbtc_transactions(block_hash, &[tx])
+            .await
+            .unwrap();
+
+        let store = storage.lock().await;
+        let stored_tx = store.raw_transactions.get(txid.as_byte_array()).unwrap();
+        assert_eq!(stored_tx.tx_type, model::TransactionType::Donation);
+
+        // Only the signers' input is recorded, so the deposit is not
+        // taken to be swept.
+        let prevout = store.bitcoin_prevouts.get(&txid.into()).unwrap();
+        assert_eq!(prevout.prevout_type, model::TxPrevoutType::SignersInput);
+        assert_eq!(prevout.prevout_txid, signer_outpoint.txid.into());
+
+        // Only the output that the signers control is recorded, and it is
+        // a donation rather than a signers' output.
+        let output = store.bitcoin_outputs.get(&txid.into()).unwrap();
+        assert_eq!(output.output_type, model::TxOutputType::Donation);
+        assert_eq!(output.output_index, 0);
+
+        // The unknown magic bytes are not recorded either.
+        assert!(store.sweep_magic_bytes.is_empty());
+    }
+
+    /// Check that the signer set in the signer state follows the
+    /// `sbtc-registry` contract, and that we fall back on the bootstrap
+    /// signer set when the contract has not been set.
+    #[test(tokio::test)]
+    async fn update_signer_set_follows_the_registry() {
+        let mut ctx = TestContext::default_mocked();
+        let bootstrap_signer_set = ctx.config().signer.bootstrap_signing_set();
+
+        ctx.with_stacks_client(|client| {
+            client
+                .expect_get_current_signer_set()
+                .returning(|_| Box::pin(std::future::ready(Ok(Vec::new()))));
+            client
+                .expect_get_current_signature_threshold()
+                .returning(|_| Box::pin(std::future::ready(Ok(None))));
+        })
+        .await;
+
+        update_signer_set(&ctx).await.unwrap();
+        assert!(ctx.state().registry_signer_set().is_none());
+        let signer_set: BTreeSet<PublicKey> = ctx
+            .state()
+            .current_signer_set()
+            .get_signers()
+            .into_iter()
+            .map(|signer| *signer.public_key())
+            .collect();
+        assert_eq!(signer_set, bootstrap_signer_set);
+
+        let new_signer_set: Vec<PublicKey> = (0..3).map(|_| fake::Faker.fake()).collect();
+        let registry_signer_set = new_signer_set.clone();
+        ctx.with_stacks_client(|client| {
+            client.checkpoint();
+            client
+                .expect_get_current_signer_set()
+                .returning(move |_| Box::pin(std::future::ready(Ok(registry_signer_set.clone()))));
+            client
+                .expect_get_current_signature_threshold()
+                .returning(|_| Box::pin(std::future::ready(Ok(Some(2)))));
+        })
+        .await;
+
+        update_signer_set(&ctx).await.unwrap();
+        let expected = RegistrySignerSet {
+            signer_set: new_signer_set.iter().copied().collect(),
+            signatures_required: 2,
+        };
+        assert_eq!(ctx.state().registry_signer_set(), Some(expected.clone()));
+        let (signer_set, signatures_required) = load_current_signer_set(&ctx).await.unwrap();
+        assert_eq!(signer_set, expected.signer_set);
+        assert_eq!(signatures_required, 2);
+
+        for public_key in bootstrap_signer_set.difference(&signer_set) {
+            assert!(!ctx.state().current_signer_set().is_signer(public_key));
+        }
+        for public_key in &new_signer_set {
+            assert!(ctx.state().current_signer_set().is_signer(public_key));
+        }
+
+        // If the contract cannot be reached then we keep the signer set
+        // that we read from it last.
+        ctx.with_stacks_client(|client| {
+            client.checkpoint();
+            client
+                .expect_get_current_signer_set()
+                .returning(|_| Box::pin(std::future::ready(Err(Error::InvalidStacksResponse("")))));
+        })
+        .await;
+
+        update_signer_set(&ctx).await.unwrap();
+        assert_eq!(ctx.state().registry_signer_set(), Some(expected));
+    }
 }
@@ -11,6 +11,7 @@ use blocklist_api::apis::Error as ClientError;
 use blocklist_api::models::BlocklistStatus;
 use std::future::Future;
 
+use crate::config::BlocklistClientConfig;
 use crate::context::Context;
 
 /// A trait for checking if an address is blocklisted.
@@ -21,6 +22,11 @@ pub trait BlocklistChecker {
         &self,
         address: &str,
     ) -> impl Future<Output = Result<bool, ClientError<CheckAddressError>>> + Send;
+
+    /// Point the checker at the blocklist service in the given
+    /// configuration, after the configuration has been reloaded.
+    /// Checkers that do not talk to a configured service ignore this.
+    fn reload_config(&mut self, _config: &BlocklistClientConfig) {}
 }
 
 /// A client for interacting with the blocklist service.
@@ -37,13 +43,22 @@ impl BlocklistChecker for BlocklistClient {
         let resp: BlocklistStatus = check_address(&config, address).await?;
         Ok(resp.accept)
     }
+
+    fn reload_config(&mut self, config: &BlocklistClientConfig) {
+        *self = Self::from_config(config);
+    }
 }
 
 impl BlocklistClient {
     /// Construct a new [`BlocklistClient`]
     pub fn new(ctx: &impl Context) -> Option<Self> {
-        let config = ctx.config().blocklist_client.as_ref()?;
+        let config = ctx.config();
+        Some(Self::from_config(config.blocklist_client.as_ref()?))
+    }
 
+    /// Construct a new [`BlocklistClient`] for the blocklist service in
+    /// the given configuration.
+    fn from_config(config: &BlocklistClientConfig) -> Self {
         let mut config = Configuration {
             base_path: config.endpoint.to_string(),
             ..Default::default()
@@ -57,7 +72,7 @@ impl BlocklistClient {
             .trim_end_matches("/")
             .to_string();
 
-        Some(BlocklistClient { config })
+        BlocklistClient { config }
     }
 
     #[cfg(test)]
@@ -208,4 +223,14 @@ mod tests {
 
         assert_eq!(client.config.base_path, "http://localhost:8080");
     }
+
+    #[test]
+    fn reload_config_changes_the_endpoint() {
+        let mut client = BlocklistClient::with_base_url("http://localhost:8080".to_string());
+
+        let endpoint = Url::parse("http://blocklist.internal:3032/").unwrap();
+        client.reload_config(&BlocklistClientConfig { endpoint });
+
+        assert_eq!(client.config.base_path, "http://blocklist.internal:3032");
+    }
 }
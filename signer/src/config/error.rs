@@ -59,4 +59,8 @@ pub enum SignerConfigError {
 
     #[error("The provided Bitcoin processing delay must be small than {0}s, got {1}s")]
     InvalidBitcoinProcessingDelay(u64, u64),
+
+    /// The magic bytes override must be exactly two ASCII characters.
+    #[error("Invalid magic bytes: expected exactly two ASCII characters, got '{0}'")]
+    InvalidMagicBytes(String),
 }
@@ -46,6 +46,26 @@ where
     ))
 }
 
+/// A deserializer for the optional two byte magic bytes override. The
+/// magic bytes are given as a two character ASCII string, like "X2".
+pub fn magic_bytes_deserializer<'de, D>(deserializer: D) -> Result<Option<[u8; 2]>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(s) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    if s.len() != 2 || !s.is_ascii() {
+        return Err(serde::de::Error::custom(
+            SignerConfigError::InvalidMagicBytes(s),
+        ));
+    }
+
+    let bytes = s.as_bytes();
+    Ok(Some([bytes[0], bytes[1]]))
+}
+
 pub fn p2p_multiaddr_deserializer_vec<'de, D>(deserializer: D) -> Result<Vec<Multiaddr>, D::Error>
 where
     D: Deserializer<'de>,
@@ -84,6 +104,17 @@ where
     }
 }
 
+/// A deserializer for an optional [`PrivateKey`], see
+/// [`private_key_deserializer`].
+pub fn optional_private_key_deserializer<'de, D>(
+    deserializer: D,
+) -> Result<Option<PrivateKey>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    private_key_deserializer(deserializer).map(Some)
+}
+
 pub fn try_parse_p2p_multiaddr(s: &str) -> Result<Multiaddr, SignerConfigError> {
     // Keeping these local here as this is the only place these should need to be used.
     use libp2p::multiaddr::Protocol;
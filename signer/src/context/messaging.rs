@@ -1,6 +1,10 @@
 //! This module contains types related to the application's internal
 //! messaging via the [`Context`].
 
+use std::sync::Arc;
+
+use crate::config::Settings;
+
 /// Signals that can be sent within the signer binary.
 #[derive(Debug, Clone, PartialEq)]
 pub enum SignerSignal {
@@ -28,6 +32,11 @@ pub enum SignerCommand {
     P2PPublish(crate::network::Msg),
     /// Signal to shut down the application
     Shutdown,
+    /// Signals to the application that the configuration was reloaded.
+    /// The settings only differ from the current ones in the settings
+    /// that can be changed while the signer is running, see
+    /// [`Settings::merge_reloadable`].
+    ReloadConfig(Arc<Settings>),
 }
 
 /// Events that can be received on the signalling channel.
@@ -37,6 +46,10 @@ pub enum SignerEvent {
     P2P(P2PEvent),
     /// Signals that a block observer event has occurred.
     BitcoinBlockObserved,
+    /// Signals that the block observer has stored new deposit requests
+    /// that passed validation, and that they should be shared with the
+    /// other signers.
+    NewDepositRequestsObserved(Vec<crate::message::NewDepositRequest>),
     /// A Request decider event has occurred.
     RequestDecider(RequestDeciderEvent),
     /// Transaction signer events
@@ -5,6 +5,8 @@ mod signer_context;
 mod signer_state;
 mod termination;
 
+use std::sync::Arc;
+
 use tokio::sync::broadcast::error::RecvError;
 use tokio_stream::wrappers::ReceiverStream;
 
@@ -25,7 +27,10 @@ pub use termination::*;
 /// Context trait that is implemented by the [`SignerContext`].
 pub trait Context: Clone + Sync + Send {
     /// Get the current configuration for the signer.
-    fn config(&self) -> &Settings;
+    fn config(&self) -> Arc<Settings>;
+    /// Replace the configuration, in this context and all of its clones,
+    /// with settings that were reloaded while the signer is running.
+    fn set_config(&self, config: Settings);
     /// Get the current state for the signer.
     fn state(&self) -> &SignerState;
     /// Subscribe to the application signalling channel, returning a receiver
@@ -1,11 +1,11 @@
 use std::sync::Arc;
+use std::sync::RwLock;
 
 use tokio::sync::broadcast::Sender;
-use url::Url;
 
 use crate::{
     bitcoin::BitcoinInteract,
-    config::{EmilyClientConfig, Settings},
+    config::{BitcoinConfig, EmilyClientConfig, Settings},
     emily_client::EmilyInteract,
     error::Error,
     stacks::api::StacksInteract,
@@ -19,7 +19,10 @@ use super::{Context, SignerSignal, SignerState, TerminationHandle};
 /// signer binary.
 #[derive(Debug, Clone)]
 pub struct SignerContext<S, BC, ST, EM> {
-    config: Settings,
+    /// The current configuration for the signer. It is shared by all
+    /// clones of the context so that every component sees the settings
+    /// once they are reloaded.
+    config: Arc<RwLock<Arc<Settings>>>,
     // Handle to the app signalling channel. This keeps the channel alive
     // for the duration of the program and is used both to send messages
     // and to hand out new receivers.
@@ -37,10 +40,14 @@ pub struct SignerContext<S, BC, ST, EM> {
     // TODO: Additional clients to be added in future PRs. We may want
     // to break the clients out into a separate struct to keep the field
     // count down.
-    /// Handle to a Stacks-RPC fallback-client.
-    stacks_client: ST,
-    /// Handle to a Emily-API fallback-client.
-    emily_client: EM,
+    /// Handle to a Stacks-RPC fallback-client. It is shared by all clones
+    /// of the context so that it can be replaced when the configuration
+    /// is reloaded.
+    stacks_client: Arc<RwLock<ST>>,
+    /// Handle to a Emily-API fallback-client. It is shared by all clones
+    /// of the context so that it can be replaced when the configuration
+    /// is reloaded.
+    emily_client: Arc<RwLock<EM>>,
     // /// Handle to a Blocklist-API fallback-client.
     //blocklist_client: ApiFallbackClient<BL>,
 }
@@ -48,22 +55,42 @@ pub struct SignerContext<S, BC, ST, EM> {
 impl<S, BC, ST, EM> SignerContext<S, BC, ST, EM>
 where
     S: DbRead + DbWrite + Clone + Sync + Send + 'static,
-    BC: for<'a> TryFrom<&'a [Url]> + BitcoinInteract + Clone + 'static,
+    BC: for<'a> TryFrom<&'a BitcoinConfig> + BitcoinInteract + Clone + 'static,
     ST: for<'a> TryFrom<&'a Settings> + StacksInteract + Clone + Sync + Send + 'static,
     EM: for<'a> TryFrom<&'a EmilyClientConfig> + EmilyInteract + Clone + Sync + Send + 'static,
-    Error: for<'a> From<<BC as TryFrom<&'a [Url]>>::Error>,
+    Error: for<'a> From<<BC as TryFrom<&'a BitcoinConfig>>::Error>,
     Error: for<'a> From<<ST as TryFrom<&'a Settings>>::Error>,
     Error: for<'a> From<<EM as TryFrom<&'a EmilyClientConfig>>::Error>,
 {
     /// Initializes a new [`SignerContext`], automatically creating clients
     /// based on the provided types.
     pub fn init(config: Settings, db: S) -> Result<Self, Error> {
-        let bc = BC::try_from(&config.bitcoin.rpc_endpoints)?;
+        let bc = BC::try_from(&config.bitcoin)?;
         let st = ST::try_from(&config)?;
         let em = EM::try_from(&config.emily)?;
 
         Ok(Self::new(config, db, bc, st, em))
     }
+
+    /// Replace the Stacks and Emily clients, in this context and all of
+    /// its clones, with clients for the endpoints in the given
+    /// configuration.
+    pub fn reload_clients(&self, config: &Settings) -> Result<(), Error> {
+        let st = ST::try_from(config)?;
+        let em = EM::try_from(&config.emily)?;
+
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        *self
+            .stacks_client
+            .write()
+            .expect("BUG: Failed to acquire write lock") = st;
+        *self
+            .emily_client
+            .write()
+            .expect("BUG: Failed to acquire write lock") = em;
+
+        Ok(())
+    }
 }
 
 impl<S, BC, ST, EM> SignerContext<S, BC, ST, EM>
@@ -88,14 +115,14 @@ where
         let (term_tx, _) = tokio::sync::watch::channel(false);
 
         Self {
-            config,
+            config: Arc::new(RwLock::new(Arc::new(config))),
             state: Default::default(),
             signal_tx,
             term_tx,
             storage: db,
             bitcoin_client,
-            stacks_client,
-            emily_client,
+            stacks_client: Arc::new(RwLock::new(stacks_client)),
+            emily_client: Arc::new(RwLock::new(emily_client)),
         }
     }
 }
@@ -107,8 +134,20 @@ where
     ST: StacksInteract + Clone + Sync + Send + 'static,
     EM: EmilyInteract + Clone + Sync + Send + 'static,
 {
-    fn config(&self) -> &Settings {
-        &self.config
+    fn config(&self) -> Arc<Settings> {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        self.config
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .clone()
+    }
+
+    fn set_config(&self, config: Settings) {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        *self
+            .config
+            .write()
+            .expect("BUG: Failed to acquire write lock") = Arc::new(config);
     }
 
     fn state(&self) -> &SignerState {
@@ -154,19 +193,30 @@ where
     }
 
     fn get_stacks_client(&self) -> impl StacksInteract + Clone + 'static {
-        self.stacks_client.clone()
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        self.stacks_client
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .clone()
     }
 
     fn get_emily_client(&self) -> impl EmilyInteract + Clone + 'static {
-        self.emily_client.clone()
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        self.emily_client
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .clone()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::{
-        atomic::{AtomicU8, Ordering},
-        Arc,
+    use std::{
+        sync::{
+            atomic::{AtomicU8, Ordering},
+            Arc,
+        },
+        time::Duration,
     };
 
     use tokio::sync::Notify;
@@ -236,4 +286,29 @@ mod tests {
         // Ensure that the signal was received.
         assert_eq!(recv_count.load(std::sync::atomic::Ordering::Relaxed), 1);
     }
+
+    /// This test shows that a configuration that is set on one handle to
+    /// the context is seen by all of its clones, including clones that
+    /// were made before the configuration was set.
+    #[test]
+    fn context_clone_sees_reloaded_config() {
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let context_clone = context.clone();
+        let original = context_clone.config();
+
+        let mut settings = (*context.config()).clone();
+        settings.signer.bitcoin_processing_delay += Duration::from_secs(1);
+        context.set_config(settings.clone());
+
+        assert_eq!(*context.config(), settings);
+        assert_eq!(*context_clone.config(), settings);
+        assert_eq!(*context.clone().config(), settings);
+
+        // Configurations handed out before the reload are unaffected.
+        assert_ne!(*original, settings);
+    }
 }
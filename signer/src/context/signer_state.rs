@@ -1,12 +1,15 @@
 //! Module for signer state
 
+use std::collections::BTreeSet;
 use std::sync::RwLock;
 
 use bitcoin::Amount;
 use hashbrown::HashSet;
 use libp2p::PeerId;
+use tokio::time::Instant;
 
 use crate::keys::PublicKey;
+use crate::storage::model::BitcoinBlockHash;
 
 /// A struct for holding internal signer state. This struct is served by
 /// the [`SignerContext`] and can be used to cache global state instead of
@@ -15,6 +18,10 @@ use crate::keys::PublicKey;
 pub struct SignerState {
     current_signer_set: SignerSet,
     current_limits: RwLock<SbtcLimits>,
+    registry_signer_set: RwLock<Option<RegistrySignerSet>>,
+    bitcoin_chain_tip_observed_at: RwLock<Option<(BitcoinBlockHash, Instant)>>,
+    coordinator_activity: RwLock<Option<(BitcoinBlockHash, usize, Instant)>>,
+    connected_peers: RwLock<HashSet<PeerId>>,
 }
 
 impl SignerState {
@@ -23,6 +30,120 @@ impl SignerState {
         &self.current_signer_set
     }
 
+    /// Get the signer set last read from the `sbtc-registry` contract, if
+    /// it has been set in the contract.
+    pub fn registry_signer_set(&self) -> Option<RegistrySignerSet> {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        self.registry_signer_set
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .clone()
+    }
+
+    /// Update the signer set read from the `sbtc-registry` contract.
+    pub fn update_registry_signer_set(&self, new_signer_set: Option<RegistrySignerSet>) {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        let mut signer_set = self
+            .registry_signer_set
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        *signer_set = new_signer_set;
+    }
+
+    /// Get the instant at which the given bitcoin chain tip was first
+    /// observed by this signer. The first call for a new chain tip records
+    /// the current instant, replacing the one recorded for the previous
+    /// chain tip.
+    pub fn bitcoin_chain_tip_observed_at(&self, chain_tip: &BitcoinBlockHash) -> Instant {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        let observed = *self
+            .bitcoin_chain_tip_observed_at
+            .read()
+            .expect("BUG: Failed to acquire read lock");
+        match observed {
+            Some((block_hash, instant)) if &block_hash == chain_tip => instant,
+            _ => {
+                let mut observed = self
+                    .bitcoin_chain_tip_observed_at
+                    .write()
+                    .expect("BUG: Failed to acquire write lock");
+                // Another task may have recorded the chain tip while we
+                // were waiting for the write lock.
+                match *observed {
+                    Some((block_hash, instant)) if &block_hash == chain_tip => instant,
+                    _ => {
+                        let now = Instant::now();
+                        *observed = Some((*chain_tip, now));
+                        now
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the coordinator failover round that was active when we last
+    /// heard from the coordinator for the given bitcoin chain tip, along
+    /// with the instant at which that happened. If we have not heard from
+    /// a coordinator for the chain tip, then this is the first round and
+    /// the instant at which the chain tip was first observed.
+    pub fn coordinator_activity(&self, chain_tip: &BitcoinBlockHash) -> (usize, Instant) {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        let activity = *self
+            .coordinator_activity
+            .read()
+            .expect("BUG: Failed to acquire read lock");
+        match activity {
+            Some((block_hash, round, instant)) if &block_hash == chain_tip => (round, instant),
+            _ => (0, self.bitcoin_chain_tip_observed_at(chain_tip)),
+        }
+    }
+
+    /// Record that we heard from the coordinator of the given failover
+    /// round for the given bitcoin chain tip at the given instant.
+    pub fn set_coordinator_activity(
+        &self,
+        chain_tip: &BitcoinBlockHash,
+        round: usize,
+        instant: Instant,
+    ) {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        let mut activity = self
+            .coordinator_activity
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        *activity = Some((*chain_tip, round, instant));
+    }
+
+    /// Get the peers in the signer set that we currently have a connection
+    /// to.
+    pub fn connected_peers(&self) -> Vec<PeerId> {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        self.connected_peers
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Record that we have a connection to the given peer.
+    pub fn peer_connected(&self, peer_id: PeerId) {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        self.connected_peers
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .insert(peer_id);
+    }
+
+    /// Record that we no longer have any connection to the given peer.
+    pub fn peer_disconnected(&self, peer_id: &PeerId) {
+        // We should never fail to acquire a lock from the RwLock so that it panics.
+        self.connected_peers
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .remove(peer_id);
+    }
+
     /// Get the current sBTC limits.
     pub fn get_current_limits(&self) -> SbtcLimits {
         // We should never fail to acquire a lock from the RwLock so that it panics.
@@ -103,6 +224,16 @@ impl SbtcLimits {
     }
 }
 
+/// The signer set and signature threshold stored in the `sbtc-registry`
+/// contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrySignerSet {
+    /// The public keys of the signers in the signer set.
+    pub signer_set: BTreeSet<PublicKey>,
+    /// The number of signatures required by the signers' multi-sig wallet.
+    pub signatures_required: u16,
+}
+
 /// Represents a signer in the current signer set.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Signer {
@@ -194,6 +325,26 @@ impl SignerSet {
         }
     }
 
+    /// Replace the known active signer set with the given signers,
+    /// removing the signers that are not in the given set. Returns whether
+    /// the signer set changed.
+    pub fn replace_signers(&self, signers: &BTreeSet<PublicKey>) -> bool {
+        let current: BTreeSet<PublicKey> = self
+            .get_signers()
+            .into_iter()
+            .map(|signer| signer.public_key)
+            .collect();
+
+        for removed in current.difference(signers) {
+            self.remove_signer(removed);
+        }
+        for added in signers.difference(&current) {
+            self.add_signer(*added);
+        }
+
+        &current != signers
+    }
+
     /// Returns the current set of public keys for the known active signers.
     pub fn get_signers(&self) -> Vec<Signer> {
         #[allow(clippy::expect_used)]
@@ -246,6 +397,46 @@ mod tests {
         assert!(!signer_set.is_signer(&public_key));
     }
 
+    #[test]
+    fn test_replace_signers() {
+        use super::*;
+
+        let signer_set = SignerSet::default();
+        let keys: Vec<PublicKey> = (0..3)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut OsRng)))
+            .collect();
+
+        signer_set.add_signer(keys[0]);
+        signer_set.add_signer(keys[1]);
+
+        let new_set = BTreeSet::from([keys[1], keys[2]]);
+        assert!(signer_set.replace_signers(&new_set));
+        assert!(!signer_set.is_signer(&keys[0]));
+        assert!(!signer_set.is_allowed_peer(&keys[0].into()));
+        assert!(signer_set.is_signer(&keys[1]));
+        assert!(signer_set.is_signer(&keys[2]));
+        assert!(signer_set.is_allowed_peer(&keys[2].into()));
+
+        // Replacing the set with the same signers is a no-op.
+        assert!(!signer_set.replace_signers(&new_set));
+        assert_eq!(signer_set.get_signers().len(), 2);
+    }
+
+    #[test]
+    fn test_connected_peers() {
+        use super::*;
+
+        let state = SignerState::default();
+        let peer_id: PeerId = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng)).into();
+
+        assert!(state.connected_peers().is_empty());
+        state.peer_connected(peer_id);
+        state.peer_connected(peer_id);
+        assert_eq!(state.connected_peers(), vec![peer_id]);
+        state.peer_disconnected(&peer_id);
+        assert!(state.connected_peers().is_empty());
+    }
+
     #[test]
     fn test_is_allowed_peer() {
         use super::*;
@@ -0,0 +1,486 @@
+//! Export and import of the signer's DKG shares
+//!
+//! The signer's shares of the aggregate key only live in the `dkg_shares`
+//! table of its database, encrypted with the signer's private key. This
+//! module writes them to a portable backup so that they can be restored
+//! if the database is lost.
+//!
+//! A backup has two layers of encryption. The private shares stay
+//! encrypted with the signer's private key, exactly as they are stored
+//! in the database, and the whole set of shares is encrypted once more
+//! with a key derived from a passphrase. Restoring a backup therefore
+//! requires both the passphrase and the private key of the signer that
+//! exported it.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::codec::Decode as _;
+use crate::error::Error;
+use crate::keys::PrivateKey;
+use crate::keys::PublicKey;
+use crate::keys::SignerScriptPubKey as _;
+use crate::storage::model;
+use crate::storage::model::EncryptedDkgShares;
+use crate::storage::DbRead;
+use crate::storage::DbWrite;
+
+/// The version of the backup format written by this signer.
+pub const DKG_BACKUP_VERSION: u32 = 1;
+
+/// The number of PBKDF2 iterations used when deriving the encryption key
+/// from the passphrase of a new backup. Unit tests use far fewer, since
+/// they are built without optimizations.
+const PBKDF2_ITERATIONS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+
+/// The length of the random salt used when deriving the encryption key.
+const SALT_LENGTH: usize = 16;
+
+/// Errors that can occur when exporting or importing DKG shares.
+#[derive(Debug, thiserror::Error)]
+pub enum DkgBackupError {
+    /// The backup was written in a format that this signer does not
+    /// understand.
+    #[error("unsupported DKG backup version {0}, expected version {DKG_BACKUP_VERSION}")]
+    UnsupportedVersion(u32),
+
+    /// The passphrase was empty.
+    #[error("the DKG backup passphrase must not be empty")]
+    EmptyPassphrase,
+
+    /// The backup asks for zero key derivation iterations.
+    #[error("invalid number of key derivation iterations in the DKG backup: {0}")]
+    InvalidIterations(u32),
+
+    /// The backup could not be decrypted with the passphrase.
+    #[error("could not decrypt the DKG backup, is the passphrase correct?")]
+    Decryption,
+
+    /// The backup or its decrypted contents are not valid JSON in the
+    /// expected shape.
+    #[error("the DKG backup is malformed: {0}")]
+    MalformedJson(#[source] serde_json::Error),
+
+    /// A hex encoded field of the backup is not valid hex.
+    #[error("the DKG backup is malformed: {0}")]
+    MalformedHex(#[source] hex::FromHexError),
+
+    /// The tweaked aggregate key does not follow from the aggregate key.
+    #[error("the tweaked aggregate key does not match aggregate key {0}")]
+    TweakedAggregateKeyMismatch(PublicKey),
+
+    /// The scriptPubKey does not follow from the aggregate key.
+    #[error("the scriptPubKey does not match aggregate key {0}")]
+    ScriptPubKeyMismatch(PublicKey),
+
+    /// The signer set is empty, unsorted or contains duplicates, or the
+    /// signature threshold cannot be met by it.
+    #[error("invalid signer set or signature threshold for aggregate key {0}")]
+    InvalidSignerSet(PublicKey),
+
+    /// This signer did not take part in the DKG round for the shares.
+    #[error("this signer is not in the signer set for aggregate key {0}")]
+    NotInSignerSet(PublicKey),
+
+    /// The private key of this signer cannot decrypt the private shares.
+    #[error("the private shares for aggregate key {0} cannot be decrypted with this signer's key")]
+    UndecryptableShares(PublicKey),
+
+    /// The decrypted private shares are for a different aggregate key.
+    #[error("the private shares for aggregate key {expected} are for aggregate key {actual}")]
+    AggregateKeyMismatch {
+        /// The aggregate key that the shares are stored under.
+        expected: PublicKey,
+        /// The aggregate key in the decrypted private shares.
+        actual: PublicKey,
+    },
+
+    /// The database already holds different shares for the aggregate key.
+    #[error("different DKG shares are already stored for aggregate key {0}")]
+    ConflictingShares(PublicKey),
+}
+
+/// A passphrase encrypted backup of all of a signer's DKG shares.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgSharesBackup {
+    /// The version of the backup format.
+    pub version: u32,
+    /// How the encryption key was derived from the passphrase.
+    pub key_derivation: KeyDerivation,
+    /// The hex encoded, encrypted JSON array of DKG shares.
+    pub ciphertext: String,
+}
+
+/// The function used to derive the encryption key of a backup from its
+/// passphrase.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "algorithm", rename_all = "kebab-case")]
+pub enum KeyDerivation {
+    /// PBKDF2 with HMAC-SHA256.
+    Pbkdf2HmacSha256 {
+        /// The number of iterations.
+        iterations: u32,
+        /// The hex encoded salt.
+        salt: String,
+    },
+}
+
+impl KeyDerivation {
+    fn derive_key(&self, passphrase: &str) -> Result<[u8; 32], Error> {
+        let KeyDerivation::Pbkdf2HmacSha256 { iterations, salt } = self;
+        if *iterations == 0 {
+            return Err(DkgBackupError::InvalidIterations(*iterations).into());
+        }
+        let salt = hex::decode(salt).map_err(DkgBackupError::MalformedHex)?;
+
+        let mut key = [0; 32];
+        pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), &salt, *iterations, &mut key);
+        Ok(key)
+    }
+}
+
+/// The DKG shares as they are written inside of a backup.
+#[derive(Debug, Serialize, Deserialize)]
+struct BackedUpDkgShares {
+    aggregate_key: PublicKey,
+    tweaked_aggregate_key: PublicKey,
+    script_pubkey: String,
+    encrypted_private_shares: String,
+    public_shares: String,
+    signer_set_public_keys: Vec<PublicKey>,
+    signature_share_threshold: u16,
+}
+
+impl From<&EncryptedDkgShares> for BackedUpDkgShares {
+    fn from(shares: &EncryptedDkgShares) -> Self {
+        Self {
+            aggregate_key: shares.aggregate_key,
+            tweaked_aggregate_key: shares.tweaked_aggregate_key,
+            script_pubkey: hex::encode(shares.script_pubkey.as_bytes()),
+            encrypted_private_shares: hex::encode(&shares.encrypted_private_shares),
+            public_shares: hex::encode(&shares.public_shares),
+            signer_set_public_keys: shares.signer_set_public_keys.clone(),
+            signature_share_threshold: shares.signature_share_threshold,
+        }
+    }
+}
+
+impl TryFrom<BackedUpDkgShares> for EncryptedDkgShares {
+    type Error = Error;
+
+    fn try_from(shares: BackedUpDkgShares) -> Result<Self, Self::Error> {
+        let decode = |data: &str| hex::decode(data).map_err(DkgBackupError::MalformedHex);
+        let script_pubkey = bitcoin::ScriptBuf::from_bytes(decode(&shares.script_pubkey)?);
+
+        Ok(EncryptedDkgShares {
+            aggregate_key: shares.aggregate_key,
+            tweaked_aggregate_key: shares.tweaked_aggregate_key,
+            script_pubkey: script_pubkey.into(),
+            encrypted_private_shares: decode(&shares.encrypted_private_shares)?,
+            public_shares: decode(&shares.public_shares)?,
+            signer_set_public_keys: shares.signer_set_public_keys,
+            signature_share_threshold: shares.signature_share_threshold,
+        })
+    }
+}
+
+impl DkgSharesBackup {
+    /// Encrypt the given DKG shares with a key derived from the
+    /// passphrase.
+    pub fn encrypt<R>(
+        shares: &[EncryptedDkgShares],
+        passphrase: &str,
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        R: rand::RngCore + rand::CryptoRng,
+    {
+        if passphrase.is_empty() {
+            return Err(DkgBackupError::EmptyPassphrase.into());
+        }
+
+        let mut salt = [0; SALT_LENGTH];
+        rng.fill_bytes(&mut salt);
+        let key_derivation = KeyDerivation::Pbkdf2HmacSha256 {
+            iterations: PBKDF2_ITERATIONS,
+            salt: hex::encode(salt),
+        };
+        let key = key_derivation.derive_key(passphrase)?;
+
+        let shares = shares
+            .iter()
+            .map(BackedUpDkgShares::from)
+            .collect::<Vec<_>>();
+        let plaintext = serde_json::to_vec(&shares).map_err(Error::JsonSerialize)?;
+        let ciphertext =
+            wsts::util::encrypt(&key, &plaintext, rng).map_err(|_| Error::Encryption)?;
+
+        Ok(Self {
+            version: DKG_BACKUP_VERSION,
+            key_derivation,
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt the DKG shares in the backup using the passphrase.
+    ///
+    /// The private shares remain encrypted with the private key of the
+    /// signer that exported them.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Vec<EncryptedDkgShares>, Error> {
+        if self.version != DKG_BACKUP_VERSION {
+            return Err(DkgBackupError::UnsupportedVersion(self.version).into());
+        }
+
+        let key = self.key_derivation.derive_key(passphrase)?;
+        let ciphertext = hex::decode(&self.ciphertext).map_err(DkgBackupError::MalformedHex)?;
+        let plaintext =
+            wsts::util::decrypt(&key, &ciphertext).map_err(|_| DkgBackupError::Decryption)?;
+
+        let shares: Vec<BackedUpDkgShares> =
+            serde_json::from_slice(&plaintext).map_err(DkgBackupError::MalformedJson)?;
+
+        shares
+            .into_iter()
+            .map(EncryptedDkgShares::try_from)
+            .collect()
+    }
+
+    /// Parse a backup from its JSON representation.
+    pub fn from_json(data: &[u8]) -> Result<Self, Error> {
+        serde_json::from_slice(data).map_err(|err| DkgBackupError::MalformedJson(err).into())
+    }
+
+    /// Return the JSON representation of the backup.
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self).map_err(Error::JsonSerialize)
+    }
+}
+
+/// Check that the DKG shares are internally consistent and that they
+/// belong to the signer with the given private key.
+///
+/// This checks that the tweaked aggregate key and the scriptPubKey follow
+/// from the aggregate key, that the signer set is sorted, free of
+/// duplicates, includes this signer and can meet the signature threshold,
+/// and that the private shares decrypt, using the private key, to shares
+/// of the aggregate key.
+pub fn validate_dkg_shares(
+    shares: &EncryptedDkgShares,
+    private_key: &PrivateKey,
+) -> Result<(), Error> {
+    let aggregate_key = shares.aggregate_key;
+
+    if shares.tweaked_aggregate_key != aggregate_key.signers_tweaked_pubkey()? {
+        return Err(DkgBackupError::TweakedAggregateKeyMismatch(aggregate_key).into());
+    }
+
+    let script_pubkey: model::ScriptPubKey = aggregate_key.signers_script_pubkey().into();
+    if shares.script_pubkey != script_pubkey {
+        return Err(DkgBackupError::ScriptPubKeyMismatch(aggregate_key).into());
+    }
+
+    let signer_set = &shares.signer_set_public_keys;
+    let is_sorted_and_unique = signer_set.windows(2).all(|pair| pair[0] < pair[1]);
+    let threshold = usize::from(shares.signature_share_threshold);
+    if !is_sorted_and_unique || threshold == 0 || threshold > signer_set.len() {
+        return Err(DkgBackupError::InvalidSignerSet(aggregate_key).into());
+    }
+
+    let public_key = PublicKey::from_private_key(private_key);
+    if !signer_set.contains(&public_key) {
+        return Err(DkgBackupError::NotInSignerSet(aggregate_key).into());
+    }
+
+    let decrypted = wsts::util::decrypt(&private_key.to_bytes(), &shares.encrypted_private_shares)
+        .map_err(|_| DkgBackupError::UndecryptableShares(aggregate_key))?;
+    let saved_state = wsts::traits::SignerState::decode(decrypted.as_slice())?;
+
+    let actual = PublicKey::try_from(&saved_state.group_key)?;
+    if actual != aggregate_key {
+        let expected = aggregate_key;
+        return Err(DkgBackupError::AggregateKeyMismatch { expected, actual }.into());
+    }
+
+    Ok(())
+}
+
+/// Export all of the DKG shares in the database to a backup that is
+/// encrypted with the passphrase.
+pub async fn export_dkg_shares<S, R>(
+    storage: &S,
+    passphrase: &str,
+    rng: &mut R,
+) -> Result<DkgSharesBackup, Error>
+where
+    S: DbRead,
+    R: rand::RngCore + rand::CryptoRng,
+{
+    let shares = storage.get_all_encrypted_dkg_shares().await?;
+    DkgSharesBackup::encrypt(&shares, passphrase, rng)
+}
+
+/// Import the DKG shares in the backup into the database, returning the
+/// aggregate keys of the shares that were written.
+///
+/// All shares are validated with [`validate_dkg_shares`] before any of
+/// them are written. Shares that are already in the database are skipped,
+/// while different shares for an aggregate key that is already in the
+/// database are an error.
+pub async fn import_dkg_shares<S>(
+    storage: &S,
+    backup: &DkgSharesBackup,
+    passphrase: &str,
+    private_key: &PrivateKey,
+) -> Result<Vec<PublicKey>, Error>
+where
+    S: DbRead + DbWrite,
+{
+    let shares = backup.decrypt(passphrase)?;
+
+    let mut new_shares = Vec::new();
+    for shares in shares {
+        validate_dkg_shares(&shares, private_key)?;
+
+        match storage
+            .get_encrypted_dkg_shares(&shares.aggregate_key)
+            .await?
+        {
+            Some(stored) if stored == shares => continue,
+            Some(_) => {
+                return Err(DkgBackupError::ConflictingShares(shares.aggregate_key).into());
+            }
+            None => new_shares.push(shares),
+        }
+    }
+
+    for shares in new_shares.iter() {
+        storage.write_encrypted_dkg_shares(shares).await?;
+    }
+
+    Ok(new_shares
+        .iter()
+        .map(|shares| shares.aggregate_key)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+    use rand::rngs::OsRng;
+
+    use crate::storage::in_memory::Store;
+    use crate::testing::dummy;
+
+    use super::*;
+
+    /// Return DKG shares that the signer with the given private key can
+    /// import.
+    fn dkg_shares(private_key: &PrivateKey) -> EncryptedDkgShares {
+        let aggregate_key: PublicKey = Faker.fake_with_rng(&mut OsRng);
+        let mut shares =
+            dummy::encrypted_dkg_shares(&Faker, &mut OsRng, &private_key.to_bytes(), aggregate_key);
+        shares.signer_set_public_keys = vec![PublicKey::from_private_key(private_key)];
+        shares
+    }
+
+    #[test]
+    fn backups_round_trip_through_json() {
+        let private_key = PrivateKey::new(&mut OsRng);
+        let shares = vec![dkg_shares(&private_key), dkg_shares(&private_key)];
+
+        let backup = DkgSharesBackup::encrypt(&shares, "passphrase", &mut OsRng).unwrap();
+        let json = backup.to_json().unwrap();
+        let backup = DkgSharesBackup::from_json(json.as_bytes()).unwrap();
+
+        assert_eq!(backup.version, DKG_BACKUP_VERSION);
+        assert_eq!(backup.decrypt("passphrase").unwrap(), shares);
+    }
+
+    #[test]
+    fn backups_cannot_be_decrypted_with_the_wrong_passphrase() {
+        let private_key = PrivateKey::new(&mut OsRng);
+        let shares = vec![dkg_shares(&private_key)];
+        let backup = DkgSharesBackup::encrypt(&shares, "passphrase", &mut OsRng).unwrap();
+
+        match backup.decrypt("wrong passphrase") {
+            Err(Error::DkgBackup(DkgBackupError::Decryption)) => {}
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn backups_with_an_unknown_version_are_rejected() {
+        let backup = DkgSharesBackup::encrypt(&[], "passphrase", &mut OsRng).unwrap();
+        let backup = DkgSharesBackup {
+            version: DKG_BACKUP_VERSION + 1,
+            ..backup
+        };
+
+        match backup.decrypt("passphrase") {
+            Err(Error::DkgBackup(DkgBackupError::UnsupportedVersion(_))) => {}
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn inconsistent_shares_are_rejected() {
+        let private_key = PrivateKey::new(&mut OsRng);
+        let shares = dkg_shares(&private_key);
+        validate_dkg_shares(&shares, &private_key).unwrap();
+
+        let other_key: PublicKey = Faker.fake_with_rng(&mut OsRng);
+        let bad_shares = EncryptedDkgShares {
+            script_pubkey: other_key.signers_script_pubkey().into(),
+            ..shares.clone()
+        };
+        match validate_dkg_shares(&bad_shares, &private_key) {
+            Err(Error::DkgBackup(DkgBackupError::ScriptPubKeyMismatch(_))) => {}
+            result => panic!("unexpected result: {result:?}"),
+        }
+
+        let bad_shares = EncryptedDkgShares {
+            signature_share_threshold: 2,
+            ..shares.clone()
+        };
+        match validate_dkg_shares(&bad_shares, &private_key) {
+            Err(Error::DkgBackup(DkgBackupError::InvalidSignerSet(_))) => {}
+            result => panic!("unexpected result: {result:?}"),
+        }
+
+        // Another signer can neither find itself in the signer set nor
+        // decrypt the private shares.
+        let other_private_key = PrivateKey::new(&mut OsRng);
+        match validate_dkg_shares(&shares, &other_private_key) {
+            Err(Error::DkgBackup(DkgBackupError::NotInSignerSet(_))) => {}
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn exported_shares_can_be_imported_into_an_empty_database() {
+        let private_key = PrivateKey::new(&mut OsRng);
+        let shares = dkg_shares(&private_key);
+
+        let source = Store::new_shared();
+        source.write_encrypted_dkg_shares(&shares).await.unwrap();
+        let backup = export_dkg_shares(&source, "passphrase", &mut OsRng)
+            .await
+            .unwrap();
+
+        let target = Store::new_shared();
+        let imported = import_dkg_shares(&target, &backup, "passphrase", &private_key)
+            .await
+            .unwrap();
+        assert_eq!(imported, vec![shares.aggregate_key]);
+
+        let stored = target.get_all_encrypted_dkg_shares().await.unwrap();
+        assert_eq!(stored, vec![shares]);
+
+        // Importing the same backup again does not write anything.
+        let imported = import_dkg_shares(&target, &backup, "passphrase", &private_key)
+            .await
+            .unwrap();
+        assert!(imported.is_empty());
+    }
+}
@@ -65,6 +65,7 @@ use sha2::Digest as _;
 
 use crate::codec::ProtoSerializable;
 use crate::error::Error;
+use crate::key_backend::SignerKeyBackend;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
 use crate::message::SignerMessage;
@@ -198,6 +199,14 @@ impl<T> std::ops::DerefMut for Signed<T> {
 pub trait SignEcdsa: Sized {
     /// Wrap this type into a [`Signed<Self>`]
     fn sign_ecdsa(self, private_key: &PrivateKey) -> Signed<Self>;
+
+    /// Wrap this type into a [`Signed<Self>`], signing it with the key
+    /// held by the given backend. Unlike signing with a [`PrivateKey`],
+    /// this can fail, since the backend may have to ask an external
+    /// signer for the signature.
+    fn sign_ecdsa_with<K>(self, key: &K) -> Result<Signed<Self>, Error>
+    where
+        K: SignerKeyBackend + ?Sized;
 }
 
 impl SignEcdsa for SignerMessage {
@@ -211,6 +220,20 @@ impl SignEcdsa for SignerMessage {
             signer_public_key: public_key,
         }
     }
+
+    fn sign_ecdsa_with<K>(self, key: &K) -> Result<Signed<Self>, Error>
+    where
+        K: SignerKeyBackend + ?Sized,
+    {
+        let public_key = key.public_key();
+        let msg = secp256k1::Message::from_digest(self.to_digest(public_key));
+
+        Ok(Signed {
+            signature: key.sign_ecdsa(&msg)?,
+            inner: self,
+            signer_public_key: public_key,
+        })
+    }
 }
 
 #[cfg(feature = "testing")]
@@ -268,6 +291,7 @@ mod tests {
     #[test_case(PhantomData::<message::SweepTransactionInfo> ; "SweepTransactionInfo")]
     #[test_case(PhantomData::<message::BitcoinPreSignRequest> ; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<message::BitcoinPreSignAck> ; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<message::NewDepositRequest> ; "NewDepositRequest")]
     fn payload_signing_recovery<T>(_: PhantomData<T>)
     where
         T: Into<message::Payload> + fake::Dummy<Faker>,
@@ -329,6 +353,7 @@ mod tests {
     #[test_case(PhantomData::<message::SweepTransactionInfo> ; "SweepTransactionInfo")]
     #[test_case(PhantomData::<message::BitcoinPreSignRequest> ; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<message::BitcoinPreSignAck> ; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<message::NewDepositRequest> ; "NewDepositRequest")]
     fn payload_signing_failing_validation<T>(_: PhantomData<T>)
     where
         T: Into<message::Payload> + fake::Dummy<Faker>,
@@ -418,6 +443,7 @@ mod tests {
     #[test_case(PhantomData::<message::SweepTransactionInfo> ; "SweepTransactionInfo")]
     #[test_case(PhantomData::<message::BitcoinPreSignRequest> ; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<message::BitcoinPreSignAck> ; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<message::NewDepositRequest> ; "NewDepositRequest")]
     fn backwards_compatible_updates<T>(_: PhantomData<T>)
     where
         T: Into<message::Payload> + fake::Dummy<Faker>,
@@ -16,6 +16,7 @@ use emily_client::apis::Error as EmilyError;
 use emily_client::apis::ResponseContent;
 use emily_client::models::Chainstate;
 use emily_client::models::CreateWithdrawalRequestBody;
+use emily_client::models::DepositInfo;
 use emily_client::models::DepositUpdate;
 use emily_client::models::Status;
 use emily_client::models::UpdateDepositsRequestBody;
@@ -76,6 +77,15 @@ pub enum EmilyClientError {
     GetLimits(EmilyError<limits_api::GetLimitsError>),
 }
 
+/// A pending deposit request in Emily.
+#[derive(Debug, Clone)]
+pub struct PendingDeposit {
+    /// The deposit request.
+    pub request: CreateDepositRequest,
+    /// The Stacks block height at which Emily last updated the deposit.
+    pub last_update_height: u64,
+}
+
 /// Trait describing the interactions with Emily API.
 #[cfg_attr(any(test, feature = "testing"), mockall::automock())]
 pub trait EmilyInteract: Sync + Send {
@@ -86,10 +96,12 @@ pub trait EmilyInteract: Sync + Send {
         output_index: u32,
     ) -> impl std::future::Future<Output = Result<Option<CreateDepositRequest>, Error>> + Send;
 
-    /// Get pending deposits from Emily.
-    fn get_deposits(
+    /// Get the pending deposits that Emily last updated at or after the
+    /// given Stacks block height, going through all pages of results.
+    fn get_deposits_updated_since(
         &self,
-    ) -> impl std::future::Future<Output = Result<Vec<CreateDepositRequest>, Error>> + Send;
+        min_height: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<PendingDeposit>, Error>> + Send;
 
     /// Update accepted deposits after their sweep bitcoin transaction has been
     /// confirmed (but before being finalized -- the stacks transaction minting
@@ -182,6 +194,20 @@ impl TryFrom<&Url> for EmilyClient {
     }
 }
 
+/// Convert a deposit returned by Emily into a deposit request.
+fn deposit_request(deposit: &DepositInfo) -> Result<CreateDepositRequest, Error> {
+    Ok(CreateDepositRequest {
+        outpoint: OutPoint {
+            txid: Txid::from_str(&deposit.bitcoin_txid).map_err(Error::DecodeHexTxid)?,
+            vout: deposit.bitcoin_tx_output_index,
+        },
+        reclaim_script: ScriptBuf::from_hex(&deposit.reclaim_script)
+            .map_err(Error::DecodeHexScript)?,
+        deposit_script: ScriptBuf::from_hex(&deposit.deposit_script)
+            .map_err(Error::DecodeHexScript)?,
+    })
+}
+
 impl EmilyInteract for EmilyClient {
     async fn get_deposit(
         &self,
@@ -214,30 +240,41 @@ impl EmilyInteract for EmilyClient {
                 .map_err(Error::DecodeHexScript)?,
         }))
     }
-    async fn get_deposits(&self) -> Result<Vec<CreateDepositRequest>, Error> {
-        // TODO: hanlde pagination -- if the queried data is over 1MB DynamoDB will
-        // paginate the results even if we pass `None` as page limit.
-        let resp = deposit_api::get_deposits(&self.config, Status::Pending, None, None)
+    async fn get_deposits_updated_since(
+        &self,
+        min_height: u64,
+    ) -> Result<Vec<PendingDeposit>, Error> {
+        let mut deposits = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let resp = deposit_api::get_deposits(
+                &self.config,
+                Status::Pending,
+                next_token.as_deref(),
+                None,
+                Some(min_height),
+            )
             .await
             .map_err(EmilyClientError::GetDeposits)
             .map_err(Error::EmilyApi)?;
 
-        resp.deposits
-            .iter()
-            .map(|deposit| {
-                Ok(CreateDepositRequest {
-                    outpoint: OutPoint {
-                        txid: Txid::from_str(&deposit.bitcoin_txid)
-                            .map_err(Error::DecodeHexTxid)?,
-                        vout: deposit.bitcoin_tx_output_index,
-                    },
-                    reclaim_script: ScriptBuf::from_hex(&deposit.reclaim_script)
-                        .map_err(Error::DecodeHexScript)?,
-                    deposit_script: ScriptBuf::from_hex(&deposit.deposit_script)
-                        .map_err(Error::DecodeHexScript)?,
-                })
-            })
-            .collect()
+            for deposit in resp.deposits.iter() {
+                deposits.push(PendingDeposit {
+                    request: deposit_request(deposit)?,
+                    last_update_height: deposit.last_update_height,
+                });
+            }
+
+            // Emily hands out a next token for as long as there may be
+            // more deposits to fetch.
+            next_token = resp.next_token.flatten();
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(deposits)
     }
 
     async fn update_deposits(
@@ -362,10 +399,11 @@ impl EmilyInteract for ApiFallbackClient<EmilyClient> {
             .await
     }
 
-    fn get_deposits(
+    fn get_deposits_updated_since(
         &self,
-    ) -> impl std::future::Future<Output = Result<Vec<CreateDepositRequest>, Error>> {
-        self.exec(|client, _| client.get_deposits())
+        min_height: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<PendingDeposit>, Error>> {
+        self.exec(move |client, _| client.get_deposits_updated_since(min_height))
     }
 
     async fn update_deposits(
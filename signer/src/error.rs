@@ -5,9 +5,12 @@ use blockstack_lib::types::chainstate::StacksBlockId;
 
 use crate::codec;
 use crate::emily_client::EmilyClientError;
+use crate::stacks::contracts::CompleteDepositsValidationError;
+use crate::stacks::contracts::CompleteWithdrawalsValidationError;
 use crate::stacks::contracts::DepositValidationError;
 use crate::stacks::contracts::RotateKeysValidationError;
 use crate::stacks::contracts::WithdrawalAcceptValidationError;
+use crate::stacks::contracts::WithdrawalRejectValidationError;
 use crate::storage::model::SigHash;
 
 /// Top-level signer error
@@ -58,6 +61,11 @@ pub enum Error {
     #[error("bitcoin-core getblock RPC error for hash {1}: {0}")]
     BitcoinCoreGetBlock(#[source] bitcoincore_rpc::Error, bitcoin::BlockHash),
 
+    /// Attempt to fetch a bitcoin block header ended in an unexpected
+    /// error. This is not triggered if the block is missing.
+    #[error("bitcoin-core getblockheader RPC error for hash {1}: {0}")]
+    BitcoinCoreGetBlockHeader(#[source] bitcoincore_rpc::Error, bitcoin::BlockHash),
+
     /// Received an error in response to getrawtransaction RPC call
     #[error("failed to retrieve the raw transaction for txid {1} from bitcoin-core. {0}")]
     BitcoinCoreGetTransaction(#[source] bitcoincore_rpc::Error, bitcoin::Txid),
@@ -72,11 +80,34 @@ pub enum Error {
     #[error("transaction is missing, txid: {0}, block hash {1:?}")]
     BitcoinTxMissing(bitcoin::Txid, Option<bitcoin::BlockHash>),
 
+    /// The OP_RETURN output of a sweep transaction does not follow the
+    /// layout that the signers use for their sBTC data.
+    #[error("the OP_RETURN output of sweep transaction {0} is malformed")]
+    SweepOpReturnMalformed(bitcoin::Txid),
+
+    /// The OP_RETURN output of a sweep transaction starts with magic
+    /// bytes that this signer has never used.
+    #[error(
+        "unknown magic bytes {magic_bytes:?} in the OP_RETURN output of sweep transaction {txid}"
+    )]
+    SweepOpReturnMagicBytes {
+        /// The ID of the sweep transaction.
+        txid: bitcoin::Txid,
+        /// The magic bytes found in the OP_RETURN output.
+        magic_bytes: [u8; 2],
+    },
+
     /// This is the error that is returned when validating a bitcoin
     /// trasnaction.
     #[error("bitcoin validation error: {0}")]
     BitcoinValidation(#[from] Box<crate::bitcoin::validation::BitcoinValidationError>),
 
+    /// This is the error that is returned when a bitcoin transaction sign
+    /// request does not match the transaction package that this signer
+    /// validated and acked.
+    #[error("invalid bitcoin transaction sign request: {0}")]
+    BitcoinSignRequest(#[from] Box<crate::bitcoin::validation::BitcoinSignRequestError>),
+
     /// This can only be thrown when the number of bytes for a sighash or
     /// not exactly equal to 32. This should never occur.
     #[error("could not convert message in nonce request to sighash {0}")]
@@ -198,6 +229,15 @@ pub enum Error {
     #[error("deposit validation error: {0}")]
     DepositValidation(#[from] Box<DepositValidationError>),
 
+    /// Failed to validate the complete-deposits contract call transaction.
+    #[error("complete deposits validation error: {0}")]
+    CompleteDepositsValidation(#[source] Box<CompleteDepositsValidationError>),
+
+    /// Failed to validate the complete-withdrawals contract call
+    /// transaction.
+    #[error("complete withdrawals validation error: {0}")]
+    CompleteWithdrawalsValidation(#[source] Box<CompleteWithdrawalsValidationError>),
+
     /// An error when serializing an object to JSON
     #[error("JSON serialization error: {0}")]
     JsonSerialize(#[source] serde_json::Error),
@@ -278,6 +318,18 @@ pub enum Error {
     #[error("outpoint missing from transaction when assessing fee {0}")]
     OutPointMissing(bitcoin::OutPoint),
 
+    /// This should never happen. It is thrown when we attempt to create a
+    /// transaction completing deposit requests without any deposit
+    /// requests.
+    #[error("no deposit requests to complete")]
+    NoDepositRequests,
+
+    /// This should never happen. It is thrown when we attempt to create a
+    /// transaction completing withdrawal requests without any withdrawal
+    /// requests.
+    #[error("no withdrawal requests to complete")]
+    NoWithdrawalRequests,
+
     /// This is thrown when failing to parse a hex string into an integer.
     #[error("could not parse the hex string into an integer")]
     ParseHexInt(#[source] std::num::ParseIntError),
@@ -377,6 +429,12 @@ pub enum Error {
     #[error("the database is missing bitcoin block {0}")]
     MissingBitcoinBlock(crate::storage::model::BitcoinBlockHash),
 
+    /// We could not get the median-time-past of a bitcoin block from
+    /// bitcoin-core, either because the block header is missing or
+    /// because it did not include the median time.
+    #[error("could not determine the median-time-past of bitcoin block {0}")]
+    MissingMedianTimePast(bitcoin::BlockHash),
+
     /// Missing block
     #[error("missing block")]
     MissingBlock,
@@ -385,6 +443,10 @@ pub enum Error {
     #[error("missing dkg shares for the given aggregate key: {0}")]
     MissingDkgShares(crate::keys::PublicKey),
 
+    /// Missing dkg shares for the aggregate key locking a UTXO
+    #[error("missing dkg shares for the aggregate key locking the UTXO: {0}")]
+    MissingDkgSharesForUtxo(bitcoin::XOnlyPublicKey),
+
     /// Missing public key
     #[error("missing public key")]
     MissingPublicKey,
@@ -431,6 +493,14 @@ pub enum Error {
     #[error("encryption error")]
     Encryption,
 
+    /// An error from the backend that holds the signer's private key
+    #[error("signer key backend error: {0}")]
+    KeyBackend(#[from] crate::key_backend::KeyBackendError),
+
+    /// An error when exporting or importing DKG shares
+    #[error("DKG shares backup error: {0}")]
+    DkgBackup(#[from] crate::dkg_backup::DkgBackupError),
+
     /// Invalid configuration
     #[error("invalid configuration")]
     InvalidConfiguration,
@@ -463,6 +533,11 @@ pub enum Error {
     #[error("withdrawal accept validation error: {0}")]
     WithdrawalAcceptValidation(#[source] Box<WithdrawalAcceptValidationError>),
 
+    /// The error for when the request to sign a withdrawal-reject
+    /// transaction fails at the validation step.
+    #[error("withdrawal reject validation error: {0}")]
+    WithdrawalRejectValidation(#[source] Box<WithdrawalRejectValidationError>),
+
     /// WSTS error.
     #[error("WSTS error: {0}")]
     Wsts(#[source] wsts::state_machine::signer::Error),
@@ -506,6 +581,16 @@ pub enum Error {
     #[error("ZMQ connect error: {0}")]
     ZmqConnect(#[source] zeromq::ZmqError),
 
+    /// Timed out connecting to bitcoin-core with a zeromq subscription
+    /// socket.
+    #[error("timed out connecting to ZMQ endpoint {0}")]
+    ZmqConnectTimeout(url::Url),
+
+    /// No bitcoin-core zeromq endpoints were given for the block hash
+    /// stream.
+    #[error("no bitcoin-core ZMQ block hash stream endpoints were provided")]
+    NoBlockHashStreamEndpoints,
+
     /// Error when receiving a message from to bitcoin-core over zeromq.
     #[error("ZMQ receive error: {0}")]
     ZmqReceive(#[source] zeromq::ZmqError),
@@ -0,0 +1,433 @@
+//! Backends that hold the private key of the signer
+//!
+//! The signer uses its private key for ECDSA signatures over the messages
+//! that it sends to other signers and over Stacks transactions, as its
+//! libp2p identity, as the network key in the WSTS protocol and to
+//! encrypt its DKG shares. A [`SignerKeyBackend`] provides the ECDSA
+//! signatures, and never hands out the private key.
+//!
+//! There are three backends:
+//! * The private key in the signer's configuration, which is the
+//!   [`PrivateKey`] itself.
+//! * A key file, see [`read_private_key_file`], which keeps the key out of
+//!   the configuration file and the environment.
+//! * An external signer process that listens on a Unix socket, see
+//!   [`UnixSocketKeyBackend`], which never reveals the private key.
+//!
+//! WSTS and libp2p need the private key itself, so they do not go through
+//! a backend. They get the key from
+//! [`SignerConfig::network_private_key`](crate::config::SignerConfig::network_private_key),
+//! which is only available with the first two backends.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr as _;
+
+use secp256k1::ecdsa::RecoverableSignature;
+use secp256k1::ecdsa::Signature;
+
+use crate::error::Error;
+use crate::keys::PrivateKey;
+use crate::keys::PublicKey;
+
+/// Errors that can occur when using a signer key backend.
+#[derive(Debug, thiserror::Error)]
+pub enum KeyBackendError {
+    /// The signer configuration does not have a private key.
+    #[error("the signer configuration does not have a private key")]
+    MissingPrivateKey,
+
+    /// The configured backend does not reveal the private key, which WSTS
+    /// and libp2p need.
+    #[error("the signer key backend does not reveal the private key, which WSTS and libp2p need")]
+    PrivateKeyUnavailable,
+
+    /// The key file could not be read.
+    #[error("could not read the signer key file {0}: {1}")]
+    ReadKeyFile(PathBuf, #[source] std::io::Error),
+
+    /// The key file can be accessed by users other than its owner.
+    #[error(
+        "the signer key file {0} must not be accessible by other users, but it has mode {1:o}"
+    )]
+    KeyFilePermissions(PathBuf, u32),
+
+    /// The key file does not contain a hex encoded private key.
+    #[error("the signer key file {0} does not contain a valid private key")]
+    InvalidKeyFile(PathBuf),
+
+    /// Communication with the external signer process failed.
+    #[error("could not communicate with the external signer at {0}: {1}")]
+    ExternalSignerIo(PathBuf, #[source] std::io::Error),
+
+    /// The external signer sent a response that could not be parsed, or
+    /// that did not answer the request.
+    #[error("invalid response from the external signer: {0}")]
+    InvalidExternalSignerResponse(String),
+
+    /// The external signer returned an error.
+    #[error("the external signer returned an error: {0}")]
+    ExternalSigner(String),
+
+    /// The external signer holds the key for a different public key than
+    /// the one in the configuration.
+    #[error("the external signer has public key {actual}, expected {expected}")]
+    ExternalSignerPublicKeyMismatch {
+        /// The public key in the configuration.
+        expected: PublicKey,
+        /// The public key reported by the external signer.
+        actual: PublicKey,
+    },
+
+    /// The external signer returned a signature that does not verify
+    /// against its public key.
+    #[error("the external signer returned an invalid signature")]
+    InvalidExternalSignature,
+}
+
+/// A holder of the signer's private key that can create ECDSA signatures
+/// with it.
+pub trait SignerKeyBackend: std::fmt::Debug + Send + Sync {
+    /// The public key of the signer.
+    fn public_key(&self) -> PublicKey;
+
+    /// Construct an ECDSA signature over the message, in "low S" form.
+    fn sign_ecdsa(&self, msg: &secp256k1::Message) -> Result<Signature, Error>;
+
+    /// Construct a recoverable ECDSA signature over the message.
+    fn sign_ecdsa_recoverable(
+        &self,
+        msg: &secp256k1::Message,
+    ) -> Result<RecoverableSignature, Error>;
+}
+
+impl SignerKeyBackend for PrivateKey {
+    fn public_key(&self) -> PublicKey {
+        PublicKey::from_private_key(self)
+    }
+
+    fn sign_ecdsa(&self, msg: &secp256k1::Message) -> Result<Signature, Error> {
+        Ok(PrivateKey::sign_ecdsa(self, msg))
+    }
+
+    fn sign_ecdsa_recoverable(
+        &self,
+        msg: &secp256k1::Message,
+    ) -> Result<RecoverableSignature, Error> {
+        Ok(PrivateKey::sign_ecdsa_recoverable(self, msg))
+    }
+}
+
+/// Read the private key from the file at the given path.
+///
+/// The file must contain the hex encoded private key, optionally followed
+/// by the `01` compression byte marker, just like the `private_key` field
+/// of the signer configuration. On Unix systems the file must not be
+/// accessible by any user other than its owner.
+pub fn read_private_key_file(path: &Path) -> Result<PrivateKey, Error> {
+    let read_error = |err| KeyBackendError::ReadKeyFile(path.to_path_buf(), err);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+
+        let mode = std::fs::metadata(path)
+            .map_err(read_error)?
+            .permissions()
+            .mode();
+        if mode & 0o077 != 0 {
+            let path = path.to_path_buf();
+            return Err(KeyBackendError::KeyFilePermissions(path, mode & 0o777).into());
+        }
+    }
+
+    let contents = std::fs::read_to_string(path).map_err(read_error)?;
+    let contents = contents.trim();
+    let hex = match contents.len() {
+        66 if contents.ends_with("01") => &contents[..64],
+        _ => contents,
+    };
+
+    PrivateKey::from_str(hex)
+        .map_err(|_| KeyBackendError::InvalidKeyFile(path.to_path_buf()).into())
+}
+
+#[cfg(unix)]
+pub use unix_socket::UnixSocketKeyBackend;
+
+#[cfg(unix)]
+mod unix_socket {
+    use std::io::BufRead as _;
+    use std::io::BufReader;
+    use std::io::Write as _;
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    use serde::Deserialize;
+    use serde::Serialize;
+
+    use super::*;
+
+    /// A request to the external signer process.
+    #[derive(Debug, Serialize)]
+    #[serde(tag = "method", rename_all = "snake_case")]
+    enum Request {
+        PublicKey,
+        SignEcdsa { digest: String },
+        SignEcdsaRecoverable { digest: String },
+    }
+
+    /// A response from the external signer process. Exactly one of the
+    /// `public_key`, `signature` and `error` fields is set.
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default)]
+    struct Response {
+        public_key: Option<PublicKey>,
+        signature: Option<String>,
+        recovery_id: Option<i32>,
+        error: Option<String>,
+    }
+
+    /// An external signer process that creates ECDSA signatures with the
+    /// signer's private key, without revealing it.
+    ///
+    /// The backend connects to the Unix socket once for every request,
+    /// writes the request as a single line of JSON and reads a single line
+    /// of JSON in response. The requests are
+    /// * `{"method": "public_key"}`, answered with
+    ///   `{"public_key": "<hex encoded compressed public key>"}`.
+    /// * `{"method": "sign_ecdsa", "digest": "<hex encoded 32 bytes>"}`,
+    ///   answered with `{"signature": "<hex encoded 64 byte compact
+    ///   signature>"}`.
+    /// * `{"method": "sign_ecdsa_recoverable", "digest": "<hex encoded 32
+    ///   bytes>"}`, answered with `{"signature": "<hex encoded 64 byte
+    ///   compact signature>", "recovery_id": <0 to 3>}`.
+    ///
+    /// Failed requests are answered with `{"error": "<message>"}`. All
+    /// signatures are verified against the public key before they are
+    /// used.
+    #[derive(Debug, Clone)]
+    pub struct UnixSocketKeyBackend {
+        path: PathBuf,
+        public_key: PublicKey,
+        timeout: Duration,
+    }
+
+    impl UnixSocketKeyBackend {
+        /// Connect to the external signer listening on the socket at the
+        /// given path, and check that it holds the private key for the
+        /// given public key.
+        pub fn connect(
+            path: PathBuf,
+            public_key: PublicKey,
+            timeout: Duration,
+        ) -> Result<Self, Error> {
+            let backend = Self { path, public_key, timeout };
+
+            let actual = backend
+                .request(&Request::PublicKey)?
+                .public_key
+                .ok_or_else(|| invalid_response("missing public key"))?;
+
+            if actual != public_key {
+                let expected = public_key;
+                return Err(
+                    KeyBackendError::ExternalSignerPublicKeyMismatch { expected, actual }.into(),
+                );
+            }
+
+            Ok(backend)
+        }
+
+        /// Send a request to the external signer and read its response.
+        fn request(&self, request: &Request) -> Result<Response, Error> {
+            let io_error = |err| KeyBackendError::ExternalSignerIo(self.path.clone(), err);
+
+            let mut stream = UnixStream::connect(&self.path).map_err(io_error)?;
+            stream
+                .set_read_timeout(Some(self.timeout))
+                .map_err(io_error)?;
+            stream
+                .set_write_timeout(Some(self.timeout))
+                .map_err(io_error)?;
+
+            let mut line = serde_json::to_vec(request).map_err(Error::JsonSerialize)?;
+            line.push(b'\n');
+            stream.write_all(&line).map_err(io_error)?;
+
+            let mut line = String::new();
+            BufReader::new(stream)
+                .read_line(&mut line)
+                .map_err(io_error)?;
+
+            let response: Response =
+                serde_json::from_str(&line).map_err(|err| invalid_response(err.to_string()))?;
+
+            match response.error {
+                Some(error) => Err(KeyBackendError::ExternalSigner(error).into()),
+                None => Ok(response),
+            }
+        }
+
+        /// Ask the external signer to sign the message and return the
+        /// compact signature and the recovery ID, if there is one.
+        fn sign(&self, request: Request) -> Result<([u8; 64], Option<i32>), Error> {
+            let response = self.request(&request)?;
+            let signature = response
+                .signature
+                .ok_or_else(|| invalid_response("missing signature"))?;
+
+            let mut compact = [0; 64];
+            hex::decode_to_slice(&signature, &mut compact)
+                .map_err(|err| invalid_response(err.to_string()))?;
+
+            Ok((compact, response.recovery_id))
+        }
+    }
+
+    fn invalid_response(msg: impl Into<String>) -> Error {
+        KeyBackendError::InvalidExternalSignerResponse(msg.into()).into()
+    }
+
+    impl SignerKeyBackend for UnixSocketKeyBackend {
+        fn public_key(&self) -> PublicKey {
+            self.public_key
+        }
+
+        fn sign_ecdsa(&self, msg: &secp256k1::Message) -> Result<Signature, Error> {
+            let digest = hex::encode(msg.as_ref());
+            let (compact, _) = self.sign(Request::SignEcdsa { digest })?;
+
+            let mut signature = Signature::from_compact(&compact)
+                .map_err(|err| invalid_response(err.to_string()))?;
+            signature.normalize_s();
+
+            signature
+                .verify(msg, &self.public_key)
+                .map_err(|_| KeyBackendError::InvalidExternalSignature)?;
+
+            Ok(signature)
+        }
+
+        fn sign_ecdsa_recoverable(
+            &self,
+            msg: &secp256k1::Message,
+        ) -> Result<RecoverableSignature, Error> {
+            let digest = hex::encode(msg.as_ref());
+            let (compact, recovery_id) = self.sign(Request::SignEcdsaRecoverable { digest })?;
+
+            let recovery_id = recovery_id.ok_or_else(|| invalid_response("missing recovery ID"))?;
+            let recovery_id = secp256k1::ecdsa::RecoveryId::from_i32(recovery_id)
+                .map_err(|err| invalid_response(err.to_string()))?;
+            let signature = RecoverableSignature::from_compact(&compact, recovery_id)
+                .map_err(|err| invalid_response(err.to_string()))?;
+
+            match signature.recover(msg) {
+                Ok(key) if PublicKey::from(key) == self.public_key => Ok(signature),
+                _ => Err(KeyBackendError::InvalidExternalSignature.into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    /// Return a path in the temporary directory that does not exist yet.
+    fn temp_path(name: &str) -> PathBuf {
+        let suffix: u64 = rand::random();
+        std::env::temp_dir().join(format!("{name}-{suffix:x}"))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn key_files_must_only_be_readable_by_their_owner() {
+        use std::os::unix::fs::PermissionsExt as _;
+
+        let private_key = PrivateKey::new(&mut OsRng);
+        let path = temp_path("signer-key");
+        std::fs::write(
+            &path,
+            format!("{}01\n", hex::encode(private_key.to_bytes())),
+        )
+        .unwrap();
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        match read_private_key_file(&path) {
+            Err(Error::KeyBackend(KeyBackendError::KeyFilePermissions(_, 0o644))) => {}
+            result => panic!("unexpected result: {result:?}"),
+        }
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(read_private_key_file(&path).unwrap(), private_key);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_backend_signs_with_the_external_signer() {
+        use std::io::BufRead as _;
+        use std::io::Write as _;
+        use std::os::unix::net::UnixListener;
+        use std::time::Duration;
+
+        let private_key = PrivateKey::new(&mut OsRng);
+        let public_key = PublicKey::from_private_key(&private_key);
+        let path = temp_path("signer-key.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        // A minimal external signer that answers the public key request
+        // made when connecting, and the two signing requests below.
+        let server = std::thread::spawn(move || {
+            for stream in listener.incoming().take(3) {
+                let mut stream = stream.unwrap();
+                let mut line = String::new();
+                std::io::BufReader::new(&stream)
+                    .read_line(&mut line)
+                    .unwrap();
+                let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+
+                let digest = |request: &serde_json::Value| {
+                    let digest = hex::decode(request["digest"].as_str().unwrap()).unwrap();
+                    secp256k1::Message::from_digest_slice(&digest).unwrap()
+                };
+                let response = match request["method"].as_str().unwrap() {
+                    "public_key" => serde_json::json!({ "public_key": public_key }),
+                    "sign_ecdsa" => {
+                        let sig = private_key.sign_ecdsa(&digest(&request));
+                        serde_json::json!({ "signature": hex::encode(sig.serialize_compact()) })
+                    }
+                    _ => {
+                        let sig = private_key.sign_ecdsa_recoverable(&digest(&request));
+                        let (recovery_id, sig) = sig.serialize_compact();
+                        serde_json::json!({
+                            "signature": hex::encode(sig),
+                            "recovery_id": recovery_id.to_i32(),
+                        })
+                    }
+                };
+                writeln!(stream, "{response}").unwrap();
+            }
+        });
+
+        let timeout = Duration::from_secs(5);
+        let backend = UnixSocketKeyBackend::connect(path.clone(), public_key, timeout).unwrap();
+        let msg = secp256k1::Message::from_digest([7; 32]);
+
+        let signature = SignerKeyBackend::sign_ecdsa(&backend, &msg).unwrap();
+        signature.verify(&msg, &public_key).unwrap();
+
+        let signature = SignerKeyBackend::sign_ecdsa_recoverable(&backend, &msg).unwrap();
+        assert_eq!(
+            PublicKey::from(signature.recover(&msg).unwrap()),
+            public_key
+        );
+
+        server.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
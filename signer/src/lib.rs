@@ -13,13 +13,17 @@ pub mod blocklist_client;
 pub mod codec;
 pub mod config;
 pub mod context;
+pub mod dkg_backup;
 pub mod ecdsa;
 pub mod emily_client;
 pub mod error;
+pub mod key_backend;
 pub mod keys;
 pub mod logging;
 pub mod message;
+pub mod metrics;
 pub mod network;
+pub mod participation;
 pub mod proto;
 pub mod request_decider;
 pub mod signature;
@@ -50,12 +54,12 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 const MAX_KEYS: u16 = 128;
 
 /// Each deposit has a reclaim script spend path that can be executed after
-/// some "time". Right now this "time", the locktime, can only be
-/// denominated in bitcoin blocks. Once locktime number of blocks have been
-/// added to the blockchain after the deposit has been confirmed, the
-/// depositer can reclaim the deposit transaction. Signers will not attempt
-/// to sweep in the deposited funds if the number of blocks left is less
-/// than or equal to this value.
+/// some "time". This "time", the locktime, can be denominated in bitcoin
+/// blocks or in units of 512 seconds. For block based locktimes, once
+/// locktime number of blocks have been added to the blockchain after the
+/// deposit has been confirmed, the depositer can reclaim the deposit
+/// transaction. Signers will not attempt to sweep in the deposited funds
+/// if the number of blocks left is less than or equal to this value.
 ///
 /// If the current chain tip is at height 1000, the reclaim script on a
 /// deposit can be spent on or after block 1001, and this constant value is
@@ -64,6 +68,44 @@ const MAX_KEYS: u16 = 128;
 /// the deposit.
 pub const DEPOSIT_LOCKTIME_BLOCK_BUFFER: u16 = 3;
 
+/// This is the time based counterpart of [`DEPOSIT_LOCKTIME_BLOCK_BUFFER`],
+/// in seconds.
+///
+/// For deposits with a time based locktime, the depositor can reclaim the
+/// deposit once the median-time-past of the bitcoin chain tip has moved
+/// locktime * 512 seconds past the median-time-past of the parent of the
+/// block that confirmed the deposit. Signers will not attempt to sweep in the
+/// deposited funds if the number of seconds left is less than or equal to
+/// this value. It corresponds to roughly [`DEPOSIT_LOCKTIME_BLOCK_BUFFER`]
+/// blocks worth of time.
+pub const DEPOSIT_LOCKTIME_TIME_BUFFER: u64 = DEPOSIT_LOCKTIME_BLOCK_BUFFER as u64 * 600;
+
+/// The number of bitcoin blocks after a withdrawal request has been
+/// anchored to the bitcoin blockchain before it is considered expired.
+///
+/// Signers will not attempt to reject a withdrawal request that has been
+/// accepted by enough signers until it has expired. Once expired, the
+/// signers will reject it using a `reject-withdrawal-request` contract
+/// call, returning the locked sBTC to the user.
+pub const WITHDRAWAL_BLOCKS_EXPIRY: u64 = 144;
+
+/// The number of confirmations that the transaction handing the signers'
+/// UTXO over to a new aggregate key needs before the signers retire the
+/// old aggregate key.
+///
+/// A reorg can undo a handover with fewer confirmations, and then the
+/// signers of the old DKG round need to sign for the UTXO again.
+pub const ROTATION_SWEEP_MIN_CONFIRMATIONS: u64 = 6;
+
+/// The maximum fee, in sats, that the signers pay for a transaction that
+/// hands their UTXO over to a new aggregate key without servicing any
+/// requests.
+///
+/// The fees of deposits and withdrawals are capped by the max fee in each
+/// request, but nobody else pays for such a transaction, so the fees come
+/// out of the signers' UTXO.
+pub const ROTATION_SWEEP_MAX_FEE: u64 = 100_000;
+
 /// This is the capacity of the channel used for messages sent within the
 /// signer.
 pub const SIGNER_CHANNEL_CAPACITY: usize = 1024;
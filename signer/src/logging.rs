@@ -3,23 +3,47 @@
 
 use tracing_subscriber::fmt::time::UtcTime;
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Registry;
+
+/// A handle for replacing the log filter of the running application.
+pub type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
 
 /// Sets up logging based on the provided format preference
 ///
 /// # Arguments
 ///
 /// * `pretty` - A boolean that determines if the logging format should be pretty or JSON
-
-pub fn setup_logging(directives: &str, pretty: bool) {
+///
+/// Returns a handle that can be used to change the log filter later on.
+pub fn setup_logging(directives: &str, pretty: bool) -> LogFilterHandle {
     match pretty {
         true => setup_logging_pretty(directives),
         false => setup_logging_json(directives),
     }
 }
 
-fn setup_logging_json(directives: &str) {
+/// Replace the log filter of the running application with the given
+/// directives, or with the directives in the `RUST_LOG` environment
+/// variable if there are none.
+pub fn reload_log_filter(
+    handle: &LogFilterHandle,
+    directives: Option<&str>,
+) -> Result<(), reload::Error> {
+    let filter = match directives {
+        Some(directives) => EnvFilter::new(directives),
+        None => env_filter(""),
+    };
+    handle.reload(filter)
+}
+
+fn env_filter(directives: &str) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(directives))
+}
+
+fn setup_logging_json(directives: &str) -> LogFilterHandle {
     let main_layer = tracing_subscriber::fmt::layer()
         .json()
         .flatten_event(true)
@@ -30,17 +54,25 @@ fn setup_logging_json(directives: &str) {
         .with_file(true)
         .with_timer(UtcTime::rfc_3339());
 
+    let (filter, handle) = reload::Layer::new(env_filter(directives));
+
     tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(directives)))
+        .with(filter)
         .with(main_layer)
-        .init()
+        .init();
+
+    handle
 }
 
-fn setup_logging_pretty(directives: &str) {
+fn setup_logging_pretty(directives: &str) -> LogFilterHandle {
     let main_layer = tracing_subscriber::fmt::layer().with_timer(UtcTime::rfc_3339());
 
+    let (filter, handle) = reload::Layer::new(env_filter(directives));
+
     tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(directives)))
+        .with(filter)
         .with(main_layer)
-        .init()
+        .init();
+
+    handle
 }
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
+use std::io::Write as _;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
+use axum::extract::DefaultBodyLimit;
 use axum::http::Request;
 use axum::http::Response;
 use axum::routing::get;
@@ -12,31 +14,58 @@ use axum::routing::post;
 use axum::Router;
 use cfg_if::cfg_if;
 use clap::Parser;
+use clap::Subcommand;
 use clap::ValueEnum;
+use futures::StreamExt as _;
 use signer::api;
 use signer::api::ApiState;
+use signer::bitcoin::poller::PollingBlockHashStream;
 use signer::bitcoin::rpc::BitcoinCoreClient;
-use signer::bitcoin::zmq::BitcoinCoreMessageStream;
+use signer::bitcoin::zmq::FailoverBlockHashStream;
 use signer::block_observer;
 use signer::blocklist_client::BlocklistClient;
+use signer::config::BlockHashSource;
+use signer::config::NetworkKind;
 use signer::config::Settings;
 use signer::context::Context;
 use signer::context::SignerContext;
+use signer::dkg_backup;
+use signer::dkg_backup::DkgSharesBackup;
 use signer::emily_client::EmilyClient;
 use signer::error::Error;
+use signer::key_backend::SignerKeyBackend;
+use signer::keys::PrivateKey;
+use signer::keys::PublicKey;
+use signer::logging::LogFilterHandle;
 use signer::network::libp2p::SignerSwarmBuilder;
 use signer::network::P2PNetwork;
 use signer::request_decider::RequestDeciderEventLoop;
 use signer::stacks::api::StacksClient;
 use signer::storage::postgres::PgStore;
+use signer::storage::DbRead as _;
 use signer::transaction_coordinator;
 use signer::transaction_signer;
 use signer::util::ApiFallbackClient;
+use stacks_common::types::chainstate::StacksAddress;
+use stacks_common::util::secp256k1::Secp256k1PublicKey;
 use tokio::signal;
+use tower::limit::ConcurrencyLimitLayer;
 use tower_http::trace::TraceLayer;
 use tracing::Instrument;
 use tracing::Span;
 
+/// The signer context used by the signer binary.
+type SignerCtx = SignerContext<
+    PgStore,
+    ApiFallbackClient<BitcoinCoreClient>,
+    ApiFallbackClient<StacksClient>,
+    ApiFallbackClient<EmilyClient>,
+>;
+
+/// The environment variable holding the passphrase of DKG shares
+/// backups.
+const DKG_BACKUP_PASSPHRASE_ENV: &str = "SIGNER_DKG_BACKUP_PASSPHRASE";
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum LogOutputFormat {
     Json,
@@ -59,6 +88,110 @@ struct SignerArgs {
 
     #[clap(short = 'o', long = "output-format", default_value = "pretty")]
     output_format: Option<LogOutputFormat>,
+
+    /// An operator command to run instead of the signer.
+    #[clap(subcommand)]
+    command: Option<OperatorCommand>,
+}
+
+/// Operator commands that run against the signer's configuration and
+/// database, and exit.
+#[derive(Debug, Subcommand)]
+enum OperatorCommand {
+    /// Check the signer's configuration.
+    #[clap(subcommand)]
+    Config(ConfigCommand),
+    /// Generate or show the signer's keys.
+    #[clap(subcommand)]
+    Keys(KeysCommand),
+    /// Manage the signer's database.
+    #[clap(subcommand)]
+    Db(DbCommand),
+    /// Show the state of distributed key generation (DKG).
+    #[clap(subcommand)]
+    Dkg(DkgCommand),
+    /// Back up or restore the signer's DKG shares.
+    #[clap(subcommand)]
+    DkgShares(DkgSharesCommand),
+    /// Show the deposit and withdrawal requests known to the signer.
+    #[clap(subcommand)]
+    Requests(RequestsCommand),
+    /// Print how often each signer responded to the rounds run by this
+    /// signer when it was the coordinator.
+    Participation {
+        /// How many of the latest participation records of each signer to
+        /// look at. Defaults to the configured participation window.
+        #[clap(long)]
+        window: Option<u32>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigCommand {
+    /// Load and validate the configuration, and print the effective
+    /// settings with the secrets redacted.
+    Check,
+}
+
+#[derive(Debug, Subcommand)]
+enum KeysCommand {
+    /// Generate a new private key, and print it along with the public
+    /// key, peer ID and Stacks address derived from it.
+    Generate {
+        /// The network of the Stacks address.
+        #[clap(long, default_value = "mainnet", value_parser = parse_network_kind)]
+        network: NetworkKind,
+    },
+    /// Print the public key, peer ID and Stacks address of the signer.
+    Show,
+}
+
+#[derive(Debug, Subcommand)]
+enum DbCommand {
+    /// Apply any pending migrations to the database.
+    Migrate,
+    /// Print the migrations and whether they have been applied.
+    Status,
+}
+
+#[derive(Debug, Subcommand)]
+enum DkgCommand {
+    /// Print the latest DKG shares, with their aggregate key and signer
+    /// set, and the latest DKG round.
+    Status,
+}
+
+#[derive(Debug, Subcommand)]
+enum RequestsCommand {
+    /// Print the pending deposit and withdrawal requests, along with the
+    /// votes of the signers.
+    List {
+        /// How many bitcoin blocks back from the chain tip to look for
+        /// requests.
+        #[clap(long, default_value_t = 10000)]
+        context_window: u16,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum DkgSharesCommand {
+    /// Export all DKG shares in the database to a passphrase encrypted
+    /// backup file. The passphrase is read from the
+    /// `SIGNER_DKG_BACKUP_PASSPHRASE` environment variable, or from stdin
+    /// if it is not set.
+    Export {
+        /// The path of the backup file to create. It must not exist.
+        #[clap(long)]
+        output: PathBuf,
+    },
+    /// Import the DKG shares in a backup file into the database. The
+    /// shares must have been exported by a signer with the same private
+    /// key. The passphrase is read the same way as for `export`.
+    Import {
+        /// The path of the backup file.
+        #[clap(long)]
+        input: PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -69,10 +202,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Configure the binary's stdout/err output based on the provided output format.
     let pretty = matches!(args.output_format, Some(LogOutputFormat::Pretty));
-    signer::logging::setup_logging("", pretty);
+    let log_filter = signer::logging::setup_logging("", pretty);
+
+    // Run the operator command, if there is one, instead of the signer.
+    if let Some(command) = args.command {
+        return run_command(command, args.config.as_ref(), args.migrate_db).await;
+    }
 
     // Load the configuration file and/or environment variables.
-    let settings = Settings::new(args.config)?;
+    let settings = Settings::new(args.config.as_ref())?;
+
+    // The log level in the config takes precedence over `RUST_LOG`.
+    if let Some(directives) = settings.signer.log_level.as_deref() {
+        signer::logging::reload_log_filter(&log_filter, Some(directives))?;
+    }
 
     // Open a connection to the signer db.
     let db = PgStore::connect(settings.signer.db_endpoint.as_str()).await?;
@@ -82,20 +225,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         db.apply_migrations().await?;
     }
 
+    // The WSTS protocol and libp2p need the private key itself, so the
+    // signer cannot run with a key backend that does not reveal it.
+    // Everything else signs through the key backend, which is built once
+    // and shared by every component that signs with the signer's key.
+    let network_key = settings.signer.network_private_key()?;
+    let signer_key = settings.signer.key_backend()?;
+
     // Initialize the signer context.
-    let context = SignerContext::<
-        _,
-        ApiFallbackClient<BitcoinCoreClient>,
-        ApiFallbackClient<StacksClient>,
-        ApiFallbackClient<EmilyClient>,
-    >::init(settings, db)?;
-
-    // TODO: We should first check "another source of truth" for the current
-    // signing set, and only assume we are bootstrapping if that source is
-    // empty.
-    let settings = context.config();
-    for signer in settings.signer.bootstrap_signing_set() {
-        context.state().current_signer_set().add_signer(signer);
+    let context = SignerCtx::init(settings, db)?;
+
+    // Seed the signer set from the `sbtc-registry` contract, falling back
+    // on the last key rotation and then the bootstrap signing set. The
+    // block observer keeps it up to date from here on out.
+    if let Err(error) = block_observer::update_signer_set(&context).await {
+        tracing::warn!(%error, "could not load the signer set, using the bootstrap signer set");
+        let settings = context.config();
+        for signer in settings.signer.bootstrap_signing_set() {
+            context.state().current_signer_set().add_signer(signer);
+        }
     }
 
     // Run the application components concurrently. We're `join!`ing them
@@ -110,21 +258,302 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // does occur, otherwise the `join` will continue running indefinitely.
     let _ = tokio::join!(
         // Our global termination signal watcher. This does not run using `run_checked`
-        // as it sends its own shutdown signal.
-        run_shutdown_signal_watcher(context.clone()),
+        // as it sends its own shutdown signal. It also reloads the config on SIGHUP.
+        run_shutdown_signal_watcher(context.clone(), args.config, log_filter),
         // The rest of our services which run concurrently, and must all be
         // running for the signer to be operational.
         run_checked(run_api, &context),
-        run_checked(run_libp2p_swarm, &context),
+        run_checked(|ctx| run_libp2p_swarm(ctx, network_key), &context),
         run_checked(run_block_observer, &context),
-        run_checked(run_request_decider, &context),
-        run_checked(run_transaction_coordinator, &context),
-        run_checked(run_transaction_signer, &context),
+        run_checked(|ctx| run_request_decider(ctx, signer_key.clone()), &context),
+        run_checked(
+            |ctx| run_transaction_coordinator(ctx, signer_key.clone(), network_key),
+            &context,
+        ),
+        run_checked(
+            |ctx| run_transaction_signer(ctx, signer_key.clone(), network_key),
+            &context,
+        ),
     );
 
     Ok(())
 }
 
+/// Run an operator command.
+async fn run_command(
+    command: OperatorCommand,
+    config_path: Option<&PathBuf>,
+    migrate_db: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Generating a new key is the only command that does not need the
+    // configuration.
+    if let OperatorCommand::Keys(KeysCommand::Generate { network }) = command {
+        let private_key = PrivateKey::new(&mut rand::rngs::OsRng);
+        println!("private key: {}", hex::encode(private_key.to_bytes()));
+        print_public_key(&PublicKey::from_private_key(&private_key), network);
+        return Ok(());
+    }
+
+    let settings = Settings::new(config_path)?;
+
+    match command {
+        OperatorCommand::Config(ConfigCommand::Check) => {
+            println!("{:#?}", settings.redacted());
+            println!("signer public key: {}", settings.signer.public_key()?);
+            println!("the configuration is valid");
+            return Ok(());
+        }
+        OperatorCommand::Keys(KeysCommand::Show) => {
+            print_public_key(&settings.signer.public_key()?, settings.signer.network);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // The remaining commands all work against the database.
+    let db = PgStore::connect(settings.signer.db_endpoint.as_str()).await?;
+    if migrate_db || matches!(command, OperatorCommand::Db(DbCommand::Migrate)) {
+        db.apply_migrations().await?;
+    }
+
+    match command {
+        OperatorCommand::Config(_) | OperatorCommand::Keys(_) => {}
+        OperatorCommand::Db(_) => {
+            for (migration, applied) in db.migration_status().await? {
+                let status = if applied { "applied" } else { "pending" };
+                println!("{status:<8} {migration}");
+            }
+        }
+        OperatorCommand::Dkg(DkgCommand::Status) => print_dkg_status(&db).await?,
+        OperatorCommand::Requests(RequestsCommand::List { context_window }) => {
+            print_requests(&settings, &db, context_window).await?
+        }
+        OperatorCommand::Participation { window } => {
+            let window = window.unwrap_or(settings.signer.participation.window);
+            print_participation(&db, window).await?
+        }
+        OperatorCommand::DkgShares(DkgSharesCommand::Export { output }) => {
+            let passphrase = read_backup_passphrase()?;
+            let backup =
+                dkg_backup::export_dkg_shares(&db, &passphrase, &mut rand::rngs::OsRng).await?;
+
+            // The backup is only as safe as the passphrase, so we keep
+            // it away from other users and never overwrite a file.
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+            let mut file = options.open(&output)?;
+            file.write_all(backup.to_json()?.as_bytes())?;
+            file.sync_all()?;
+
+            tracing::info!(path = %output.display(), "exported the DKG shares");
+        }
+        OperatorCommand::DkgShares(DkgSharesCommand::Import { input }) => {
+            let passphrase = read_backup_passphrase()?;
+            let backup = DkgSharesBackup::from_json(&std::fs::read(&input)?)?;
+            let private_key = settings.signer.network_private_key()?;
+            let imported =
+                dkg_backup::import_dkg_shares(&db, &backup, &passphrase, &private_key).await?;
+
+            for aggregate_key in imported.iter() {
+                tracing::info!(%aggregate_key, "imported DKG shares");
+            }
+            tracing::info!(count = imported.len(), "imported the DKG shares");
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the passphrase of a DKG shares backup.
+///
+/// The passphrase is never taken from the command line, where it would
+/// end up in the shell history and be visible to other users in the
+/// process list. It is read from the `SIGNER_DKG_BACKUP_PASSPHRASE`
+/// environment variable if it is set, and from the first line of stdin
+/// otherwise.
+fn read_backup_passphrase() -> Result<String, Box<dyn std::error::Error>> {
+    if let Ok(passphrase) = std::env::var(DKG_BACKUP_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+
+    eprint!("DKG backup passphrase: ");
+    std::io::stderr().flush()?;
+
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    let passphrase = line.trim_end_matches(['\r', '\n']);
+    if passphrase.is_empty() {
+        return Err("the DKG backup passphrase must not be empty".into());
+    }
+
+    Ok(passphrase.to_string())
+}
+
+/// Parse the network of a Stacks address given on the command line.
+fn parse_network_kind(network: &str) -> Result<NetworkKind, String> {
+    match network {
+        "mainnet" => Ok(NetworkKind::Mainnet),
+        "testnet" => Ok(NetworkKind::Testnet),
+        "regtest" => Ok(NetworkKind::Regtest),
+        _ => Err(format!(
+            "unknown network {network}, expected one of mainnet, testnet or regtest"
+        )),
+    }
+}
+
+/// Print the public key along with the libp2p peer ID and the Stacks
+/// address that are derived from it.
+fn print_public_key(public_key: &PublicKey, network: NetworkKind) {
+    let stacks_public_key = Secp256k1PublicKey::from(public_key);
+    let address = StacksAddress::p2pkh(network.is_mainnet(), &stacks_public_key);
+
+    println!("public key: {public_key}");
+    println!("peer id: {}", libp2p::PeerId::from(*public_key));
+    println!("stacks address: {address}");
+}
+
+/// Print the latest DKG shares and the outcome of the latest DKG round.
+async fn print_dkg_status(db: &PgStore) -> Result<(), Box<dyn std::error::Error>> {
+    match db.get_latest_encrypted_dkg_shares().await? {
+        Some(shares) => {
+            println!("aggregate key: {}", shares.aggregate_key);
+            println!("tweaked aggregate key: {}", shares.tweaked_aggregate_key);
+            println!("script pubkey: {}", shares.script_pubkey.to_hex_string());
+            println!("signature threshold: {}", shares.signature_share_threshold);
+            println!("signer set:");
+            for public_key in shares.signer_set_public_keys.iter() {
+                println!("  {public_key}");
+            }
+        }
+        None => println!("no DKG shares have been stored"),
+    }
+
+    let Some(attempt) = db.get_latest_dkg_attempt().await? else {
+        return Ok(());
+    };
+
+    println!("latest DKG round: {}", attempt.dkg_id);
+    println!("  bitcoin chain tip: {}", attempt.bitcoin_chain_tip);
+    println!("  status: {:?}", attempt.status);
+    if let Some(aggregate_key) = attempt.aggregate_key {
+        println!("  aggregate key: {aggregate_key}");
+    }
+    for failure in db.get_dkg_failures(&attempt.dkg_id).await? {
+        println!("  failure: {failure:?}");
+    }
+
+    Ok(())
+}
+
+/// Print the pending deposit and withdrawal requests along with the votes
+/// that the signers have cast on them.
+async fn print_requests(
+    settings: &Settings,
+    db: &PgStore,
+    context_window: u16,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(chain_tip) = db.get_bitcoin_canonical_chain_tip().await? else {
+        println!("no bitcoin blocks have been stored");
+        return Ok(());
+    };
+    let signer_public_key = settings.signer.public_key()?;
+    // The current signer set is the one that ran the latest DKG round,
+    // the bootstrap threshold only applies until the first one.
+    let threshold = db
+        .get_latest_encrypted_dkg_shares()
+        .await?
+        .map(|shares| shares.signature_share_threshold)
+        .unwrap_or(settings.signer.bootstrap_signatures_required);
+
+    let undecided = db
+        .get_pending_deposit_requests(&chain_tip, context_window, &signer_public_key)
+        .await?;
+    let accepted = db
+        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .await?;
+
+    for (heading, requests) in [("undecided", undecided), ("accepted", accepted)] {
+        println!("{heading} deposit requests: {}", requests.len());
+        for req in requests {
+            println!(
+                "  {}:{} amount={} max_fee={} recipient={}",
+                req.txid,
+                req.output_index,
+                req.amount,
+                req.max_fee,
+                req.recipient.to_string()
+            );
+            for vote in db.get_deposit_signers(&req.txid, req.output_index).await? {
+                println!(
+                    "    {} can_accept={} can_sign={}",
+                    vote.signer_pub_key, vote.can_accept, vote.can_sign
+                );
+            }
+        }
+    }
+
+    let undecided = db
+        .get_pending_withdrawal_requests(&chain_tip, context_window, &signer_public_key)
+        .await?;
+    let accepted = db
+        .get_pending_accepted_withdrawal_requests(&chain_tip, context_window, threshold)
+        .await?;
+
+    for (heading, requests) in [("undecided", undecided), ("accepted", accepted)] {
+        println!("{heading} withdrawal requests: {}", requests.len());
+        for req in requests {
+            println!(
+                "  {} block={} amount={} max_fee={} recipient={}",
+                req.request_id,
+                req.block_hash,
+                req.amount,
+                req.max_fee,
+                req.recipient.to_hex_string()
+            );
+            let votes = db
+                .get_withdrawal_signers(req.request_id, &req.block_hash)
+                .await?;
+            for vote in votes {
+                println!("    {} accepted={}", vote.signer_pub_key, vote.is_accepted);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print a summary of the latest `window` participation records of each
+/// signer.
+async fn print_participation(db: &PgStore, window: u32) -> Result<(), Box<dyn std::error::Error>> {
+    let stats = db.get_signer_participation_stats(window).await?;
+    if stats.is_empty() {
+        println!("no signer participation has been recorded");
+        return Ok(());
+    }
+
+    println!("signer participation over the latest {window} rounds:");
+    for stat in stats {
+        let avg_response_time = stat
+            .avg_response_time_ms
+            .map(|ms| format!("{ms}ms"))
+            .unwrap_or_else(|| "n/a".to_string());
+        println!(
+            "  {} responded={} invalid={} missing={} failure_rate={:.2} avg_response_time={}",
+            stat.signer_public_key,
+            stat.responded,
+            stat.invalid,
+            stat.missing,
+            stat.failure_rate(),
+            avg_response_time
+        );
+    }
+
+    Ok(())
+}
+
 /// A helper method that captures errors from the provided future and sends a
 /// shutdown signal to the application if an error is encountered. This is needed
 /// as otherwise the application would continue running indefinitely (since no
@@ -144,10 +573,15 @@ where
     Ok(())
 }
 
-/// Runs the shutdown-signal watcher. On Unix systems, this listens for SIGHUP,
-/// SIGTERM, and SIGINT. On other systems, it listens for Ctrl-C.
-#[tracing::instrument(skip(ctx), name = "shutdown-watcher")]
-async fn run_shutdown_signal_watcher(ctx: impl Context) -> Result<(), Error> {
+/// Runs the shutdown-signal watcher. On Unix systems, this listens for
+/// SIGTERM and SIGINT, and reloads the configuration on SIGHUP. On other
+/// systems, it listens for Ctrl-C.
+#[tracing::instrument(skip_all, name = "shutdown-watcher")]
+async fn run_shutdown_signal_watcher(
+    ctx: SignerCtx,
+    config_path: Option<PathBuf>,
+    log_filter: LogFilterHandle,
+) -> Result<(), Error> {
     let mut term = ctx.get_termination_handle();
 
     cfg_if! {
@@ -157,26 +591,32 @@ async fn run_shutdown_signal_watcher(ctx: impl Context) -> Result<(), Error> {
             let mut hangup = tokio::signal::unix::signal(signal::unix::SignalKind::hangup())?;
             let mut interrupt = tokio::signal::unix::signal(signal::unix::SignalKind::interrupt())?;
 
-            tokio::select! {
-                // If the shutdown signal is received, we'll shut down the signal watcher
-                // by returning early; the rest of the components have already received
-                // the shutdown signal.
-                _ = term.wait_for_shutdown() => {
-                    tracing::info!("termination signal received, signal watcher is shutting down");
-                    return Ok(());
-                },
-                // SIGTERM (kill -15 "nice")
-                _ = terminate.recv() => {
-                    tracing::info!(signal = "SIGTERM", "received termination signal");
-                },
-                // SIGHUP (kill -1)
-                _ = hangup.recv() => {
-                    tracing::info!(signal = "SIGHUP", "received termination signal");
-                },
-                // Ctrl-C will be received as a SIGINT (kill -2)
-                _ = interrupt.recv() => {
-                    tracing::info!(signal = "SIGINT", "received termination signal");
-                },
+            loop {
+                tokio::select! {
+                    // If the shutdown signal is received, we'll shut down the signal watcher
+                    // by returning early; the rest of the components have already received
+                    // the shutdown signal.
+                    _ = term.wait_for_shutdown() => {
+                        tracing::info!("termination signal received, signal watcher is shutting down");
+                        return Ok(());
+                    },
+                    // SIGTERM (kill -15 "nice")
+                    _ = terminate.recv() => {
+                        tracing::info!(signal = "SIGTERM", "received termination signal");
+                    },
+                    // SIGHUP (kill -1) reloads the configuration instead
+                    // of shutting down.
+                    _ = hangup.recv() => {
+                        tracing::info!(signal = "SIGHUP", "received reload signal");
+                        reload_config(&ctx, config_path.as_ref(), &log_filter);
+                        continue;
+                    },
+                    // Ctrl-C will be received as a SIGINT (kill -2)
+                    _ = interrupt.recv() => {
+                        tracing::info!(signal = "SIGINT", "received termination signal");
+                    },
+                }
+                break;
             }
         // Otherwise, we'll just listen for Ctrl-C, which is the most portable.
         } else {
@@ -202,20 +642,66 @@ async fn run_shutdown_signal_watcher(ctx: impl Context) -> Result<(), Error> {
     Ok(())
 }
 
+/// Re-read the configuration and apply the settings that can be changed
+/// while the signer is running. The settings that require a restart keep
+/// their current values.
+fn reload_config(ctx: &SignerCtx, config_path: Option<&PathBuf>, log_filter: &LogFilterHandle) {
+    let reloaded = match Settings::new(config_path) {
+        Ok(settings) => settings,
+        Err(error) => {
+            tracing::error!(%error, "could not reload the configuration; keeping the current one");
+            return;
+        }
+    };
+
+    let (settings, refused) = ctx.config().merge_reloadable(&reloaded);
+    for setting in refused {
+        tracing::warn!(
+            setting,
+            "this setting cannot be changed while the signer is running; restart the signer to apply it"
+        );
+    }
+
+    if settings == *ctx.config() {
+        tracing::info!("no settings to reload");
+        return;
+    }
+
+    if let Err(error) = ctx.reload_clients(&settings) {
+        tracing::error!(%error, "could not create the clients for the reloaded configuration; keeping the current one");
+        return;
+    }
+
+    if settings.signer.log_level != ctx.config().signer.log_level {
+        let directives = settings.signer.log_level.as_deref();
+        if let Err(error) = signer::logging::reload_log_filter(log_filter, directives) {
+            tracing::warn!(%error, "could not change the log level");
+        }
+    }
+
+    ctx.set_config(settings.clone());
+    let command = signer::context::SignerCommand::ReloadConfig(Arc::new(settings));
+    if let Err(error) = ctx.signal(command.into()) {
+        tracing::error!(%error, "could not send the reloaded configuration to the application");
+        return;
+    }
+
+    tracing::info!("reloaded the configuration");
+}
+
 /// Runs the libp2p swarm.
 #[tracing::instrument(skip_all, name = "p2p")]
-async fn run_libp2p_swarm(ctx: impl Context) -> Result<(), Error> {
+async fn run_libp2p_swarm(ctx: impl Context, private_key: PrivateKey) -> Result<(), Error> {
     tracing::info!("initializing the p2p network");
 
     // Build the swarm.
     tracing::debug!("building the libp2p swarm");
     let config = ctx.config();
-    let mut swarm =
-        SignerSwarmBuilder::new(&config.signer.private_key, config.signer.p2p.enable_mdns)
-            .add_listen_endpoints(&ctx.config().signer.p2p.listen_on)
-            .add_seed_addrs(&ctx.config().signer.p2p.seeds)
-            .add_external_addresses(&ctx.config().signer.p2p.public_endpoints)
-            .build()?;
+    let mut swarm = SignerSwarmBuilder::new(&private_key, config.signer.p2p.enable_mdns)
+        .add_listen_endpoints(&ctx.config().signer.p2p.listen_on)
+        .add_seed_addrs(&ctx.config().signer.p2p.seeds)
+        .add_external_addresses(&ctx.config().signer.p2p.public_endpoints)
+        .build()?;
 
     // Start the libp2p swarm. This will run until either the shutdown signal is
     // received, or an unrecoverable error has occurred.
@@ -240,7 +726,18 @@ async fn run_api(ctx: impl Context + 'static) -> Result<(), Error> {
     // Build the signer API application
     let app = Router::new()
         .route("/", get(api::status_handler))
+        .route("/status", get(api::signer_status_handler))
+        .route("/health/ready", get(api::readiness_handler))
+        .route("/metrics", get(api::metrics_handler))
         .route("/new_block", post(api::new_block_handler))
+        .route(
+            "/deposits",
+            post(api::deposit_handler)
+                .layer(DefaultBodyLimit::max(api::deposits::MAX_BODY_SIZE))
+                .layer(ConcurrencyLimitLayer::new(
+                    api::deposits::MAX_CONCURRENT_REQUESTS,
+                )),
+        )
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(|request: &Request<_>| {
@@ -286,21 +783,29 @@ async fn run_api(ctx: impl Context + 'static) -> Result<(), Error> {
 
 /// Run the block observer event-loop.
 async fn run_block_observer(ctx: impl Context) -> Result<(), Error> {
-    let config = ctx.config().clone();
+    let config = ctx.config();
 
-    // TODO: Need to handle multiple endpoints, so some sort of
-    // failover-stream-wrapper.
-    let stream = BitcoinCoreMessageStream::new_from_endpoint(
-        config.bitcoin.block_hash_stream_endpoints[0].as_str(),
-        &["hashblock"],
-    )
-    .await
-    .unwrap();
+    let stream = match config.bitcoin.block_hash_source {
+        // We listen to one bitcoin-core node at a time, failing over to
+        // the next configured endpoint whenever the connection drops.
+        BlockHashSource::Zmq => FailoverBlockHashStream::new(
+            config.bitcoin.block_hash_stream_endpoints.clone(),
+            ctx.get_bitcoin_client(),
+        )?
+        .boxed(),
+        // Some deployments cannot expose bitcoin-core's ZeroMQ interface,
+        // so we poll its RPC interface for the chain tip instead.
+        BlockHashSource::Rpc => PollingBlockHashStream::new(
+            ctx.get_bitcoin_client(),
+            config.bitcoin.block_hash_poll_interval,
+        )
+        .boxed(),
+    };
 
     // TODO: We should have a new() method that builds from the context
     let block_observer = block_observer::BlockObserver {
         context: ctx,
-        bitcoin_blocks: stream.to_block_hash_stream(),
+        bitcoin_blocks: stream,
         horizon: 20,
     };
 
@@ -308,8 +813,12 @@ async fn run_block_observer(ctx: impl Context) -> Result<(), Error> {
 }
 
 /// Run the transaction signer event-loop.
-async fn run_transaction_signer(ctx: impl Context) -> Result<(), Error> {
-    let config = ctx.config().clone();
+async fn run_transaction_signer(
+    ctx: impl Context,
+    signer_key: Arc<dyn SignerKeyBackend>,
+    private_key: PrivateKey,
+) -> Result<(), Error> {
+    let config = ctx.config();
     let network = P2PNetwork::new(&ctx);
 
     let signer = transaction_signer::TxSignerEventLoop {
@@ -318,18 +827,23 @@ async fn run_transaction_signer(ctx: impl Context) -> Result<(), Error> {
         context_window: 10000,
         threshold: config.signer.bootstrap_signatures_required.into(),
         rng: rand::thread_rng(),
-        signer_private_key: config.signer.private_key,
+        signer_private_key: private_key,
+        signer_key,
         wsts_state_machines: HashMap::new(),
         dkg_begin_pause: Some(Duration::from_secs(10)),
+        bitcoin_presign_package: Vec::new(),
     };
 
     signer.run().await
 }
 
 /// Run the transaction coordinator event-loop.
-async fn run_transaction_coordinator(ctx: impl Context) -> Result<(), Error> {
-    let config = ctx.config().clone();
-    let private_key = config.signer.private_key;
+async fn run_transaction_coordinator(
+    ctx: impl Context,
+    signer_key: Arc<dyn SignerKeyBackend>,
+    private_key: PrivateKey,
+) -> Result<(), Error> {
+    let config = ctx.config();
     let network = P2PNetwork::new(&ctx);
 
     let coord = transaction_coordinator::TxCoordinatorEventLoop {
@@ -337,10 +851,11 @@ async fn run_transaction_coordinator(ctx: impl Context) -> Result<(), Error> {
         context: ctx,
         context_window: 10000,
         private_key,
+        signer_key,
         signing_round_max_duration: Duration::from_secs(30),
         bitcoin_presign_request_max_duration: Duration::from_secs(30),
         threshold: config.signer.bootstrap_signatures_required,
-        dkg_max_duration: Duration::from_secs(120),
+        dkg_max_duration: config.signer.dkg.round_timeout,
         sbtc_contracts_deployed: false,
         is_epoch3: false,
     };
@@ -349,8 +864,10 @@ async fn run_transaction_coordinator(ctx: impl Context) -> Result<(), Error> {
 }
 
 /// Run the request decider event-loop.
-async fn run_request_decider(ctx: impl Context) -> Result<(), Error> {
-    let config = ctx.config().clone();
+async fn run_request_decider(
+    ctx: impl Context,
+    signer_key: Arc<dyn SignerKeyBackend>,
+) -> Result<(), Error> {
     let network = P2PNetwork::new(&ctx);
 
     let decider = RequestDeciderEventLoop {
@@ -358,7 +875,7 @@ async fn run_request_decider(ctx: impl Context) -> Result<(), Error> {
         context: ctx.clone(),
         context_window: 10000,
         blocklist_checker: BlocklistClient::new(&ctx),
-        signer_private_key: config.signer.private_key,
+        signer_key,
     };
 
     decider.run().await
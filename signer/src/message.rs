@@ -1,5 +1,8 @@
 //! Signer message definition for network communication
 
+use std::collections::BTreeSet;
+
+use sbtc::deposits::CreateDepositRequest;
 use secp256k1::ecdsa::RecoverableSignature;
 
 use crate::bitcoin::utxo::Fees;
@@ -20,7 +23,7 @@ pub struct SignerMessage {
 }
 
 /// The different variants of signer messages
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, strum::IntoStaticStr)]
 pub enum Payload {
     /// A decision related to signer deposit
     SignerDepositDecision(SignerDepositDecision),
@@ -42,6 +45,8 @@ pub enum Payload {
     BitcoinPreSignRequest(BitcoinPreSignRequest),
     /// An acknowledgment of a BitconPreSignRequest
     BitcoinPreSignAck(BitcoinPreSignAck),
+    /// A deposit request that a signer has learned about
+    NewDepositRequest(NewDepositRequest),
 }
 
 impl std::fmt::Display for Payload {
@@ -78,6 +83,7 @@ impl std::fmt::Display for Payload {
             Self::SweepTransactionInfo(_) => write!(f, "SweepTransactionInfo(..)"),
             Self::BitcoinPreSignRequest(_) => write!(f, "BitcoinPreSignRequest(..)"),
             Self::BitcoinPreSignAck(_) => write!(f, "BitcoinPreSignAck(..)"),
+            Self::NewDepositRequest(_) => write!(f, "NewDepositRequest(..)"),
         }
     }
 }
@@ -152,6 +158,12 @@ impl From<BitcoinPreSignAck> for Payload {
     }
 }
 
+impl From<NewDepositRequest> for Payload {
+    fn from(value: NewDepositRequest) -> Self {
+        Self::NewDepositRequest(value)
+    }
+}
+
 /// Represents information about a new sweep transaction.
 #[derive(Debug, Clone, PartialEq)]
 pub struct SweepTransactionInfo {
@@ -354,6 +366,40 @@ pub struct BitcoinPreSignRequest {
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct BitcoinPreSignAck;
 
+/// A deposit request that a signer has learned about, either from Emily
+/// or from a submission to its API. Signers share these with each other
+/// so that they learn about deposits even when Emily is unavailable, and
+/// each signer validates them before storing them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewDepositRequest {
+    /// The output index and txid of the depositing transaction.
+    pub outpoint: bitcoin::OutPoint,
+    /// The raw reclaim script.
+    pub reclaim_script: bitcoin::ScriptBuf,
+    /// The raw deposit script.
+    pub deposit_script: bitcoin::ScriptBuf,
+}
+
+impl From<CreateDepositRequest> for NewDepositRequest {
+    fn from(value: CreateDepositRequest) -> Self {
+        NewDepositRequest {
+            outpoint: value.outpoint,
+            reclaim_script: value.reclaim_script,
+            deposit_script: value.deposit_script,
+        }
+    }
+}
+
+impl From<NewDepositRequest> for CreateDepositRequest {
+    fn from(value: NewDepositRequest) -> Self {
+        CreateDepositRequest {
+            outpoint: value.outpoint,
+            reclaim_script: value.reclaim_script,
+            deposit_script: value.deposit_script,
+        }
+    }
+}
+
 /// A wsts message.
 #[derive(Debug, Clone, PartialEq)]
 pub struct WstsMessage {
@@ -362,6 +408,10 @@ pub struct WstsMessage {
     pub txid: bitcoin::Txid,
     /// The wsts message
     pub inner: wsts::net::Message,
+    /// The public keys of the signers taking part in a DKG round. The
+    /// coordinator sets this in `DkgBegin` messages so that all signers
+    /// agree on who takes part, and it is empty for all other messages.
+    pub dkg_participants: BTreeSet<PublicKey>,
 }
 
 /// Convenient type aliases
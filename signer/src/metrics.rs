@@ -0,0 +1,233 @@
+//! Prometheus metrics for the signer.
+//!
+//! The metrics are kept in a global registry so that every component can
+//! record them without having to thread a handle through the context. They
+//! are served in the Prometheus text format by the `GET /metrics` endpoint
+//! of the signer API.
+
+use std::sync::OnceLock;
+
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::exponential_buckets;
+use prometheus_client::metrics::histogram::Histogram;
+use prometheus_client::registry::Registry;
+
+/// The metrics of this signer, created on first use.
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+/// Get the metrics of this signer.
+pub fn metrics() -> &'static Metrics {
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// Labels for metrics that count the outcome of an operation.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct OutcomeLabels {
+    /// How the operation ended.
+    pub outcome: &'static str,
+}
+
+/// Labels for the decisions on deposit and withdrawal requests.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct DecisionLabels {
+    /// The kind of request, either `deposit` or `withdrawal`.
+    pub request: &'static str,
+    /// Whether this signer `sent` the decision or `received` it from
+    /// another signer.
+    pub direction: &'static str,
+}
+
+/// Labels for the WSTS signing rounds coordinated by this signer.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct SigningRoundLabels {
+    /// The type of signature, either `frost`, `schnorr` or `taproot`.
+    pub signature_type: &'static str,
+    /// Whether the round ended with a signature.
+    pub outcome: &'static str,
+}
+
+/// Labels for the Stacks transactions rejected by the stacks node.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct RejectionLabels {
+    /// The reason code given by the stacks node.
+    pub reason: &'static str,
+}
+
+/// Labels for the messages sent and received over the P2P network.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct P2PMessageLabels {
+    /// Whether the message was `inbound` or `outbound`.
+    pub direction: &'static str,
+    /// The type of the message payload.
+    pub payload: &'static str,
+}
+
+/// Labels for the failovers of the API clients.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct FailoverLabels {
+    /// The type of API client.
+    pub client: &'static str,
+    /// The position of the failing endpoint in the configured list of
+    /// endpoints. We do not use the URL since it may hold credentials.
+    pub endpoint: String,
+}
+
+/// The metrics recorded by the signer.
+pub struct Metrics {
+    registry: Registry,
+    /// The bitcoin blocks processed by the block observer.
+    pub bitcoin_blocks_processed: Counter,
+    /// The deposit requests checked by the block observer, labeled by
+    /// whether they were `validated`, are still `unconfirmed` or were
+    /// `rejected`, either because they are invalid or because bitcoin-core
+    /// could not be reached.
+    pub deposit_requests_validated: Family<OutcomeLabels, Counter>,
+    /// The decisions on deposit and withdrawal requests sent and received
+    /// by the request decider.
+    pub decisions: Family<DecisionLabels, Counter>,
+    /// How long the WSTS signing rounds coordinated by this signer took.
+    pub signing_round_duration_seconds: Family<SigningRoundLabels, Histogram, fn() -> Histogram>,
+    /// The sweep transaction packages built by the coordinator.
+    pub sweep_packages_built: Counter,
+    /// The fees, in sats, paid by the sweep transactions broadcast by the
+    /// coordinator.
+    pub sweep_fees_paid_sats: Counter,
+    /// The Stacks transactions accepted by the stacks node.
+    pub stacks_transactions_submitted: Counter,
+    /// The Stacks transactions rejected by the stacks node.
+    pub stacks_transactions_rejected: Family<RejectionLabels, Counter>,
+    /// The number of signers that we are connected to.
+    pub connected_peers: Gauge,
+    /// The messages sent and received over the P2P network.
+    pub p2p_messages: Family<P2PMessageLabels, Counter>,
+    /// The failovers of the API clients from one endpoint to the next.
+    pub api_client_failovers: Family<FailoverLabels, Counter>,
+}
+
+impl std::fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metrics").finish_non_exhaustive()
+    }
+}
+
+/// Signing rounds take anywhere from a fraction of a second to the
+/// configured maximum duration, so the buckets go from 100ms up to about
+/// 7 minutes.
+fn signing_round_histogram() -> Histogram {
+    Histogram::new(exponential_buckets(0.1, 2.0, 13))
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let mut metrics = Metrics {
+            registry: Registry::with_prefix("signer"),
+            bitcoin_blocks_processed: Counter::default(),
+            deposit_requests_validated: Family::default(),
+            decisions: Family::default(),
+            signing_round_duration_seconds: Family::new_with_constructor(
+                signing_round_histogram as fn() -> Histogram,
+            ),
+            sweep_packages_built: Counter::default(),
+            sweep_fees_paid_sats: Counter::default(),
+            stacks_transactions_submitted: Counter::default(),
+            stacks_transactions_rejected: Family::default(),
+            connected_peers: Gauge::default(),
+            p2p_messages: Family::default(),
+            api_client_failovers: Family::default(),
+        };
+
+        let registry = &mut metrics.registry;
+        registry.register(
+            "bitcoin_blocks_processed",
+            "The bitcoin blocks processed by the block observer",
+            metrics.bitcoin_blocks_processed.clone(),
+        );
+        registry.register(
+            "deposit_requests_validated",
+            "The deposit requests checked by the block observer",
+            metrics.deposit_requests_validated.clone(),
+        );
+        registry.register(
+            "decisions",
+            "The decisions on deposit and withdrawal requests",
+            metrics.decisions.clone(),
+        );
+        registry.register(
+            "signing_round_duration_seconds",
+            "How long the signing rounds coordinated by this signer took",
+            metrics.signing_round_duration_seconds.clone(),
+        );
+        registry.register(
+            "sweep_packages_built",
+            "The sweep transaction packages built by the coordinator",
+            metrics.sweep_packages_built.clone(),
+        );
+        registry.register(
+            "sweep_fees_paid_sats",
+            "The fees paid by the sweep transactions broadcast by the coordinator",
+            metrics.sweep_fees_paid_sats.clone(),
+        );
+        registry.register(
+            "stacks_transactions_submitted",
+            "The Stacks transactions accepted by the stacks node",
+            metrics.stacks_transactions_submitted.clone(),
+        );
+        registry.register(
+            "stacks_transactions_rejected",
+            "The Stacks transactions rejected by the stacks node",
+            metrics.stacks_transactions_rejected.clone(),
+        );
+        registry.register(
+            "connected_peers",
+            "The number of signers that we are connected to",
+            metrics.connected_peers.clone(),
+        );
+        registry.register(
+            "p2p_messages",
+            "The messages sent and received over the P2P network",
+            metrics.p2p_messages.clone(),
+        );
+        registry.register(
+            "api_client_failovers",
+            "The failovers of the API clients from one endpoint to the next",
+            metrics.api_client_failovers.clone(),
+        );
+
+        metrics
+    }
+
+    /// Encode the metrics in the Prometheus text format.
+    pub fn encode(&self) -> Result<String, std::fmt::Error> {
+        let mut buffer = String::new();
+        prometheus_client::encoding::text::encode(&mut buffer, &self.registry)?;
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_metrics_are_encoded() {
+        let metrics = metrics();
+        metrics.bitcoin_blocks_processed.inc();
+        metrics
+            .p2p_messages
+            .get_or_create(&P2PMessageLabels {
+                direction: "outbound",
+                payload: "SignerDepositDecision",
+            })
+            .inc();
+
+        let encoded = metrics.encode().unwrap();
+        assert!(encoded.contains("signer_bitcoin_blocks_processed_total"));
+        assert!(encoded.contains(
+            r#"signer_p2p_messages_total{direction="outbound",payload="SignerDepositDecision"}"#
+        ));
+        assert!(encoded.ends_with("# EOF\n"));
+    }
+}
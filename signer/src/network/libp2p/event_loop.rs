@@ -10,6 +10,9 @@ use tokio::sync::Mutex;
 use crate::codec::Encode;
 use crate::context::{Context, P2PEvent, SignerCommand, SignerSignal};
 use crate::error::Error;
+use crate::message::Payload;
+use crate::metrics::metrics;
+use crate::metrics::P2PMessageLabels;
 use crate::network::Msg;
 
 use super::swarm::{SignerBehavior, SignerBehaviorEvent};
@@ -35,18 +38,31 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
     // Here we create a future that listens for `P2PPublish` commands from the
     // app signalling channel and pushes them into the outbound message queue.
     // This queue is then polled by the `poll_swarm` event loop to publish the
-    // messages to the network.
+    // messages to the network. It also dials any seeds that were added when
+    // the configuration is reloaded.
     let outbox = Mutex::new(Vec::<Msg>::new());
+    let mut seeds = ctx.config().signer.p2p.seeds.clone();
     let poll_outbound = async {
         tracing::debug!("p2p outbound message polling started");
         loop {
-            let Ok(SignerSignal::Command(SignerCommand::P2PPublish(payload))) =
-                signal_rx.recv().await
-            else {
-                continue;
-            };
-
-            outbox.lock().await.push(payload);
+            match signal_rx.recv().await {
+                Ok(SignerSignal::Command(SignerCommand::P2PPublish(payload))) => {
+                    outbox.lock().await.push(payload);
+                }
+                Ok(SignerSignal::Command(SignerCommand::ReloadConfig(settings))) => {
+                    for addr in settings.signer.p2p.seeds.iter() {
+                        if seeds.contains(addr) {
+                            continue;
+                        }
+                        tracing::info!(%addr, "dialing new seed from the reloaded configuration");
+                        if let Err(error) = swarm.lock().await.dial(addr.clone()) {
+                            tracing::warn!(%addr, %error, "failed to dial seed");
+                        }
+                    }
+                    seeds.clone_from(&settings.signer.p2p.seeds);
+                }
+                _ => continue,
+            }
         }
     };
 
@@ -107,9 +123,23 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                             continue;
                         }
                         tracing::debug!(%peer_id, ?endpoint, "connected to peer");
+                        ctx.state().peer_connected(peer_id);
+                        record_connected_peers(ctx);
                     }
-                    SwarmEvent::ConnectionClosed { peer_id, cause, endpoint, .. } => {
+                    SwarmEvent::ConnectionClosed {
+                        peer_id,
+                        cause,
+                        endpoint,
+                        num_established,
+                        ..
+                    } => {
                         tracing::trace!(%peer_id, ?cause, ?endpoint, "connection closed");
+                        // We may have more than one connection to a peer,
+                        // it is only gone once the last one closes.
+                        if num_established == 0 {
+                            ctx.state().peer_disconnected(&peer_id);
+                            record_connected_peers(ctx);
+                        }
                     }
                     SwarmEvent::IncomingConnection { local_addr, send_back_addr, .. } => {
                         tracing::trace!(%local_addr, %send_back_addr, "incoming connection");
@@ -207,6 +237,7 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                         // and send a success signal to the application so that it can
                         // handle the success as needed.
                         tracing::trace!(?msg_id, "message published successfully");
+                        record_message("outbound", &payload.inner.payload);
                         let _ = signal_tx.send(P2PEvent::PublishSuccess(msg_id).into());
                     });
             }
@@ -325,6 +356,21 @@ fn handle_identify_event(
     }
 }
 
+/// Update the gauge of connected signers.
+fn record_connected_peers(ctx: &impl Context) {
+    let connected_peers = ctx.state().connected_peers().len();
+    metrics().connected_peers.set(connected_peers as i64);
+}
+
+/// Count a message sent or received over the P2P network.
+fn record_message(direction: &'static str, payload: &Payload) {
+    let labels = P2PMessageLabels {
+        direction,
+        payload: payload.into(),
+    };
+    metrics().p2p_messages.get_or_create(&labels).inc();
+}
+
 #[tracing::instrument(skip_all, name = "gossipsub")]
 fn handle_gossipsub_event(
     swarm: &mut Swarm<SignerBehavior>,
@@ -340,13 +386,14 @@ fn handle_gossipsub_event(
             ..
         } => {
             let current_signer_set = ctx.state().current_signer_set();
-            // The following check should be unnecessary. In order to
-            // receive a message the peer needs to establish a connection,
-            // and in order to do that the peer needs to be in the current
-            // signer set. When we implement the signing set changing code,
-            // we should re-evaluate whether we should remove this check.
+            // In order to receive a message the peer needs to establish a
+            // connection, and in order to do that the peer needs to be in
+            // the current signer set. But the signer set can change while
+            // the connection is open, so we disconnect peers that have
+            // been removed from it.
             if !current_signer_set.is_allowed_peer(&peer_id) {
-                tracing::warn!(%peer_id, "ignoring message from unknown peer");
+                tracing::warn!(%peer_id, "ignoring message from unknown peer, disconnecting");
+                let _ = swarm.disconnect_peer_id(peer_id);
                 return;
             }
 
@@ -383,6 +430,7 @@ fn handle_gossipsub_event(
                         return Err(error)
                     }
 
+                    record_message("inbound", &msg.inner.payload);
                     let _ = ctx.get_signal_sender()
                         .send(P2PEvent::MessageReceived(msg).into())
                         .inspect_err(|error| {
@@ -142,7 +142,7 @@ mod tests {
             .with_in_memory_storage()
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = key1;
+                settings.signer.private_key = Some(key1);
             })
             .build();
         context1
@@ -154,7 +154,7 @@ mod tests {
             .with_in_memory_storage()
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = key2;
+                settings.signer.private_key = Some(key2);
             })
             .build();
         context2
@@ -223,21 +223,21 @@ mod tests {
             .with_in_memory_storage()
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = key1;
+                settings.signer.private_key = Some(key1);
             })
             .build();
         let context2 = TestContext::builder()
             .with_in_memory_storage()
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = key2;
+                settings.signer.private_key = Some(key2);
             })
             .build();
         let context3 = TestContext::builder()
             .with_in_memory_storage()
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = key3;
+                settings.signer.private_key = Some(key3);
             })
             .build();
 
@@ -367,21 +367,21 @@ mod tests {
             .with_in_memory_storage()
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = key1;
+                settings.signer.private_key = Some(key1);
             })
             .build();
         let context2 = TestContext::builder()
             .with_in_memory_storage()
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = key2;
+                settings.signer.private_key = Some(key2);
             })
             .build();
         let context3 = TestContext::builder()
             .with_in_memory_storage()
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = key3;
+                settings.signer.private_key = Some(key3);
             })
             .build();
 
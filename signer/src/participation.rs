@@ -0,0 +1,305 @@
+//! Tracking of how signers take part in the rounds run by the coordinator
+//!
+//! The coordinator runs DKG rounds, WSTS signing rounds and Stacks
+//! multi-sig rounds, and each of them stalls until enough signers respond.
+//! A [`RoundParticipation`] records, for a single round, which signers
+//! responded, how long they took and who sent invalid data, so that the
+//! coordinator can write it to the database once the round has ended. The
+//! recorded participation is later used to prefer reliable signers when
+//! gathering nonces, see [`reliable_signers`].
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use tokio::time::Instant;
+
+use crate::keys::PublicKey;
+use crate::storage::model;
+use crate::storage::model::ParticipationOutcome;
+use crate::storage::model::ParticipationRoundType;
+
+/// The participation of signers in the phases of a single round.
+#[derive(Debug)]
+pub struct RoundParticipation {
+    round_id: [u8; 32],
+    bitcoin_chain_tip: model::BitcoinBlockHash,
+    phases: BTreeMap<ParticipationRoundType, Phase>,
+    preferred_nonce_responders: Option<BTreeSet<PublicKey>>,
+}
+
+#[derive(Debug)]
+struct Phase {
+    started_at: Instant,
+    expected: BTreeSet<PublicKey>,
+    outcomes: BTreeMap<PublicKey, (ParticipationOutcome, Option<i64>)>,
+    completed: bool,
+}
+
+impl RoundParticipation {
+    /// Create a new tracker for the round with the given identifier.
+    pub fn new(round_id: [u8; 32], bitcoin_chain_tip: model::BitcoinBlockHash) -> Self {
+        Self {
+            round_id,
+            bitcoin_chain_tip,
+            phases: BTreeMap::new(),
+            preferred_nonce_responders: None,
+        }
+    }
+
+    /// Prefer the nonces of the given signers. See
+    /// [`RoundParticipation::should_hold_back_nonce`].
+    pub fn with_preferred_nonce_responders(mut self, signers: BTreeSet<PublicKey>) -> Self {
+        self.preferred_nonce_responders = Some(signers);
+        self
+    }
+
+    /// Start a phase of the round, where the given signers are expected
+    /// to respond. Response times are measured from now.
+    pub fn start(
+        &mut self,
+        round_type: ParticipationRoundType,
+        expected: impl IntoIterator<Item = PublicKey>,
+    ) {
+        let phase = Phase {
+            started_at: Instant::now(),
+            expected: expected.into_iter().collect(),
+            outcomes: BTreeMap::new(),
+            completed: false,
+        };
+        self.phases.insert(round_type, phase);
+    }
+
+    /// Mark the phase as completed. Signers that did not respond in a
+    /// completed phase are not blamed, since they were not needed.
+    pub fn complete(&mut self, round_type: ParticipationRoundType) {
+        if let Some(phase) = self.phases.get_mut(&round_type) {
+            phase.completed = true;
+        }
+    }
+
+    /// Record a valid response from the signer. Only the first response
+    /// of a signer in a phase counts.
+    pub fn responded(&mut self, round_type: ParticipationRoundType, signer: PublicKey) {
+        let Some(phase) = self.phases.get_mut(&round_type) else {
+            return;
+        };
+        let elapsed = phase.started_at.elapsed().as_millis();
+        let response_time_ms = i64::try_from(elapsed).unwrap_or(i64::MAX);
+        phase
+            .outcomes
+            .entry(signer)
+            .or_insert((ParticipationOutcome::Responded, Some(response_time_ms)));
+    }
+
+    /// Record that the signer sent invalid data. This overrides any
+    /// valid response from the signer in the same phase.
+    pub fn invalid(&mut self, round_type: ParticipationRoundType, signer: PublicKey) {
+        let Some(phase) = self.phases.get_mut(&round_type) else {
+            return;
+        };
+        phase
+            .outcomes
+            .insert(signer, (ParticipationOutcome::Invalid, None));
+    }
+
+    /// Whether the nonce response from the given signer should be held
+    /// back in favour of the nonces from preferred signers.
+    ///
+    /// Nonces are held back only while the nonce phase is within its
+    /// grace period, and only if the preferred signers that are expected
+    /// to respond can meet the signing threshold on their own.
+    pub fn should_hold_back_nonce(
+        &self,
+        signer: &PublicKey,
+        threshold: usize,
+        grace_period: std::time::Duration,
+    ) -> bool {
+        let (Some(preferred), Some(phase)) = (
+            &self.preferred_nonce_responders,
+            self.phases.get(&ParticipationRoundType::Nonce),
+        ) else {
+            return false;
+        };
+
+        let num_preferred = phase.expected.intersection(preferred).count();
+        !preferred.contains(signer)
+            && num_preferred >= threshold
+            && phase.started_at.elapsed() < grace_period
+    }
+
+    /// The instant at which nonces that have been held back should be
+    /// released.
+    pub fn nonce_grace_deadline(&self, grace_period: std::time::Duration) -> Option<Instant> {
+        let phase = self.phases.get(&ParticipationRoundType::Nonce)?;
+        phase.started_at.checked_add(grace_period)
+    }
+
+    /// Finish the round and return the participation records for all of
+    /// its phases. Expected signers that did not respond in a phase that
+    /// was not completed are recorded as missing.
+    pub fn finish(self) -> Vec<model::SignerParticipation> {
+        let mut records = Vec::new();
+        for (round_type, phase) in self.phases {
+            let missing = phase
+                .expected
+                .iter()
+                .filter(|signer| !phase.completed && !phase.outcomes.contains_key(signer))
+                .map(|signer| (*signer, (ParticipationOutcome::Missing, None)))
+                .collect::<Vec<_>>();
+
+            let outcomes = phase.outcomes.into_iter().chain(missing);
+            records.extend(
+                outcomes.map(|(signer_public_key, (outcome, response_time_ms))| {
+                    model::SignerParticipation {
+                        round_id: self.round_id,
+                        round_type,
+                        bitcoin_chain_tip: self.bitcoin_chain_tip,
+                        signer_public_key,
+                        outcome,
+                        response_time_ms,
+                    }
+                }),
+            );
+        }
+        records
+    }
+}
+
+/// Return the phase of a round that a WSTS message from a signer is a
+/// response in, or `None` for messages sent by the coordinator.
+pub fn wsts_round_type(msg: &wsts::net::Message) -> Option<ParticipationRoundType> {
+    match msg {
+        wsts::net::Message::DkgPublicShares(_)
+        | wsts::net::Message::DkgPrivateShares(_)
+        | wsts::net::Message::DkgEnd(_) => Some(ParticipationRoundType::Dkg),
+        wsts::net::Message::NonceResponse(_) => Some(ParticipationRoundType::Nonce),
+        wsts::net::Message::SignatureShareResponse(_) => {
+            Some(ParticipationRoundType::SignatureShare)
+        }
+        wsts::net::Message::DkgBegin(_)
+        | wsts::net::Message::DkgPrivateBegin(_)
+        | wsts::net::Message::DkgEndBegin(_)
+        | wsts::net::Message::NonceRequest(_)
+        | wsts::net::Message::SignatureShareRequest(_) => None,
+    }
+}
+
+/// Return the signers whose failure rate over their recent rounds is at
+/// most `max_failure_rate`. Signers without any recorded participation
+/// are considered reliable.
+pub fn reliable_signers(
+    signers: &BTreeSet<PublicKey>,
+    stats: &[model::SignerParticipationStats],
+    max_failure_rate: f64,
+) -> BTreeSet<PublicKey> {
+    let unreliable: BTreeSet<PublicKey> = stats
+        .iter()
+        .filter(|stats| stats.failure_rate() > max_failure_rate)
+        .map(|stats| stats.signer_public_key)
+        .collect();
+
+    signers.difference(&unreliable).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::keys::PrivateKey;
+
+    use super::*;
+
+    fn signers(num_signers: usize) -> Vec<PublicKey> {
+        let keys: BTreeSet<PublicKey> = (0..num_signers)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng)))
+            .collect();
+        keys.into_iter().collect()
+    }
+
+    fn outcomes(
+        records: &[model::SignerParticipation],
+        round_type: ParticipationRoundType,
+    ) -> BTreeMap<PublicKey, ParticipationOutcome> {
+        records
+            .iter()
+            .filter(|record| record.round_type == round_type)
+            .map(|record| (record.signer_public_key, record.outcome))
+            .collect()
+    }
+
+    #[test]
+    fn incomplete_phases_blame_missing_signers() {
+        let keys = signers(4);
+        let mut participation = RoundParticipation::new([1; 32], Faker.fake());
+
+        participation.start(ParticipationRoundType::Nonce, keys.clone());
+        participation.responded(ParticipationRoundType::Nonce, keys[0]);
+        participation.responded(ParticipationRoundType::Nonce, keys[1]);
+        participation.responded(ParticipationRoundType::Nonce, keys[2]);
+        participation.complete(ParticipationRoundType::Nonce);
+
+        participation.start(ParticipationRoundType::SignatureShare, keys[..3].to_vec());
+        participation.responded(ParticipationRoundType::SignatureShare, keys[0]);
+        participation.responded(ParticipationRoundType::SignatureShare, keys[1]);
+        participation.invalid(ParticipationRoundType::SignatureShare, keys[1]);
+
+        let records = participation.finish();
+
+        // The nonce phase completed, so the signer that did not respond
+        // in it is not blamed.
+        let nonce = outcomes(&records, ParticipationRoundType::Nonce);
+        assert_eq!(nonce.len(), 3);
+        assert!(!nonce.contains_key(&keys[3]));
+
+        let shares = outcomes(&records, ParticipationRoundType::SignatureShare);
+        assert_eq!(shares[&keys[0]], ParticipationOutcome::Responded);
+        assert_eq!(shares[&keys[1]], ParticipationOutcome::Invalid);
+        assert_eq!(shares[&keys[2]], ParticipationOutcome::Missing);
+        assert!(!shares.contains_key(&keys[3]));
+
+        for record in records {
+            assert_eq!(record.round_id, [1; 32]);
+            let responded = record.outcome == ParticipationOutcome::Responded;
+            assert_eq!(record.response_time_ms.is_some(), responded);
+        }
+    }
+
+    #[test]
+    fn unreliable_signers_are_not_preferred() {
+        let keys = signers(3);
+        let stats = |signer_public_key, responded, missing| model::SignerParticipationStats {
+            signer_public_key,
+            responded,
+            invalid: 0,
+            missing,
+            avg_response_time_ms: None,
+        };
+        let stats = [stats(keys[0], 9, 1), stats(keys[1], 5, 5)];
+
+        let reliable = reliable_signers(&keys.iter().copied().collect(), &stats, 0.2);
+        assert_eq!(reliable, BTreeSet::from([keys[0], keys[2]]));
+    }
+
+    #[test]
+    fn nonces_are_held_back_only_when_preferred_signers_suffice() {
+        let keys = signers(4);
+        let grace_period = Duration::from_secs(60);
+        let preferred = BTreeSet::from([keys[0], keys[1]]);
+        let mut participation = RoundParticipation::new([2; 32], Faker.fake())
+            .with_preferred_nonce_responders(preferred);
+
+        // Nothing is held back before the nonce phase starts.
+        assert!(!participation.should_hold_back_nonce(&keys[3], 2, grace_period));
+
+        participation.start(ParticipationRoundType::Nonce, keys.clone());
+        assert!(!participation.should_hold_back_nonce(&keys[0], 2, grace_period));
+        assert!(participation.should_hold_back_nonce(&keys[3], 2, grace_period));
+        // Two preferred signers cannot meet a threshold of three.
+        assert!(!participation.should_hold_back_nonce(&keys[3], 3, grace_period));
+        // Nothing is held back once the grace period is over.
+        assert!(!participation.should_hold_back_nonce(&keys[3], 2, Duration::ZERO));
+    }
+}
@@ -51,6 +51,7 @@ use crate::message::BitcoinPreSignAck;
 use crate::message::BitcoinPreSignRequest;
 use crate::message::BitcoinTransactionSignAck;
 use crate::message::BitcoinTransactionSignRequest;
+use crate::message::NewDepositRequest;
 use crate::message::Payload;
 use crate::message::SignerDepositDecision;
 use crate::message::SignerMessage;
@@ -64,11 +65,14 @@ use crate::message::WstsMessage;
 use crate::proto;
 use crate::stacks::contracts::AcceptWithdrawalV1;
 use crate::stacks::contracts::CompleteDepositV1;
+use crate::stacks::contracts::CompleteDepositsV1;
+use crate::stacks::contracts::CompleteWithdrawalsV1;
 use crate::stacks::contracts::ContractCall;
 use crate::stacks::contracts::RejectWithdrawalV1;
 use crate::stacks::contracts::RotateKeysV1;
 use crate::stacks::contracts::SmartContract;
 use crate::stacks::contracts::StacksTx;
+use crate::stacks::contracts::WithdrawalCompletion;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::BitcoinTxId;
 use crate::storage::model::QualifiedRequestId;
@@ -413,6 +417,29 @@ impl TryFrom<proto::CompleteDeposit> for CompleteDepositV1 {
     }
 }
 
+impl From<CompleteDepositsV1> for proto::CompleteDeposits {
+    fn from(value: CompleteDepositsV1) -> Self {
+        proto::CompleteDeposits {
+            deposits: value.deposits.into_iter().map(|v| v.into()).collect(),
+            deployer: Some(value.deployer.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::CompleteDeposits> for CompleteDepositsV1 {
+    type Error = Error;
+    fn try_from(value: proto::CompleteDeposits) -> Result<Self, Self::Error> {
+        Ok(CompleteDepositsV1 {
+            deposits: value
+                .deposits
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<Vec<_>, Error>>()?,
+            deployer: value.deployer.required()?.try_into()?,
+        })
+    }
+}
+
 impl From<AcceptWithdrawalV1> for proto::AcceptWithdrawal {
     fn from(value: AcceptWithdrawalV1) -> Self {
         proto::AcceptWithdrawal {
@@ -423,6 +450,7 @@ impl From<AcceptWithdrawalV1> for proto::AcceptWithdrawal {
             deployer: Some(value.deployer.into()),
             sweep_block_hash: Some(value.sweep_block_hash.into()),
             sweep_block_height: value.sweep_block_height,
+            sweep_txid: Some(value.sweep_txid.into()),
         }
     }
 }
@@ -451,6 +479,7 @@ impl TryFrom<proto::AcceptWithdrawal> for AcceptWithdrawalV1 {
             deployer: value.deployer.required()?.try_into()?,
             sweep_block_hash: value.sweep_block_hash.required()?.try_into()?,
             sweep_block_height: value.sweep_block_height,
+            sweep_txid: value.sweep_txid.required()?.try_into()?,
         })
     }
 }
@@ -489,6 +518,57 @@ impl TryFrom<proto::RejectWithdrawal> for RejectWithdrawalV1 {
     }
 }
 
+impl From<WithdrawalCompletion> for proto::WithdrawalCompletion {
+    fn from(value: WithdrawalCompletion) -> Self {
+        let completion = match value {
+            WithdrawalCompletion::Accept(inner) => {
+                proto::withdrawal_completion::Completion::Accept(inner.into())
+            }
+            WithdrawalCompletion::Reject(inner) => {
+                proto::withdrawal_completion::Completion::Reject(inner.into())
+            }
+        };
+        proto::WithdrawalCompletion { completion: Some(completion) }
+    }
+}
+
+impl TryFrom<proto::WithdrawalCompletion> for WithdrawalCompletion {
+    type Error = Error;
+    fn try_from(value: proto::WithdrawalCompletion) -> Result<Self, Self::Error> {
+        Ok(match value.completion.required()? {
+            proto::withdrawal_completion::Completion::Accept(inner) => {
+                WithdrawalCompletion::Accept(inner.try_into()?)
+            }
+            proto::withdrawal_completion::Completion::Reject(inner) => {
+                WithdrawalCompletion::Reject(inner.try_into()?)
+            }
+        })
+    }
+}
+
+impl From<CompleteWithdrawalsV1> for proto::CompleteWithdrawals {
+    fn from(value: CompleteWithdrawalsV1) -> Self {
+        proto::CompleteWithdrawals {
+            withdrawals: value.withdrawals.into_iter().map(|v| v.into()).collect(),
+            deployer: Some(value.deployer.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::CompleteWithdrawals> for CompleteWithdrawalsV1 {
+    type Error = Error;
+    fn try_from(value: proto::CompleteWithdrawals) -> Result<Self, Self::Error> {
+        Ok(CompleteWithdrawalsV1 {
+            withdrawals: value
+                .withdrawals
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<Vec<_>, Error>>()?,
+            deployer: value.deployer.required()?.try_into()?,
+        })
+    }
+}
+
 impl From<RotateKeysV1> for proto::RotateKeys {
     fn from(value: RotateKeysV1) -> Self {
         proto::RotateKeys {
@@ -554,6 +634,11 @@ impl From<StacksTransactionSignRequest> for proto::StacksTransactionSignRequest
                         inner.into(),
                     )
                 }
+                ContractCall::CompleteDepositsV1(inner) => {
+                    proto::stacks_transaction_sign_request::ContractTx::CompleteDeposits(
+                        inner.into(),
+                    )
+                }
                 ContractCall::AcceptWithdrawalV1(inner) => {
                     proto::stacks_transaction_sign_request::ContractTx::AcceptWithdrawal(
                         inner.into(),
@@ -564,6 +649,11 @@ impl From<StacksTransactionSignRequest> for proto::StacksTransactionSignRequest
                         inner.into(),
                     )
                 }
+                ContractCall::CompleteWithdrawalsV1(inner) => {
+                    proto::stacks_transaction_sign_request::ContractTx::CompleteWithdrawals(
+                        inner.into(),
+                    )
+                }
                 ContractCall::RotateKeysV1(inner) => {
                     proto::stacks_transaction_sign_request::ContractTx::RotateKeys(inner.into())
                 }
@@ -591,12 +681,18 @@ impl TryFrom<proto::StacksTransactionSignRequest> for StacksTransactionSignReque
             proto::ContractTx::CompleteDeposit(inner) => {
                 StacksTx::ContractCall(ContractCall::CompleteDepositV1(inner.try_into()?))
             }
+            proto::ContractTx::CompleteDeposits(inner) => {
+                StacksTx::ContractCall(ContractCall::CompleteDepositsV1(inner.try_into()?))
+            }
             proto::ContractTx::AcceptWithdrawal(inner) => {
                 StacksTx::ContractCall(ContractCall::AcceptWithdrawalV1(inner.try_into()?))
             }
             proto::ContractTx::RejectWithdrawal(inner) => {
                 StacksTx::ContractCall(ContractCall::RejectWithdrawalV1(inner.try_into()?))
             }
+            proto::ContractTx::CompleteWithdrawals(inner) => {
+                StacksTx::ContractCall(ContractCall::CompleteWithdrawalsV1(inner.try_into()?))
+            }
             proto::ContractTx::RotateKeys(inner) => {
                 StacksTx::ContractCall(ContractCall::RotateKeysV1(inner.try_into()?))
             }
@@ -1116,6 +1212,11 @@ impl From<WstsMessage> for proto::WstsMessage {
         proto::WstsMessage {
             txid: Some(BitcoinTxId::from(value.txid).into()),
             inner: Some(inner),
+            dkg_participants: value
+                .dkg_participants
+                .into_iter()
+                .map(|v| v.into())
+                .collect(),
         }
     }
 }
@@ -1158,6 +1259,11 @@ impl TryFrom<proto::WstsMessage> for WstsMessage {
         Ok(WstsMessage {
             txid: BitcoinTxId::try_from(value.txid.required()?)?.into(),
             inner,
+            dkg_participants: value
+                .dkg_participants
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<BTreeSet<_>, Error>>()?,
         })
     }
 }
@@ -1414,6 +1520,27 @@ impl From<proto::BitcoinPreSignAck> for BitcoinPreSignAck {
     }
 }
 
+impl From<NewDepositRequest> for proto::NewDepositRequest {
+    fn from(value: NewDepositRequest) -> Self {
+        proto::NewDepositRequest {
+            outpoint: Some(value.outpoint.into()),
+            reclaim_script: value.reclaim_script.into_bytes(),
+            deposit_script: value.deposit_script.into_bytes(),
+        }
+    }
+}
+
+impl TryFrom<proto::NewDepositRequest> for NewDepositRequest {
+    type Error = Error;
+    fn try_from(value: proto::NewDepositRequest) -> Result<Self, Self::Error> {
+        Ok(NewDepositRequest {
+            outpoint: value.outpoint.required()?.try_into()?,
+            reclaim_script: bitcoin::ScriptBuf::from_bytes(value.reclaim_script),
+            deposit_script: bitcoin::ScriptBuf::from_bytes(value.deposit_script),
+        })
+    }
+}
+
 impl From<SignerMessage> for proto::SignerMessage {
     fn from(value: SignerMessage) -> Self {
         proto::SignerMessage {
@@ -1466,6 +1593,9 @@ impl From<Payload> for proto::Payload {
             Payload::BitcoinPreSignAck(inner) => {
                 proto::signer_message::Payload::BitcoinPreSignAck(inner.into())
             }
+            Payload::NewDepositRequest(inner) => {
+                proto::signer_message::Payload::NewDepositRequest(inner.into())
+            }
         }
     }
 }
@@ -1504,6 +1634,9 @@ impl TryFrom<proto::Payload> for Payload {
             proto::signer_message::Payload::BitcoinPreSignAck(inner) => {
                 Payload::BitcoinPreSignAck(inner.into())
             }
+            proto::signer_message::Payload::NewDepositRequest(inner) => {
+                Payload::NewDepositRequest(inner.try_into()?)
+            }
         };
         Ok(payload)
     }
@@ -1815,6 +1948,7 @@ impl codec::ProtoSerializable for SignerMessage {
             Payload::SweepTransactionInfo(_) => "SBTC_SWEEP_TRANSACTION_INFO",
             Payload::BitcoinPreSignRequest(_) => "SBTC_BITCOIN_PRE_SIGN_REQUEST",
             Payload::BitcoinPreSignAck(_) => "SBTC_BITCOIN_PRE_SIGN_ACK",
+            Payload::NewDepositRequest(_) => "SBTC_NEW_DEPOSIT_REQUEST",
         }
     }
 }
@@ -2243,6 +2377,8 @@ mod tests {
     #[test_case(PhantomData::<(BitcoinTransactionSignAck, proto::BitcoinTransactionSignAck)>; "BitcoinTransactionSignAck")]
     #[test_case(PhantomData::<(StacksTransactionSignature, proto::StacksTransactionSignature)>; "StacksTransactionSignature")]
     #[test_case(PhantomData::<(CompleteDepositV1, proto::CompleteDeposit)>; "CompleteDeposit")]
+    #[test_case(PhantomData::<(CompleteDepositsV1, proto::CompleteDeposits)>; "CompleteDeposits")]
+    #[test_case(PhantomData::<(CompleteWithdrawalsV1, proto::CompleteWithdrawals)>; "CompleteWithdrawals")]
     #[test_case(PhantomData::<(AcceptWithdrawalV1, proto::AcceptWithdrawal)>; "AcceptWithdrawal")]
     #[test_case(PhantomData::<(RejectWithdrawalV1, proto::RejectWithdrawal)>; "RejectWithdrawal")]
     #[test_case(PhantomData::<(RotateKeysV1, proto::RotateKeys)>; "RotateKeys")]
@@ -2261,6 +2397,7 @@ mod tests {
     #[test_case(PhantomData::<(Fees, proto::Fees)>; "Fees")]
     #[test_case(PhantomData::<(BitcoinPreSignRequest, proto::BitcoinPreSignRequest)>; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<(BitcoinPreSignAck, proto::BitcoinPreSignAck)>; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<(NewDepositRequest, proto::NewDepositRequest)>; "NewDepositRequest")]
     fn convert_protobuf_type<T, U, E>(_: PhantomData<(T, U)>)
     where
         // `.unwrap()` requires that `E` implement `std::fmt::Debug` and
@@ -87,7 +87,7 @@ pub struct StacksTransactionSignRequest {
     /// The contract transaction to sign.
     #[prost(
         oneof = "stacks_transaction_sign_request::ContractTx",
-        tags = "5, 6, 7, 8, 9"
+        tags = "5, 6, 7, 8, 9, 10, 11"
     )]
     pub contract_tx: ::core::option::Option<stacks_transaction_sign_request::ContractTx>,
 }
@@ -112,6 +112,12 @@ pub mod stacks_transaction_sign_request {
         /// Ssmart contract deployment
         #[prost(enumeration = "super::SmartContract", tag = "9")]
         SmartContract(i32),
+        /// The `complete-deposits-wrapper` contract call
+        #[prost(message, tag = "10")]
+        CompleteDeposits(super::CompleteDeposits),
+        /// The `complete-withdrawals` contract call
+        #[prost(message, tag = "11")]
+        CompleteWithdrawals(super::CompleteWithdrawals),
     }
 }
 /// For making a `complete-deposit` contract call in the sbtc-deposit
@@ -149,6 +155,20 @@ pub struct CompleteDeposit {
     #[prost(uint64, tag = "7")]
     pub sweep_block_height: u64,
 }
+/// For making a `complete-deposits-wrapper` contract call in the
+/// sbtc-deposit smart contract.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompleteDeposits {
+    /// The deposits to complete. Each one must be a valid `complete-deposit`
+    /// contract call on its own.
+    #[prost(message, repeated, tag = "1")]
+    pub deposits: ::prost::alloc::vec::Vec<CompleteDeposit>,
+    /// The address that deployed the sBTC smart contract containing the
+    /// complete-deposits-wrapper contract call.
+    #[prost(message, optional, tag = "2")]
+    pub deployer: ::core::option::Option<super::super::StacksAddress>,
+}
 /// For making a `accept-withdrawal-request` contract call in the
 /// sbtc-withdrawal smart contract.
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -184,6 +204,10 @@ pub struct AcceptWithdrawal {
     /// The block height associated with the above bitcoin block hash.
     #[prost(uint64, tag = "7")]
     pub sweep_block_height: u64,
+    /// The transaction ID of the sweep transaction that fulfilled the
+    /// withdrawal request.
+    #[prost(message, optional, tag = "8")]
+    pub sweep_txid: ::core::option::Option<super::super::super::bitcoin::BitcoinTxid>,
 }
 /// For making a `reject-withdrawal-request` contract call in the
 /// sbtc-withdrawal smart contract.
@@ -204,6 +228,41 @@ pub struct RejectWithdrawal {
     #[prost(message, optional, tag = "3")]
     pub deployer: ::core::option::Option<super::super::StacksAddress>,
 }
+/// A single entry in a `complete-withdrawals` contract call.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithdrawalCompletion {
+    #[prost(oneof = "withdrawal_completion::Completion", tags = "1, 2")]
+    pub completion: ::core::option::Option<withdrawal_completion::Completion>,
+}
+/// Nested message and enum types in `WithdrawalCompletion`.
+pub mod withdrawal_completion {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Completion {
+        /// Accept the withdrawal request.
+        #[prost(message, tag = "1")]
+        Accept(super::AcceptWithdrawal),
+        /// Reject the withdrawal request.
+        #[prost(message, tag = "2")]
+        Reject(super::RejectWithdrawal),
+    }
+}
+/// For making a `complete-withdrawals` contract call in the
+/// sbtc-withdrawal smart contract.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompleteWithdrawals {
+    /// The withdrawals to accept or reject. Each one must be a valid
+    /// `accept-withdrawal-request` or `reject-withdrawal-request` contract
+    /// call on its own.
+    #[prost(message, repeated, tag = "1")]
+    pub withdrawals: ::prost::alloc::vec::Vec<WithdrawalCompletion>,
+    /// The address that deployed the sBTC smart contract containing the
+    /// complete-withdrawals contract call.
+    #[prost(message, optional, tag = "2")]
+    pub deployer: ::core::option::Option<super::super::StacksAddress>,
+}
 /// For making a `rotate-keys-wrapper` contract call in the
 /// `sbtc-bootstrap-signers` smart contract.
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -281,7 +340,7 @@ pub struct SignerMessage {
         super::super::super::bitcoin::BitcoinBlockHash,
     >,
     /// The message payload
-    #[prost(oneof = "signer_message::Payload", tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
+    #[prost(oneof = "signer_message::Payload", tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12")]
     pub payload: ::core::option::Option<signer_message::Payload>,
 }
 /// Nested message and enum types in `SignerMessage`.
@@ -320,8 +379,27 @@ pub mod signer_message {
         /// Represents an acknowledgment of a BitcoinPreSignRequest
         #[prost(message, tag = "11")]
         BitcoinPreSignAck(super::BitcoinPreSignAck),
+        /// A deposit request that a signer has learned about
+        #[prost(message, tag = "12")]
+        NewDepositRequest(super::NewDepositRequest),
     }
 }
+/// A deposit request that a signer has learned about, either from Emily or
+/// from a submission to its API. Signers share these with each other so
+/// that they learn about deposits even when Emily is unavailable.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NewDepositRequest {
+    /// The output index and txid of the depositing transaction.
+    #[prost(message, optional, tag = "1")]
+    pub outpoint: ::core::option::Option<super::super::super::bitcoin::OutPoint>,
+    /// The raw reclaim script.
+    #[prost(bytes = "vec", tag = "2")]
+    pub reclaim_script: ::prost::alloc::vec::Vec<u8>,
+    /// The raw deposit script.
+    #[prost(bytes = "vec", tag = "3")]
+    pub deposit_script: ::prost::alloc::vec::Vec<u8>,
+}
 /// Represents information about a deposit request being swept-in by a sweep transaction.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -413,6 +491,11 @@ pub struct WstsMessage {
     /// The wsts message
     #[prost(oneof = "wsts_message::Inner", tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
     pub inner: ::core::option::Option<wsts_message::Inner>,
+    /// The public keys of the signers taking part in a DKG round. The
+    /// coordinator sets this in `DkgBegin` messages, and it is empty for all
+    /// other messages.
+    #[prost(message, repeated, tag = "12")]
+    pub dkg_participants: ::prost::alloc::vec::Vec<super::super::super::crypto::PublicKey>,
 }
 /// Nested message and enum types in `WstsMessage`.
 pub mod wsts_message {
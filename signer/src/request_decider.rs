@@ -5,8 +5,11 @@
 //!
 //! For more details, see the [`RequestDeciderEventLoop`] documentation.
 
-use crate::block_observer::BlockObserver;
+use std::sync::Arc;
+
+use crate::block_observer::validate_and_store_deposit_request;
 use crate::blocklist_client::BlocklistChecker;
+use crate::config::Settings;
 use crate::context::Context;
 use crate::context::P2PEvent;
 use crate::context::RequestDeciderEvent;
@@ -17,12 +20,15 @@ use crate::ecdsa::SignEcdsa as _;
 use crate::ecdsa::Signed;
 use crate::emily_client::EmilyInteract;
 use crate::error::Error;
-use crate::keys::PrivateKey;
+use crate::key_backend::SignerKeyBackend;
 use crate::keys::PublicKey;
+use crate::message::NewDepositRequest;
 use crate::message::Payload;
 use crate::message::SignerDepositDecision;
 use crate::message::SignerMessage;
 use crate::message::SignerWithdrawalDecision;
+use crate::metrics::metrics;
+use crate::metrics::DecisionLabels;
 use crate::network::MessageTransfer;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockHash;
@@ -33,6 +39,7 @@ use crate::storage::DbWrite as _;
 
 use futures::StreamExt;
 use futures::TryStreamExt;
+use sbtc::deposits::CreateDepositRequest;
 
 /// This struct is responsible for deciding whether to accept or reject
 /// requests and persisting requests from other signers.
@@ -44,8 +51,9 @@ pub struct RequestDeciderEventLoop<C, N, B> {
     pub network: N,
     /// Blocklist checker.
     pub blocklist_checker: Option<B>,
-    /// Private key of the signer for network communication.
-    pub signer_private_key: PrivateKey,
+    /// The backend holding the signer's private key, used to sign the
+    /// messages sent to other signers.
+    pub signer_key: Arc<dyn SignerKeyBackend>,
     /// How many bitcoin blocks back from the chain tip the signer will look for requests.
     pub context_window: u16,
 }
@@ -56,8 +64,10 @@ fn run_loop_message_filter(signal: &SignerSignal) -> bool {
     matches!(
         signal,
         SignerSignal::Command(SignerCommand::Shutdown)
+            | SignerSignal::Command(SignerCommand::ReloadConfig(_))
             | SignerSignal::Event(SignerEvent::P2P(P2PEvent::MessageReceived(_)))
             | SignerSignal::Event(SignerEvent::BitcoinBlockObserved)
+            | SignerSignal::Event(SignerEvent::NewDepositRequestsObserved(_))
     )
 }
 
@@ -86,12 +96,20 @@ where
             match message {
                 SignerSignal::Command(SignerCommand::Shutdown) => break,
                 SignerSignal::Command(SignerCommand::P2PPublish(_)) => {}
+                SignerSignal::Command(SignerCommand::ReloadConfig(settings)) => {
+                    self.reload_config(&settings);
+                }
                 SignerSignal::Event(event) => match event {
                     SignerEvent::P2P(P2PEvent::MessageReceived(msg)) => {
                         if let Err(error) = self.handle_signer_message(&msg).await {
                             tracing::error!(%error, "error handling signer message");
                         }
                     }
+                    SignerEvent::NewDepositRequestsObserved(requests) => {
+                        if let Err(error) = self.broadcast_deposit_requests(requests).await {
+                            tracing::warn!(%error, "error broadcasting new deposit requests");
+                        }
+                    }
                     SignerEvent::BitcoinBlockObserved => {
                         if let Err(error) = self.handle_new_requests().await {
                             tracing::warn!(%error, "error handling new requests; skipping this round");
@@ -116,6 +134,17 @@ where
         Ok(())
     }
 
+    /// Pick up the settings that were changed while the signer is
+    /// running.
+    fn reload_config(&mut self, settings: &Settings) {
+        if let (Some(checker), Some(config)) =
+            (&mut self.blocklist_checker, &settings.blocklist_client)
+        {
+            checker.reload_config(config);
+        }
+        tracing::debug!("reloaded the configuration");
+    }
+
     #[tracing::instrument(skip_all, fields(chain_tip = tracing::field::Empty))]
     async fn handle_new_requests(&mut self) -> Result<(), Error> {
         let db = self.context.get_storage();
@@ -168,18 +197,46 @@ where
         Ok(())
     }
 
+    /// Share the deposit requests that our block observer learned about
+    /// with the other signers, so that they do not need to rely on their
+    /// own Emily to know about them.
+    #[tracing::instrument(skip_all)]
+    async fn broadcast_deposit_requests(
+        &mut self,
+        requests: Vec<NewDepositRequest>,
+    ) -> Result<(), Error> {
+        let chain_tip = self
+            .context
+            .get_storage()
+            .get_bitcoin_canonical_chain_tip()
+            .await?
+            .ok_or(Error::NoChainTip)?;
+
+        for request in requests {
+            tracing::debug!(outpoint = %request.outpoint, "broadcasting new deposit request");
+            self.send_message(request, &chain_tip).await?;
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all)]
     async fn handle_signer_message(&mut self, msg: &Signed<SignerMessage>) -> Result<(), Error> {
         tracing::trace!(payload = %msg.inner.payload, "handling message");
         match &msg.inner.payload {
             Payload::SignerDepositDecision(decision) => {
+                record_decision("deposit", "received");
                 self.persist_received_deposit_decision(decision, msg.signer_public_key)
                     .await?;
             }
             Payload::SignerWithdrawalDecision(decision) => {
+                record_decision("withdrawal", "received");
                 self.persist_received_withdraw_decision(decision, msg.signer_public_key)
                     .await?;
             }
+            Payload::NewDepositRequest(request) => {
+                self.persist_received_deposit_request(request).await?;
+            }
             Payload::StacksTransactionSignRequest(_)
             | Payload::BitcoinTransactionSignRequest(_)
             | Payload::BitcoinPreSignRequest(_)
@@ -244,6 +301,7 @@ where
         db.write_deposit_signer_decision(&signer_decision).await?;
 
         self.send_message(msg, chain_tip).await?;
+        record_decision("deposit", "sent");
 
         self.context
             .signal(RequestDeciderEvent::PendingDepositRequestRegistered.into())?;
@@ -284,6 +342,7 @@ where
             .await?;
 
         self.send_message(msg, chain_tip).await?;
+        record_decision("withdrawal", "sent");
 
         self.context
             .signal(RequestDeciderEvent::PendingWithdrawalRequestRegistered.into())?;
@@ -339,6 +398,39 @@ where
         Ok(can_accept)
     }
 
+    /// Validate a deposit request received from another signer and
+    /// store it if it passes validation.
+    #[tracing::instrument(skip_all)]
+    pub async fn persist_received_deposit_request(
+        &mut self,
+        request: &NewDepositRequest,
+    ) -> Result<(), Error> {
+        let txid = request.outpoint.txid.into();
+        let output_index = request.outpoint.vout;
+
+        // Gossipsub already relays the message to the other signers, so
+        // all that is left is to check whether we already know about the
+        // request and, if not, validate and store it like we do for the
+        // ones that we get from Emily.
+        if self
+            .context
+            .get_storage()
+            .deposit_request_exists(&txid, output_index)
+            .await?
+        {
+            return Ok(());
+        }
+
+        tracing::debug!(outpoint = %request.outpoint, "received new deposit request from a signer");
+        let request = CreateDepositRequest::from(request.clone());
+        // A deposit request that fails validation, or that is not
+        // confirmed yet, is not an error in the request decider.
+        if let Err(error) = validate_and_store_deposit_request(&self.context, &request).await {
+            tracing::warn!(%error, "could not validate deposit request");
+        }
+        Ok(())
+    }
+
     /// Save the given decision into the database
     ///
     /// If we do not have a record of the associated deposit request in our
@@ -367,11 +459,6 @@ where
         // storing the decision.
         if !db.deposit_request_exists(&txid, output_index).await? {
             tracing::debug!("no record of the deposit request, fetching from emily");
-            let processor = BlockObserver {
-                context: self.context.clone(),
-                horizon: 20,
-                bitcoin_blocks: (),
-            };
             let deposit_request = self
                 .context
                 .get_emily_client()
@@ -379,7 +466,10 @@ where
                 .await?;
 
             if let Some(request) = deposit_request {
-                processor.load_requests(&[request]).await?;
+                let result = validate_and_store_deposit_request(&self.context, &request).await;
+                if let Err(error) = result {
+                    tracing::warn!(%error, "could not validate deposit request");
+                }
             }
         }
         // We still might not have a record of the deposit request (perhaps
@@ -439,7 +529,7 @@ where
         let payload: Payload = msg.into();
         let msg = payload
             .to_message(*chain_tip)
-            .sign_ecdsa(&self.signer_private_key);
+            .sign_ecdsa_with(&*self.signer_key)?;
 
         self.network.broadcast(msg).await?;
 
@@ -447,19 +537,40 @@ where
     }
 
     fn signer_public_key(&self) -> PublicKey {
-        PublicKey::from_private_key(&self.signer_private_key)
+        self.signer_key.public_key()
     }
 }
 
+/// Count a decision on a deposit or withdrawal request.
+fn record_decision(request: &'static str, direction: &'static str) {
+    metrics()
+        .decisions
+        .get_or_create(&DecisionLabels { request, direction })
+        .inc();
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use rand::SeedableRng as _;
+    use test_case::test_case;
+
+    use crate::bitcoin::rpc::GetTxResponse;
     use crate::bitcoin::MockBitcoinInteract;
     use crate::emily_client::MockEmilyInteract;
+    use crate::keys::PrivateKey;
+    use crate::message::NewDepositRequest;
+    use crate::network::in_memory2::SignerNetwork;
     use crate::stacks::api::MockStacksInteract;
+    use crate::storage;
     use crate::storage::in_memory::SharedStore;
     use crate::testing;
+    use crate::testing::block_observer::TestHarness;
     use crate::testing::context::*;
 
+    use super::RequestDeciderEventLoop;
+
     fn test_environment() -> testing::request_decider::TestEnvironment<
         TestContext<
             SharedStore,
@@ -510,4 +621,84 @@ mod tests {
             .assert_should_store_decisions_received_from_other_signers()
             .await;
     }
+
+    /// How the deposit transaction of a gossiped deposit request looks to
+    /// bitcoin-core.
+    enum GossipedDeposit {
+        Valid,
+        Invalid,
+        Unconfirmed,
+    }
+
+    /// Test that `RequestDeciderEventLoop::persist_received_deposit_request`
+    /// only stores deposit requests received from other signers if they
+    /// pass validation.
+    #[test_case(GossipedDeposit::Valid, true; "valid deposits are stored")]
+    #[test_case(GossipedDeposit::Invalid, false; "invalid deposits are not stored")]
+    #[test_case(GossipedDeposit::Unconfirmed, false; "unconfirmed deposits are not stored")]
+    #[tokio::test]
+    async fn persist_received_deposit_request_validates_the_request(
+        deposit: GossipedDeposit,
+        is_stored: bool,
+    ) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+        let mut test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+        let block_hash = test_harness
+            .bitcoin_blocks()
+            .first()
+            .map(|block| block.block_hash());
+
+        let setup = sbtc::testing::deposits::tx_setup(150, 32000, 500_000);
+        let other_setup = sbtc::testing::deposits::tx_setup(300, 2000, 500_000);
+        let mut request = NewDepositRequest {
+            outpoint: bitcoin::OutPoint {
+                txid: setup.tx.compute_txid(),
+                vout: 0,
+            },
+            deposit_script: setup.deposit.deposit_script(),
+            reclaim_script: setup.reclaim.reclaim_script(),
+        };
+        let block_hash = match deposit {
+            GossipedDeposit::Valid => block_hash,
+            // The deposit script does not match the one locking the
+            // deposit output.
+            GossipedDeposit::Invalid => {
+                request.deposit_script = other_setup.deposit.deposit_script();
+                block_hash
+            }
+            GossipedDeposit::Unconfirmed => None,
+        };
+        let response = GetTxResponse {
+            tx: setup.tx.clone(),
+            block_hash,
+            confirmations: None,
+            block_time: None,
+        };
+        test_harness.add_deposit(setup.tx.compute_txid(), response);
+
+        let storage = storage::in_memory::Store::new_shared();
+        let ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_stacks_client(test_harness.clone())
+            .with_emily_client(test_harness.clone())
+            .with_bitcoin_client(test_harness.clone())
+            .build();
+
+        let mut request_decider = RequestDeciderEventLoop {
+            context: ctx.clone(),
+            network: SignerNetwork::single(&ctx).spawn(),
+            blocklist_checker: Some(()),
+            signer_key: Arc::new(PrivateKey::new(&mut rng)),
+            context_window: 6,
+        };
+
+        request_decider
+            .persist_received_deposit_request(&request)
+            .await
+            .unwrap();
+
+        let db_outpoint = (request.outpoint.txid.into(), request.outpoint.vout);
+        let db = storage.lock().await;
+        assert_eq!(db.deposit_requests.contains_key(&db_outpoint), is_stored);
+    }
 }
@@ -11,6 +11,7 @@ use secp256k1::ecdsa::RecoverableSignature;
 use serde::Deserialize;
 
 use crate::error::Error;
+use crate::key_backend::SignerKeyBackend;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
 
@@ -156,6 +157,19 @@ pub fn sign_stacks_tx(tx: &StacksTransaction, private_key: &PrivateKey) -> Recov
     private_key.sign_ecdsa_recoverable(&msg)
 }
 
+/// Generate a signature for the transaction using the key held by the
+/// given backend, in the same way as [`sign_stacks_tx`].
+pub fn sign_stacks_tx_with<K>(
+    tx: &StacksTransaction,
+    key: &K,
+) -> Result<RecoverableSignature, Error>
+where
+    K: SignerKeyBackend + ?Sized,
+{
+    let msg = secp256k1::Message::from_digest(tx.digest());
+    key.sign_ecdsa_recoverable(&msg)
+}
+
 /// A module for Serialize and Deserialize implementations of the
 /// [`RecoverableSignature`] type
 pub mod serde_utils {
@@ -140,6 +140,17 @@ pub trait StacksInteract: Send + Sync {
         contract_principal: &StacksAddress,
     ) -> impl Future<Output = Result<Option<PublicKey>, Error>> + Send;
 
+    /// Retrieve the current signature threshold of the signers' multi-sig
+    /// wallet from the `sbtc-registry` contract. Returns None if the
+    /// threshold has not been set yet.
+    ///
+    /// This is done by making a `GET /v2/data_var/<contract-principal>/sbtc-registry/current-signature-threshold`
+    /// request.
+    fn get_current_signature_threshold(
+        &self,
+        contract_principal: &StacksAddress,
+    ) -> impl Future<Output = Result<Option<u16>, Error>> + Send;
+
     /// Get the latest account info for the given address.
     fn get_account(
         &self,
@@ -1136,6 +1147,31 @@ impl StacksInteract for StacksClient {
         }
     }
 
+    async fn get_current_signature_threshold(
+        &self,
+        contract_principal: &StacksAddress,
+    ) -> Result<Option<u16>, Error> {
+        let result = self
+            .get_data_var(
+                contract_principal,
+                &ContractName::from("sbtc-registry"),
+                &ClarityName::from("current-signature-threshold"),
+            )
+            .await?;
+
+        // The initial value of the data var is zero, which means that the
+        // signers have not rotated keys yet.
+        match result {
+            Value::UInt(0) => Ok(None),
+            Value::UInt(threshold) => u16::try_from(threshold)
+                .map(Some)
+                .map_err(|_| Error::InvalidStacksResponse("signature threshold is too large")),
+            _ => Err(Error::InvalidStacksResponse(
+                "expected a uint but got something else",
+            )),
+        }
+    }
+
     async fn get_account(&self, address: &StacksAddress) -> Result<AccountInfo, Error> {
         self.get_account(address).await
     }
@@ -1331,6 +1367,20 @@ impl StacksInteract for ApiFallbackClient<StacksClient> {
         .await
     }
 
+    async fn get_current_signature_threshold(
+        &self,
+        contract_principal: &StacksAddress,
+    ) -> Result<Option<u16>, Error> {
+        self.exec(|client, retry| async move {
+            let result = client
+                .get_current_signature_threshold(contract_principal)
+                .await;
+            retry.abort_if(|| matches!(result, Err(Error::InvalidStacksResponse(_))));
+            result
+        })
+        .await
+    }
+
     async fn get_account(&self, address: &StacksAddress) -> Result<AccountInfo, Error> {
         self.exec(|client, _| client.get_account(address)).await
     }
@@ -1843,6 +1893,54 @@ mod tests {
         mock.assert();
     }
 
+    #[test_case(|url| StacksClient::new(url, 20).unwrap(), 0, None; "stacks-client-unset")]
+    #[test_case(|url| StacksClient::new(url, 20).unwrap(), 11, Some(11); "stacks-client-set")]
+    #[test_case(|url| ApiFallbackClient::new(vec![StacksClient::new(url, 20).unwrap()]).unwrap(), 0, None; "fallback-client-unset")]
+    #[test_case(|url| ApiFallbackClient::new(vec![StacksClient::new(url, 20).unwrap()]).unwrap(), 11, Some(11); "fallback-client-set")]
+    #[tokio::test]
+    async fn get_current_signature_threshold_works<F, C>(
+        client: F,
+        threshold: u128,
+        expected: Option<u16>,
+    ) where
+        C: StacksInteract,
+        F: Fn(Url) -> C,
+    {
+        let threshold_clarity = Value::UInt(threshold);
+
+        // The format of the response JSON is `{"data": "0x<serialized-value>"}` (excluding the proof).
+        let raw_json_response = format!(
+            r#"{{"data":"0x{}"}}"#,
+            Value::serialize_to_hex(&threshold_clarity).expect("failed to serialize value")
+        );
+
+        // Setup our mock server
+        let mut stacks_node_server = mockito::Server::new_async().await;
+        let mock = stacks_node_server
+            .mock("GET", "/v2/data_var/ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM/sbtc-registry/current-signature-threshold?proof=0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(&raw_json_response)
+            .expect(1)
+            .create();
+
+        // Setup our Stacks client
+        let client = client(url::Url::parse(stacks_node_server.url().as_str()).unwrap());
+
+        // Make the request to the mock server
+        let resp = client
+            .get_current_signature_threshold(
+                &StacksAddress::from_string("ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM")
+                    .expect("failed to parse stacks address"),
+            )
+            .await
+            .unwrap();
+
+        // Assert that the response is what we expect
+        assert_eq!(resp, expected);
+        mock.assert();
+    }
+
     #[test_case(0; "empty-list")]
     #[test_case(128; "list-128")]
     #[tokio::test]
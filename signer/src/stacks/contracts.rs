@@ -5,6 +5,9 @@
 //! * [`CompleteDepositV1`]: Used for calling the complete-deposit-wrapper
 //!   function in the sbtc-deposit contract. This finalizes the deposit by
 //!   minting sBTC and sending it to the depositor.
+//! * [`CompleteDepositsV1`]: Used for calling the
+//!   complete-deposits-wrapper function in the sbtc-deposit contract.
+//!   This finalizes many deposits in a single transaction.
 //! * [`AcceptWithdrawalV1`]: Used for calling the
 //!   accept-withdrawal-request function in the sbtc-withdrawal contract.
 //!   This finalizes the withdrawal request by burning the locked sBTC.
@@ -12,11 +15,15 @@
 //!   reject-withdrawal-request function in the sbtc-withdrawal contract.
 //!   This finalizes the withdrawal request by returning the locked sBTC to
 //!   the requester.
+//! * [`CompleteWithdrawalsV1`]: Used for calling the complete-withdrawals
+//!   function in the sbtc-withdrawal contract. This accepts or rejects
+//!   many withdrawal requests in a single transaction.
 //! * [`RotateKeysV1`]: Used for calling the rotate-keys-wrapper function
 //!   in the sbtc-bootstrap-signers contract. This changes the valid caller
 //!   of most sBTC related functions to a new multi-sig wallet.
 
 use std::collections::BTreeSet;
+use std::collections::HashSet;
 use std::future::Future;
 use std::ops::Deref;
 use std::sync::OnceLock;
@@ -27,6 +34,8 @@ use bitcoin::OutPoint;
 use bitcoin::TxOut;
 use bitvec::array::BitArray;
 use bitvec::field::BitField as _;
+use blockstack_lib::chainstate::stacks::FungibleConditionCode;
+use blockstack_lib::chainstate::stacks::PostConditionPrincipal;
 use blockstack_lib::chainstate::stacks::TransactionContractCall;
 use blockstack_lib::chainstate::stacks::TransactionPayload;
 use blockstack_lib::chainstate::stacks::TransactionPostCondition;
@@ -37,6 +46,10 @@ use blockstack_lib::clarity::vm::types::ListData;
 use blockstack_lib::clarity::vm::types::ListTypeData;
 use blockstack_lib::clarity::vm::types::PrincipalData;
 use blockstack_lib::clarity::vm::types::SequenceData;
+use blockstack_lib::clarity::vm::types::TupleData;
+use blockstack_lib::clarity::vm::types::TupleTypeSignature;
+use blockstack_lib::clarity::vm::types::TypeSignature;
+use blockstack_lib::clarity::vm::types::BUFF_32;
 use blockstack_lib::clarity::vm::types::BUFF_33;
 use blockstack_lib::clarity::vm::ClarityName;
 use blockstack_lib::clarity::vm::ContractName;
@@ -54,7 +67,9 @@ use crate::stacks::wallet::SignerWallet;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::BitcoinTxId;
+use crate::storage::model::EncryptedDkgShares;
 use crate::storage::DbRead;
+use crate::WITHDRAWAL_BLOCKS_EXPIRY;
 
 use super::api::StacksInteract;
 
@@ -236,12 +251,18 @@ pub enum ContractCall {
     /// Call the `complete-deposit-wrapper` function in the `sbtc-deposit`
     /// smart contract
     CompleteDepositV1(CompleteDepositV1),
+    /// Call the `complete-deposits-wrapper` function in the
+    /// `sbtc-deposit` smart contract
+    CompleteDepositsV1(CompleteDepositsV1),
     /// Call the `accept-withdrawal-request` function in the
     /// `sbtc-withdrawal` smart contract.
     AcceptWithdrawalV1(AcceptWithdrawalV1),
     /// Call the `reject-withdrawal-request` function in the
     /// `sbtc-withdrawal` smart contract.
     RejectWithdrawalV1(RejectWithdrawalV1),
+    /// Call the `complete-withdrawals` function in the `sbtc-withdrawal`
+    /// smart contract.
+    CompleteWithdrawalsV1(CompleteWithdrawalsV1),
     /// Call the `rotate-keys-wrapper` function in the
     /// `sbtc-bootstrap-signers` smart contract.
     RotateKeysV1(RotateKeysV1),
@@ -252,6 +273,8 @@ impl AsTxPayload for ContractCall {
         match self {
             ContractCall::AcceptWithdrawalV1(contract) => contract.tx_payload(),
             ContractCall::CompleteDepositV1(contract) => contract.tx_payload(),
+            ContractCall::CompleteDepositsV1(contract) => contract.tx_payload(),
+            ContractCall::CompleteWithdrawalsV1(contract) => contract.tx_payload(),
             ContractCall::RejectWithdrawalV1(contract) => contract.tx_payload(),
             ContractCall::RotateKeysV1(contract) => contract.tx_payload(),
         }
@@ -260,6 +283,10 @@ impl AsTxPayload for ContractCall {
         match self {
             ContractCall::AcceptWithdrawalV1(contract) => AsContractCall::post_conditions(contract),
             ContractCall::CompleteDepositV1(contract) => AsContractCall::post_conditions(contract),
+            ContractCall::CompleteDepositsV1(contract) => AsContractCall::post_conditions(contract),
+            ContractCall::CompleteWithdrawalsV1(contract) => {
+                AsContractCall::post_conditions(contract)
+            }
             ContractCall::RejectWithdrawalV1(contract) => AsContractCall::post_conditions(contract),
             ContractCall::RotateKeysV1(contract) => AsContractCall::post_conditions(contract),
         }
@@ -601,6 +628,253 @@ impl DepositErrorMsg {
     }
 }
 
+/// This struct is used to generate a properly formatted Stacks transaction
+/// for calling the complete-deposits-wrapper function in the sbtc-deposit
+/// smart contract.
+///
+/// This bundles many `complete-deposit-wrapper` calls into a single
+/// contract call, so that the signers only need one signing round for all
+/// deposits swept in by a sweep transaction.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct CompleteDepositsV1 {
+    /// The deposits to complete. Each one of these must be a valid
+    /// `complete-deposit-wrapper` contract call on its own.
+    pub deposits: Vec<CompleteDepositV1>,
+    /// The address that deployed the contract.
+    pub deployer: StacksAddress,
+}
+
+impl CompleteDepositsV1 {
+    /// The maximum number of deposits that can be completed in one
+    /// contract call. This matches the length of the list argument to the
+    /// `complete-deposits-wrapper` clarity function.
+    pub const MAX_DEPOSITS: usize = 650;
+
+    /// This function returns the clarity description of the input to the
+    /// contract call.
+    ///
+    /// # Notes
+    ///
+    /// The input, deposits, is a (list 650 {txid: (buff 32), vout-index:
+    /// uint, amount: uint, recipient: principal, burn-hash: (buff 32),
+    /// burn-height: uint, sweep-txid: (buff 32)}). This function
+    /// represents this data type.
+    fn list_data_type() -> &'static ListTypeData {
+        static DEPOSITS_ARGUMENT_DATA_TYPE: OnceLock<ListTypeData> = OnceLock::new();
+        DEPOSITS_ARGUMENT_DATA_TYPE.get_or_init(|| {
+            let tuple_type = TupleTypeSignature::try_from(vec![
+                (ClarityName::from("txid"), BUFF_32.clone()),
+                (ClarityName::from("vout-index"), TypeSignature::UIntType),
+                (ClarityName::from("amount"), TypeSignature::UIntType),
+                (ClarityName::from("recipient"), TypeSignature::PrincipalType),
+                (ClarityName::from("burn-hash"), BUFF_32.clone()),
+                (ClarityName::from("burn-height"), TypeSignature::UIntType),
+                (ClarityName::from("sweep-txid"), BUFF_32.clone()),
+            ])
+            .expect("Error: legal TupleTypeSignature marked as invalid");
+            // A Result::Err is returned whenever the "depth" of the type
+            // is too large or if the maximum size of an input with the
+            // given type is too large. None of this is true for us, the
+            // depth is 2 and the size is well under the limit of 1 MB.
+            let tuple_type = TypeSignature::TupleType(tuple_type);
+            ListTypeData::new_list(tuple_type, Self::MAX_DEPOSITS as u32)
+                .expect("Error: legal ListTypeData marked as invalid")
+        })
+    }
+
+    /// Convert one of the deposits into the tuple that the
+    /// `complete-deposits-wrapper` function expects.
+    fn as_tuple(deposit: &CompleteDepositV1) -> ClarityValue {
+        // The arguments to the complete-deposit-wrapper function are in
+        // the same order as the fields of the tuple.
+        let names = [
+            "txid",
+            "vout-index",
+            "amount",
+            "recipient",
+            "burn-hash",
+            "burn-height",
+            "sweep-txid",
+        ];
+        let data = names
+            .into_iter()
+            .map(ClarityName::from)
+            .zip(deposit.as_contract_args())
+            .collect();
+        // This only errors if there are duplicate names or if the tuple
+        // is too large, neither of which can happen here.
+        let tuple = TupleData::from_data(data).expect("Error: legal TupleData marked as invalid");
+        ClarityValue::Tuple(tuple)
+    }
+
+    /// Validate each of the deposits on its own and remove the ones that
+    /// fail validation, returning them along with their validation error.
+    ///
+    /// The `complete-deposits-wrapper` contract call fails as a whole,
+    /// both during validation and on chain, if any one of its deposits is
+    /// invalid, so one bad deposit would hold up all the others in the
+    /// batch. Errors other than deposit validation errors are returned.
+    pub async fn remove_invalid_deposits<C>(
+        &mut self,
+        ctx: &C,
+        req_ctx: &ReqContext,
+    ) -> Result<Vec<(CompleteDepositV1, Error)>, Error>
+    where
+        C: Context + Send + Sync,
+    {
+        let mut invalid = Vec::new();
+        for deposit in self.deposits.iter() {
+            match deposit.validate(ctx, req_ctx).await {
+                Ok(()) => {}
+                Err(error @ Error::DepositValidation(_)) => invalid.push((deposit.clone(), error)),
+                Err(error) => return Err(error),
+            }
+        }
+
+        self.deposits
+            .retain(|deposit| !invalid.iter().any(|(dep, _)| dep == deposit));
+        Ok(invalid)
+    }
+}
+
+impl AsTxPayload for CompleteDepositsV1 {
+    fn tx_payload(&self) -> TransactionPayload {
+        TransactionPayload::ContractCall(self.as_contract_call())
+    }
+    fn post_conditions(&self) -> StacksTxPostConditions {
+        AsContractCall::post_conditions(self)
+    }
+}
+
+impl AsContractCall for CompleteDepositsV1 {
+    const CONTRACT_NAME: &'static str = "sbtc-deposit";
+    const FUNCTION_NAME: &'static str = "complete-deposits-wrapper";
+
+    fn deployer_address(&self) -> StacksAddress {
+        self.deployer
+    }
+    /// Construct the input arguments to the complete-deposits-wrapper
+    /// contract call.
+    fn as_contract_args(&self) -> Vec<ClarityValue> {
+        let deposits = ListData {
+            data: self.deposits.iter().map(Self::as_tuple).collect(),
+            type_signature: Self::list_data_type().clone(),
+        };
+
+        vec![ClarityValue::Sequence(SequenceData::List(deposits))]
+    }
+    /// Validates that the complete-deposits-wrapper satisfies the
+    /// following criteria:
+    ///
+    /// 1. That there is at least one deposit, and no more than
+    ///    [`CompleteDepositsV1::MAX_DEPOSITS`] deposits.
+    /// 2. That the smart contract deployer matches the deployer in our
+    ///    context, and the deployer in each of the deposits.
+    /// 3. That each deposit outpoint appears only once.
+    /// 4. That each deposit passes the validation in
+    ///    [`CompleteDepositV1::validate`].
+    async fn validate<C>(&self, ctx: &C, req_ctx: &ReqContext) -> Result<(), Error>
+    where
+        C: Context + Send + Sync,
+    {
+        // 1. That there is at least one deposit, and no more than
+        //    MAX_DEPOSITS deposits.
+        if self.deposits.is_empty() {
+            return Err(CompleteDepositsErrorMsg::NoDeposits.into_error(req_ctx, self));
+        }
+        if self.deposits.len() > Self::MAX_DEPOSITS {
+            return Err(CompleteDepositsErrorMsg::TooManyDeposits.into_error(req_ctx, self));
+        }
+        // 2. That the smart contract deployer matches the deployer in our
+        //    context, and the deployer in each of the deposits.
+        if self.deployer != req_ctx.deployer {
+            return Err(CompleteDepositsErrorMsg::DeployerMismatch.into_error(req_ctx, self));
+        }
+        if self
+            .deposits
+            .iter()
+            .any(|dep| dep.deployer != self.deployer)
+        {
+            return Err(CompleteDepositsErrorMsg::DeployerMismatch.into_error(req_ctx, self));
+        }
+        // 3. That each deposit outpoint appears only once.
+        //
+        // The contract will not mint twice for the same deposit, but the
+        // whole transaction would fail if we included a duplicate.
+        let mut outpoints = HashSet::with_capacity(self.deposits.len());
+        if !self
+            .deposits
+            .iter()
+            .all(|dep| outpoints.insert(dep.outpoint))
+        {
+            return Err(CompleteDepositsErrorMsg::DuplicateDeposit.into_error(req_ctx, self));
+        }
+        // 4. That each deposit passes the validation of a single
+        //    complete-deposit-wrapper contract call.
+        for deposit in self.deposits.iter() {
+            deposit.validate(ctx, req_ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A struct for a validation error containing all the necessary context.
+#[derive(Debug)]
+pub struct CompleteDepositsValidationError {
+    /// The specific error that happened during validation.
+    pub error: CompleteDepositsErrorMsg,
+    /// The additional information that was used when trying to validate
+    /// the `complete-deposits-wrapper` contract call. This includes the
+    /// public key of the signer that was attempting to generate the
+    /// `complete-deposits-wrapper` transaction.
+    pub context: ReqContext,
+    /// The specific transaction that was being validated.
+    pub tx: CompleteDepositsV1,
+}
+
+impl std::fmt::Display for CompleteDepositsValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // TODO(191): Add the other variables to the error message.
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for CompleteDepositsValidationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The responses for validation of a complete-deposits-wrapper smart
+/// contract call transaction. Failures of the individual deposits are
+/// reported using [`DepositErrorMsg`].
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CompleteDepositsErrorMsg {
+    /// The smart contract deployer is fixed, so this should always match.
+    #[error("the deployer in the transaction does not match the expected deployer")]
+    DeployerMismatch,
+    /// Each deposit can only be completed once.
+    #[error("the same deposit outpoint appears more than once in the transaction")]
+    DuplicateDeposit,
+    /// There must be at least one deposit in the transaction.
+    #[error("the transaction does not include any deposits")]
+    NoDeposits,
+    /// The contract only accepts a bounded list of deposits.
+    #[error("the transaction includes more deposits than the contract accepts")]
+    TooManyDeposits,
+}
+
+impl CompleteDepositsErrorMsg {
+    fn into_error(self, ctx: &ReqContext, tx: &CompleteDepositsV1) -> Error {
+        Error::CompleteDepositsValidation(Box::new(CompleteDepositsValidationError {
+            error: self,
+            context: *ctx,
+            tx: tx.clone(),
+        }))
+    }
+}
+
 /// This struct is used to generate a properly formatted Stacks transaction
 /// for calling the accept-withdrawal-request function in the
 /// sbtc-withdrawal smart contract.
@@ -632,6 +906,10 @@ pub struct AcceptWithdrawalV1 {
     pub sweep_block_hash: BitcoinBlockHash,
     /// The block height associated with the above bitcoin block hash.
     pub sweep_block_height: u64,
+    /// The transaction ID of the sweep transaction that fulfilled the
+    /// withdrawal request. This must be the transaction ID in the above
+    /// `outpoint`.
+    pub sweep_txid: BitcoinTxId,
 }
 
 impl AsTxPayload for AcceptWithdrawalV1 {
@@ -659,6 +937,9 @@ impl AsContractCall for AcceptWithdrawalV1 {
         let burn_hash = BurnchainHeaderHash::from(self.sweep_block_hash);
         let burn_hash_data = burn_hash.into_bytes().to_vec();
         let burn_hash_buff = BuffData { data: burn_hash_data };
+        let sweep_txid = BuffData {
+            data: self.sweep_txid.to_byte_array().to_vec(),
+        };
 
         vec![
             ClarityValue::UInt(self.request_id as u128),
@@ -668,6 +949,7 @@ impl AsContractCall for AcceptWithdrawalV1 {
             ClarityValue::UInt(self.tx_fee as u128),
             ClarityValue::Sequence(SequenceData::Buffer(burn_hash_buff)),
             ClarityValue::UInt(self.sweep_block_height as u128),
+            ClarityValue::Sequence(SequenceData::Buffer(sweep_txid)),
         ]
     }
     /// Validates that the accept-withdrawal-request satisfies the
@@ -680,6 +962,7 @@ impl AsContractCall for AcceptWithdrawalV1 {
     ///  3. That the signer bitcoin transaction sweeping out the users'
     ///     funds is on the canonical bitcoin blockchain.
     ///  4. That the sweep transaction has the UTXO indicated by the
+    ///     `outpoint`, and that the `sweep_txid` is the txid of the
     ///     `outpoint`.
     ///  5. The `scriptPubKey` of the UTXO matches the one in the
     ///     withdrawal request.
@@ -786,6 +1069,7 @@ impl AcceptWithdrawalV1 {
     /// 3. That the signer bitcoin transaction sweeping out the users'
     ///    funds is on the canonical bitcoin blockchain.
     /// 4. That the sweep transaction has the UTXO indicated by the
+    ///    outpoint, and that the `sweep_txid` is the txid of the
     ///    outpoint.
     /// 8. That the fee matches the expected assessed fee for the output.
     /// 9. That the first input into the sweep transaction is the signers'
@@ -799,6 +1083,9 @@ impl AcceptWithdrawalV1 {
         // First we check that bitcoin-core has a record of the transaction
         // where we think it should be.
         let txid = &self.outpoint.txid;
+        if self.sweep_txid != BitcoinTxId::from(*txid) {
+            return Err(WithdrawalErrorMsg::SweepTxidMismatch.into_error(req_ctx, self));
+        }
         let Some(sweep_tx) = rpc.get_tx_info(txid, &self.sweep_block_hash).await? else {
             return Err(WithdrawalErrorMsg::SweepTransactionMissing.into_error(req_ctx, self));
         };
@@ -931,6 +1218,10 @@ pub enum WithdrawalErrorMsg {
     /// transaction now will likely lead to a failed stacks transaction.
     #[error("sweep transaction has been affected by a reorg")]
     SweepTransactionReorged,
+    /// The sweep transaction ID must be the transaction ID in the
+    /// withdrawal outpoint.
+    #[error("the sweep txid does not match the txid of the withdrawal outpoint")]
+    SweepTxidMismatch,
     /// The withdrawal outpoint is missing from the indicated sweep
     /// transaction.
     #[error("withdrawal outpoint is missing from the indicated sweep transaction")]
@@ -989,17 +1280,599 @@ impl AsContractCall for RejectWithdrawalV1 {
     /// Validates that the reject-withdrawal-request satisfies the
     /// following criteria:
     ///
-    /// 1. That the transaction with the associated request_id is stored as
-    ///    an event on the canonical Stacks blockchain.
-    /// 2. That the signer bitmap matches the signer decisions stored in
+    /// 1. That the smart contract deployer matches the deployer in our
+    ///    context.
+    /// 2. That the signer has a record of the withdrawal request on the
+    ///    canonical Stacks blockchain, and that the request has not been
+    ///    fulfilled by a sweep transaction confirmed on the canonical
+    ///    bitcoin blockchain or already responded to.
+    /// 3. That the signer bitmap matches the signer decisions stored in
     ///    this signer's database.
-    async fn validate<C>(&self, _ctx: &C, _req_ctx: &ReqContext) -> Result<(), Error>
+    /// 4. That the withdrawal request has either expired relative to the
+    ///    bitcoin chain tip, or that enough signers have rejected the
+    ///    request so that it cannot be accepted.
+    /// 5. That there is no sweep transaction fulfilling the withdrawal
+    ///    request in the mempool.
+    async fn validate<C>(&self, ctx: &C, req_ctx: &ReqContext) -> Result<(), Error>
     where
         C: Context + Send + Sync,
     {
-        // TODO(255): Add validation implementation
+        // Covers points 1-4
+        self.validate_request(ctx, req_ctx).await?;
+        // Covers point 5
+        self.validate_no_pending_sweep(ctx, req_ctx).await
+    }
+}
+
+impl RejectWithdrawalV1 {
+    /// Validate the variables in this transaction against the withdrawal
+    /// request and the votes in this signer's database.
+    ///
+    /// Specifically, this function checks the following points (from the
+    /// docs of [`RejectWithdrawalV1::validate`]):
+    /// 1. That the smart contract deployer matches the deployer in our
+    ///    context.
+    /// 2. That the signer has a record of the withdrawal request on the
+    ///    canonical Stacks blockchain, and that the request has not been
+    ///    fulfilled by a sweep transaction confirmed on the canonical
+    ///    bitcoin blockchain or already responded to.
+    /// 3. That the signer bitmap matches the signer decisions stored in
+    ///    this signer's database.
+    /// 4. That the withdrawal request has either expired relative to the
+    ///    bitcoin chain tip, or that enough signers have rejected the
+    ///    request so that it cannot be accepted.
+    async fn validate_request<C>(&self, ctx: &C, req_ctx: &ReqContext) -> Result<(), Error>
+    where
+        C: Context + Send + Sync,
+    {
+        let db = ctx.get_storage();
+        // 1. That the smart contract deployer matches the deployer in our
+        //    context.
+        if self.deployer != req_ctx.deployer {
+            return Err(WithdrawalRejectErrorMsg::DeployerMismatch.into_error(req_ctx, self));
+        }
+        // 2. That the signer has a record of the withdrawal request on the
+        //    canonical Stacks blockchain, and that the request has not
+        //    been fulfilled or responded to.
+        let chain_tip = &req_ctx.chain_tip.block_hash;
+        let maybe_request = db
+            .get_unfulfilled_withdrawal_request(chain_tip, req_ctx.context_window, self.request_id)
+            .await?;
+
+        let Some(request) = maybe_request else {
+            // The request is either unknown to us or has been fulfilled.
+            // Let's see if it was fulfilled so that the error is more
+            // informative.
+            let is_swept = db
+                .get_swept_withdrawal_request(chain_tip, req_ctx.context_window, self.request_id)
+                .await?
+                .is_some();
+            let msg = if is_swept {
+                WithdrawalRejectErrorMsg::RequestFulfilled
+            } else {
+                WithdrawalRejectErrorMsg::RequestMissing
+            };
+            return Err(msg.into_error(req_ctx, self));
+        };
+
+        // 3. That the signer bitmap matches the signer decisions stored
+        //    in this signer's database.
+        let votes = db
+            .get_withdrawal_request_signer_votes(&request.qualified_id(), &req_ctx.aggregate_key)
+            .await?;
+        let max_accepts = votes.len().saturating_sub(votes.num_rejections());
+
+        if self.signer_bitmap != BitArray::from(votes) {
+            return Err(WithdrawalRejectErrorMsg::BitmapMismatch.into_error(req_ctx, self));
+        }
+
+        // 4. That the withdrawal request has either expired relative to
+        //    the bitcoin chain tip, or that enough signers have rejected
+        //    the request.
+        //
+        // Only explicit rejections count here. A signer that has not
+        // voted yet could still accept the request, so the request can
+        // only be rejected early if the signers that did not reject it
+        // are too few to reach the threshold.
+        if max_accepts < req_ctx.signatures_required as usize {
+            return Ok(());
+        }
+
+        // The request expires relative to the bitcoin block that anchors
+        // the stacks block that included the withdrawal request.
+        let anchor_height = match db.get_stacks_block(&request.block_hash).await? {
+            Some(block) => db
+                .get_bitcoin_block(&block.bitcoin_anchor)
+                .await?
+                .map(|anchor| anchor.block_height),
+            None => None,
+        };
+        let is_expired = anchor_height
+            .map(|height| height.saturating_add(WITHDRAWAL_BLOCKS_EXPIRY))
+            .is_some_and(|expiry_height| req_ctx.chain_tip.block_height >= expiry_height);
+
+        if !is_expired {
+            return Err(WithdrawalRejectErrorMsg::RequestNotRejectable.into_error(req_ctx, self));
+        }
+
         Ok(())
     }
+
+    /// This function checks that there is no sweep transaction in the
+    /// mempool that fulfills the withdrawal request.
+    ///
+    /// Specifically, this function checks the following points (from the
+    /// docs of [`RejectWithdrawalV1::validate`]):
+    /// 5. That there is no sweep transaction fulfilling the withdrawal
+    ///    request in the mempool.
+    async fn validate_no_pending_sweep<C>(&self, ctx: &C, req_ctx: &ReqContext) -> Result<(), Error>
+    where
+        C: Context + Send + Sync,
+    {
+        let db = ctx.get_storage();
+        let rpc = ctx.get_bitcoin_client();
+        let chain_tip = &req_ctx.chain_tip.block_hash;
+
+        // Unconfirmed sweep transactions must spend the signers' UTXO, so
+        // if there is no such UTXO then there can be no pending sweep.
+        let Some(signer_utxo) = db
+            .get_signer_utxo(chain_tip, req_ctx.context_window)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let prevout_txid: BitcoinTxId = signer_utxo.outpoint.txid.into();
+        let sweep_transactions = db
+            .get_latest_unconfirmed_sweep_transactions(
+                chain_tip,
+                req_ctx.context_window,
+                &prevout_txid,
+            )
+            .await?;
+
+        let fulfilling_sweeps = sweep_transactions.iter().filter(|sweep| {
+            sweep
+                .swept_withdrawals
+                .iter()
+                .any(|swept| swept.withdrawal_request_id == self.request_id)
+        });
+
+        // We only care about sweep transactions that bitcoin-core still
+        // has in its mempool, since any others were dropped and will not
+        // be confirmed.
+        for sweep in fulfilling_sweeps {
+            if rpc.get_mempool_entry(&sweep.txid).await?.is_some() {
+                let msg = WithdrawalRejectErrorMsg::RequestBeingFulfilled;
+                return Err(msg.into_error(req_ctx, self));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A struct for a validation error containing all the necessary context.
+#[derive(Debug)]
+pub struct WithdrawalRejectValidationError {
+    /// The specific error that happened during validation.
+    pub error: WithdrawalRejectErrorMsg,
+    /// The additional information that was used when trying to validate
+    /// the `reject-withdrawal-request` contract call. This includes the
+    /// public key of the signer that was attempting to generate the
+    /// `reject-withdrawal-request` transaction.
+    pub context: ReqContext,
+    /// The specific transaction that was being validated.
+    pub tx: RejectWithdrawalV1,
+}
+
+impl std::fmt::Display for WithdrawalRejectValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // TODO(191): Add the other variables to the error message.
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for WithdrawalRejectValidationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The responses for validation of a reject-withdrawal-request smart
+/// contract call transaction.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum WithdrawalRejectErrorMsg {
+    /// The bitmap set in the transaction object should match the one in
+    /// our database.
+    #[error("bitmap does not match expected bitmap from our records")]
+    BitmapMismatch,
+    /// The smart contract deployer is fixed, so this should always match.
+    #[error("the deployer in the transaction does not match the expected deployer")]
+    DeployerMismatch,
+    /// There is a sweep transaction in the mempool that fulfills the
+    /// withdrawal request.
+    #[error("a sweep transaction fulfilling the withdrawal request is in the mempool")]
+    RequestBeingFulfilled,
+    /// The withdrawal request has been fulfilled by a sweep transaction
+    /// that has been confirmed on the canonical bitcoin blockchain.
+    #[error("the withdrawal request has been fulfilled by a confirmed sweep transaction")]
+    RequestFulfilled,
+    /// We do not have a record of the withdrawal request in our list of
+    /// unfulfilled withdrawal requests.
+    #[error("no record of withdrawal request in unfulfilled withdrawal requests")]
+    RequestMissing,
+    /// The withdrawal request has not expired and not enough signers have
+    /// rejected it, so it may still be accepted.
+    #[error("the withdrawal request has not expired and has not been rejected by the signers")]
+    RequestNotRejectable,
+}
+
+impl WithdrawalRejectErrorMsg {
+    fn into_error(self, ctx: &ReqContext, tx: &RejectWithdrawalV1) -> Error {
+        Error::WithdrawalRejectValidation(Box::new(WithdrawalRejectValidationError {
+            error: self,
+            context: *ctx,
+            tx: *tx,
+        }))
+    }
+}
+
+/// A single entry in a complete-withdrawals contract call. Each entry
+/// either accepts or rejects a withdrawal request.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub enum WithdrawalCompletion {
+    /// Accept the withdrawal request, burning the locked sBTC.
+    Accept(AcceptWithdrawalV1),
+    /// Reject the withdrawal request, returning the locked sBTC to the
+    /// requester.
+    Reject(RejectWithdrawalV1),
+}
+
+impl WithdrawalCompletion {
+    /// The ID of the withdrawal request that this entry completes.
+    pub fn request_id(&self) -> u64 {
+        match self {
+            WithdrawalCompletion::Accept(accept) => accept.request_id,
+            WithdrawalCompletion::Reject(reject) => reject.request_id,
+        }
+    }
+
+    /// The address that deployed the contract.
+    pub fn deployer(&self) -> StacksAddress {
+        match self {
+            WithdrawalCompletion::Accept(accept) => accept.deployer,
+            WithdrawalCompletion::Reject(reject) => reject.deployer,
+        }
+    }
+
+    /// Validate this entry in the same way that the corresponding
+    /// stand-alone contract call is validated.
+    async fn validate<C>(&self, ctx: &C, req_ctx: &ReqContext) -> Result<(), Error>
+    where
+        C: Context + Send + Sync,
+    {
+        match self {
+            WithdrawalCompletion::Accept(accept) => accept.validate(ctx, req_ctx).await,
+            WithdrawalCompletion::Reject(reject) => reject.validate(ctx, req_ctx).await,
+        }
+    }
+}
+
+/// This struct is used to generate a properly formatted Stacks transaction
+/// for calling the complete-withdrawals function in the sbtc-withdrawal
+/// smart contract.
+///
+/// This bundles many `accept-withdrawal-request` and
+/// `reject-withdrawal-request` calls into a single contract call, so that
+/// the signers can settle all withdrawals fulfilled by a sweep
+/// transaction in one signing round.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct CompleteWithdrawalsV1 {
+    /// The withdrawal requests to accept or reject. Each one of these must
+    /// be a valid contract call on its own.
+    pub withdrawals: Vec<WithdrawalCompletion>,
+    /// The address that deployed the contract.
+    pub deployer: StacksAddress,
+}
+
+impl CompleteWithdrawalsV1 {
+    /// The maximum number of withdrawals that can be completed in one
+    /// contract call. This matches the length of the list argument to the
+    /// `complete-withdrawals` clarity function.
+    pub const MAX_WITHDRAWALS: usize = 600;
+
+    /// This function returns the clarity description of the input to the
+    /// contract call.
+    ///
+    /// # Notes
+    ///
+    /// The input, withdrawals, is a (list 600 {request-id: uint, status:
+    /// bool, signer-bitmap: uint, bitcoin-txid: (optional (buff 32)),
+    /// output-index: (optional uint), fee: (optional uint), burn-hash:
+    /// (buff 32), burn-height: uint, sweep-txid: (optional (buff 32))}).
+    /// This function represents this data type.
+    fn list_data_type() -> &'static ListTypeData {
+        static WITHDRAWALS_ARGUMENT_DATA_TYPE: OnceLock<ListTypeData> = OnceLock::new();
+        WITHDRAWALS_ARGUMENT_DATA_TYPE.get_or_init(|| {
+            let optional_buff_32 = TypeSignature::OptionalType(Box::new(BUFF_32.clone()));
+            let optional_uint = TypeSignature::OptionalType(Box::new(TypeSignature::UIntType));
+            let tuple_type = TupleTypeSignature::try_from(vec![
+                (ClarityName::from("request-id"), TypeSignature::UIntType),
+                (ClarityName::from("status"), TypeSignature::BoolType),
+                (ClarityName::from("signer-bitmap"), TypeSignature::UIntType),
+                (ClarityName::from("bitcoin-txid"), optional_buff_32.clone()),
+                (ClarityName::from("output-index"), optional_uint.clone()),
+                (ClarityName::from("fee"), optional_uint),
+                (ClarityName::from("burn-hash"), BUFF_32.clone()),
+                (ClarityName::from("burn-height"), TypeSignature::UIntType),
+                (ClarityName::from("sweep-txid"), optional_buff_32),
+            ])
+            .expect("Error: legal TupleTypeSignature marked as invalid");
+            // A Result::Err is returned whenever the "depth" of the type
+            // is too large or if the maximum size of an input with the
+            // given type is too large. None of this is true for us, the
+            // depth is 3 and the size is well under the limit of 1 MB.
+            let tuple_type = TypeSignature::TupleType(tuple_type);
+            ListTypeData::new_list(tuple_type, Self::MAX_WITHDRAWALS as u32)
+                .expect("Error: legal ListTypeData marked as invalid")
+        })
+    }
+
+    /// Convert one of the withdrawals into the tuple that the
+    /// `complete-withdrawals` function expects.
+    ///
+    /// Rejections only make use of the `request-id` and `signer-bitmap`
+    /// fields, the contract ignores the others. We still need to give
+    /// them a value of the right type, so the optional fields are set to
+    /// `none`, the burn hash is all zeros and the burn height is zero.
+    fn as_tuple(withdrawal: &WithdrawalCompletion) -> ClarityValue {
+        // Wrapping a value in an optional only fails if the resulting
+        // value is too large, and our values are tiny.
+        let some =
+            |value| ClarityValue::some(value).expect("Error: legal optional marked as invalid");
+        let buff = |data: Vec<u8>| ClarityValue::Sequence(SequenceData::Buffer(BuffData { data }));
+
+        let data = match withdrawal {
+            WithdrawalCompletion::Accept(accept) => {
+                let txid = accept.outpoint.txid.to_byte_array().to_vec();
+                // We first convert it into this type because the
+                // BitcoinBlockHash has the underlying bytes in that type
+                // are reversed from what the stacks-node expects.
+                let burn_hash = BurnchainHeaderHash::from(accept.sweep_block_hash);
+                vec![
+                    ("request-id", ClarityValue::UInt(accept.request_id as u128)),
+                    ("status", ClarityValue::Bool(true)),
+                    (
+                        "signer-bitmap",
+                        ClarityValue::UInt(accept.signer_bitmap.load_le()),
+                    ),
+                    ("bitcoin-txid", some(buff(txid))),
+                    (
+                        "output-index",
+                        some(ClarityValue::UInt(accept.outpoint.vout as u128)),
+                    ),
+                    ("fee", some(ClarityValue::UInt(accept.tx_fee as u128))),
+                    ("burn-hash", buff(burn_hash.into_bytes().to_vec())),
+                    (
+                        "burn-height",
+                        ClarityValue::UInt(accept.sweep_block_height as u128),
+                    ),
+                    (
+                        "sweep-txid",
+                        some(buff(accept.sweep_txid.to_byte_array().to_vec())),
+                    ),
+                ]
+            }
+            WithdrawalCompletion::Reject(reject) => vec![
+                ("request-id", ClarityValue::UInt(reject.request_id as u128)),
+                ("status", ClarityValue::Bool(false)),
+                (
+                    "signer-bitmap",
+                    ClarityValue::UInt(reject.signer_bitmap.load_le()),
+                ),
+                ("bitcoin-txid", ClarityValue::none()),
+                ("output-index", ClarityValue::none()),
+                ("fee", ClarityValue::none()),
+                ("burn-hash", buff(vec![0; 32])),
+                ("burn-height", ClarityValue::UInt(0)),
+                ("sweep-txid", ClarityValue::none()),
+            ],
+        };
+        let data = data
+            .into_iter()
+            .map(|(name, value)| (ClarityName::from(name), value))
+            .collect();
+        // This only errors if there are duplicate names or if the tuple
+        // is too large, neither of which can happen here.
+        let tuple = TupleData::from_data(data).expect("Error: legal TupleData marked as invalid");
+        ClarityValue::Tuple(tuple)
+    }
+
+    /// Validate each of the withdrawals on its own and remove the ones
+    /// that fail validation, returning them along with their validation
+    /// error.
+    ///
+    /// The `complete-withdrawals` contract call fails as a whole, both
+    /// during validation and on chain, if any one of its withdrawals is
+    /// invalid. Errors other than withdrawal validation errors are
+    /// returned.
+    pub async fn remove_invalid_withdrawals<C>(
+        &mut self,
+        ctx: &C,
+        req_ctx: &ReqContext,
+    ) -> Result<Vec<(WithdrawalCompletion, Error)>, Error>
+    where
+        C: Context + Send + Sync,
+    {
+        let mut invalid = Vec::new();
+        for withdrawal in self.withdrawals.iter() {
+            match withdrawal.validate(ctx, req_ctx).await {
+                Ok(()) => {}
+                Err(error @ Error::WithdrawalAcceptValidation(_))
+                | Err(error @ Error::WithdrawalRejectValidation(_)) => {
+                    invalid.push((withdrawal.clone(), error))
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        self.withdrawals
+            .retain(|withdrawal| !invalid.iter().any(|(wd, _)| wd == withdrawal));
+        Ok(invalid)
+    }
+}
+
+impl AsTxPayload for CompleteWithdrawalsV1 {
+    fn tx_payload(&self) -> TransactionPayload {
+        TransactionPayload::ContractCall(self.as_contract_call())
+    }
+    fn post_conditions(&self) -> StacksTxPostConditions {
+        AsContractCall::post_conditions(self)
+    }
+}
+
+impl AsContractCall for CompleteWithdrawalsV1 {
+    const CONTRACT_NAME: &'static str = "sbtc-withdrawal";
+    const FUNCTION_NAME: &'static str = "complete-withdrawals";
+
+    fn deployer_address(&self) -> StacksAddress {
+        self.deployer
+    }
+    /// Construct the input arguments to the complete-withdrawals contract
+    /// call.
+    fn as_contract_args(&self) -> Vec<ClarityValue> {
+        let withdrawals = ListData {
+            data: self.withdrawals.iter().map(Self::as_tuple).collect(),
+            type_signature: Self::list_data_type().clone(),
+        };
+
+        vec![ClarityValue::Sequence(SequenceData::List(withdrawals))]
+    }
+    /// The sBTC burns and transfers happen within the contract, so we
+    /// cannot deny unlisted asset transfers. We do make sure that the
+    /// signers' wallet does not send any STX with this transaction.
+    fn post_conditions(&self) -> StacksTxPostConditions {
+        StacksTxPostConditions {
+            post_condition_mode: TransactionPostConditionMode::Allow,
+            post_conditions: vec![TransactionPostCondition::STX(
+                PostConditionPrincipal::Origin,
+                FungibleConditionCode::SentEq,
+                0,
+            )],
+        }
+    }
+    /// Validates that the complete-withdrawals satisfies the following
+    /// criteria:
+    ///
+    /// 1. That there is at least one withdrawal, and no more than
+    ///    [`CompleteWithdrawalsV1::MAX_WITHDRAWALS`] withdrawals.
+    /// 2. That the smart contract deployer matches the deployer in our
+    ///    context, and the deployer in each of the withdrawals.
+    /// 3. That each withdrawal request ID appears only once.
+    /// 4. That each accepted withdrawal passes the validation in
+    ///    [`AcceptWithdrawalV1::validate`] and each rejected withdrawal
+    ///    passes the validation in [`RejectWithdrawalV1::validate`].
+    async fn validate<C>(&self, ctx: &C, req_ctx: &ReqContext) -> Result<(), Error>
+    where
+        C: Context + Send + Sync,
+    {
+        // 1. That there is at least one withdrawal, and no more than
+        //    MAX_WITHDRAWALS withdrawals.
+        if self.withdrawals.is_empty() {
+            return Err(CompleteWithdrawalsErrorMsg::NoWithdrawals.into_error(req_ctx, self));
+        }
+        if self.withdrawals.len() > Self::MAX_WITHDRAWALS {
+            return Err(CompleteWithdrawalsErrorMsg::TooManyWithdrawals.into_error(req_ctx, self));
+        }
+        // 2. That the smart contract deployer matches the deployer in our
+        //    context, and the deployer in each of the withdrawals.
+        if self.deployer != req_ctx.deployer {
+            return Err(CompleteWithdrawalsErrorMsg::DeployerMismatch.into_error(req_ctx, self));
+        }
+        if self
+            .withdrawals
+            .iter()
+            .any(|withdrawal| withdrawal.deployer() != self.deployer)
+        {
+            return Err(CompleteWithdrawalsErrorMsg::DeployerMismatch.into_error(req_ctx, self));
+        }
+        // 3. That each withdrawal request ID appears only once.
+        //
+        // The contract would fail the whole transaction if it attempted
+        // to complete the same withdrawal request twice.
+        let mut request_ids = HashSet::with_capacity(self.withdrawals.len());
+        if !self
+            .withdrawals
+            .iter()
+            .all(|withdrawal| request_ids.insert(withdrawal.request_id()))
+        {
+            return Err(CompleteWithdrawalsErrorMsg::DuplicateRequest.into_error(req_ctx, self));
+        }
+        // 4. That each withdrawal passes the validation of the
+        //    corresponding stand-alone contract call.
+        for withdrawal in self.withdrawals.iter() {
+            withdrawal.validate(ctx, req_ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A struct for a validation error containing all the necessary context.
+#[derive(Debug)]
+pub struct CompleteWithdrawalsValidationError {
+    /// The specific error that happened during validation.
+    pub error: CompleteWithdrawalsErrorMsg,
+    /// The additional information that was used when trying to validate
+    /// the `complete-withdrawals` contract call. This includes the public
+    /// key of the signer that was attempting to generate the
+    /// `complete-withdrawals` transaction.
+    pub context: ReqContext,
+    /// The specific transaction that was being validated.
+    pub tx: CompleteWithdrawalsV1,
+}
+
+impl std::fmt::Display for CompleteWithdrawalsValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // TODO(191): Add the other variables to the error message.
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for CompleteWithdrawalsValidationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The responses for validation of a complete-withdrawals smart contract
+/// call transaction. Failures of the individual withdrawals are reported
+/// using [`WithdrawalErrorMsg`] and [`WithdrawalRejectErrorMsg`].
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CompleteWithdrawalsErrorMsg {
+    /// The smart contract deployer is fixed, so this should always match.
+    #[error("the deployer in the transaction does not match the expected deployer")]
+    DeployerMismatch,
+    /// Each withdrawal request can only be completed once.
+    #[error("the same withdrawal request appears more than once in the transaction")]
+    DuplicateRequest,
+    /// There must be at least one withdrawal in the transaction.
+    #[error("the transaction does not include any withdrawals")]
+    NoWithdrawals,
+    /// The contract only accepts a bounded list of withdrawals.
+    #[error("the transaction includes more withdrawals than the contract accepts")]
+    TooManyWithdrawals,
+}
+
+impl CompleteWithdrawalsErrorMsg {
+    fn into_error(self, ctx: &ReqContext, tx: &CompleteWithdrawalsV1) -> Error {
+        Error::CompleteWithdrawalsValidation(Box::new(CompleteWithdrawalsValidationError {
+            error: self,
+            context: *ctx,
+            tx: tx.clone(),
+        }))
+    }
 }
 
 /// This struct is used to generate a properly formatted Stacks transaction
@@ -1034,6 +1907,18 @@ impl RotateKeysV1 {
         }
     }
 
+    /// Create a new instance of a RotateKeysV1 transaction object that
+    /// rotates to the signer set, threshold and aggregate key of the given
+    /// DKG shares.
+    pub fn from_dkg_shares(shares: &EncryptedDkgShares, deployer: StacksAddress) -> Self {
+        Self {
+            aggregate_key: shares.aggregate_key,
+            new_keys: shares.signer_set_public_keys.iter().copied().collect(),
+            deployer,
+            signatures_required: shares.signature_share_threshold,
+        }
+    }
+
     /// This function returns the clarity description of one of the inputs
     /// to the contract call.
     ///
@@ -1373,6 +2258,32 @@ mod tests {
         let _ = call.as_contract_call();
     }
 
+    #[test]
+    fn complete_deposits_contract_call_creation() {
+        // This is to check that the CompleteDepositsV1::list_data_type
+        // function doesn't panic. If it doesn't panic now, it can never
+        // panic at runtime.
+        let _ = CompleteDepositsV1::list_data_type();
+
+        let deposit = CompleteDepositV1 {
+            outpoint: OutPoint::null(),
+            amount: 15000,
+            recipient: PrincipalData::from(StacksAddress::burn_address(true)),
+            deployer: StacksAddress::burn_address(false),
+            sweep_txid: BitcoinTxId::from([0; 32]),
+            sweep_block_hash: BitcoinBlockHash::from([0; 32]),
+            sweep_block_height: 7,
+        };
+        let call = CompleteDepositsV1 {
+            deposits: vec![deposit.clone(), deposit],
+            deployer: StacksAddress::burn_address(false),
+        };
+
+        // This is to check that this function doesn't implicitly panic. If
+        // it doesn't panic now, it can never panic at runtime.
+        let _ = call.as_contract_call();
+    }
+
     #[test]
     fn withdrawal_accept_contract_call_creation() {
         // This is to check that this function doesn't implicitly panic. If
@@ -1385,11 +2296,73 @@ mod tests {
             deployer: StacksAddress::burn_address(false),
             sweep_block_hash: BitcoinBlockHash::from([0; 32]),
             sweep_block_height: 7,
+            sweep_txid: BitcoinTxId::from([0; 32]),
         };
 
         let _ = call.as_contract_call();
     }
 
+    /// Return the number of arguments of the public function with the
+    /// given name in the given clarity contract.
+    fn public_function_arity(contract_body: &str, name: &str) -> usize {
+        let signature = format!("(define-public ({name} ");
+        let start = contract_body
+            .find(&signature)
+            .expect("public function missing from contract");
+
+        // Each argument is a parenthesized (name type) pair, so we count
+        // the parentheses that open at the top level of the function
+        // signature.
+        let mut depth = 1;
+        let mut arity = 0;
+        for c in contract_body[start + signature.len()..].chars() {
+            match c {
+                '(' => {
+                    if depth == 1 {
+                        arity += 1;
+                    }
+                    depth += 1;
+                }
+                ')' if depth == 1 => break,
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        arity
+    }
+
+    #[test]
+    fn withdrawal_accept_args_match_contract() {
+        let call = AcceptWithdrawalV1 {
+            request_id: 42,
+            outpoint: OutPoint::null(),
+            tx_fee: 125,
+            signer_bitmap: BitArray::ZERO,
+            deployer: StacksAddress::burn_address(false),
+            sweep_block_hash: BitcoinBlockHash::from([0; 32]),
+            sweep_block_height: 7,
+            sweep_txid: BitcoinTxId::from([0; 32]),
+        };
+        let contract_body = SmartContract::SbtcWithdrawal.contract_body();
+        let arity = public_function_arity(contract_body, AcceptWithdrawalV1::FUNCTION_NAME);
+
+        assert_eq!(arity, 8);
+        assert_eq!(call.as_contract_args().len(), arity);
+    }
+
+    #[test]
+    fn withdrawal_reject_args_match_contract() {
+        let call = RejectWithdrawalV1 {
+            request_id: 42,
+            signer_bitmap: BitArray::ZERO,
+            deployer: StacksAddress::burn_address(false),
+        };
+        let contract_body = SmartContract::SbtcWithdrawal.contract_body();
+        let arity = public_function_arity(contract_body, RejectWithdrawalV1::FUNCTION_NAME);
+
+        assert_eq!(call.as_contract_args().len(), arity);
+    }
+
     #[test]
     fn reject_withdrawal_contract_call_creation() {
         // This is to check that this function doesn't implicitly panic. If
@@ -1403,6 +2376,41 @@ mod tests {
         let _ = call.as_contract_call();
     }
 
+    #[test]
+    fn complete_withdrawals_contract_call_creation() {
+        // This is to check that the CompleteWithdrawalsV1::list_data_type
+        // function doesn't panic. If it doesn't panic now, it can never
+        // panic at runtime.
+        let _ = CompleteWithdrawalsV1::list_data_type();
+
+        let accept = AcceptWithdrawalV1 {
+            request_id: 42,
+            outpoint: OutPoint::null(),
+            tx_fee: 125,
+            signer_bitmap: BitArray::ZERO,
+            deployer: StacksAddress::burn_address(false),
+            sweep_block_hash: BitcoinBlockHash::from([0; 32]),
+            sweep_block_height: 7,
+            sweep_txid: BitcoinTxId::from([0; 32]),
+        };
+        let reject = RejectWithdrawalV1 {
+            request_id: 43,
+            signer_bitmap: BitArray::new([1; 16]),
+            deployer: StacksAddress::burn_address(false),
+        };
+        let call = CompleteWithdrawalsV1 {
+            withdrawals: vec![
+                WithdrawalCompletion::Accept(accept),
+                WithdrawalCompletion::Reject(reject),
+            ],
+            deployer: StacksAddress::burn_address(false),
+        };
+
+        // This is to check that this function doesn't implicitly panic. If
+        // it doesn't panic now, it can never panic at runtime.
+        let _ = call.as_contract_call();
+    }
+
     #[test]
     fn rotate_keys_wrapper_contract_call_creation() {
         // This is to check that the RotateKeysV1::list_data_type function
@@ -175,7 +175,8 @@ impl SignerWallet {
         // what the stacks network thinks the signers' address is.
         let last_key_rotation = ctx.get_storage().get_last_key_rotation(chain_tip).await?;
 
-        let config = &ctx.config().signer;
+        let settings = ctx.config();
+        let config = &settings.signer;
         let network_kind = config.network;
 
         match last_key_rotation {
@@ -712,7 +713,8 @@ mod tests {
         // We haven't stored any RotateKeysTransactions into the database
         // yet, so it will try to load the wallet from the context.
         let wallet0 = SignerWallet::load(&ctx, &bitcoin_chain_tip).await.unwrap();
-        let config = &ctx.config().signer;
+        let settings = ctx.config();
+        let config = &settings.signer;
         let bootstrap_aggregate_key =
             PublicKey::combine_keys(&config.bootstrap_signing_set()).unwrap();
         assert_eq!(wallet0.aggregate_key, bootstrap_aggregate_key);
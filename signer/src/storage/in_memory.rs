@@ -1,6 +1,7 @@
 //! In-memory store implementation - useful for tests
 
 use bitcoin::consensus::Decodable as _;
+use bitcoin::relative::LockTime;
 use bitcoin::OutPoint;
 use blockstack_lib::types::chainstate::StacksBlockId;
 use std::collections::BTreeMap;
@@ -24,6 +25,7 @@ use crate::stacks::events::WithdrawalCreateEvent;
 use crate::stacks::events::WithdrawalRejectEvent;
 use crate::storage::model;
 use crate::DEPOSIT_LOCKTIME_BLOCK_BUFFER;
+use crate::DEPOSIT_LOCKTIME_TIME_BUFFER;
 
 use super::util::get_utxo;
 
@@ -126,6 +128,26 @@ pub struct Store {
     /// Bitcoin withdrawal outputs
     pub bitcoin_withdrawal_outputs:
         HashMap<(u64, model::StacksBlockHash), model::BitcoinWithdrawalOutput>,
+
+    /// Magic bytes used in the OP_RETURN output of sweep transactions
+    pub sweep_magic_bytes: BTreeSet<[u8; 2]>,
+
+    /// DKG rounds, along with when they were first written
+    pub dkg_attempts: HashMap<model::BitcoinTxId, (OffsetDateTime, model::DkgAttempt)>,
+
+    /// Failures reported for DKG rounds, along with when they were written
+    pub dkg_failures: Vec<(OffsetDateTime, model::DkgSignerFailure)>,
+
+    /// Signer participation records, in the order they were written
+    pub signer_participation: Vec<model::SignerParticipation>,
+
+    /// The Stacks block height from which the next sync of pending
+    /// deposits from Emily starts
+    pub emily_deposit_sync_height: Option<u64>,
+
+    /// The deposit requests from Emily that are validated again on the
+    /// next sync
+    pub emily_deposit_sync_retries: BTreeMap<DepositRequestPk, model::EmilyDepositSyncRetry>,
 }
 
 impl Store {
@@ -139,6 +161,56 @@ impl Store {
         Arc::new(Mutex::new(Self::new()))
     }
 
+    async fn get_utxo_for_aggregate_key(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        aggregate_key: &PublicKey,
+        context_window: u16,
+    ) -> Result<Option<SignerUtxo>, Error> {
+        let script_pubkey = aggregate_key.signers_script_pubkey();
+        let bitcoin_blocks = &self.bitcoin_blocks;
+        let first = bitcoin_blocks.get(chain_tip);
+
+        // Traverse the canonical chain backwards and find the first block containing relevant sbtc tx(s)
+        let sbtc_txs = std::iter::successors(first, |block| bitcoin_blocks.get(&block.parent_hash))
+            .take(context_window as usize)
+            .filter_map(|block| {
+                let txs = self.bitcoin_block_to_transactions.get(&block.block_hash)?;
+
+                let mut sbtc_txs = txs
+                    .iter()
+                    .filter_map(|tx| self.raw_transactions.get(&tx.into_bytes()))
+                    .filter(|sbtc_tx| sbtc_tx.tx_type == model::TransactionType::SbtcTransaction)
+                    .filter_map(|tx| {
+                        bitcoin::Transaction::consensus_decode(&mut tx.tx.as_slice()).ok()
+                    })
+                    .filter(|tx| {
+                        tx.output
+                            .first()
+                            .is_some_and(|out| out.script_pubkey == script_pubkey)
+                    })
+                    .peekable();
+
+                if sbtc_txs.peek().is_some() {
+                    Some(sbtc_txs.collect::<Vec<_>>())
+                } else {
+                    None
+                }
+            })
+            .next();
+
+        // `sbtc_txs` contains all the txs in the highest canonical block where the first
+        // output is spendable by script_pubkey
+        let Some(sbtc_txs) = sbtc_txs else {
+            // if no sbtc tx exists, consider donations
+            return self
+                .get_utxo_from_donation(chain_tip, aggregate_key, context_window)
+                .await;
+        };
+
+        get_utxo(aggregate_key, sbtc_txs)
+    }
+
     async fn get_utxo_from_donation(
         &self,
         chain_tip: &model::BitcoinBlockHash,
@@ -350,10 +422,14 @@ impl super::DbRead for SharedStore {
         // Add one to the acceptable unlock height because the chain tip is at height one less
         // than the height of the next block, which is the block for which we are assessing
         // the threshold.
+        let chain_tip_block = store.bitcoin_blocks.get(chain_tip).unwrap();
         let minimum_acceptable_unlock_height =
-            store.bitcoin_blocks.get(chain_tip).unwrap().block_height as u32
-                + DEPOSIT_LOCKTIME_BLOCK_BUFFER as u32
-                + 1;
+            chain_tip_block.block_height as u32 + DEPOSIT_LOCKTIME_BLOCK_BUFFER as u32 + 1;
+        // A median-time-past of zero means that the block was written
+        // before we started recording it, so we do not know it.
+        let minimum_acceptable_unlock_time = Some(chain_tip_block.median_time_past)
+            .filter(|&median_time_past| median_time_past > 0)
+            .map(|median_time_past| median_time_past + DEPOSIT_LOCKTIME_TIME_BUFFER + 1);
 
         // Get all canonical blocks in the context window.
         let canonical_bitcoin_blocks = std::iter::successors(Some(chain_tip), |block_hash| {
@@ -376,9 +452,31 @@ impl super::DbRead for SharedStore {
                     .filter(|block_hash| canonical_bitcoin_blocks.contains(block_hash))
                     .filter_map(|block_hash| store.bitcoin_blocks.get(block_hash))
                     .map(|block_included: &model::BitcoinBlock| {
-                        let unlock_height =
-                            block_included.block_height as u32 + deposit_request.lock_time;
-                        unlock_height >= minimum_acceptable_unlock_height
+                        match LockTime::from_consensus(deposit_request.lock_time) {
+                            Ok(LockTime::Blocks(height)) => {
+                                let unlock_height =
+                                    block_included.block_height as u32 + height.value() as u32;
+                                unlock_height >= minimum_acceptable_unlock_height
+                            }
+                            // BIP-68 measures time based lock-times from
+                            // the median-time-past of the parent block.
+                            Ok(LockTime::Time(time)) => {
+                                let parent_median_time_past = store
+                                    .bitcoin_blocks
+                                    .get(&block_included.parent_hash)
+                                    .map(|parent| parent.median_time_past)
+                                    .filter(|&median_time_past| median_time_past > 0);
+                                match (parent_median_time_past, minimum_acceptable_unlock_time) {
+                                    (Some(parent_median_time_past), Some(minimum)) => {
+                                        let unlock_time =
+                                            parent_median_time_past + time.value() as u64 * 512;
+                                        unlock_time >= minimum
+                                    }
+                                    _ => false,
+                                }
+                            }
+                            Err(_) => false,
+                        }
                     })
                     .next()
                     .unwrap_or(false)
@@ -548,6 +646,28 @@ impl super::DbRead for SharedStore {
             .collect())
     }
 
+    async fn get_unfulfilled_withdrawal_requests(
+        &self,
+        _chain_tip: &model::BitcoinBlockHash,
+        _context_window: u16,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        unimplemented!("can only be tested using integration tests for now.");
+    }
+
+    async fn get_unfulfilled_withdrawal_request(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        request_id: u64,
+    ) -> Result<Option<model::WithdrawalRequest>, Error> {
+        let requests = self
+            .get_unfulfilled_withdrawal_requests(chain_tip, context_window)
+            .await?;
+        Ok(requests
+            .into_iter()
+            .find(|req| req.request_id == request_id))
+    }
+
     async fn get_withdrawal_request_report(
         &self,
         _chain_tip: &model::BitcoinBlockHash,
@@ -590,6 +710,19 @@ impl super::DbRead for SharedStore {
             .map(|(_, shares)| shares.clone()))
     }
 
+    async fn get_encrypted_dkg_shares_by_script_pubkey(
+        &self,
+        script_pubkey: &model::ScriptPubKey,
+    ) -> Result<Option<model::EncryptedDkgShares>, Error> {
+        Ok(self
+            .lock()
+            .await
+            .encrypted_dkg_shares
+            .values()
+            .find(|(_, shares)| &shares.script_pubkey == script_pubkey)
+            .map(|(_, shares)| shares.clone()))
+    }
+
     async fn get_latest_encrypted_dkg_shares(
         &self,
     ) -> Result<Option<model::EncryptedDkgShares>, Error> {
@@ -602,6 +735,144 @@ impl super::DbRead for SharedStore {
             .map(|(_, shares)| shares.clone()))
     }
 
+    async fn get_all_encrypted_dkg_shares(&self) -> Result<Vec<model::EncryptedDkgShares>, Error> {
+        let store = self.lock().await;
+        let mut shares = store.encrypted_dkg_shares.values().collect::<Vec<_>>();
+        shares.sort_by_key(|(time, _)| *time);
+
+        Ok(shares
+            .into_iter()
+            .map(|(_, shares)| shares.clone())
+            .collect())
+    }
+
+    async fn get_latest_dkg_attempt(&self) -> Result<Option<model::DkgAttempt>, Error> {
+        Ok(self
+            .lock()
+            .await
+            .dkg_attempts
+            .values()
+            .max_by_key(|(time, _)| time)
+            .map(|(_, attempt)| attempt.clone()))
+    }
+
+    async fn get_dkg_failures(
+        &self,
+        dkg_id: &model::BitcoinTxId,
+    ) -> Result<Vec<model::DkgSignerFailure>, Error> {
+        let mut failures: Vec<_> = self
+            .lock()
+            .await
+            .dkg_failures
+            .iter()
+            .filter(|(_, failure)| &failure.dkg_id == dkg_id)
+            .map(|(_, failure)| failure.clone())
+            .collect();
+
+        failures.sort();
+        Ok(failures)
+    }
+
+    async fn get_dkg_failure_counts(&self) -> Result<BTreeMap<PublicKey, u32>, Error> {
+        let store = self.lock().await;
+        let latest_shares = store
+            .encrypted_dkg_shares
+            .values()
+            .map(|(time, _)| *time)
+            .max();
+
+        let blamed_rounds: BTreeSet<(PublicKey, model::BitcoinTxId)> = store
+            .dkg_failures
+            .iter()
+            .filter(|(time, _)| latest_shares.map_or(true, |shares_time| *time > shares_time))
+            .map(|(_, failure)| (failure.signer_public_key, failure.dkg_id))
+            .collect();
+
+        let mut counts = BTreeMap::new();
+        for (public_key, _) in blamed_rounds {
+            *counts.entry(public_key).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    async fn get_dkg_blamed_signers(&self) -> Result<BTreeSet<PublicKey>, Error> {
+        Ok(self
+            .lock()
+            .await
+            .dkg_failures
+            .iter()
+            .map(|(_, failure)| failure.signer_public_key)
+            .collect())
+    }
+
+    async fn get_signer_participation(
+        &self,
+        round_id: &[u8; 32],
+    ) -> Result<Vec<model::SignerParticipation>, Error> {
+        Ok(self
+            .lock()
+            .await
+            .signer_participation
+            .iter()
+            .filter(|record| &record.round_id == round_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_emily_deposit_sync_height(&self) -> Result<Option<u64>, Error> {
+        Ok(self.lock().await.emily_deposit_sync_height)
+    }
+
+    async fn get_emily_deposit_sync_retries(
+        &self,
+    ) -> Result<Vec<model::EmilyDepositSyncRetry>, Error> {
+        let store = self.lock().await;
+        Ok(store.emily_deposit_sync_retries.values().cloned().collect())
+    }
+
+    async fn get_signer_participation_stats(
+        &self,
+        window: u32,
+    ) -> Result<Vec<model::SignerParticipationStats>, Error> {
+        let store = self.lock().await;
+        let mut records: BTreeMap<PublicKey, Vec<&model::SignerParticipation>> = BTreeMap::new();
+        for record in store.signer_participation.iter().rev() {
+            let signer_records = records.entry(record.signer_public_key).or_default();
+            if signer_records.len() < window as usize {
+                signer_records.push(record);
+            }
+        }
+
+        let stats = records
+            .into_iter()
+            .filter(|(_, records)| !records.is_empty())
+            .map(|(signer_public_key, records)| {
+                let count = |outcome| {
+                    records
+                        .iter()
+                        .filter(|record| record.outcome == outcome)
+                        .count() as u32
+                };
+                let response_times: Vec<i64> = records
+                    .iter()
+                    .filter_map(|record| record.response_time_ms)
+                    .collect();
+                let avg_response_time_ms = (!response_times.is_empty())
+                    .then(|| response_times.iter().sum::<i64>() / response_times.len() as i64);
+
+                model::SignerParticipationStats {
+                    signer_public_key,
+                    responded: count(model::ParticipationOutcome::Responded),
+                    invalid: count(model::ParticipationOutcome::Invalid),
+                    missing: count(model::ParticipationOutcome::Missing),
+                    avg_response_time_ms,
+                }
+            })
+            .collect();
+
+        Ok(stats)
+    }
+
     async fn get_last_key_rotation(
         &self,
         chain_tip: &model::BitcoinBlockHash,
@@ -648,58 +919,39 @@ impl super::DbRead for SharedStore {
             .collect())
     }
 
+    async fn get_sweep_magic_bytes(&self) -> Result<Vec<[u8; 2]>, Error> {
+        Ok(self
+            .lock()
+            .await
+            .sweep_magic_bytes
+            .iter()
+            .copied()
+            .collect())
+    }
+
     async fn get_signer_utxo(
         &self,
         chain_tip: &model::BitcoinBlockHash,
         context_window: u16,
     ) -> Result<Option<SignerUtxo>, Error> {
-        let Some(dkg_shares) = self.get_latest_encrypted_dkg_shares().await? else {
-            return Ok(None);
-        };
-        let aggregate_key = dkg_shares.aggregate_key;
-        let script_pubkey = aggregate_key.signers_script_pubkey();
         let store = self.lock().await;
-        let bitcoin_blocks = &store.bitcoin_blocks;
-        let first = bitcoin_blocks.get(chain_tip);
-
-        // Traverse the canonical chain backwards and find the first block containing relevant sbtc tx(s)
-        let sbtc_txs = std::iter::successors(first, |block| bitcoin_blocks.get(&block.parent_hash))
-            .take(context_window as usize)
-            .filter_map(|block| {
-                let txs = store.bitcoin_block_to_transactions.get(&block.block_hash)?;
-
-                let mut sbtc_txs = txs
-                    .iter()
-                    .filter_map(|tx| store.raw_transactions.get(&tx.into_bytes()))
-                    .filter(|sbtc_tx| sbtc_tx.tx_type == model::TransactionType::SbtcTransaction)
-                    .filter_map(|tx| {
-                        bitcoin::Transaction::consensus_decode(&mut tx.tx.as_slice()).ok()
-                    })
-                    .filter(|tx| {
-                        tx.output
-                            .first()
-                            .is_some_and(|out| out.script_pubkey == script_pubkey)
-                    })
-                    .peekable();
 
-                if sbtc_txs.peek().is_some() {
-                    Some(sbtc_txs.collect::<Vec<_>>())
-                } else {
-                    None
-                }
-            })
-            .next();
-
-        // `sbtc_txs` contains all the txs in the highest canonical block where the first
-        // output is spendable by script_pubkey
-        let Some(sbtc_txs) = sbtc_txs else {
-            // if no sbtc tx exists, consider donations
-            return store
-                .get_utxo_from_donation(chain_tip, &aggregate_key, context_window)
-                .await;
-        };
+        // The signers' UTXO stays locked by an old aggregate key until it
+        // is swept over to the new one, so we check each of our aggregate
+        // keys, starting with the most recent one.
+        let mut dkg_shares: Vec<_> = store.encrypted_dkg_shares.values().collect();
+        dkg_shares.sort_by_key(|(time, _)| std::cmp::Reverse(*time));
+
+        for (_, shares) in dkg_shares {
+            let utxo = store
+                .get_utxo_for_aggregate_key(chain_tip, &shares.aggregate_key, context_window)
+                .await?;
+            if utxo.is_some() {
+                return Ok(utxo);
+            }
+        }
 
-        get_utxo(&aggregate_key, sbtc_txs)
+        Ok(None)
     }
 
     async fn get_deposit_request_signer_votes(
@@ -874,6 +1126,20 @@ impl super::DbRead for SharedStore {
         //     .collect::<Result<Vec<_>, Error>>()
     }
 
+    async fn get_swept_withdrawal_request(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        request_id: u64,
+    ) -> Result<Option<model::SweptWithdrawalRequest>, Error> {
+        let requests = self
+            .get_swept_withdrawal_requests(chain_tip, context_window)
+            .await?;
+        Ok(requests
+            .into_iter()
+            .find(|req| req.request_id == request_id))
+    }
+
     async fn get_latest_sweep_transaction(
         &self,
         chain_tip: &model::BitcoinBlockHash,
@@ -1149,6 +1415,77 @@ impl super::DbWrite for SharedStore {
         Ok(())
     }
 
+    async fn write_dkg_attempt(&self, attempt: &model::DkgAttempt) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        let now = OffsetDateTime::now_utc();
+        let (_, entry) = store
+            .dkg_attempts
+            .entry(attempt.dkg_id)
+            .or_insert_with(|| (now, attempt.clone()));
+
+        entry.status = attempt.status;
+        entry.aggregate_key = attempt.aggregate_key.or(entry.aggregate_key);
+
+        Ok(())
+    }
+
+    async fn write_dkg_failures(&self, failures: &[model::DkgSignerFailure]) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        let now = OffsetDateTime::now_utc();
+        for failure in failures {
+            let exists = store.dkg_failures.iter().any(|(_, known)| known == failure);
+            if !exists {
+                store.dkg_failures.push((now, failure.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn write_signer_participation(
+        &self,
+        records: &[model::SignerParticipation],
+    ) -> Result<(), Error> {
+        self.lock()
+            .await
+            .signer_participation
+            .extend(records.iter().cloned());
+
+        Ok(())
+    }
+
+    async fn set_emily_deposit_sync_height(&self, min_height: u64) -> Result<(), Error> {
+        self.lock().await.emily_deposit_sync_height = Some(min_height);
+
+        Ok(())
+    }
+
+    async fn write_emily_deposit_sync_retry(
+        &self,
+        retry: &model::EmilyDepositSyncRetry,
+    ) -> Result<(), Error> {
+        self.lock()
+            .await
+            .emily_deposit_sync_retries
+            .entry((retry.txid, retry.output_index))
+            .or_insert_with(|| retry.clone());
+
+        Ok(())
+    }
+
+    async fn delete_emily_deposit_sync_retry(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<(), Error> {
+        self.lock()
+            .await
+            .emily_deposit_sync_retries
+            .remove(&(*txid, output_index));
+
+        Ok(())
+    }
+
     async fn write_rotate_keys_transaction(
         &self,
         key_rotation: &model::RotateKeysTransaction,
@@ -1227,6 +1564,12 @@ impl super::DbWrite for SharedStore {
         Ok(())
     }
 
+    async fn write_sweep_magic_bytes(&self, magic_bytes: [u8; 2]) -> Result<(), Error> {
+        self.lock().await.sweep_magic_bytes.insert(magic_bytes);
+
+        Ok(())
+    }
+
     async fn write_sweep_transaction(&self, tx: &model::SweepTransaction) -> Result<(), Error> {
         let mut store = self.lock().await;
         store.sweep_transactions.push(tx.clone());
@@ -12,6 +12,7 @@ pub mod postgres;
 pub mod sqlx;
 pub mod util;
 
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::future::Future;
 
@@ -167,6 +168,39 @@ pub trait DbRead {
         threshold: u16,
     ) -> impl Future<Output = Result<Vec<model::WithdrawalRequest>, Error>> + Send;
 
+    /// Get withdrawal requests on the canonical stacks blockchain that
+    /// have not been swept out and have no responses.
+    ///
+    /// These are the withdrawal requests where:
+    /// 1. There is no sweep transaction fulfilling the request that has
+    ///    been confirmed on the bitcoin blockchain identified by the
+    ///    given chain tip.
+    /// 2. There is no `withdrawal-accept` or `withdrawal-reject` event
+    ///    for the request on the canonical stacks blockchain.
+    ///
+    /// These requests are candidates for a `reject-withdrawal-request`
+    /// contract call, but whether they should be rejected depends on
+    /// whether they have expired or how the signers voted.
+    fn get_unfulfilled_withdrawal_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> impl Future<Output = Result<Vec<model::WithdrawalRequest>, Error>> + Send;
+
+    /// Get the withdrawal request with the given request ID on the
+    /// canonical stacks blockchain if it has not been swept out and has
+    /// no responses.
+    ///
+    /// This applies the same criteria as
+    /// [`DbRead::get_unfulfilled_withdrawal_requests`] to a single
+    /// request.
+    fn get_unfulfilled_withdrawal_request(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        request_id: u64,
+    ) -> impl Future<Output = Result<Option<model::WithdrawalRequest>, Error>> + Send;
+
     /// This function returns a withdrawal request report that does the
     /// following:
     ///
@@ -209,12 +243,75 @@ pub trait DbRead {
         aggregate_key: &PublicKey,
     ) -> impl Future<Output = Result<Option<model::EncryptedDkgShares>, Error>> + Send;
 
+    /// Return the DKG shares whose aggregate key locks UTXOs with the
+    /// given scriptPubKey.
+    fn get_encrypted_dkg_shares_by_script_pubkey(
+        &self,
+        script_pubkey: &model::ScriptPubKey,
+    ) -> impl Future<Output = Result<Option<model::EncryptedDkgShares>, Error>> + Send;
+
     /// Return the most recent DKG shares, and return None if the table is
     /// empty.
     fn get_latest_encrypted_dkg_shares(
         &self,
     ) -> impl Future<Output = Result<Option<model::EncryptedDkgShares>, Error>> + Send;
 
+    /// Return all DKG shares, ordered from the oldest to the most recent.
+    fn get_all_encrypted_dkg_shares(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::EncryptedDkgShares>, Error>> + Send;
+
+    /// Return the most recently started DKG round, and return None if
+    /// the signer has not taken part in one.
+    fn get_latest_dkg_attempt(
+        &self,
+    ) -> impl Future<Output = Result<Option<model::DkgAttempt>, Error>> + Send;
+
+    /// Return the failures that were reported for the given DKG round.
+    fn get_dkg_failures(
+        &self,
+        dkg_id: &model::BitcoinTxId,
+    ) -> impl Future<Output = Result<Vec<model::DkgSignerFailure>, Error>> + Send;
+
+    /// Return, for each signer that has been blamed for a DKG failure
+    /// since the latest DKG shares were written, the number of distinct
+    /// DKG rounds it was blamed for.
+    fn get_dkg_failure_counts(
+        &self,
+    ) -> impl Future<Output = Result<BTreeMap<PublicKey, u32>, Error>> + Send;
+
+    /// Return every signer that has ever been blamed for a DKG failure,
+    /// including failures from before the latest DKG shares were written.
+    fn get_dkg_blamed_signers(
+        &self,
+    ) -> impl Future<Output = Result<BTreeSet<PublicKey>, Error>> + Send;
+
+    /// Return the participation records of the rounds with the given
+    /// identifier.
+    fn get_signer_participation(
+        &self,
+        round_id: &[u8; 32],
+    ) -> impl Future<Output = Result<Vec<model::SignerParticipation>, Error>> + Send;
+
+    /// Return, for each signer with recorded participation, a summary of
+    /// its latest `window` participation records.
+    fn get_signer_participation_stats(
+        &self,
+        window: u32,
+    ) -> impl Future<Output = Result<Vec<model::SignerParticipationStats>, Error>> + Send;
+
+    /// Return the Stacks block height from which the next sync of pending
+    /// deposits from Emily should start, if there has been a sync.
+    fn get_emily_deposit_sync_height(
+        &self,
+    ) -> impl Future<Output = Result<Option<u64>, Error>> + Send;
+
+    /// Return the deposit requests from Emily that have to be validated
+    /// again on the next sync.
+    fn get_emily_deposit_sync_retries(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::EmilyDepositSyncRetry>, Error>> + Send;
+
     /// Return the latest rotate-keys transaction confirmed by the given `chain-tip`.
     fn get_last_key_rotation(
         &self,
@@ -237,6 +334,10 @@ pub trait DbRead {
         &self,
     ) -> impl Future<Output = Result<Vec<model::Bytes>, Error>> + Send;
 
+    /// Get all magic bytes that the signers have used in the `OP_RETURN`
+    /// output of their sweep transactions.
+    fn get_sweep_magic_bytes(&self) -> impl Future<Output = Result<Vec<[u8; 2]>, Error>> + Send;
+
     /// Get the outstanding signer UTXO.
     ///
     /// Under normal conditions, the signer will have only one UTXO they
@@ -250,6 +351,11 @@ pub trait DbRead {
     ///    exactly one output satisfying points 1-3 will be unspent.
     /// 4. The block that includes the transaction that satisfies points
     ///    1-4 has the greatest height of all such blocks.
+    ///
+    /// The UTXO may be locked by the aggregate key of any DKG round, and
+    /// not just the latest one. After the signers rotate keys their UTXO
+    /// stays locked by the old aggregate key until a sweep transaction
+    /// moves it over to the new one.
     fn get_signer_utxo(
         &self,
         chain_tip: &model::BitcoinBlockHash,
@@ -321,6 +427,16 @@ pub trait DbRead {
         context_window: u16,
     ) -> impl Future<Output = Result<Vec<model::SweptWithdrawalRequest>, Error>> + Send;
 
+    /// Fetch the swept withdrawal request with the given request ID,
+    /// applying the same criteria as
+    /// [`DbRead::get_swept_withdrawal_requests`] to a single request.
+    fn get_swept_withdrawal_request(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        request_id: u64,
+    ) -> impl Future<Output = Result<Option<model::SweptWithdrawalRequest>, Error>> + Send;
+
     /// Get the latest sweep transaction package.
     fn get_latest_sweep_transaction(
         &self,
@@ -448,6 +564,48 @@ pub trait DbWrite {
         shares: &model::EncryptedDkgShares,
     ) -> impl Future<Output = Result<(), Error>> + Send;
 
+    /// Write a DKG round, or update the status and aggregate key of an
+    /// existing one.
+    fn write_dkg_attempt(
+        &self,
+        attempt: &model::DkgAttempt,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Write the failures reported for a DKG round.
+    fn write_dkg_failures(
+        &self,
+        failures: &[model::DkgSignerFailure],
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Write how signers took part in a round.
+    fn write_signer_participation(
+        &self,
+        records: &[model::SignerParticipation],
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Write the Stacks block height from which the next sync of pending
+    /// deposits from Emily should start.
+    fn set_emily_deposit_sync_height(
+        &self,
+        min_height: u64,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Write a deposit request from Emily that has to be validated again
+    /// on the next sync. Writing the same deposit request twice is a
+    /// no-op.
+    fn write_emily_deposit_sync_retry(
+        &self,
+        retry: &model::EmilyDepositSyncRetry,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Delete the deposit request with the given outpoint from the
+    /// deposit requests that are validated again on the next sync.
+    fn delete_emily_deposit_sync_retry(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
     /// Write rotate-keys transaction
     fn write_rotate_keys_transaction(
         &self,
@@ -496,6 +654,13 @@ pub trait DbWrite {
         prevout: &model::TxPrevout,
     ) -> impl Future<Output = Result<(), Error>> + Send;
 
+    /// Write the magic bytes used in the `OP_RETURN` output of a sweep
+    /// transaction to the database.
+    fn write_sweep_magic_bytes(
+        &self,
+        magic_bytes: [u8; 2],
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
     /// Write the bitcoin transactions sighaes to the database.
     fn write_bitcoin_txs_sighashes(
         &self,
@@ -7,6 +7,7 @@ use bitcoin::hashes::Hash as _;
 use bitvec::array::BitArray;
 use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
 use clarity::vm::types::PrincipalData;
+use sbtc::deposits::CreateDepositRequest;
 use stacks_common::types::chainstate::BurnchainHeaderHash;
 use stacks_common::types::chainstate::StacksBlockId;
 
@@ -262,26 +263,32 @@ pub struct BitcoinBlock {
     pub block_height: u64,
     /// Hash of the parent block.
     pub parent_hash: BitcoinBlockHash,
+    /// The median-time-past of the block, which is the median of the
+    /// timestamps of this block and the ten blocks before it. It is a unix
+    /// timestamp in seconds, and is what time based relative locktimes are
+    /// measured against.
+    #[sqlx(try_from = "i64")]
+    #[cfg_attr(feature = "testing", dummy(faker = "1_231_006_505..2_000_000_000"))]
+    pub median_time_past: u64,
 }
 
-impl From<&bitcoin::Block> for BitcoinBlock {
-    fn from(block: &bitcoin::Block) -> Self {
+impl BitcoinBlock {
+    /// Create a new bitcoin block from the block and its median-time-past.
+    ///
+    /// The median-time-past depends on the timestamps of the ancestors of
+    /// the block, so it cannot be derived from the block alone.
+    pub fn new(block: &bitcoin::Block, median_time_past: u64) -> Self {
         BitcoinBlock {
             block_hash: block.block_hash().into(),
             block_height: block
                 .bip34_block_height()
                 .expect("Failed to get block height"),
             parent_hash: block.header.prev_blockhash.into(),
+            median_time_past,
         }
     }
 }
 
-impl From<bitcoin::Block> for BitcoinBlock {
-    fn from(block: bitcoin::Block) -> Self {
-        BitcoinBlock::from(&block)
-    }
-}
-
 /// Stacks block.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
 #[cfg_attr(feature = "testing", derive(fake::Dummy))]
@@ -385,6 +392,49 @@ impl DepositRequest {
     }
 }
 
+/// A deposit request fetched from Emily that could not be stored yet,
+/// because its transaction has not been confirmed or because validating
+/// it hit a transient error. The block observer validates it again on
+/// every sync until it is stored or fails validation.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct EmilyDepositSyncRetry {
+    /// Transaction ID of the deposit request transaction.
+    pub txid: BitcoinTxId,
+    /// Index of the deposit request UTXO.
+    #[cfg_attr(feature = "testing", dummy(faker = "0..100"))]
+    #[sqlx(try_from = "i32")]
+    pub output_index: u32,
+    /// Script spendable by the sBTC signers.
+    pub spend_script: Bytes,
+    /// Script spendable by the depositor.
+    pub reclaim_script: Bytes,
+}
+
+impl From<&CreateDepositRequest> for EmilyDepositSyncRetry {
+    fn from(request: &CreateDepositRequest) -> Self {
+        Self {
+            txid: request.outpoint.txid.into(),
+            output_index: request.outpoint.vout,
+            spend_script: request.deposit_script.to_bytes(),
+            reclaim_script: request.reclaim_script.to_bytes(),
+        }
+    }
+}
+
+impl From<EmilyDepositSyncRetry> for CreateDepositRequest {
+    fn from(retry: EmilyDepositSyncRetry) -> Self {
+        Self {
+            outpoint: bitcoin::OutPoint {
+                txid: retry.txid.into(),
+                vout: retry.output_index,
+            },
+            deposit_script: bitcoin::ScriptBuf::from_bytes(retry.spend_script),
+            reclaim_script: bitcoin::ScriptBuf::from_bytes(retry.reclaim_script),
+        }
+    }
+}
+
 /// A signer acknowledging a deposit request.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
 #[cfg_attr(feature = "testing", derive(fake::Dummy))]
@@ -573,6 +623,11 @@ pub struct SweptWithdrawalRequest {
     /// The block height of the block that includes the sweep transaction.
     #[sqlx(try_from = "i64")]
     pub sweep_block_height: u64,
+    /// The index of the output in the sweep transaction that fulfills
+    /// this withdrawal request.
+    #[cfg_attr(feature = "testing", dummy(faker = "2..100"))]
+    #[sqlx(try_from = "i32")]
+    pub output_index: u32,
     /// Request ID of the withdrawal request. These are supposed to be
     /// unique, but there can be duplicates if there is a reorg that
     /// affects a transaction that calls the `initiate-withdrawal-request`
@@ -601,6 +656,26 @@ pub struct SweptWithdrawalRequest {
     pub sender_address: StacksPrincipal,
 }
 
+impl SweptWithdrawalRequest {
+    /// The OutPoint of the sweep transaction output that fulfills the
+    /// withdrawal request.
+    pub fn withdrawal_outpoint(&self) -> bitcoin::OutPoint {
+        bitcoin::OutPoint {
+            txid: self.sweep_txid.into(),
+            vout: self.output_index,
+        }
+    }
+
+    /// Return the identifier for the withdrawal request.
+    pub fn qualified_id(&self) -> QualifiedRequestId {
+        QualifiedRequestId {
+            request_id: self.request_id,
+            txid: self.txid,
+            block_hash: self.block_hash,
+        }
+    }
+}
+
 /// Persisted DKG shares
 ///
 /// This struct represents the output of a successful run of distributed
@@ -630,6 +705,92 @@ pub struct EncryptedDkgShares {
     pub signature_share_threshold: u16,
 }
 
+/// A round of distributed key generation (DKG) that the signer has taken
+/// part in.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct DkgAttempt {
+    /// The identifier of the DKG round. WSTS messages carry this in their
+    /// `txid` field, even though it is not a transaction ID.
+    pub dkg_id: BitcoinTxId,
+    /// The bitcoin chain tip when the DKG round was started.
+    pub bitcoin_chain_tip: BitcoinBlockHash,
+    /// The public keys of the signers that were asked to take part.
+    pub signer_set_public_keys: Vec<PublicKey>,
+    /// How far the DKG round has gotten.
+    pub status: DkgAttemptStatus,
+    /// The aggregate key generated by the round, if it succeeded.
+    pub aggregate_key: Option<PublicKey>,
+}
+
+/// A signer being blamed for the failure of a DKG round.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct DkgSignerFailure {
+    /// The identifier of the failed DKG round.
+    pub dkg_id: BitcoinTxId,
+    /// The public key of the signer that is blamed for the failure.
+    pub signer_public_key: PublicKey,
+    /// The public key of the signer that reported the failure.
+    pub reported_by: PublicKey,
+    /// What the blamed signer did wrong.
+    pub reason: DkgFailureReason,
+}
+
+/// How a signer took part in a DKG, WSTS signing or Stacks multi-sig round
+/// coordinated by this signer.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct SignerParticipation {
+    /// The identifier of the round. This is the `txid` field of the WSTS
+    /// messages for DKG and WSTS signing rounds, and the transaction ID of
+    /// the Stacks transaction for Stacks multi-sig rounds.
+    pub round_id: [u8; 32],
+    /// The kind of round.
+    pub round_type: ParticipationRoundType,
+    /// The bitcoin chain tip when the round was run.
+    pub bitcoin_chain_tip: BitcoinBlockHash,
+    /// The public key of the signer.
+    pub signer_public_key: PublicKey,
+    /// Whether the signer responded, sent invalid data, or did not respond.
+    pub outcome: ParticipationOutcome,
+    /// The number of milliseconds between the start of the round and the
+    /// signer's response, if the signer responded.
+    pub response_time_ms: Option<i64>,
+}
+
+/// A summary of how a signer took part in its most recent rounds.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct SignerParticipationStats {
+    /// The public key of the signer.
+    pub signer_public_key: PublicKey,
+    /// The number of rounds in which the signer responded.
+    #[sqlx(try_from = "i64")]
+    pub responded: u32,
+    /// The number of rounds in which the signer sent invalid data.
+    #[sqlx(try_from = "i64")]
+    pub invalid: u32,
+    /// The number of rounds in which the signer did not respond.
+    #[sqlx(try_from = "i64")]
+    pub missing: u32,
+    /// The average number of milliseconds the signer took to respond.
+    pub avg_response_time_ms: Option<i64>,
+}
+
+impl SignerParticipationStats {
+    /// The fraction of rounds in which the signer either sent invalid
+    /// data or did not respond at all.
+    pub fn failure_rate(&self) -> f64 {
+        let failures = self.invalid as f64 + self.missing as f64;
+        let total = failures + self.responded as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        failures / total
+    }
+}
+
 /// Persisted public DKG shares from other signers
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
 #[cfg_attr(feature = "testing", derive(fake::Dummy))]
@@ -673,6 +834,17 @@ impl Deref for SignerVotes {
     }
 }
 
+impl SignerVotes {
+    /// The number of signers that explicitly voted against the request.
+    /// Missing votes are not counted.
+    pub fn num_rejections(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|vote| vote.is_accepted == Some(false))
+            .count()
+    }
+}
+
 impl From<Vec<SignerVote>> for SignerVotes {
     fn from(mut votes: Vec<SignerVote>) -> Self {
         votes.sort_by_key(|vote| vote.signer_public_key);
@@ -767,6 +939,79 @@ pub enum TxPrevoutType {
     Deposit,
 }
 
+/// The status of a round of distributed key generation.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::Type, strum::Display)]
+#[sqlx(type_name = "dkg_attempt_status", rename_all = "snake_case")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum DkgAttemptStatus {
+    /// The round has started but has not ended yet.
+    Pending,
+    /// The round ended with a new aggregate key.
+    Success,
+    /// At least one signer reported that the round failed.
+    Failed,
+    /// The round did not end before the round timeout.
+    TimedOut,
+}
+
+/// The kinds of rounds for which signer participation is tracked.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::Type, strum::Display)]
+#[sqlx(type_name = "participation_round_type", rename_all = "snake_case")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum ParticipationRoundType {
+    /// A round of distributed key generation.
+    Dkg,
+    /// The nonce gathering phase of a WSTS signing round.
+    Nonce,
+    /// The signature share phase of a WSTS signing round.
+    SignatureShare,
+    /// The gathering of signatures for a Stacks multi-sig transaction.
+    StacksSignature,
+}
+
+/// How a signer took part in a round.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::Type, strum::Display)]
+#[sqlx(type_name = "participation_outcome", rename_all = "snake_case")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum ParticipationOutcome {
+    /// The signer sent a valid response.
+    Responded,
+    /// The signer sent a response that failed validation.
+    Invalid,
+    /// The signer did not respond before the round ended without
+    /// completing.
+    Missing,
+}
+
+/// The reasons for blaming a signer for a failed DKG round. Most of these
+/// mirror the failures that WSTS signers report in their `DkgEnd`
+/// messages.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::Type, strum::Display)]
+#[sqlx(type_name = "dkg_failure_reason", rename_all = "snake_case")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum DkgFailureReason {
+    /// The signer reported that its own state machine was in a bad state.
+    BadState,
+    /// The signer did not send its public shares.
+    MissingPublicShares,
+    /// The signer sent invalid public shares.
+    BadPublicShares,
+    /// The signer did not send its private shares.
+    MissingPrivateShares,
+    /// The signer sent invalid private shares.
+    BadPrivateShares,
+    /// The signer sent DKG shares that were not signed by its key.
+    BadSignature,
+}
+
 /// An identifier for a withdrawal request, comprised of the Stacks
 /// transaction ID, the Stacks block ID that included the transaction, and
 /// the request-id generated by the clarity contract for the withdrawal
@@ -1,5 +1,6 @@
 //! Postgres storage implementation.
 
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::sync::OnceLock;
@@ -19,6 +20,7 @@ use crate::bitcoin::utxo::SignerUtxo;
 use crate::bitcoin::validation::DepositConfirmationStatus;
 use crate::bitcoin::validation::DepositRequestReport;
 use crate::bitcoin::validation::WithdrawalRequestReport;
+use crate::bitcoin::validation::WithdrawalRequestStatus;
 use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::keys::PublicKeyXOnly;
@@ -29,6 +31,7 @@ use crate::stacks::events::WithdrawalRejectEvent;
 use crate::storage::model;
 use crate::storage::model::TransactionType;
 use crate::DEPOSIT_LOCKTIME_BLOCK_BUFFER;
+use crate::DEPOSIT_LOCKTIME_TIME_BUFFER;
 
 /// All migration scripts from the `signer/migrations` directory.
 static PGSQL_MIGRATIONS: include_dir::Dir =
@@ -50,11 +53,13 @@ const CONTRACT_NAMES: [&str; 4] = [
 ];
 
 #[rustfmt::skip]
-const CONTRACT_FUNCTION_NAMES: [(&str, TransactionType); 5] = [
+const CONTRACT_FUNCTION_NAMES: [(&str, TransactionType); 7] = [
     ("initiate-withdrawal-request", TransactionType::WithdrawRequest),
     ("complete-deposit-wrapper", TransactionType::DepositAccept),
+    ("complete-deposits-wrapper", TransactionType::DepositAccept),
     ("accept-withdrawal-request", TransactionType::WithdrawAccept),
     ("reject-withdrawal-request", TransactionType::WithdrawReject),
+    ("complete-withdrawals", TransactionType::WithdrawAccept),
     ("rotate-keys-wrapper", TransactionType::RotateKeys),
 ];
 
@@ -111,6 +116,9 @@ struct DepositStatusSummary {
     block_height: Option<i64>,
     /// The block hash that confirmed the deposit request.
     block_hash: Option<model::BitcoinBlockHash>,
+    /// The median-time-past of the parent of the block that confirmed
+    /// the deposit request transaction.
+    parent_median_time_past: Option<i64>,
     /// The bitcoin consensus encoded locktime in the reclaim script.
     #[sqlx(try_from = "i64")]
     lock_time: u32,
@@ -129,6 +137,28 @@ struct DepositStatusSummary {
     signers_public_key: PublicKeyXOnly,
 }
 
+/// A convenience struct for retrieving a withdrawal request report
+#[derive(sqlx::FromRow)]
+struct WithdrawalStatusSummary {
+    /// The current signer may not have a record of their vote for the
+    /// withdrawal request. When that happens the `is_accepted` field will
+    /// be None.
+    is_accepted: Option<bool>,
+    /// The amount of sats to withdraw.
+    #[sqlx(try_from = "i64")]
+    amount: u64,
+    /// The maximum amount to spend for the bitcoin miner fee when sweeping
+    /// out the funds.
+    #[sqlx(try_from = "i64")]
+    max_fee: u64,
+    /// The scriptPubKey of the recipient of the withdrawal.
+    recipient: model::ScriptPubKey,
+    /// The height of the stacks block that includes the withdrawal
+    /// request.
+    #[sqlx(try_from = "i64")]
+    stacks_block_height: u64,
+}
+
 // A convenience struct for retriving the signers' UTXO
 #[derive(sqlx::FromRow)]
 struct PgSignerUtxo {
@@ -261,6 +291,47 @@ impl PgStore {
         Ok(())
     }
 
+    /// Return the file name of every migration script, in the order that
+    /// they are applied, along with whether it has been applied to the
+    /// database.
+    pub async fn migration_status(&self) -> Result<Vec<(String, bool)>, Error> {
+        // The migrations table is created by the first migration run, so
+        // it may not exist yet.
+        let table_exists = sqlx::query_scalar::<_, bool>(
+            "SELECT to_regclass('public.__sbtc_migrations') IS NOT NULL;",
+        )
+        .fetch_one(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let applied: BTreeSet<String> = if table_exists {
+            sqlx::query_scalar::<_, String>("SELECT key FROM public.__sbtc_migrations;")
+                .fetch_all(&self.0)
+                .await
+                .map_err(Error::SqlxQuery)?
+                .into_iter()
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+
+        let mut migrations = PGSQL_MIGRATIONS
+            .files()
+            .filter_map(|file| file.path().file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .filter(|key| key.ends_with(".sql"))
+            .collect::<Vec<_>>();
+        migrations.sort();
+
+        Ok(migrations
+            .into_iter()
+            .map(|key| {
+                let is_applied = applied.contains(&key);
+                (key, is_applied)
+            })
+            .collect())
+    }
+
     /// Check if a migration with the given `key` exists.
     async fn check_migration_existence(
         &self,
@@ -535,6 +606,7 @@ impl PgStore {
                     block_hash
                   , block_height
                   , parent_hash
+                  , median_time_past
                 FROM sbtc_signer.bitcoin_blocks
                 WHERE block_hash = $1
 
@@ -544,6 +616,7 @@ impl PgStore {
                     child.block_hash
                   , child.block_height
                   , child.parent_hash
+                  , child.median_time_past
                 FROM sbtc_signer.bitcoin_blocks AS child
                 JOIN block_chain AS parent
                   ON child.block_hash = parent.parent_hash
@@ -560,9 +633,12 @@ impl PgStore {
               , dr.signers_public_key
               , bc.block_height
               , bc.block_hash
+              , parent.median_time_past AS parent_median_time_past
             FROM sbtc_signer.deposit_requests AS dr
             JOIN sbtc_signer.bitcoin_transactions USING (txid)
             LEFT JOIN block_chain AS bc USING (block_hash)
+            LEFT JOIN sbtc_signer.bitcoin_blocks AS parent
+              ON parent.block_hash = bc.parent_hash
             LEFT JOIN sbtc_signer.deposit_signers AS ds
               ON dr.txid = ds.txid
              AND dr.output_index = ds.output_index
@@ -582,6 +658,214 @@ impl PgStore {
         .map_err(Error::SqlxQuery)
     }
 
+    /// Fetch a status summary of a withdrawal request.
+    ///
+    /// This includes the details of the withdrawal request, the height of
+    /// the stacks block that includes it, and how this signer voted on
+    /// it. `None` is returned if we do not have a record of the withdrawal
+    /// request.
+    async fn get_withdrawal_request_status_summary(
+        &self,
+        id: &model::QualifiedRequestId,
+        signer_public_key: &PublicKey,
+    ) -> Result<Option<WithdrawalStatusSummary>, Error> {
+        sqlx::query_as::<_, WithdrawalStatusSummary>(
+            r#"
+            SELECT
+                ws.is_accepted
+              , wr.amount
+              , wr.max_fee
+              , wr.recipient
+              , sb.block_height AS stacks_block_height
+            FROM sbtc_signer.withdrawal_requests AS wr
+            JOIN sbtc_signer.stacks_blocks AS sb
+              ON sb.block_hash = wr.block_hash
+            LEFT JOIN sbtc_signer.withdrawal_signers AS ws
+              ON ws.request_id = wr.request_id
+             AND ws.block_hash = wr.block_hash
+             AND ws.signer_pub_key = $4
+            WHERE wr.request_id = $1
+              AND wr.block_hash = $2
+              AND wr.txid = $3
+            LIMIT 1
+            "#,
+        )
+        .bind(i64::try_from(id.request_id).map_err(Error::ConversionDatabaseInt)?)
+        .bind(id.block_hash)
+        .bind(id.txid)
+        .bind(signer_public_key)
+        .fetch_optional(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    /// Return the height and block hash of the bitcoin block anchoring
+    /// the stacks block that includes the withdrawal request, if that
+    /// stacks block is on the stacks blockchain identified by the given
+    /// stacks chain tip.
+    ///
+    /// The `min_block_height` is the height of the stacks block that
+    /// includes the withdrawal request, and is used as the stopping
+    /// criteria when walking back the stacks blockchain.
+    async fn get_withdrawal_request_anchor(
+        &self,
+        stacks_chain_tip: &model::StacksBlockHash,
+        id: &model::QualifiedRequestId,
+        min_block_height: u64,
+    ) -> Result<Option<(u64, model::BitcoinBlockHash)>, Error> {
+        let anchor = sqlx::query_as::<_, (i64, model::BitcoinBlockHash)>(
+            r#"
+            WITH RECURSIVE stacks_chain AS (
+                SELECT
+                    block_hash
+                  , block_height
+                  , parent_hash
+                  , bitcoin_anchor
+                FROM sbtc_signer.stacks_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.block_height
+                  , parent.parent_hash
+                  , parent.bitcoin_anchor
+                FROM sbtc_signer.stacks_blocks AS parent
+                JOIN stacks_chain AS child
+                  ON parent.block_hash = child.parent_hash
+                WHERE parent.block_height >= $2
+            )
+            SELECT
+                bb.block_height
+              , bb.block_hash
+            FROM stacks_chain AS sc
+            JOIN sbtc_signer.bitcoin_blocks AS bb
+              ON bb.block_hash = sc.bitcoin_anchor
+            WHERE sc.block_hash = $3
+            LIMIT 1
+            "#,
+        )
+        .bind(stacks_chain_tip)
+        .bind(i64::try_from(min_block_height).map_err(Error::ConversionDatabaseInt)?)
+        .bind(id.block_hash)
+        .fetch_optional(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        anchor
+            .map(|(height, block_hash)| {
+                // Block heights are stored as BIGINTs after conversion
+                // from u64s, so converting back to u64s is actually safe.
+                let height = u64::try_from(height).map_err(Error::ConversionDatabaseInt)?;
+                Ok((height, block_hash))
+            })
+            .transpose()
+    }
+
+    /// Return the txid of the bitcoin transaction that fulfilled the
+    /// withdrawal request. The sweep transaction must be confirmed on the
+    /// blockchain identified by the given chain tip.
+    ///
+    /// This query only looks back at transactions that are confirmed at or
+    /// after the given `min_block_height`.
+    async fn get_withdrawal_sweep_txid(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        id: &model::QualifiedRequestId,
+        min_block_height: u64,
+    ) -> Result<Option<model::BitcoinTxId>, Error> {
+        sqlx::query_scalar::<_, model::BitcoinTxId>(
+            r#"
+            WITH RECURSIVE block_chain AS (
+                SELECT
+                    block_hash
+                  , block_height
+                  , parent_hash
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.block_height
+                  , parent.parent_hash
+                FROM sbtc_signer.bitcoin_blocks AS parent
+                JOIN block_chain AS child
+                  ON parent.block_hash = child.parent_hash
+                WHERE parent.block_height >= $2
+            )
+            SELECT sw.sweep_transaction_txid
+            FROM sbtc_signer.swept_withdrawals AS sw
+            JOIN sbtc_signer.bitcoin_transactions AS bt
+              ON bt.txid = sw.sweep_transaction_txid
+            JOIN block_chain USING (block_hash)
+            WHERE sw.withdrawal_request_id = $3
+              AND sw.withdrawal_request_block_hash = $4
+            LIMIT 1
+            "#,
+        )
+        .bind(chain_tip)
+        .bind(i64::try_from(min_block_height).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(id.request_id).map_err(Error::ConversionDatabaseInt)?)
+        .bind(id.block_hash)
+        .fetch_optional(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    /// Check whether there is a `withdrawal-reject` event for the
+    /// withdrawal request on the stacks blockchain identified by the
+    /// given stacks chain tip.
+    ///
+    /// The `min_block_height` is the height of the stacks block that
+    /// includes the withdrawal request, since a rejection cannot happen
+    /// before the request was made.
+    async fn withdrawal_request_rejected(
+        &self,
+        stacks_chain_tip: &model::StacksBlockHash,
+        id: &model::QualifiedRequestId,
+        min_block_height: u64,
+    ) -> Result<bool, Error> {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            WITH RECURSIVE stacks_chain AS (
+                SELECT
+                    block_hash
+                  , block_height
+                  , parent_hash
+                FROM sbtc_signer.stacks_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.block_height
+                  , parent.parent_hash
+                FROM sbtc_signer.stacks_blocks AS parent
+                JOIN stacks_chain AS child
+                  ON parent.block_hash = child.parent_hash
+                WHERE parent.block_height >= $2
+            )
+            SELECT EXISTS (
+                SELECT TRUE
+                FROM sbtc_signer.withdrawal_reject_events AS wre
+                JOIN stacks_chain AS sc
+                  ON sc.block_hash = wre.block_hash
+                WHERE wre.request_id = $3
+            )
+            "#,
+        )
+        .bind(stacks_chain_tip)
+        .bind(i64::try_from(min_block_height).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(id.request_id).map_err(Error::ConversionDatabaseInt)?)
+        .fetch_one(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
     /// Attempts to retrieve an entire transaction package by id.
     ///
     /// TODO: This could be made more efficient and shorter by returning a table
@@ -663,6 +947,176 @@ impl PgStore {
 
         Ok(Some(transaction))
     }
+
+    /// Get the withdrawal requests on the canonical stacks blockchain
+    /// that have not been swept out and have no responses, optionally
+    /// restricted to the request with the given request ID.
+    ///
+    /// See [`crate::storage::DbRead::get_unfulfilled_withdrawal_requests`]
+    /// for the criteria used here.
+    async fn fetch_unfulfilled_withdrawal_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        stacks_chain_tip: &model::StacksBlockHash,
+        request_id: Option<u64>,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        let request_id = request_id
+            .map(i64::try_from)
+            .transpose()
+            .map_err(Error::ConversionDatabaseInt)?;
+
+        sqlx::query_as::<_, model::WithdrawalRequest>(
+            r#"
+            WITH bitcoin_blockchain AS (
+                SELECT block_hash
+                FROM bitcoin_blockchain_of($1, $2)
+            ),
+            stacks_blockchain AS (
+                SELECT block_hash
+                FROM stacks_blockchain_of($3, $1, $2)
+            )
+            SELECT
+                wr.request_id
+              , wr.txid
+              , wr.block_hash
+              , wr.recipient
+              , wr.amount
+              , wr.max_fee
+              , wr.sender_address
+            FROM sbtc_signer.withdrawal_requests AS wr
+            JOIN stacks_blockchain AS sb
+              ON sb.block_hash = wr.block_hash
+            WHERE ($4::BIGINT IS NULL OR wr.request_id = $4)
+            AND NOT EXISTS (
+                SELECT 1
+                FROM sbtc_signer.swept_withdrawals AS sw
+                JOIN sbtc_signer.bitcoin_transactions AS bt
+                  ON bt.txid = sw.sweep_transaction_txid
+                JOIN bitcoin_blockchain AS bb
+                  ON bb.block_hash = bt.block_hash
+                WHERE sw.withdrawal_request_id = wr.request_id
+                  AND sw.withdrawal_request_block_hash = wr.block_hash
+            )
+            AND NOT EXISTS (
+                SELECT 1
+                FROM sbtc_signer.withdrawal_accept_events AS wae
+                JOIN stacks_blockchain AS sb2
+                  ON sb2.block_hash = wae.block_hash
+                WHERE wae.request_id = wr.request_id
+            )
+            AND NOT EXISTS (
+                SELECT 1
+                FROM sbtc_signer.withdrawal_reject_events AS wre
+                JOIN stacks_blockchain AS sb3
+                  ON sb3.block_hash = wre.block_hash
+                WHERE wre.request_id = wr.request_id
+            )
+            "#,
+        )
+        .bind(chain_tip)
+        .bind(i32::from(context_window))
+        .bind(stacks_chain_tip)
+        .bind(request_id)
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    /// Get the withdrawal requests that have been swept out by a sweep
+    /// transaction on the canonical bitcoin blockchain and have no
+    /// responses, optionally restricted to the request with the given
+    /// request ID.
+    ///
+    /// See [`crate::storage::DbRead::get_swept_withdrawal_requests`] for
+    /// the criteria used here.
+    async fn fetch_swept_withdrawal_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        stacks_chain_tip: &model::StacksBlockHash,
+        request_id: Option<u64>,
+    ) -> Result<Vec<model::SweptWithdrawalRequest>, Error> {
+        // The following tests define the criteria for this query:
+        // - [X] get_swept_withdrawal_requests_returns_swept_withdrawal_requests
+        // - [X] get_swept_withdrawal_requests_does_not_return_unswept_withdrawal_requests
+        // - [X] get_swept_withdrawal_requests_does_not_return_withdrawal_requests_with_responses
+        // - [X] get_swept_withdrawal_requests_does_not_return_rejected_withdrawal_requests
+        // - [X] get_swept_withdrawal_requests_response_tx_reorged
+
+        let request_id = request_id
+            .map(i64::try_from)
+            .transpose()
+            .map_err(Error::ConversionDatabaseInt)?;
+
+        sqlx::query_as::<_, model::SweptWithdrawalRequest>(
+            "
+            SELECT
+                bc_trx.txid AS sweep_txid
+              , bc_trx.block_hash AS sweep_block_hash
+              , bc_blocks.block_height AS sweep_block_height
+              , swept_withdrawal.output_index
+              , wr.request_id
+              , wr.txid
+              , wr.block_hash
+              , wr.recipient
+              , wr.amount
+              , wr.max_fee
+              , wr.sender_address
+            FROM
+                bitcoin_blockchain_of($1, $2) AS bc_blocks
+            INNER JOIN
+                bitcoin_transactions AS bc_trx
+                    ON bc_trx.block_hash = bc_blocks.block_hash
+            INNER JOIN
+                sweep_transactions AS sweep_tx
+                    ON bc_trx.txid = sweep_tx.txid
+            INNER JOIN
+                swept_withdrawals AS swept_withdrawal
+                    ON swept_withdrawal.sweep_transaction_txid = sweep_tx.txid
+            INNER JOIN
+                withdrawal_requests AS wr
+                    ON wr.request_id = swept_withdrawal.withdrawal_request_id
+                    AND wr.block_hash = swept_withdrawal.withdrawal_request_block_hash
+            LEFT JOIN
+                withdrawal_accept_events AS wae
+                    ON wae.request_id = wr.request_id
+            LEFT JOIN
+                stacks_blockchain_of($3, $1, $2) sb
+                    ON sb.block_hash = wae.block_hash
+            LEFT JOIN
+                withdrawal_reject_events AS wre
+                    ON wre.request_id = wr.request_id
+            LEFT JOIN
+                stacks_blockchain_of($3, $1, $2) sb_reject
+                    ON sb_reject.block_hash = wre.block_hash
+            WHERE
+                $4::BIGINT IS NULL OR wr.request_id = $4
+            GROUP BY
+                bc_trx.txid
+              , bc_trx.block_hash
+              , bc_blocks.block_height
+              , swept_withdrawal.output_index
+              , wr.request_id
+              , wr.txid
+              , wr.block_hash
+              , wr.recipient
+              , wr.amount
+              , wr.max_fee
+              , wr.sender_address
+            HAVING
+                COUNT(sb.block_hash) = 0
+                AND COUNT(sb_reject.block_hash) = 0
+        ",
+        )
+        .bind(chain_tip)
+        .bind(i32::from(context_window))
+        .bind(stacks_chain_tip)
+        .bind(request_id)
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
 }
 
 impl From<sqlx::PgPool> for PgStore {
@@ -681,6 +1135,7 @@ impl super::DbRead for PgStore {
                 block_hash
               , block_height
               , parent_hash
+              , median_time_past
             FROM sbtc_signer.bitcoin_blocks
             WHERE block_hash = $1;",
         )
@@ -717,6 +1172,7 @@ impl super::DbRead for PgStore {
                 block_hash
               , block_height
               , parent_hash
+              , median_time_past
              FROM sbtc_signer.bitcoin_blocks
              ORDER BY block_height DESC, block_hash DESC
              LIMIT 1",
@@ -835,16 +1291,27 @@ impl super::DbRead for PgStore {
         context_window: u16,
         threshold: u16,
     ) -> Result<Vec<model::DepositRequest>, Error> {
+        let chain_tip_block = self
+            .get_bitcoin_block(chain_tip)
+            .await?
+            .ok_or(Error::MissingBitcoinBlock(*chain_tip))?;
         // Add one to the acceptable unlock height because the chain tip is at height one less
         // than the height of the next block, which is the block for which we are assessing
         // the threshold.
-        let minimum_acceptable_unlock_height = self
-            .get_bitcoin_block(chain_tip)
-            .await?
-            .ok_or(Error::MissingBitcoinBlock(*chain_tip))?
-            .block_height as i32
-            + DEPOSIT_LOCKTIME_BLOCK_BUFFER as i32
-            + 1;
+        let minimum_acceptable_unlock_height =
+            chain_tip_block.block_height as i32 + DEPOSIT_LOCKTIME_BLOCK_BUFFER as i32 + 1;
+        // Time based lock-times become spendable in the next block once the
+        // median-time-past of the chain tip reaches the unlock time, so we
+        // also add one here. A median-time-past of zero means that the
+        // block was written before we started recording it, and then we
+        // leave out all deposits with time based lock-times.
+        let minimum_acceptable_unlock_time = match chain_tip_block.median_time_past {
+            0 => None,
+            median_time_past => Some(
+                i64::try_from(median_time_past + DEPOSIT_LOCKTIME_TIME_BUFFER + 1)
+                    .map_err(Error::ConversionDatabaseInt)?,
+            ),
+        };
 
         sqlx::query_as::<_, model::DepositRequest>(
             r#"
@@ -855,7 +1322,14 @@ impl super::DbRead for PgStore {
                 SELECT
                     transactions.txid
                   , blocks_in_window.block_height
+                  , parents.median_time_past AS parent_median_time_past
                 FROM context_window blocks_in_window
+                JOIN sbtc_signer.bitcoin_blocks blocks ON
+                    blocks.block_hash = blocks_in_window.block_hash
+                -- BIP-68 measures time based lock-times from the
+                -- median-time-past of the parent of the confirming block.
+                LEFT JOIN sbtc_signer.bitcoin_blocks parents ON
+                    parents.block_hash = blocks.parent_hash
                 JOIN sbtc_signer.bitcoin_transactions transactions ON
                     transactions.block_hash = blocks_in_window.block_hash
             ),
@@ -878,7 +1352,18 @@ impl super::DbRead for PgStore {
                 WHERE
                     signers.can_accept
                     AND signers.can_sign
-                    AND (transactions.block_height + deposit_requests.lock_time) >= $4
+                    -- Bit 22 of the lock-time is set when it is time based
+                    -- (BIP-68), in which case the 16 least significant bits
+                    -- are in units of 512 seconds.
+                    AND CASE
+                        WHEN (deposit_requests.lock_time & 4194304) = 0 THEN
+                            (transactions.block_height + deposit_requests.lock_time) >= $4
+                        -- A median-time-past of zero is unknown.
+                        ELSE
+                            transactions.parent_median_time_past > 0
+                            AND (transactions.parent_median_time_past
+                                + (deposit_requests.lock_time & 65535) * 512) >= $5
+                    END
                 GROUP BY deposit_requests.txid, deposit_requests.output_index
                 HAVING COUNT(signers.txid) >= $3
             )
@@ -911,6 +1396,7 @@ impl super::DbRead for PgStore {
         .bind(i32::from(context_window))
         .bind(i32::from(threshold))
         .bind(minimum_acceptable_unlock_height)
+        .bind(minimum_acceptable_unlock_time)
         .fetch_all(&self.0)
         .await
         .map_err(Error::SqlxQuery)
@@ -1078,6 +1564,14 @@ impl super::DbRead for PgStore {
             status,
             can_sign: summary.can_sign,
             can_accept: summary.can_accept,
+            // A median-time-past of zero means that the block was written
+            // before we started recording it, so we do not know it.
+            parent_median_time_past: summary
+                .parent_median_time_past
+                .filter(|&median_time_past| median_time_past > 0)
+                .map(u64::try_from)
+                .transpose()
+                .map_err(Error::ConversionDatabaseInt)?,
             amount: summary.amount,
             max_fee: summary.max_fee,
             lock_time: bitcoin::relative::LockTime::from_consensus(summary.lock_time)
@@ -1341,19 +1835,110 @@ impl super::DbRead for PgStore {
         .map_err(Error::SqlxQuery)
     }
 
-    async fn get_withdrawal_request_report(
+    async fn get_unfulfilled_withdrawal_requests(
         &self,
-        _chain_tip: &model::BitcoinBlockHash,
-        _id: &model::QualifiedRequestId,
-        _signer_public_key: &PublicKey,
-    ) -> Result<Option<WithdrawalRequestReport>, Error> {
-        // Returning Ok(None) means that all withdrawals fail validation,
-        // because without a report we assume the withdrawal request does
-        // not exist.
-        Ok(None)
-    }
-
-    async fn get_bitcoin_blocks_with_transaction(
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        let Some(stacks_chain_tip) = self.get_stacks_chain_tip(chain_tip).await? else {
+            return Ok(Vec::new());
+        };
+        let stacks_chain_tip = &stacks_chain_tip.block_hash;
+
+        self.fetch_unfulfilled_withdrawal_requests(
+            chain_tip,
+            context_window,
+            stacks_chain_tip,
+            None,
+        )
+        .await
+    }
+
+    async fn get_unfulfilled_withdrawal_request(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        request_id: u64,
+    ) -> Result<Option<model::WithdrawalRequest>, Error> {
+        let Some(stacks_chain_tip) = self.get_stacks_chain_tip(chain_tip).await? else {
+            return Ok(None);
+        };
+        let stacks_chain_tip = &stacks_chain_tip.block_hash;
+
+        let requests = self
+            .fetch_unfulfilled_withdrawal_requests(
+                chain_tip,
+                context_window,
+                stacks_chain_tip,
+                Some(request_id),
+            )
+            .await?;
+        Ok(requests.into_iter().next())
+    }
+
+    async fn get_withdrawal_request_report(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        id: &model::QualifiedRequestId,
+        signer_public_key: &PublicKey,
+    ) -> Result<Option<WithdrawalRequestReport>, Error> {
+        let summary_fut = self.get_withdrawal_request_status_summary(id, signer_public_key);
+        let Some(summary) = summary_fut.await? else {
+            return Ok(None);
+        };
+
+        let Some(stacks_chain_tip) = self.get_stacks_chain_tip(chain_tip).await? else {
+            return Ok(Some(WithdrawalRequestReport {
+                id: *id,
+                status: WithdrawalRequestStatus::Unconfirmed,
+                amount: summary.amount,
+                max_fee: summary.max_fee,
+                script_pubkey: summary.recipient.into(),
+                is_accepted: summary.is_accepted,
+            }));
+        };
+        let stacks_chain_tip = stacks_chain_tip.block_hash;
+        let min_stacks_height = summary.stacks_block_height;
+
+        let block_info_fut =
+            self.get_withdrawal_request_anchor(&stacks_chain_tip, id, min_stacks_height);
+
+        let status = match block_info_fut.await? {
+            // Now that we know that it has been confirmed on the canonical
+            // stacks blockchain, check whether it has been fulfilled by a
+            // sweep transaction or rejected already. We use the height of
+            // the anchor block as the min height for when a sweep
+            // transaction could be confirmed.
+            Some((block_height, block_hash)) => {
+                let sweep_txid_fut = self.get_withdrawal_sweep_txid(chain_tip, id, block_height);
+                let rejected_fut =
+                    self.withdrawal_request_rejected(&stacks_chain_tip, id, min_stacks_height);
+
+                if let Some(txid) = sweep_txid_fut.await? {
+                    WithdrawalRequestStatus::Fulfilled(txid)
+                } else if rejected_fut.await? {
+                    WithdrawalRequestStatus::Rejected
+                } else {
+                    WithdrawalRequestStatus::Confirmed(block_height, block_hash)
+                }
+            }
+            // If we didn't find the stacks block with the request in the
+            // stacks blockchain identified by the chain tip, then the
+            // request is not confirmed on the canonical blockchain.
+            None => WithdrawalRequestStatus::Unconfirmed,
+        };
+
+        Ok(Some(WithdrawalRequestReport {
+            id: *id,
+            status,
+            amount: summary.amount,
+            max_fee: summary.max_fee,
+            script_pubkey: summary.recipient.into(),
+            is_accepted: summary.is_accepted,
+        }))
+    }
+
+    async fn get_bitcoin_blocks_with_transaction(
         &self,
         txid: &model::BitcoinTxId,
     ) -> Result<Vec<model::BitcoinBlockHash>, Error> {
@@ -1409,6 +1994,30 @@ impl super::DbRead for PgStore {
         .map_err(Error::SqlxQuery)
     }
 
+    async fn get_encrypted_dkg_shares_by_script_pubkey(
+        &self,
+        script_pubkey: &model::ScriptPubKey,
+    ) -> Result<Option<model::EncryptedDkgShares>, Error> {
+        sqlx::query_as::<_, model::EncryptedDkgShares>(
+            r#"
+            SELECT
+                aggregate_key
+              , tweaked_aggregate_key
+              , script_pubkey
+              , encrypted_private_shares
+              , public_shares
+              , signer_set_public_keys
+              , signature_share_threshold
+            FROM sbtc_signer.dkg_shares
+            WHERE script_pubkey = $1;
+            "#,
+        )
+        .bind(script_pubkey)
+        .fetch_optional(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
     async fn get_latest_encrypted_dkg_shares(
         &self,
     ) -> Result<Option<model::EncryptedDkgShares>, Error> {
@@ -1432,6 +2041,199 @@ impl super::DbRead for PgStore {
         .map_err(Error::SqlxQuery)
     }
 
+    async fn get_all_encrypted_dkg_shares(&self) -> Result<Vec<model::EncryptedDkgShares>, Error> {
+        sqlx::query_as::<_, model::EncryptedDkgShares>(
+            r#"
+            SELECT
+                aggregate_key
+              , tweaked_aggregate_key
+              , script_pubkey
+              , encrypted_private_shares
+              , public_shares
+              , signer_set_public_keys
+              , signature_share_threshold
+            FROM sbtc_signer.dkg_shares
+            ORDER BY created_at ASC;
+            "#,
+        )
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_latest_dkg_attempt(&self) -> Result<Option<model::DkgAttempt>, Error> {
+        sqlx::query_as::<_, model::DkgAttempt>(
+            r#"
+            SELECT
+                dkg_id
+              , bitcoin_chain_tip
+              , signer_set_public_keys
+              , status
+              , aggregate_key
+            FROM sbtc_signer.dkg_attempts
+            ORDER BY created_at DESC
+            LIMIT 1;
+            "#,
+        )
+        .fetch_optional(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_dkg_failures(
+        &self,
+        dkg_id: &model::BitcoinTxId,
+    ) -> Result<Vec<model::DkgSignerFailure>, Error> {
+        sqlx::query_as::<_, model::DkgSignerFailure>(
+            r#"
+            SELECT
+                dkg_id
+              , signer_public_key
+              , reported_by
+              , reason
+            FROM sbtc_signer.dkg_failures
+            WHERE dkg_id = $1
+            ORDER BY signer_public_key, reported_by, reason;
+            "#,
+        )
+        .bind(dkg_id)
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_dkg_failure_counts(&self) -> Result<BTreeMap<PublicKey, u32>, Error> {
+        let counts = sqlx::query_as::<_, (PublicKey, i64)>(
+            r#"
+            SELECT
+                signer_public_key
+              , COUNT(DISTINCT dkg_id)
+            FROM sbtc_signer.dkg_failures
+            WHERE created_at > COALESCE(
+                (SELECT MAX(created_at) FROM sbtc_signer.dkg_shares),
+                '-infinity'::TIMESTAMPTZ
+            )
+            GROUP BY signer_public_key;
+            "#,
+        )
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        counts
+            .into_iter()
+            .map(|(public_key, count)| {
+                let count = u32::try_from(count).map_err(Error::ConversionDatabaseInt)?;
+                Ok((public_key, count))
+            })
+            .collect()
+    }
+
+    async fn get_dkg_blamed_signers(&self) -> Result<BTreeSet<PublicKey>, Error> {
+        let blamed = sqlx::query_scalar::<_, PublicKey>(
+            r#"
+            SELECT DISTINCT signer_public_key
+            FROM sbtc_signer.dkg_failures;
+            "#,
+        )
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(blamed.into_iter().collect())
+    }
+
+    async fn get_signer_participation(
+        &self,
+        round_id: &[u8; 32],
+    ) -> Result<Vec<model::SignerParticipation>, Error> {
+        sqlx::query_as::<_, model::SignerParticipation>(
+            r#"
+            SELECT
+                round_id
+              , round_type
+              , bitcoin_chain_tip
+              , signer_public_key
+              , outcome
+              , response_time_ms
+            FROM sbtc_signer.signer_participation
+            WHERE round_id = $1
+            ORDER BY id;
+            "#,
+        )
+        .bind(round_id)
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_emily_deposit_sync_height(&self) -> Result<Option<u64>, Error> {
+        let min_height = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT min_height
+            FROM sbtc_signer.emily_deposit_sync;
+            "#,
+        )
+        .fetch_optional(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        min_height
+            .map(|height| u64::try_from(height).map_err(Error::ConversionDatabaseInt))
+            .transpose()
+    }
+
+    async fn get_emily_deposit_sync_retries(
+        &self,
+    ) -> Result<Vec<model::EmilyDepositSyncRetry>, Error> {
+        sqlx::query_as::<_, model::EmilyDepositSyncRetry>(
+            r#"
+            SELECT
+                txid
+              , output_index
+              , spend_script
+              , reclaim_script
+            FROM sbtc_signer.emily_deposit_sync_retries
+            ORDER BY created_at, txid, output_index;
+            "#,
+        )
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_signer_participation_stats(
+        &self,
+        window: u32,
+    ) -> Result<Vec<model::SignerParticipationStats>, Error> {
+        sqlx::query_as::<_, model::SignerParticipationStats>(
+            r#"
+            WITH recent AS (
+                SELECT
+                    signer_public_key
+                  , outcome
+                  , response_time_ms
+                  , ROW_NUMBER() OVER (PARTITION BY signer_public_key ORDER BY id DESC) AS rn
+                FROM sbtc_signer.signer_participation
+            )
+            SELECT
+                signer_public_key
+              , COUNT(*) FILTER (WHERE outcome = 'responded') AS responded
+              , COUNT(*) FILTER (WHERE outcome = 'invalid') AS invalid
+              , COUNT(*) FILTER (WHERE outcome = 'missing') AS missing
+              , AVG(response_time_ms)::BIGINT AS avg_response_time_ms
+            FROM recent
+            WHERE rn <= $1
+            GROUP BY signer_public_key
+            ORDER BY signer_public_key;
+            "#,
+        )
+        .bind(i64::from(window))
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
     /// Find the last key rotation by iterating backwards from the stacks
     /// chain tip scanning all transactions until we encounter a key
     /// rotation transactions.
@@ -1563,6 +2365,23 @@ impl super::DbRead for PgStore {
         .map_err(Error::SqlxQuery)
     }
 
+    async fn get_sweep_magic_bytes(&self) -> Result<Vec<[u8; 2]>, Error> {
+        let magic_bytes = sqlx::query_scalar::<_, Vec<u8>>(
+            r#"
+            SELECT magic_bytes
+            FROM sbtc_signer.sweep_magic_bytes;
+            "#,
+        )
+        .fetch_all(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(magic_bytes
+            .into_iter()
+            .filter_map(|bytes| bytes.try_into().ok())
+            .collect())
+    }
+
     async fn get_signer_utxo(
         &self,
         chain_tip: &model::BitcoinBlockHash,
@@ -1732,13 +2551,38 @@ impl super::DbRead for PgStore {
 
     async fn get_swept_withdrawal_requests(
         &self,
-        _chain_tip: &model::BitcoinBlockHash,
-        _context_window: u16,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
     ) -> Result<Vec<model::SweptWithdrawalRequest>, Error> {
-        // TODO: This can use a similiar query to
-        // `get_swept_deposit_requests()`, but using withdrawal tables instead
-        // of deposit.
-        unimplemented!()
+        let Some(stacks_chain_tip) = self.get_stacks_chain_tip(chain_tip).await? else {
+            return Ok(Vec::new());
+        };
+        let stacks_chain_tip = &stacks_chain_tip.block_hash;
+
+        self.fetch_swept_withdrawal_requests(chain_tip, context_window, stacks_chain_tip, None)
+            .await
+    }
+
+    async fn get_swept_withdrawal_request(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        request_id: u64,
+    ) -> Result<Option<model::SweptWithdrawalRequest>, Error> {
+        let Some(stacks_chain_tip) = self.get_stacks_chain_tip(chain_tip).await? else {
+            return Ok(None);
+        };
+        let stacks_chain_tip = &stacks_chain_tip.block_hash;
+
+        let requests = self
+            .fetch_swept_withdrawal_requests(
+                chain_tip,
+                context_window,
+                stacks_chain_tip,
+                Some(request_id),
+            )
+            .await?;
+        Ok(requests.into_iter().next())
     }
 
     async fn get_latest_sweep_transaction(
@@ -1913,13 +2757,15 @@ impl super::DbWrite for PgStore {
               ( block_hash
               , block_height
               , parent_hash
+              , median_time_past
               )
-            VALUES ($1, $2, $3)
+            VALUES ($1, $2, $3, $4)
             ON CONFLICT DO NOTHING",
         )
         .bind(block.block_hash)
         .bind(i64::try_from(block.block_height).map_err(Error::ConversionDatabaseInt)?)
         .bind(block.parent_hash)
+        .bind(i64::try_from(block.median_time_past).map_err(Error::ConversionDatabaseInt)?)
         .execute(&self.0)
         .await
         .map_err(Error::SqlxQuery)?;
@@ -2391,6 +3237,216 @@ impl super::DbWrite for PgStore {
         Ok(())
     }
 
+    async fn write_dkg_attempt(&self, attempt: &model::DkgAttempt) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.dkg_attempts (
+                  dkg_id
+                , bitcoin_chain_tip
+                , signer_set_public_keys
+                , status
+                , aggregate_key)
+            VALUES
+                ($1, $2, $3, $4, $5)
+            ON CONFLICT (dkg_id) DO UPDATE
+            SET status = EXCLUDED.status
+              , aggregate_key = COALESCE(EXCLUDED.aggregate_key, dkg_attempts.aggregate_key)
+              , updated_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(attempt.dkg_id)
+        .bind(attempt.bitcoin_chain_tip)
+        .bind(&attempt.signer_set_public_keys)
+        .bind(attempt.status)
+        .bind(attempt.aggregate_key)
+        .execute(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_dkg_failures(&self, failures: &[model::DkgSignerFailure]) -> Result<(), Error> {
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        let mut dkg_id = Vec::with_capacity(failures.len());
+        let mut signer_public_key = Vec::with_capacity(failures.len());
+        let mut reported_by = Vec::with_capacity(failures.len());
+        let mut reason = Vec::with_capacity(failures.len());
+
+        for failure in failures {
+            dkg_id.push(failure.dkg_id);
+            signer_public_key.push(failure.signer_public_key);
+            reported_by.push(failure.reported_by);
+            reason.push(failure.reason);
+        }
+
+        sqlx::query(
+            r#"
+            WITH dkg_ids             AS (SELECT ROW_NUMBER() OVER (), dkg_id FROM UNNEST($1::BYTEA[]) AS dkg_id)
+            , signer_public_key      AS (SELECT ROW_NUMBER() OVER (), signer_public_key FROM UNNEST($2::BYTEA[]) AS signer_public_key)
+            , reported_by            AS (SELECT ROW_NUMBER() OVER (), reported_by FROM UNNEST($3::BYTEA[]) AS reported_by)
+            , reason                 AS (SELECT ROW_NUMBER() OVER (), reason FROM UNNEST($4::sbtc_signer.dkg_failure_reason[]) AS reason)
+            INSERT INTO sbtc_signer.dkg_failures (
+                  dkg_id
+                , signer_public_key
+                , reported_by
+                , reason)
+            SELECT
+                dkg_id
+              , signer_public_key
+              , reported_by
+              , reason
+            FROM dkg_ids
+            JOIN signer_public_key USING (row_number)
+            JOIN reported_by USING (row_number)
+            JOIN reason USING (row_number)
+            ON CONFLICT DO NOTHING"#,
+        )
+        .bind(dkg_id)
+        .bind(signer_public_key)
+        .bind(reported_by)
+        .bind(reason)
+        .execute(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn set_emily_deposit_sync_height(&self, min_height: u64) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.emily_deposit_sync (min_height)
+            VALUES ($1)
+            ON CONFLICT (id) DO UPDATE
+            SET min_height = EXCLUDED.min_height
+              , updated_at = CURRENT_TIMESTAMP;
+            "#,
+        )
+        .bind(i64::try_from(min_height).map_err(Error::ConversionDatabaseInt)?)
+        .execute(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_emily_deposit_sync_retry(
+        &self,
+        retry: &model::EmilyDepositSyncRetry,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.emily_deposit_sync_retries (
+                txid
+              , output_index
+              , spend_script
+              , reclaim_script
+            )
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING;
+            "#,
+        )
+        .bind(retry.txid)
+        .bind(i32::try_from(retry.output_index).map_err(Error::ConversionDatabaseInt)?)
+        .bind(&retry.spend_script)
+        .bind(&retry.reclaim_script)
+        .execute(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn delete_emily_deposit_sync_retry(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            DELETE FROM sbtc_signer.emily_deposit_sync_retries
+            WHERE txid = $1
+              AND output_index = $2;
+            "#,
+        )
+        .bind(txid)
+        .bind(i32::try_from(output_index).map_err(Error::ConversionDatabaseInt)?)
+        .execute(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_signer_participation(
+        &self,
+        records: &[model::SignerParticipation],
+    ) -> Result<(), Error> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let mut round_id = Vec::with_capacity(records.len());
+        let mut round_type = Vec::with_capacity(records.len());
+        let mut bitcoin_chain_tip = Vec::with_capacity(records.len());
+        let mut signer_public_key = Vec::with_capacity(records.len());
+        let mut outcome = Vec::with_capacity(records.len());
+        let mut response_time_ms = Vec::with_capacity(records.len());
+
+        for record in records {
+            round_id.push(record.round_id);
+            round_type.push(record.round_type);
+            bitcoin_chain_tip.push(record.bitcoin_chain_tip);
+            signer_public_key.push(record.signer_public_key);
+            outcome.push(record.outcome);
+            response_time_ms.push(record.response_time_ms);
+        }
+
+        sqlx::query(
+            r#"
+            WITH round_ids           AS (SELECT ROW_NUMBER() OVER (), round_id FROM UNNEST($1::BYTEA[]) AS round_id)
+            , round_type             AS (SELECT ROW_NUMBER() OVER (), round_type FROM UNNEST($2::sbtc_signer.participation_round_type[]) AS round_type)
+            , bitcoin_chain_tip      AS (SELECT ROW_NUMBER() OVER (), bitcoin_chain_tip FROM UNNEST($3::BYTEA[]) AS bitcoin_chain_tip)
+            , signer_public_key      AS (SELECT ROW_NUMBER() OVER (), signer_public_key FROM UNNEST($4::BYTEA[]) AS signer_public_key)
+            , outcome                AS (SELECT ROW_NUMBER() OVER (), outcome FROM UNNEST($5::sbtc_signer.participation_outcome[]) AS outcome)
+            , response_time_ms       AS (SELECT ROW_NUMBER() OVER (), response_time_ms FROM UNNEST($6::BIGINT[]) AS response_time_ms)
+            INSERT INTO sbtc_signer.signer_participation (
+                  round_id
+                , round_type
+                , bitcoin_chain_tip
+                , signer_public_key
+                , outcome
+                , response_time_ms)
+            SELECT
+                round_id
+              , round_type
+              , bitcoin_chain_tip
+              , signer_public_key
+              , outcome
+              , response_time_ms
+            FROM round_ids
+            JOIN round_type USING (row_number)
+            JOIN bitcoin_chain_tip USING (row_number)
+            JOIN signer_public_key USING (row_number)
+            JOIN outcome USING (row_number)
+            JOIN response_time_ms USING (row_number)"#,
+        )
+        .bind(round_id)
+        .bind(round_type)
+        .bind(bitcoin_chain_tip)
+        .bind(signer_public_key)
+        .bind(outcome)
+        .bind(response_time_ms)
+        .execute(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
     async fn write_rotate_keys_transaction(
         &self,
         key_rotation: &model::RotateKeysTransaction,
@@ -2601,6 +3657,22 @@ impl super::DbWrite for PgStore {
         Ok(())
     }
 
+    async fn write_sweep_magic_bytes(&self, magic_bytes: [u8; 2]) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.sweep_magic_bytes (magic_bytes)
+            VALUES ($1)
+            ON CONFLICT DO NOTHING;
+            "#,
+        )
+        .bind(magic_bytes.as_slice())
+        .execute(&self.0)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
     async fn write_sweep_transaction(
         &self,
         transaction: &model::SweepTransaction,
@@ -3,6 +3,7 @@
 use url::Url;
 
 use crate::bitcoin::MockBitcoinInteract;
+use crate::config::BitcoinConfig;
 use crate::config::Settings;
 use crate::error::Error;
 use crate::stacks::api::MockStacksInteract;
@@ -15,6 +16,14 @@ impl TryFrom<&[Url]> for MockBitcoinInteract {
     }
 }
 
+impl TryFrom<&BitcoinConfig> for MockBitcoinInteract {
+    type Error = Error;
+
+    fn try_from(_: &BitcoinConfig) -> Result<Self, Self::Error> {
+        Ok(Self::default())
+    }
+}
+
 impl TryFrom<&Settings> for MockStacksInteract {
     type Error = Error;
 
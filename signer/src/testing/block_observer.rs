@@ -37,6 +37,7 @@ use crate::bitcoin::GetTransactionFeeResult;
 use crate::bitcoin::TransactionLookupHint;
 use crate::context::SbtcLimits;
 use crate::emily_client::EmilyInteract;
+use crate::emily_client::PendingDeposit;
 use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::stacks::api::AccountInfo;
@@ -46,6 +47,7 @@ use crate::stacks::api::SubmitTxResponse;
 use crate::stacks::api::TenureBlocks;
 use crate::stacks::wallet::SignerWallet;
 use crate::storage::model;
+use crate::testing::btc;
 use crate::testing::dummy;
 use crate::util::ApiFallbackClient;
 
@@ -101,6 +103,18 @@ impl TestHarness {
         self.deposits.insert(txid, (response, tx_info));
     }
 
+    /// Add a transaction to the test harness with the given transaction
+    /// info, for when the inputs or outputs in the info matter.
+    pub fn add_tx_info(&mut self, tx_info: BitcoinTxInfo) {
+        let response = GetTxResponse {
+            tx: tx_info.tx.clone(),
+            block_hash: Some(tx_info.block_hash),
+            confirmations: None,
+            block_time: None,
+        };
+        self.deposits.insert(tx_info.txid, (response, tx_info));
+    }
+
     /// Add multiple deposit transactions to the test harness.
     pub fn add_deposits(&mut self, deposits: &[(Txid, GetTxResponse)]) {
         for (txid, response) in deposits {
@@ -204,6 +218,38 @@ impl TryFrom<TestHarness> for ApiFallbackClient<TestHarness> {
 }
 
 impl BitcoinInteract for TestHarness {
+    async fn get_best_block_hash(&self) -> Result<bitcoin::BlockHash, Error> {
+        unimplemented!()
+    }
+
+    async fn get_block_header(
+        &self,
+        block_hash: &bitcoin::BlockHash,
+    ) -> Result<Option<bitcoincore_rpc_json::GetBlockHeaderResult>, Error> {
+        let Some(index) = self
+            .bitcoin_blocks
+            .iter()
+            .position(|block| &block.block_hash() == block_hash)
+        else {
+            return Ok(None);
+        };
+        let block = &self.bitcoin_blocks[index];
+
+        // The median-time-past is the median of the timestamps of the
+        // block and its (up to) ten ancestors.
+        let mut times: Vec<u32> = self.bitcoin_blocks[index.saturating_sub(10)..=index]
+            .iter()
+            .map(|block| block.header.time)
+            .collect();
+        times.sort_unstable();
+        let median_time = times[times.len() / 2];
+
+        let block_height = block.bip34_block_height().unwrap_or(index as u64);
+        let confirmations = (self.bitcoin_blocks.len() - index) as i32;
+        let header = btc::block_header_result(block, block_height, confirmations, median_time);
+        Ok(Some(header))
+    }
+
     async fn get_tx(&self, txid: &bitcoin::Txid) -> Result<Option<GetTxResponse>, Error> {
         Ok(self.deposits.get(txid).cloned().map(|(resp, _)| resp))
     }
@@ -275,8 +321,8 @@ impl StacksInteract for TestHarness {
         &self,
         _contract_principal: &StacksAddress,
     ) -> Result<Vec<PublicKey>, Error> {
-        // issue #118
-        todo!()
+        // The signers have not rotated keys in the test harness.
+        Ok(Vec::new())
     }
     async fn get_current_signers_aggregate_key(
         &self,
@@ -285,6 +331,12 @@ impl StacksInteract for TestHarness {
         // issue #118
         todo!()
     }
+    async fn get_current_signature_threshold(
+        &self,
+        _contract_principal: &StacksAddress,
+    ) -> Result<Option<u16>, Error> {
+        Ok(None)
+    }
     async fn get_account(&self, _address: &StacksAddress) -> Result<AccountInfo, Error> {
         // issue #118
         todo!()
@@ -448,8 +500,24 @@ impl EmilyInteract for TestHarness {
             .cloned();
         Ok(deposit)
     }
-    async fn get_deposits(&self) -> Result<Vec<CreateDepositRequest>, Error> {
-        Ok(self.pending_deposits.clone())
+    async fn get_deposits_updated_since(
+        &self,
+        min_height: u64,
+    ) -> Result<Vec<PendingDeposit>, Error> {
+        // All pending deposits are taken to have been last updated at
+        // the genesis block.
+        if min_height > 0 {
+            return Ok(Vec::new());
+        }
+        let deposits = self
+            .pending_deposits
+            .iter()
+            .map(|request| PendingDeposit {
+                request: request.clone(),
+                last_update_height: 0,
+            })
+            .collect();
+        Ok(deposits)
     }
 
     async fn update_deposits(
@@ -40,3 +40,31 @@ pub fn base_signer_transaction() -> Transaction {
         ],
     }
 }
+
+/// Return the `getblockheader` RPC response that bitcoin-core would
+/// return for the given block, using the given block height, number of
+/// confirmations, and median-time-past.
+pub fn block_header_result(
+    block: &bitcoin::Block,
+    block_height: u64,
+    confirmations: i32,
+    median_time_past: u32,
+) -> bitcoincore_rpc_json::GetBlockHeaderResult {
+    let header = serde_json::json!({
+        "hash": block.block_hash(),
+        "confirmations": confirmations,
+        "height": block_height,
+        "version": block.header.version,
+        "merkleroot": block.header.merkle_root,
+        "time": block.header.time,
+        "mediantime": median_time_past,
+        "nonce": block.header.nonce,
+        "bits": format!("{:08x}", block.header.bits.to_consensus()),
+        "difficulty": 1,
+        "chainwork": "00",
+        "nTx": block.txdata.len(),
+        "previousblockhash": block.header.prev_blockhash,
+    });
+
+    serde_json::from_value(header).expect("invalid getblockheader response")
+}
@@ -212,10 +212,14 @@ where
     Stacks: StacksInteract + Clone + Send + Sync + 'static,
     Emily: EmilyInteract + Clone + Send + Sync + 'static,
 {
-    fn config(&self) -> &Settings {
+    fn config(&self) -> Arc<Settings> {
         self.inner.config()
     }
 
+    fn set_config(&self, config: Settings) {
+        self.inner.set_config(config)
+    }
+
     fn state(&self) -> &SignerState {
         self.inner.state()
     }
@@ -297,6 +301,17 @@ where
 }
 
 impl BitcoinInteract for WrappedMock<MockBitcoinInteract> {
+    async fn get_best_block_hash(&self) -> Result<bitcoin::BlockHash, Error> {
+        self.inner.lock().await.get_best_block_hash().await
+    }
+
+    async fn get_block_header(
+        &self,
+        block_hash: &bitcoin::BlockHash,
+    ) -> Result<Option<bitcoincore_rpc_json::GetBlockHeaderResult>, Error> {
+        self.inner.lock().await.get_block_header(block_hash).await
+    }
+
     async fn get_block(
         &self,
         block_hash: &bitcoin::BlockHash,
@@ -384,6 +399,17 @@ impl StacksInteract for WrappedMock<MockStacksInteract> {
             .await
     }
 
+    async fn get_current_signature_threshold(
+        &self,
+        contract_principal: &StacksAddress,
+    ) -> Result<Option<u16>, Error> {
+        self.inner
+            .lock()
+            .await
+            .get_current_signature_threshold(contract_principal)
+            .await
+    }
+
     async fn get_account(&self, address: &StacksAddress) -> Result<AccountInfo, Error> {
         self.inner.lock().await.get_account(address).await
     }
@@ -468,8 +494,16 @@ impl EmilyInteract for WrappedMock<MockEmilyInteract> {
             .get_deposit(txid, output_index)
             .await
     }
-    async fn get_deposits(&self) -> Result<Vec<sbtc::deposits::CreateDepositRequest>, Error> {
-        self.inner.lock().await.get_deposits().await
+
+    async fn get_deposits_updated_since(
+        &self,
+        min_height: u64,
+    ) -> Result<Vec<crate::emily_client::PendingDeposit>, Error> {
+        self.inner
+            .lock()
+            .await
+            .get_deposits_updated_since(min_height)
+            .await
     }
 
     async fn update_deposits(
@@ -35,14 +35,18 @@ use crate::keys::PublicKeyXOnly;
 use crate::keys::SignerScriptPubKey as _;
 use crate::message::BitcoinPreSignAck;
 use crate::message::BitcoinPreSignRequest;
+use crate::message::NewDepositRequest;
 use crate::message::SignerMessage;
 use crate::message::SweepTransactionInfo;
 use crate::message::SweptDeposit;
 use crate::message::SweptWithdrawal;
 use crate::stacks::contracts::AcceptWithdrawalV1;
 use crate::stacks::contracts::CompleteDepositV1;
+use crate::stacks::contracts::CompleteDepositsV1;
+use crate::stacks::contracts::CompleteWithdrawalsV1;
 use crate::stacks::contracts::RejectWithdrawalV1;
 use crate::stacks::contracts::RotateKeysV1;
+use crate::stacks::contracts::WithdrawalCompletion;
 use crate::stacks::events::CompletedDepositEvent;
 use crate::stacks::events::WithdrawalAcceptEvent;
 use crate::stacks::events::WithdrawalCreateEvent;
@@ -659,16 +663,36 @@ impl fake::Dummy<fake::Faker> for CompleteDepositV1 {
     }
 }
 
+impl fake::Dummy<fake::Faker> for CompleteDepositsV1 {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        let public_key: PublicKey = config.fake_with_rng(rng);
+        let pubkey = stacks_common::util::secp256k1::Secp256k1PublicKey::from(&public_key);
+        let deployer = StacksAddress::p2pkh(false, &pubkey);
+
+        let num_deposits = rng.gen_range(1..10);
+        let deposits = std::iter::repeat_with(|| CompleteDepositV1 {
+            deployer,
+            ..config.fake_with_rng(rng)
+        })
+        .take(num_deposits)
+        .collect();
+
+        CompleteDepositsV1 { deposits, deployer }
+    }
+}
+
 impl fake::Dummy<fake::Faker> for AcceptWithdrawalV1 {
     fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
         let public_key: PublicKey = config.fake_with_rng(rng);
         let pubkey = stacks_common::util::secp256k1::Secp256k1PublicKey::from(&public_key);
         let address = StacksAddress::p2pkh(false, &pubkey);
 
+        let sweep_txid = txid(config, rng);
+
         AcceptWithdrawalV1 {
             request_id: config.fake_with_rng(rng),
             outpoint: OutPoint {
-                txid: txid(config, rng),
+                txid: sweep_txid,
                 vout: rng.next_u32(),
             },
             tx_fee: config.fake_with_rng(rng),
@@ -676,6 +700,7 @@ impl fake::Dummy<fake::Faker> for AcceptWithdrawalV1 {
             deployer: address,
             sweep_block_hash: config.fake_with_rng(rng),
             sweep_block_height: config.fake_with_rng(rng),
+            sweep_txid: sweep_txid.into(),
         }
     }
 }
@@ -694,6 +719,33 @@ impl fake::Dummy<fake::Faker> for RejectWithdrawalV1 {
     }
 }
 
+impl fake::Dummy<fake::Faker> for CompleteWithdrawalsV1 {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        let public_key: PublicKey = config.fake_with_rng(rng);
+        let pubkey = stacks_common::util::secp256k1::Secp256k1PublicKey::from(&public_key);
+        let deployer = StacksAddress::p2pkh(false, &pubkey);
+
+        let num_withdrawals = rng.gen_range(1..10);
+        let withdrawals = std::iter::repeat_with(|| {
+            if rng.gen_bool(0.5) {
+                WithdrawalCompletion::Accept(AcceptWithdrawalV1 {
+                    deployer,
+                    ..config.fake_with_rng(rng)
+                })
+            } else {
+                WithdrawalCompletion::Reject(RejectWithdrawalV1 {
+                    deployer,
+                    ..config.fake_with_rng(rng)
+                })
+            }
+        })
+        .take(num_withdrawals)
+        .collect();
+
+        CompleteWithdrawalsV1 { withdrawals, deployer }
+    }
+}
+
 impl fake::Dummy<fake::Faker> for RotateKeysV1 {
     fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
         let public_key: PublicKey = config.fake_with_rng(rng);
@@ -799,3 +851,16 @@ impl fake::Dummy<fake::Faker> for BitcoinPreSignAck {
         BitcoinPreSignAck {}
     }
 }
+
+impl fake::Dummy<fake::Faker> for NewDepositRequest {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        NewDepositRequest {
+            outpoint: OutPoint {
+                txid: txid(config, rng),
+                vout: rng.next_u32(),
+            },
+            reclaim_script: config.fake_with_rng::<ScriptPubKey, R>(rng).into(),
+            deposit_script: config.fake_with_rng::<ScriptPubKey, R>(rng).into(),
+        }
+    }
+}
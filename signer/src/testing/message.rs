@@ -56,6 +56,7 @@ impl fake::Dummy<fake::Faker> for message::Payload {
             dummy_payload::<message::WstsMessage, _>,
             dummy_payload::<message::SweepTransactionInfo, _>,
             dummy_payload::<message::BitcoinPreSignRequest, _>,
+            dummy_payload::<message::NewDepositRequest, _>,
         ];
         variants.choose(rng).unwrap()(config, rng)
     }
@@ -137,6 +138,9 @@ impl fake::Dummy<fake::Faker> for message::WstsMessage {
         Self {
             txid: dummy::txid(config, rng),
             inner: wsts::net::Message::DkgEndBegin(dkg_end_begin),
+            dkg_participants: (0..3)
+                .map(|_| config.fake_with_rng::<PublicKey, _>(rng))
+                .collect(),
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Test utilities for the transaction signer
 
 use std::collections::BTreeSet;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::context::Context;
@@ -49,7 +50,7 @@ impl<C: Context + 'static> RequestDeciderEventLoopHarness<C> {
                 context: context.clone(),
                 network: network.spawn(),
                 blocklist_checker: Some(()),
-                signer_private_key,
+                signer_key: Arc::new(signer_private_key),
                 context_window,
             },
             context,
@@ -331,7 +332,13 @@ where
             let group_key = PublicKey::combine_keys(signer_set).unwrap();
             store_dummy_dkg_shares(
                 &mut rng,
-                &handle.context.config().signer.private_key.to_bytes(),
+                &handle
+                    .context
+                    .config()
+                    .signer
+                    .private_key
+                    .unwrap()
+                    .to_bytes(),
                 &handle.context.get_storage_mut(),
                 group_key,
                 signer_set.clone(),
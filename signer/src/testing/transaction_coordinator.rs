@@ -8,6 +8,7 @@ use std::time::Duration;
 
 use crate::bitcoin::utxo::SignerUtxo;
 use crate::bitcoin::MockBitcoinInteract;
+use crate::block_observer;
 use crate::context::Context;
 use crate::context::RequestDeciderEvent;
 use crate::emily_client::MockEmilyInteract;
@@ -68,6 +69,7 @@ where
                 context: context.clone(),
                 network,
                 private_key,
+                signer_key: Arc::new(private_key),
                 context_window,
                 threshold,
                 sbtc_contracts_deployed,
@@ -135,8 +137,31 @@ where
 {
     /// Assert that a coordinator should be able to coordiante a signing round
     pub async fn assert_should_be_able_to_coordinate_signing_rounds(
+        self,
+        delay_to_process_new_blocks: Duration,
+    ) {
+        self.coordinate_signing_rounds(delay_to_process_new_blocks, 0)
+            .await;
+    }
+
+    /// Assert that the next signer in the fallback order coordinates the
+    /// signing round when the coordinator for the chain tip is offline.
+    ///
+    /// Only the event loop of the fallback coordinator is started, and it
+    /// must take over once the configured failover timeout has elapsed.
+    pub async fn assert_fallback_coordinator_takes_over(self) {
+        let failover_timeout = self.context.config().signer.coordinator_failover_timeout;
+        assert!(!failover_timeout.is_zero());
+
+        let start = tokio::time::Instant::now();
+        self.coordinate_signing_rounds(failover_timeout, 1).await;
+        assert!(start.elapsed() >= failover_timeout);
+    }
+
+    async fn coordinate_signing_rounds(
         mut self,
         delay_to_process_new_blocks: Duration,
+        failover_round: usize,
     ) {
         let mut rng = rand::rngs::StdRng::seed_from_u64(46);
         let network = network::InMemoryNetwork::new();
@@ -232,8 +257,10 @@ where
             })
             .await;
 
-        // Get the private key of the coordinator of the signer set.
-        let private_key = Self::select_coordinator(&bitcoin_chain_tip.block_hash, &signer_info);
+        // Get the private key of the coordinator of the signer set for the
+        // given failover round.
+        let private_key =
+            Self::select_coordinator(&bitcoin_chain_tip.block_hash, &signer_info, failover_round);
 
         // Bootstrap the tx coordinator within an event loop harness.
         let event_loop_harness = TxCoordinatorEventLoopHarness::create(
@@ -423,7 +450,7 @@ where
             .await;
 
         // Get the private key of the coordinator of the signer set.
-        let private_key = Self::select_coordinator(&bitcoin_chain_tip.block_hash, &signer_info);
+        let private_key = Self::select_coordinator(&bitcoin_chain_tip.block_hash, &signer_info, 0);
 
         // Bootstrap the tx coordinator within an event loop harness.
         let event_loop_harness = TxCoordinatorEventLoopHarness::create(
@@ -756,6 +783,260 @@ where
         assert_eq!(signer_utxo, expected);
     }
 
+    /// Assert we follow the signers' UTXO after the signers rotate keys
+    pub async fn assert_get_signer_utxo_follows_key_rotation(mut self) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+        let network = network::InMemoryNetwork::new();
+        let signer_info = testing::wsts::generate_signer_info(&mut rng, self.num_signers as usize);
+
+        let mut signer_set =
+            testing::wsts::SignerSet::new(&signer_info, self.signing_threshold as u32, || {
+                network.connect()
+            });
+
+        let (old_aggregate_key, bitcoin_chain_tip, mut test_data) = self
+            .prepare_database_and_run_dkg(&mut rng, &mut signer_set)
+            .await;
+
+        let original_test_data = test_data.clone();
+
+        let tx_1 = bitcoin::Transaction {
+            output: vec![bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(1_000),
+                script_pubkey: old_aggregate_key.signers_script_pubkey(),
+            }],
+            ..EMPTY_BITCOIN_TX
+        };
+        let (block_1, block_1_ref) = test_data.new_block(
+            &mut rng,
+            &signer_set.signer_keys(),
+            &self.test_model_parameters,
+            Some(&bitcoin_chain_tip),
+        );
+        test_data.push(block_1);
+        test_data.push_bitcoin_txs(
+            &block_1_ref,
+            vec![(model::TransactionType::SbtcTransaction, tx_1.clone())],
+        );
+
+        let mut new_test_data = test_data.clone();
+        new_test_data.remove(original_test_data);
+        self.write_test_data(&new_test_data).await;
+
+        // The signers run DKG again, but their UTXO is still locked by the
+        // old aggregate key.
+        let storage = self.context.get_storage_mut();
+        let dkg_txid = testing::dummy::txid(&Faker, &mut rng);
+        let (new_aggregate_key, all_dkg_shares) = signer_set
+            .run_dkg(block_1_ref.block_hash, dkg_txid, &mut rng)
+            .await;
+        storage
+            .write_encrypted_dkg_shares(all_dkg_shares.first().unwrap())
+            .await
+            .expect("failed to write encrypted shares");
+
+        let signer_utxo = storage
+            .get_signer_utxo(&block_1_ref.block_hash, self.context_window)
+            .await
+            .unwrap()
+            .expect("no signer utxo");
+
+        let expected = SignerUtxo {
+            outpoint: bitcoin::OutPoint::new(tx_1.compute_txid(), 0),
+            amount: 1_000,
+            public_key: bitcoin::XOnlyPublicKey::from(old_aggregate_key),
+        };
+        assert_eq!(signer_utxo, expected);
+
+        // Now the UTXO gets swept over to the new aggregate key.
+        let original_test_data = test_data.clone();
+        let tx_2 = bitcoin::Transaction {
+            input: vec![bitcoin::TxIn {
+                previous_output: expected.outpoint,
+                ..Default::default()
+            }],
+            output: vec![bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(900),
+                script_pubkey: new_aggregate_key.signers_script_pubkey(),
+            }],
+            ..EMPTY_BITCOIN_TX
+        };
+        let (block_2, block_2_ref) = test_data.new_block(
+            &mut rng,
+            &signer_set.signer_keys(),
+            &self.test_model_parameters,
+            Some(&block_1_ref),
+        );
+        test_data.push(block_2);
+        test_data.push_bitcoin_txs(
+            &block_2_ref,
+            vec![(model::TransactionType::SbtcTransaction, tx_2.clone())],
+        );
+        test_data.remove(original_test_data);
+        self.write_test_data(&test_data).await;
+
+        let prevout = model::TxPrevout {
+            txid: tx_2.compute_txid().into(),
+            prevout_txid: expected.outpoint.txid.into(),
+            prevout_output_index: 0,
+            script_pubkey: old_aggregate_key.signers_script_pubkey().into(),
+            amount: 1_000,
+            prevout_type: model::TxPrevoutType::SignersInput,
+        };
+        storage.write_tx_prevout(&prevout).await.unwrap();
+
+        let signer_utxo = storage
+            .get_signer_utxo(&block_2_ref.block_hash, self.context_window)
+            .await
+            .unwrap()
+            .expect("no signer utxo");
+
+        let expected = SignerUtxo {
+            outpoint: bitcoin::OutPoint::new(tx_2.compute_txid(), 0),
+            amount: 900,
+            public_key: bitcoin::XOnlyPublicKey::from(new_aggregate_key),
+        };
+        assert_eq!(signer_utxo, expected);
+    }
+
+    /// Assert that the signers keep the old aggregate key until the
+    /// transaction handing their UTXO over to the new aggregate key has
+    /// enough confirmations.
+    pub async fn assert_old_dkg_shares_retired_after_handover_confirms(mut self) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+        let network = network::InMemoryNetwork::new();
+        let signer_info = testing::wsts::generate_signer_info(&mut rng, self.num_signers as usize);
+
+        let mut signer_set =
+            testing::wsts::SignerSet::new(&signer_info, self.signing_threshold as u32, || {
+                network.connect()
+            });
+
+        let (old_aggregate_key, bitcoin_chain_tip, mut test_data) = self
+            .prepare_database_and_run_dkg(&mut rng, &mut signer_set)
+            .await;
+
+        let original_test_data = test_data.clone();
+
+        let tx_1 = bitcoin::Transaction {
+            output: vec![bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(1_000),
+                script_pubkey: old_aggregate_key.signers_script_pubkey(),
+            }],
+            ..EMPTY_BITCOIN_TX
+        };
+        let (block_1, block_1_ref) = test_data.new_block(
+            &mut rng,
+            &signer_set.signer_keys(),
+            &self.test_model_parameters,
+            Some(&bitcoin_chain_tip),
+        );
+        test_data.push(block_1);
+        test_data.push_bitcoin_txs(
+            &block_1_ref,
+            vec![(model::TransactionType::SbtcTransaction, tx_1.clone())],
+        );
+
+        let mut new_test_data = test_data.clone();
+        new_test_data.remove(original_test_data);
+        self.write_test_data(&new_test_data).await;
+
+        // The signers run DKG again, so there is a new aggregate key, but
+        // the old one still locks their UTXO.
+        let storage = self.context.get_storage_mut();
+        let dkg_txid = testing::dummy::txid(&Faker, &mut rng);
+        let (new_aggregate_key, all_dkg_shares) = signer_set
+            .run_dkg(block_1_ref.block_hash, dkg_txid, &mut rng)
+            .await;
+        storage
+            .write_encrypted_dkg_shares(all_dkg_shares.first().unwrap())
+            .await
+            .expect("failed to write encrypted shares");
+
+        let shares = block_observer::unretired_dkg_shares(
+            &self.context,
+            &block_1_ref.block_hash,
+            self.context_window,
+        )
+        .await
+        .unwrap()
+        .expect("the old aggregate key was retired before the handover");
+        assert_eq!(shares.aggregate_key, old_aggregate_key);
+
+        // Now the UTXO gets handed over to the new aggregate key.
+        let original_test_data = test_data.clone();
+        let tx_2 = bitcoin::Transaction {
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint::new(tx_1.compute_txid(), 0),
+                ..Default::default()
+            }],
+            output: vec![bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(900),
+                script_pubkey: new_aggregate_key.signers_script_pubkey(),
+            }],
+            ..EMPTY_BITCOIN_TX
+        };
+        let (block_2, block_2_ref) = test_data.new_block(
+            &mut rng,
+            &signer_set.signer_keys(),
+            &self.test_model_parameters,
+            Some(&block_1_ref),
+        );
+        test_data.push(block_2);
+        test_data.push_bitcoin_txs(
+            &block_2_ref,
+            vec![(model::TransactionType::SbtcTransaction, tx_2.clone())],
+        );
+        test_data.remove(original_test_data);
+        self.write_test_data(&test_data).await;
+
+        let prevout = model::TxPrevout {
+            txid: tx_2.compute_txid().into(),
+            prevout_txid: tx_1.compute_txid().into(),
+            prevout_output_index: 0,
+            script_pubkey: old_aggregate_key.signers_script_pubkey().into(),
+            amount: 1_000,
+            prevout_type: model::TxPrevoutType::SignersInput,
+        };
+        storage.write_tx_prevout(&prevout).await.unwrap();
+
+        // The handover transaction has one confirmation, so we keep the
+        // old aggregate key until it has enough of them.
+        let mut chain_tip = block_2_ref;
+        for _ in 1..crate::ROTATION_SWEEP_MIN_CONFIRMATIONS {
+            let shares = block_observer::unretired_dkg_shares(
+                &self.context,
+                &chain_tip.block_hash,
+                self.context_window,
+            )
+            .await
+            .unwrap()
+            .expect("the old aggregate key was retired too early");
+            assert_eq!(shares.aggregate_key, old_aggregate_key);
+
+            let original_test_data = test_data.clone();
+            let (block, block_ref) = test_data.new_block(
+                &mut rng,
+                &signer_set.signer_keys(),
+                &self.test_model_parameters,
+                Some(&chain_tip),
+            );
+            test_data.push(block);
+            test_data.remove(original_test_data);
+            self.write_test_data(&test_data).await;
+            chain_tip = block_ref;
+        }
+
+        let shares = block_observer::unretired_dkg_shares(
+            &self.context,
+            &chain_tip.block_hash,
+            self.context_window,
+        )
+        .await
+        .unwrap();
+        assert!(shares.is_none());
+    }
+
     /// Assert we get the correct UTXO in case of donations
     pub async fn assert_get_signer_utxo_donations(mut self) {
         let mut rng = rand::rngs::StdRng::seed_from_u64(46);
@@ -949,13 +1230,14 @@ where
     fn select_coordinator(
         bitcoin_chain_tip: &model::BitcoinBlockHash,
         signer_info: &[testing::wsts::SignerInfo],
+        failover_round: usize,
     ) -> keys::PrivateKey {
         let mut hasher = sha2::Sha256::new();
         hasher.update(bitcoin_chain_tip.into_bytes());
         let digest = hasher.finalize();
         let index = usize::from_be_bytes(*digest.first_chunk().expect("unexpected digest size"));
         signer_info
-            .get(index % signer_info.len())
+            .get((index % signer_info.len() + failover_round) % signer_info.len())
             .expect("missing signer info")
             .signer_private_key
     }
@@ -2,6 +2,7 @@
 
 use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::blocklist_client;
@@ -59,11 +60,13 @@ where
                 context: context.clone(),
                 network,
                 signer_private_key,
+                signer_key: Arc::new(signer_private_key),
                 context_window,
                 wsts_state_machines: HashMap::new(),
                 threshold,
                 rng,
                 dkg_begin_pause: None,
+                bitcoin_presign_package: Vec::new(),
             },
             context,
         }
@@ -148,18 +151,20 @@ impl<C> TestEnvironment<C>
 where
     C: Context + 'static,
 {
-    /// Assert that the transaction signer will respond to bitcoin transaction sign requests
-    /// with an acknowledge message. Errors after 10 seconds.
-    pub async fn assert_should_respond_to_bitcoin_transaction_sign_requests(self) {
-        let future = self.assert_should_respond_to_bitcoin_transaction_sign_requests_impl();
+    /// Assert that the transaction signer will not acknowledge bitcoin
+    /// transaction sign requests for transactions that were not part of a
+    /// pre-sign request that it validated. Errors after 10 seconds.
+    pub async fn assert_should_not_ack_unknown_bitcoin_transaction_sign_requests(self) {
+        let future = self.assert_should_not_ack_unknown_bitcoin_transaction_sign_requests_impl();
         tokio::time::timeout(Duration::from_secs(10), future)
             .await
             .unwrap()
     }
 
-    /// Assert that the transaction signer will respond to bitcoin transaction sign requests
-    /// with an acknowledge message
-    pub async fn assert_should_respond_to_bitcoin_transaction_sign_requests_impl(self) {
+    /// Assert that the transaction signer will not acknowledge bitcoin
+    /// transaction sign requests for transactions that were not part of a
+    /// pre-sign request that it validated.
+    pub async fn assert_should_not_ack_unknown_bitcoin_transaction_sign_requests_impl(self) {
         let mut rng = rand::rngs::StdRng::seed_from_u64(46);
         let wan_network = WanNetwork::default();
         let signer_info = testing::wsts::generate_signer_info(&mut rng, self.num_signers);
@@ -206,6 +211,7 @@ where
         let coordinator_public_key = transaction_coordinator::coordinator_public_key(
             &bitcoin_chain_tip,
             &signer_info.first().unwrap().signer_public_keys,
+            0,
         )
         .unwrap();
 
@@ -246,18 +252,10 @@ where
             .await
             .expect("broadcast failed");
 
-        let msg = network_handle
-            .receive()
-            .await
-            .expect("failed to receive message");
-
-        let coordinator_public_key = PublicKey::from_private_key(&coordinator_private_key);
-        assert!(msg.verify(coordinator_public_key));
-
-        assert!(matches!(
-            msg.payload,
-            message::Payload::BitcoinTransactionSignAck(_)
-        ));
+        // The signer never saw a pre-sign request for this transaction,
+        // so it should not ack it.
+        let msg = tokio::time::timeout(Duration::from_secs(1), network_handle.receive()).await;
+        assert!(msg.is_err());
     }
 
     /// Assert that a group of transaction signers together can
@@ -304,9 +302,12 @@ where
             .expect("no chain tip");
 
         // now that we have a chain tip, get the real coordinator
-        let coordinator_public_key =
-            crate::transaction_coordinator::coordinator_public_key(&bitcoin_chain_tip, signer_set)
-                .unwrap();
+        let coordinator_public_key = crate::transaction_coordinator::coordinator_public_key(
+            &bitcoin_chain_tip,
+            signer_set,
+            0,
+        )
+        .unwrap();
         let coordinator_signer_info = signer_info
             .iter()
             .find(|signer| {
@@ -409,6 +410,7 @@ where
         let coordinator_public_key = transaction_coordinator::coordinator_public_key(
             &bitcoin_chain_tip,
             &signer_info.first().unwrap().signer_public_keys,
+            0,
         )
         .unwrap();
 
@@ -87,6 +87,7 @@ pub struct Coordinator {
     wsts_coordinator: fire::Coordinator<wsts::v2::Aggregator>,
     private_key: PrivateKey,
     num_signers: u32,
+    dkg_participants: BTreeSet<PublicKey>,
 }
 
 impl Coordinator {
@@ -98,6 +99,7 @@ impl Coordinator {
     ) -> Self {
         let num_signers = signer_info.signer_public_keys.len().try_into().unwrap();
         let message_private_key = signer_info.signer_private_key;
+        let dkg_participants = signer_info.signer_public_keys.clone();
         let signer_public_keys: hashbrown::HashMap<u32, _> = signer_info
             .signer_public_keys
             .into_iter()
@@ -131,6 +133,7 @@ impl Coordinator {
             wsts_coordinator,
             private_key: message_private_key,
             num_signers,
+            dkg_participants,
         }
     }
 
@@ -399,6 +402,7 @@ impl Signer {
 trait WstsEntity {
     fn network(&mut self) -> &mut network::in_memory::MpmcBroadcaster;
     fn private_key(&self) -> &PrivateKey;
+    fn dkg_participants(&self) -> BTreeSet<PublicKey>;
 
     async fn send_packet(
         &mut self,
@@ -406,7 +410,16 @@ trait WstsEntity {
         txid: bitcoin::Txid,
         packet: wsts::net::Packet,
     ) {
-        let payload: message::Payload = message::WstsMessage { txid, inner: packet.msg }.into();
+        let dkg_participants = match packet.msg {
+            wsts::net::Message::DkgBegin(_) => self.dkg_participants(),
+            _ => BTreeSet::new(),
+        };
+        let payload: message::Payload = message::WstsMessage {
+            txid,
+            inner: packet.msg,
+            dkg_participants,
+        }
+        .into();
 
         let msg = payload
             .to_message(bitcoin_chain_tip)
@@ -427,6 +440,10 @@ impl WstsEntity for Coordinator {
     fn private_key(&self) -> &PrivateKey {
         &self.private_key
     }
+
+    fn dkg_participants(&self) -> BTreeSet<PublicKey> {
+        self.dkg_participants.clone()
+    }
 }
 
 impl WstsEntity for Signer {
@@ -437,6 +454,10 @@ impl WstsEntity for Signer {
     fn private_key(&self) -> &PrivateKey {
         &self.private_key
     }
+
+    fn dkg_participants(&self) -> BTreeSet<PublicKey> {
+        BTreeSet::new()
+    }
 }
 
 /// A set of signers and a coordinator
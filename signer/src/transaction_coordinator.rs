@@ -5,8 +5,10 @@
 //!
 //! For more details, see the [`TxCoordinatorEventLoop`] documentation.
 
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Duration;
 
 use blockstack_lib::chainstate::stacks::StacksTransaction;
@@ -19,6 +21,8 @@ use crate::bitcoin::utxo;
 use crate::bitcoin::utxo::Fees;
 use crate::bitcoin::BitcoinInteract;
 use crate::bitcoin::TransactionLookupHint;
+use crate::block_observer::target_signer_set;
+use crate::config::SignerSetConfig;
 use crate::context::Context;
 use crate::context::P2PEvent;
 use crate::context::RequestDeciderEvent;
@@ -31,34 +35,52 @@ use crate::ecdsa::SignEcdsa as _;
 use crate::ecdsa::Signed;
 use crate::emily_client::EmilyInteract;
 use crate::error::Error;
+use crate::key_backend::SignerKeyBackend;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
+use crate::keys::SignerScriptPubKey as _;
 use crate::message;
 use crate::message::BitcoinPreSignRequest;
 use crate::message::Payload;
 use crate::message::SignerMessage;
 use crate::message::StacksTransactionSignRequest;
 use crate::message::SweepTransactionInfo;
+use crate::metrics::metrics;
+use crate::metrics::RejectionLabels;
+use crate::metrics::SigningRoundLabels;
 use crate::network;
+use crate::participation;
+use crate::participation::RoundParticipation;
 use crate::signature::TaprootSignature;
 use crate::stacks::api::FeePriority;
 use crate::stacks::api::GetNakamotoStartHeight;
 use crate::stacks::api::StacksInteract;
 use crate::stacks::api::SubmitTxResponse;
+use crate::stacks::contracts::AcceptWithdrawalV1;
 use crate::stacks::contracts::AsTxPayload;
 use crate::stacks::contracts::CompleteDepositV1;
+use crate::stacks::contracts::CompleteDepositsV1;
+use crate::stacks::contracts::CompleteWithdrawalsV1;
 use crate::stacks::contracts::ContractCall;
+use crate::stacks::contracts::RejectWithdrawalV1;
+use crate::stacks::contracts::ReqContext;
 use crate::stacks::contracts::RotateKeysV1;
 use crate::stacks::contracts::SmartContract;
+use crate::stacks::contracts::WithdrawalCompletion;
 use crate::stacks::contracts::SMART_CONTRACTS;
 use crate::stacks::wallet::MultisigTx;
 use crate::stacks::wallet::SignerWallet;
 use crate::storage::model;
+use crate::storage::model::ParticipationRoundType;
 use crate::storage::model::StacksTxId;
 use crate::storage::DbRead as _;
+use crate::storage::DbWrite as _;
 use crate::wsts_state_machine::CoordinatorStateMachine;
+use crate::ROTATION_SWEEP_MAX_FEE;
+use crate::WITHDRAWAL_BLOCKS_EXPIRY;
 
 use bitcoin::hashes::Hash as _;
+use bitvec::array::BitArray;
 use wsts::net::SignatureType;
 use wsts::state_machine::coordinator::Coordinator as _;
 use wsts::state_machine::coordinator::State as WstsCoordinatorState;
@@ -142,8 +164,12 @@ pub struct TxCoordinatorEventLoop<Context, Network> {
     pub context: Context,
     /// Interface to the signer network.
     pub network: Network,
-    /// Private key of the coordinator for network communication.
+    /// Private key of the coordinator, used only as its network key in
+    /// the WSTS protocol, which cannot work with signatures alone.
     pub private_key: PrivateKey,
+    /// The backend holding the signer's private key, used to sign the
+    /// messages sent to other signers.
+    pub signer_key: Arc<dyn SignerKeyBackend>,
     /// the number of signatures required.
     pub threshold: u16,
     /// How many bitcoin blocks back from the chain tip the signer will
@@ -155,8 +181,9 @@ pub struct TxCoordinatorEventLoop<Context, Network> {
     /// The maximum duration of a pre-sign request before the coordinator will
     /// time out and start sending the requests to the signers.
     pub bitcoin_presign_request_max_duration: Duration,
-    /// The maximum duration of distributed key generation before the
-    /// coordinator will time out and return an error.
+    /// The maximum duration of a single round of distributed key
+    /// generation before the coordinator will time out and give up on the
+    /// round.
     pub dkg_max_duration: Duration,
     /// Whether the coordinator has already deployed the contracts.
     pub sbtc_contracts_deployed: bool,
@@ -197,28 +224,49 @@ where
     pub async fn run(mut self) -> Result<(), Error> {
         tracing::info!("starting transaction coordinator event loop");
         let mut signal_stream = self.context.as_signal_stream(run_loop_message_filter);
+        // The instant at which the next signer in the fallback order takes
+        // over as coordinator for the current bitcoin chain tip, if any.
+        let mut failover_deadline: Option<tokio::time::Instant> = None;
+
+        loop {
+            let failover_timer = async move {
+                match failover_deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending().await,
+                }
+            };
 
-        while let Some(message) = signal_stream.next().await {
-            match message {
-                SignerSignal::Command(SignerCommand::Shutdown) => break,
-                SignerSignal::Command(SignerCommand::P2PPublish(_)) => {}
-                SignerSignal::Event(event) => {
-                    if let SignerEvent::RequestDecider(RequestDeciderEvent::NewRequestsHandled) =
-                        event
-                    {
+            let result = tokio::select! {
+                message = signal_stream.next() => match message {
+                    None | Some(SignerSignal::Command(SignerCommand::Shutdown)) => break,
+                    Some(SignerSignal::Event(SignerEvent::RequestDecider(
+                        RequestDeciderEvent::NewRequestsHandled,
+                    ))) => {
                         tracing::debug!("received signal; processing requests");
-                        if let Err(error) = self.process_new_blocks().await {
-                            tracing::error!(
-                                %error,
-                                "error processing requests; skipping this round"
-                            );
-                        }
-                        tracing::trace!("sending tenure completed signal");
-                        self.context
-                            .signal(TxCoordinatorEvent::TenureCompleted.into())?;
+                        self.process_new_blocks().await
                     }
+                    Some(_) => continue,
+                },
+                _ = failover_timer => {
+                    tracing::debug!("coordinator failover timeout elapsed; processing requests");
+                    self.process_chain_tip().await
                 }
+            };
+
+            if let Err(error) = result {
+                tracing::error!(%error, "error processing requests; skipping this round");
             }
+            tracing::trace!("sending tenure completed signal");
+            self.context
+                .signal(TxCoordinatorEvent::TenureCompleted.into())?;
+
+            failover_deadline = match self.next_failover_deadline().await {
+                Ok(deadline) => deadline,
+                Err(error) => {
+                    tracing::warn!(%error, "could not determine the coordinator failover deadline");
+                    None
+                }
+            };
         }
 
         tracing::info!("transaction coordinator event loop is stopping");
@@ -256,10 +304,8 @@ where
         Ok(is_epoch3)
     }
 
-    #[tracing::instrument(
-        skip_all,
-        fields(public_key = %self.signer_public_key(), chain_tip = tracing::field::Empty)
-    )]
+    /// Process a new bitcoin block, after waiting for it to propagate to
+    /// the other signers.
     async fn process_new_blocks(&mut self) -> Result<(), Error> {
         if !self.is_epoch3().await? {
             return Ok(());
@@ -271,6 +317,55 @@ where
             tokio::time::sleep(bitcoin_processing_delay).await;
         }
 
+        self.process_chain_tip().await
+    }
+
+    /// Return the instant at which the next signer in the fallback order
+    /// takes over as coordinator for the current bitcoin chain tip if we
+    /// do not hear from the active one before then, or `None` if
+    /// coordinator failover is disabled.
+    async fn next_failover_deadline(&self) -> Result<Option<tokio::time::Instant>, Error> {
+        let timeout = self.context.config().signer.coordinator_failover_timeout;
+        if timeout.is_zero() {
+            return Ok(None);
+        }
+
+        let Some(bitcoin_chain_tip) = self
+            .context
+            .get_storage()
+            .get_bitcoin_canonical_chain_tip()
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // The active coordinator is replaced once we have not heard from
+        // it for a full timeout. If we hear from it before then, we just
+        // check again when the timer fires.
+        let (_, active_at) = self
+            .context
+            .state()
+            .coordinator_activity(&bitcoin_chain_tip);
+        let next_round = failover_round(active_at.elapsed(), timeout).saturating_add(1);
+
+        let deadline = u32::try_from(next_round)
+            .ok()
+            .and_then(|rounds| timeout.checked_mul(rounds))
+            .and_then(|elapsed| active_at.checked_add(elapsed));
+        Ok(deadline)
+    }
+
+    /// Coordinate DKG and the signing of bitcoin and stacks transactions
+    /// for the current bitcoin chain tip, if we are the active coordinator.
+    #[tracing::instrument(
+        skip_all,
+        fields(public_key = %self.signer_public_key(), chain_tip = tracing::field::Empty)
+    )]
+    async fn process_chain_tip(&mut self) -> Result<(), Error> {
+        if !self.is_epoch3().await? {
+            return Ok(());
+        }
+
         let bitcoin_chain_tip = self
             .context
             .get_storage()
@@ -303,10 +398,21 @@ where
         // aggregate key, then we know that we have not run DKG yet. Since
         // we are the coordinator, we should coordinate DKG.
         let aggregate_key = match maybe_aggregate_key {
+            // If a new signer set has been approved since the last DKG
+            // round then the new set needs to run DKG and we need to
+            // rotate the keys to the new aggregate key. The signers
+            // continue to use the current aggregate key until the
+            // rotate-keys transaction has been confirmed.
+            Some(key) if self.signer_set_changed().await? => {
+                let new_key = self.run_dkg(&bitcoin_chain_tip).await?;
+                self.check_and_submit_rotate_key_transaction(&bitcoin_chain_tip, &new_key)
+                    .await?;
+                key
+            }
             Some(key) => key,
             // This function returns the new DKG aggregate key.
             None => {
-                let dkg_result = self.coordinate_dkg(&bitcoin_chain_tip).await?;
+                let dkg_result = self.run_dkg(&bitcoin_chain_tip).await?;
                 // TODO: in `run_dkg_from_scratch` test, `dkg_result` differs from
                 // value fetched from the db. Adding a temporary fix for the (probably)
                 // race condition, but we should address this properly.
@@ -384,7 +490,7 @@ where
         self.construct_and_sign_rotate_key_transaction(
             bitcoin_chain_tip,
             signing_key,
-            &last_dkg.aggregate_key,
+            &last_dkg,
             &wallet,
         )
         .await
@@ -506,10 +612,17 @@ where
             self.get_pending_requests(bitcoin_chain_tip, aggregate_key, signer_public_keys);
 
         // If Self::get_pending_requests returns Ok(None) then there are no
-        // requests to respond to, so let's just exit.
+        // requests to respond to. We may still need to move the signers'
+        // UTXO over to a new aggregate key, but otherwise let's just exit.
         let Some(pending_requests) = pending_requests_fut.await? else {
-            tracing::debug!("no requests to handle, exiting");
-            return Ok(());
+            tracing::debug!("no requests to handle");
+            return self
+                .construct_and_sign_rotation_sweep(
+                    bitcoin_chain_tip,
+                    aggregate_key,
+                    signer_public_keys,
+                )
+                .await;
         };
         tracing::debug!(
             num_deposits = %pending_requests.deposits.len(),
@@ -519,6 +632,23 @@ where
         // Construct the transaction package and store it in the database.
         let transaction_package = pending_requests.construct_transactions()?;
 
+        // The first transaction in the package spends the signers' UTXO
+        // and locks the new one with the current aggregate key, so it
+        // also hands the UTXO over after the signers rotate keys. If none
+        // of the requests made it into a transaction then we still need
+        // to hand it over on its own.
+        if transaction_package.is_empty() {
+            tracing::debug!("no requests can be swept");
+            return self
+                .construct_and_sign_rotation_sweep(
+                    bitcoin_chain_tip,
+                    aggregate_key,
+                    signer_public_keys,
+                )
+                .await;
+        }
+        metrics().sweep_packages_built.inc();
+
         self.construct_and_send_bitcoin_presign_request(
             bitcoin_chain_tip,
             &pending_requests.signer_state,
@@ -547,6 +677,77 @@ where
         Ok(())
     }
 
+    /// Construct and coordinate signing of a transaction that sweeps the
+    /// signers' UTXO over to the current aggregate key after the signers
+    /// have rotated keys.
+    ///
+    /// The signers' UTXO is locked by the aggregate key of the DKG round
+    /// that created it. Once the `rotate-keys` contract call for a new
+    /// aggregate key has been confirmed, every sweep transaction pays the
+    /// signers' new UTXO to the new key, but nothing moves the UTXO if
+    /// there are no requests to sweep. This transaction has no requests,
+    /// it spends the UTXO using the old DKG shares and pays everything
+    /// less fees to the new aggregate key. The signers pay these fees
+    /// themselves, so they are capped at [`ROTATION_SWEEP_MAX_FEE`].
+    #[tracing::instrument(skip_all)]
+    async fn construct_and_sign_rotation_sweep(
+        &mut self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        aggregate_key: &PublicKey,
+        signer_public_keys: &BTreeSet<PublicKey>,
+    ) -> Result<(), Error> {
+        let db = self.context.get_storage();
+
+        // We only hand the UTXO over once the signers have confirmed the
+        // new aggregate key on the Stacks blockchain.
+        let last_key_rotation = db.get_last_key_rotation(bitcoin_chain_tip).await?;
+        if last_key_rotation.map(|rotation| rotation.aggregate_key) != Some(*aggregate_key) {
+            return Ok(());
+        }
+
+        let signer_utxo = db
+            .get_signer_utxo(bitcoin_chain_tip, self.context_window)
+            .await?;
+        match signer_utxo {
+            Some(utxo) if utxo.public_key != bitcoin::XOnlyPublicKey::from(aggregate_key) => {}
+            _ => return Ok(()),
+        }
+
+        tracing::info!("sweeping the signers' UTXO over to the new aggregate key");
+        let signer_state = self.get_btc_state(bitcoin_chain_tip, aggregate_key).await?;
+        let requests = utxo::Requests::new(Vec::new());
+        let transaction = utxo::UnsignedTransaction::new(requests, &signer_state)?;
+        if transaction.tx_fee > ROTATION_SWEEP_MAX_FEE {
+            tracing::warn!(
+                tx_fee = %transaction.tx_fee,
+                max_fee = %ROTATION_SWEEP_MAX_FEE,
+                "the fee for handing over the signers' UTXO is too high, trying again later"
+            );
+            return Ok(());
+        }
+        let transaction_package = vec![transaction];
+        metrics().sweep_packages_built.inc();
+
+        self.construct_and_send_bitcoin_presign_request(
+            bitcoin_chain_tip,
+            &signer_state,
+            &transaction_package,
+        )
+        .await?;
+
+        for mut transaction in transaction_package {
+            self.sign_and_broadcast(
+                bitcoin_chain_tip,
+                aggregate_key,
+                signer_public_keys,
+                &mut transaction,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
     /// Construct and coordinate signing rounds for `deposit-accept`,
     /// `withdraw-accept` and `withdraw-reject` transactions.
     ///
@@ -585,21 +786,39 @@ where
         //
         // For withdrawals, we need to have a record of the `request_id`
         // associated with the bitcoin transaction's outputs.
+        let db = self.context.get_storage();
 
-        let deposit_requests = self
-            .context
-            .get_storage()
+        let deposit_requests = db
             .get_swept_deposit_requests(chain_tip, self.context_window)
             .await?;
 
-        if deposit_requests.is_empty() {
+        let swept_withdrawals = db
+            .get_swept_withdrawal_requests(chain_tip, self.context_window)
+            .await?;
+
+        // We also need to reject the withdrawal requests that have either
+        // expired or that the signers have voted against.
+        let rejected_withdrawals = self
+            .get_rejectable_withdrawal_requests(
+                chain_tip,
+                bitcoin_aggregate_key,
+                wallet.signatures_required(),
+            )
+            .await?;
+
+        if deposit_requests.is_empty()
+            && swept_withdrawals.is_empty()
+            && rejected_withdrawals.is_empty()
+        {
             tracing::debug!("no stacks transactions to create, exiting");
             return Ok(());
         }
 
         tracing::debug!(
             num_deposits = %deposit_requests.len(),
-            "we have deposit requests that have been swept that may need minting"
+            num_swept_withdrawals = %swept_withdrawals.len(),
+            num_rejected_withdrawals = %rejected_withdrawals.len(),
+            "we have requests that need a response on stacks"
         );
         // We need to know the nonce to use, so we reach out to our stacks
         // node for the account information for our multi-sig address.
@@ -609,15 +828,33 @@ where
         let account = stacks.get_account(wallet.address()).await?;
         wallet.set_nonce(account.nonce);
 
-        for req in deposit_requests {
-            let outpoint = req.deposit_outpoint();
-            let sign_request_fut =
-                self.construct_deposit_stacks_sign_request(req, bitcoin_aggregate_key, &wallet);
+        // Deposits that were swept in by the same sweep transaction are
+        // completed together using a single `complete-deposits-wrapper`
+        // contract call, so we only need one signing round for all of
+        // them. Lone deposits use the `complete-deposit-wrapper` contract
+        // call.
+        let mut deposit_requests = deposit_requests;
+        deposit_requests.sort_by_key(|req| req.sweep_txid);
+        let deposit_batches: Vec<Vec<model::SweptDepositRequest>> = deposit_requests
+            .chunk_by(|a, b| a.sweep_txid == b.sweep_txid)
+            .flat_map(|batch| batch.chunks(CompleteDepositsV1::MAX_DEPOSITS))
+            .map(<[_]>::to_vec)
+            .collect();
+
+        for batch in deposit_batches {
+            let num_deposits = batch.len();
+            let sweep_txid = batch[0].sweep_txid;
+            let sign_request_fut = self.construct_deposit_stacks_sign_request(
+                chain_tip,
+                batch,
+                bitcoin_aggregate_key,
+                &wallet,
+            );
 
             let (sign_request, multi_tx) = match sign_request_fut.await {
                 Ok(res) => res,
                 Err(error) => {
-                    tracing::error!(%error, "could not construct a transaction completing the deposit request");
+                    tracing::error!(%error, %sweep_txid, "could not construct a transaction completing the deposit requests");
                     continue;
                 }
             };
@@ -632,14 +869,107 @@ where
 
             match process_request_fut.await {
                 Ok(txid) => {
-                    tracing::info!(%txid, "successfully submitted complete-deposit transaction")
+                    tracing::info!(%txid, %num_deposits, "successfully submitted complete-deposit transaction")
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        %error,
+                        %sweep_txid,
+                        %num_deposits,
+                        "could not process the stacks sign request for deposits"
+                    );
+                    wallet.set_nonce(wallet.get_nonce().saturating_sub(1));
+                }
+            }
+        }
+
+        // Similarly, withdrawals that were swept out by the same sweep
+        // transaction are accepted together using a single
+        // `complete-withdrawals` contract call.
+        let mut swept_withdrawals = swept_withdrawals;
+        swept_withdrawals.sort_by_key(|req| req.sweep_txid);
+        let withdrawal_batches: Vec<Vec<model::SweptWithdrawalRequest>> = swept_withdrawals
+            .chunk_by(|a, b| a.sweep_txid == b.sweep_txid)
+            .flat_map(|batch| batch.chunks(CompleteWithdrawalsV1::MAX_WITHDRAWALS))
+            .map(<[_]>::to_vec)
+            .collect();
+
+        for batch in withdrawal_batches {
+            let num_withdrawals = batch.len();
+            let sweep_txid = batch[0].sweep_txid;
+            let sign_request_fut = self.construct_withdrawal_accept_stacks_sign_request(
+                chain_tip,
+                batch,
+                bitcoin_aggregate_key,
+                &wallet,
+            );
+
+            let (sign_request, multi_tx) = match sign_request_fut.await {
+                Ok(res) => res,
+                Err(error) => {
+                    tracing::error!(%error, %sweep_txid, "could not construct a transaction accepting the withdrawal requests");
+                    continue;
+                }
+            };
+
+            // Same as with deposits, a failure here is not fatal, we just
+            // need to make sure that the nonce is not consumed.
+            let process_request_fut =
+                self.process_sign_request(sign_request, chain_tip, multi_tx, &wallet);
+
+            match process_request_fut.await {
+                Ok(txid) => {
+                    tracing::info!(%txid, %num_withdrawals, "successfully submitted accept-withdrawal transaction")
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        %error,
+                        %sweep_txid,
+                        %num_withdrawals,
+                        "could not process the stacks sign request for withdrawal acceptances"
+                    );
+                    wallet.set_nonce(wallet.get_nonce().saturating_sub(1));
+                }
+            }
+        }
+
+        // The rejected withdrawals are not tied to any sweep transaction,
+        // so we reject as many of them as we can in each contract call.
+        let rejection_batches: Vec<Vec<(model::WithdrawalRequest, model::SignerVotes)>> =
+            rejected_withdrawals
+                .chunks(CompleteWithdrawalsV1::MAX_WITHDRAWALS)
+                .map(<[_]>::to_vec)
+                .collect();
+
+        for batch in rejection_batches {
+            let num_withdrawals = batch.len();
+            let sign_request_fut = self.construct_withdrawal_reject_stacks_sign_request(
+                chain_tip,
+                batch,
+                bitcoin_aggregate_key,
+                &wallet,
+            );
+
+            let (sign_request, multi_tx) = match sign_request_fut.await {
+                Ok(res) => res,
+                Err(error) => {
+                    tracing::error!(%error, %num_withdrawals, "could not construct a transaction rejecting the withdrawal requests");
+                    continue;
+                }
+            };
+
+            let process_request_fut =
+                self.process_sign_request(sign_request, chain_tip, multi_tx, &wallet);
+
+            match process_request_fut.await {
+                Ok(txid) => {
+                    tracing::info!(%txid, %num_withdrawals, "successfully submitted reject-withdrawal transaction")
                 }
                 Err(error) => {
                     tracing::warn!(
                         %error,
-                        txid = %outpoint.txid,
-                        vout = %outpoint.vout,
-                        "could not process the stacks sign request for a deposit"
+                        %num_withdrawals,
+                        "could not process the stacks sign request for withdrawal rejections"
                     );
                     wallet.set_nonce(wallet.get_nonce().saturating_sub(1));
                 }
@@ -649,21 +979,82 @@ where
         Ok(())
     }
 
-    /// Construct and coordinate signing round for a `rotate-keys-wrapper` transaction.
+    /// Fetch the withdrawal requests that should be rejected, along with
+    /// how the signers voted on them.
+    ///
+    /// A withdrawal request should be rejected if it has not been swept
+    /// out, has no response on the canonical stacks blockchain, and
+    /// either:
+    /// 1. It has expired, meaning that the bitcoin block anchoring the
+    ///    stacks block with the request is at least
+    ///    [`WITHDRAWAL_BLOCKS_EXPIRY`] blocks below the chain tip, or
+    /// 2. Enough signers have voted against the request that it can never
+    ///    be accepted.
+    #[tracing::instrument(skip_all)]
+    async fn get_rejectable_withdrawal_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        bitcoin_aggregate_key: &PublicKey,
+        signatures_required: u16,
+    ) -> Result<Vec<(model::WithdrawalRequest, model::SignerVotes)>, Error> {
+        let db = self.context.get_storage();
+
+        let chain_tip_height = db
+            .get_bitcoin_block(chain_tip)
+            .await?
+            .ok_or(Error::MissingBitcoinBlock(*chain_tip))?
+            .block_height;
+
+        let requests = db
+            .get_unfulfilled_withdrawal_requests(chain_tip, self.context_window)
+            .await?;
+
+        let mut rejected = Vec::new();
+        for req in requests {
+            let votes = db
+                .get_withdrawal_request_signer_votes(&req.qualified_id(), bitcoin_aggregate_key)
+                .await?;
+
+            let max_accepts = votes.len().saturating_sub(votes.num_rejections());
+            if max_accepts < signatures_required as usize {
+                rejected.push((req, votes));
+                continue;
+            }
+
+            let Some(stacks_block) = db.get_stacks_block(&req.block_hash).await? else {
+                continue;
+            };
+            let Some(anchor) = db.get_bitcoin_block(&stacks_block.bitcoin_anchor).await? else {
+                continue;
+            };
+
+            let expiry_height = anchor.block_height.saturating_add(WITHDRAWAL_BLOCKS_EXPIRY);
+            if chain_tip_height >= expiry_height {
+                rejected.push((req, votes));
+            }
+        }
+
+        Ok(rejected)
+    }
+
+    /// Construct and coordinate signing round for a `rotate-keys-wrapper`
+    /// transaction that rotates to the keys of the given DKG shares.
+    ///
+    /// The transaction is signed by the current signers' wallet, while the
+    /// new keys may belong to a different signer set.
     #[tracing::instrument(skip_all)]
     async fn construct_and_sign_rotate_key_transaction(
         &mut self,
         bitcoin_chain_tip: &model::BitcoinBlockHash,
         aggregate_key: &PublicKey,
-        rotate_key_aggregate_key: &PublicKey,
+        dkg_shares: &model::EncryptedDkgShares,
         wallet: &SignerWallet,
     ) -> Result<StacksTxId, Error> {
         // TODO: we should validate the contract call before asking others
         // to sign it.
-        let contract_call = ContractCall::RotateKeysV1(RotateKeysV1::new(
-            wallet,
+        let contract_call = ContractCall::RotateKeysV1(RotateKeysV1::from_dkg_shares(
+            dkg_shares,
             self.context.config().signer.deployer,
-            rotate_key_aggregate_key,
         ));
 
         // Rotate key transactions should be done as soon as possible, so
@@ -703,53 +1094,106 @@ where
             .await?;
 
         match self.context.get_stacks_client().submit_tx(&tx).await {
-            Ok(SubmitTxResponse::Acceptance(txid)) => Ok(txid.into()),
-            Ok(SubmitTxResponse::Rejection(err)) => Err(err.into()),
+            Ok(SubmitTxResponse::Acceptance(txid)) => {
+                metrics().stacks_transactions_submitted.inc();
+                Ok(txid.into())
+            }
+            Ok(SubmitTxResponse::Rejection(err)) => {
+                let labels = RejectionLabels { reason: err.reason.into() };
+                metrics()
+                    .stacks_transactions_rejected
+                    .get_or_create(&labels)
+                    .inc();
+                Err(err.into())
+            }
             Err(err) => Err(err),
         }
     }
 
-    /// Transform the swept deposit request into a Stacks sign request
+    /// Transform the swept deposit requests into a Stacks sign request
     /// object.
     ///
+    /// All the given deposit requests must have been swept in by the same
+    /// sweep transaction. Deposit requests that fail validation are left
+    /// out, since the `complete-deposits-wrapper` contract call fails if
+    /// any one of its deposits fails. A `complete-deposit-wrapper`
+    /// contract call is used if only one deposit request remains,
+    /// otherwise all of them are bundled into a
+    /// `complete-deposits-wrapper` contract call.
+    ///
     /// This function uses bitcoin-core to help with the fee assessment of
-    /// the deposit request, and stacks-core for fee estimation of the
+    /// the deposit requests, and stacks-core for fee estimation of the
     /// transaction.
     #[tracing::instrument(skip_all)]
     async fn construct_deposit_stacks_sign_request(
         &self,
-        req: model::SweptDepositRequest,
+        chain_tip: &model::BitcoinBlockHash,
+        reqs: Vec<model::SweptDepositRequest>,
         bitcoin_aggregate_key: &PublicKey,
         wallet: &SignerWallet,
     ) -> Result<(StacksTransactionSignRequest, MultisigTx), Error> {
+        let Some(first) = reqs.first() else {
+            return Err(Error::NoDepositRequests);
+        };
         // Retrieve the Bitcoin sweep transaction from the Bitcoin node. We
         // can't get it from the database because the transaction is
         // only in the node's mempool at this point.
         let tx_info = self
             .context
             .get_bitcoin_client()
-            .get_tx_info(&req.sweep_txid, &req.sweep_block_hash)
+            .get_tx_info(&first.sweep_txid, &first.sweep_block_hash)
             .await?
             .ok_or_else(|| {
-                Error::BitcoinTxMissing(req.sweep_txid.into(), Some(req.sweep_block_hash.into()))
+                Error::BitcoinTxMissing(
+                    first.sweep_txid.into(),
+                    Some(first.sweep_block_hash.into()),
+                )
             })?;
 
-        let outpoint = req.deposit_outpoint();
-        let assessed_bitcoin_fee = tx_info
-            .assess_input_fee(&outpoint)
-            .ok_or_else(|| Error::OutPointMissing(outpoint))?;
+        let deployer = self.context.config().signer.deployer;
+        let mut deposits = Vec::with_capacity(reqs.len());
 
-        // TODO: we should validate the contract call before asking others
-        // to sign it.
-        let contract_call = ContractCall::CompleteDepositV1(CompleteDepositV1 {
-            amount: req.amount - assessed_bitcoin_fee.to_sat(),
-            outpoint,
-            recipient: req.recipient.into(),
-            deployer: self.context.config().signer.deployer,
-            sweep_txid: req.sweep_txid,
-            sweep_block_hash: req.sweep_block_hash,
-            sweep_block_height: req.sweep_block_height,
-        });
+        for req in reqs {
+            let outpoint = req.deposit_outpoint();
+            let Some(assessed_bitcoin_fee) = tx_info.assess_input_fee(&outpoint) else {
+                let error = Error::OutPointMissing(outpoint);
+                tracing::warn!(%error, %outpoint, "leaving deposit out of the complete-deposits transaction");
+                continue;
+            };
+
+            deposits.push(CompleteDepositV1 {
+                amount: req.amount - assessed_bitcoin_fee.to_sat(),
+                outpoint,
+                recipient: req.recipient.into(),
+                deployer,
+                sweep_txid: req.sweep_txid,
+                sweep_block_hash: req.sweep_block_hash,
+                sweep_block_height: req.sweep_block_height,
+            });
+        }
+
+        let mut complete_deposits = CompleteDepositsV1 { deposits, deployer };
+        let req_ctx = self
+            .stacks_req_ctx(chain_tip, bitcoin_aggregate_key)
+            .await?;
+        let invalid = complete_deposits
+            .remove_invalid_deposits(&self.context, &req_ctx)
+            .await?;
+
+        for (deposit, error) in invalid {
+            tracing::warn!(
+                %error,
+                outpoint = %deposit.outpoint,
+                "leaving invalid deposit out of the complete-deposits transaction"
+            );
+        }
+
+        let mut deposits = complete_deposits.deposits;
+        let contract_call = match deposits.len() {
+            0 => return Err(Error::NoDepositRequests),
+            1 => ContractCall::CompleteDepositV1(deposits.remove(0)),
+            _ => ContractCall::CompleteDepositsV1(CompleteDepositsV1 { deposits, deployer }),
+        };
 
         // Complete deposit requests should be done as soon as possible, so
         // we set the fee rate to the high priority fee.
@@ -773,6 +1217,223 @@ where
         Ok((sign_request, multi_tx))
     }
 
+    /// Return the request context that the signers use when validating
+    /// the Stacks transactions that we ask them to sign, see
+    /// `TxSignerEventLoop::assert_valid_stacks_tx_sign_request`.
+    async fn stacks_req_ctx(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        bitcoin_aggregate_key: &PublicKey,
+    ) -> Result<ReqContext, Error> {
+        let db = self.context.get_storage();
+        let Some(shares) = db.get_encrypted_dkg_shares(bitcoin_aggregate_key).await? else {
+            return Err(Error::MissingDkgShares(*bitcoin_aggregate_key));
+        };
+        let Some(block) = db.get_bitcoin_block(chain_tip).await? else {
+            return Err(Error::MissingBitcoinBlock(*chain_tip));
+        };
+
+        Ok(ReqContext {
+            chain_tip: block.into(),
+            context_window: self.context_window,
+            origin: self.signer_public_key(),
+            aggregate_key: *bitcoin_aggregate_key,
+            signatures_required: shares.signature_share_threshold,
+            deployer: self.context.config().signer.deployer,
+        })
+    }
+
+    /// Transform the swept withdrawal requests into a Stacks sign request
+    /// object.
+    ///
+    /// All the given withdrawal requests must have been swept out by the
+    /// same sweep transaction. Withdrawal requests that fail validation
+    /// are left out, see [`Self::complete_withdrawals_contract_call`]. An
+    /// `accept-withdrawal-request` contract call is used if only one
+    /// withdrawal request remains, otherwise all of them are bundled into
+    /// a `complete-withdrawals` contract call.
+    ///
+    /// This function uses bitcoin-core to help with the fee assessment of
+    /// the withdrawal outputs, and stacks-core for fee estimation of the
+    /// transaction.
+    #[tracing::instrument(skip_all)]
+    async fn construct_withdrawal_accept_stacks_sign_request(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        reqs: Vec<model::SweptWithdrawalRequest>,
+        bitcoin_aggregate_key: &PublicKey,
+        wallet: &SignerWallet,
+    ) -> Result<(StacksTransactionSignRequest, MultisigTx), Error> {
+        let Some(first) = reqs.first() else {
+            return Err(Error::NoWithdrawalRequests);
+        };
+        let tx_info = self
+            .context
+            .get_bitcoin_client()
+            .get_tx_info(&first.sweep_txid, &first.sweep_block_hash)
+            .await?
+            .ok_or_else(|| {
+                Error::BitcoinTxMissing(
+                    first.sweep_txid.into(),
+                    Some(first.sweep_block_hash.into()),
+                )
+            })?;
+
+        let db = self.context.get_storage();
+        let deployer = self.context.config().signer.deployer;
+        let mut withdrawals = Vec::with_capacity(reqs.len());
+
+        for req in reqs {
+            let outpoint = req.withdrawal_outpoint();
+            let Some(assessed_bitcoin_fee) = tx_info.assess_output_fee(outpoint.vout as usize)
+            else {
+                let error = Error::OutPointMissing(outpoint);
+                tracing::warn!(%error, request_id = %req.request_id, "leaving withdrawal out of the complete-withdrawals transaction");
+                continue;
+            };
+
+            let votes = db
+                .get_withdrawal_request_signer_votes(&req.qualified_id(), bitcoin_aggregate_key)
+                .await?;
+
+            withdrawals.push(WithdrawalCompletion::Accept(AcceptWithdrawalV1 {
+                request_id: req.request_id,
+                outpoint,
+                tx_fee: assessed_bitcoin_fee.to_sat(),
+                signer_bitmap: BitArray::from(votes),
+                deployer,
+                sweep_block_hash: req.sweep_block_hash,
+                sweep_block_height: req.sweep_block_height,
+                sweep_txid: req.sweep_txid,
+            }));
+        }
+
+        let contract_call = self
+            .complete_withdrawals_contract_call(chain_tip, withdrawals, bitcoin_aggregate_key)
+            .await?;
+
+        // Accepting withdrawal requests should be done as soon as
+        // possible, so we set the fee rate to the high priority fee.
+        let tx_fee = self
+            .context
+            .get_stacks_client()
+            .estimate_fees(wallet, &contract_call, FeePriority::High)
+            .await?;
+
+        let multi_tx = MultisigTx::new_tx(&contract_call, wallet, tx_fee);
+        let tx = multi_tx.tx();
+
+        let sign_request = StacksTransactionSignRequest {
+            aggregate_key: *bitcoin_aggregate_key,
+            contract_tx: contract_call.into(),
+            nonce: tx.get_origin_nonce(),
+            tx_fee: tx.get_tx_fee(),
+            txid: tx.txid(),
+        };
+
+        Ok((sign_request, multi_tx))
+    }
+
+    /// Transform the withdrawal requests into a Stacks sign request
+    /// object.
+    ///
+    /// Withdrawal requests that fail validation are left out, see
+    /// [`Self::complete_withdrawals_contract_call`]. A
+    /// `reject-withdrawal-request` contract call is used if only one
+    /// withdrawal request remains, otherwise all of them are bundled into
+    /// a `complete-withdrawals` contract call.
+    #[tracing::instrument(skip_all)]
+    async fn construct_withdrawal_reject_stacks_sign_request(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        reqs: Vec<(model::WithdrawalRequest, model::SignerVotes)>,
+        bitcoin_aggregate_key: &PublicKey,
+        wallet: &SignerWallet,
+    ) -> Result<(StacksTransactionSignRequest, MultisigTx), Error> {
+        let deployer = self.context.config().signer.deployer;
+        let withdrawals: Vec<WithdrawalCompletion> = reqs
+            .into_iter()
+            .map(|(req, votes)| {
+                WithdrawalCompletion::Reject(RejectWithdrawalV1 {
+                    request_id: req.request_id,
+                    signer_bitmap: BitArray::from(votes),
+                    deployer,
+                })
+            })
+            .collect();
+
+        let contract_call = self
+            .complete_withdrawals_contract_call(chain_tip, withdrawals, bitcoin_aggregate_key)
+            .await?;
+
+        // Rejecting a withdrawal request unlocks the user's sBTC, so we
+        // want this done as soon as possible too.
+        let tx_fee = self
+            .context
+            .get_stacks_client()
+            .estimate_fees(wallet, &contract_call, FeePriority::High)
+            .await?;
+
+        let multi_tx = MultisigTx::new_tx(&contract_call, wallet, tx_fee);
+        let tx = multi_tx.tx();
+
+        let sign_request = StacksTransactionSignRequest {
+            aggregate_key: *bitcoin_aggregate_key,
+            contract_tx: contract_call.into(),
+            nonce: tx.get_origin_nonce(),
+            tx_fee: tx.get_tx_fee(),
+            txid: tx.txid(),
+        };
+
+        Ok((sign_request, multi_tx))
+    }
+
+    /// Return the contract call that completes the given withdrawal
+    /// requests.
+    ///
+    /// Withdrawal requests that fail validation are left out, since the
+    /// `complete-withdrawals` contract call fails if any one of its
+    /// withdrawals fails. The stand-alone contract call is used if only
+    /// one withdrawal request remains.
+    async fn complete_withdrawals_contract_call(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        withdrawals: Vec<WithdrawalCompletion>,
+        bitcoin_aggregate_key: &PublicKey,
+    ) -> Result<ContractCall, Error> {
+        let deployer = self.context.config().signer.deployer;
+        let mut complete_withdrawals = CompleteWithdrawalsV1 { withdrawals, deployer };
+
+        let req_ctx = self
+            .stacks_req_ctx(chain_tip, bitcoin_aggregate_key)
+            .await?;
+        let invalid = complete_withdrawals
+            .remove_invalid_withdrawals(&self.context, &req_ctx)
+            .await?;
+
+        for (withdrawal, error) in invalid {
+            tracing::warn!(
+                %error,
+                request_id = %withdrawal.request_id(),
+                "leaving invalid withdrawal out of the complete-withdrawals transaction"
+            );
+        }
+
+        let mut withdrawals = complete_withdrawals.withdrawals;
+        let contract_call = match withdrawals.len() {
+            0 => return Err(Error::NoWithdrawalRequests),
+            1 => match withdrawals.remove(0) {
+                WithdrawalCompletion::Accept(accept) => ContractCall::AcceptWithdrawalV1(accept),
+                WithdrawalCompletion::Reject(reject) => ContractCall::RejectWithdrawalV1(reject),
+            },
+            _ => {
+                ContractCall::CompleteWithdrawalsV1(CompleteWithdrawalsV1 { withdrawals, deployer })
+            }
+        };
+
+        Ok(contract_call)
+    }
+
     /// Attempt to sign the stacks transaction.
     #[tracing::instrument(skip_all)]
     async fn sign_stacks_transaction(
@@ -784,6 +1445,12 @@ where
     ) -> Result<StacksTransaction, Error> {
         let txid = req.txid;
 
+        let mut participation = RoundParticipation::new(txid.0, *chain_tip);
+        participation.start(
+            ParticipationRoundType::StacksSignature,
+            wallet.public_keys().iter().copied(),
+        );
+
         // We ask for the signers to sign our transaction (including
         // ourselves, via our tx signer event loop)
         self.send_message(req, chain_tip).await?;
@@ -821,23 +1488,71 @@ where
                     Payload::StacksTransactionSignature(sig) if sig.txid == txid => sig,
                     _ => continue,
                 };
-
-                if let Err(error) = multi_tx.add_signature(sig.signature) {
-                    tracing::warn!(
-                        %txid,
-                        %error,
-                        offending_public_key = %msg.signer_public_key,
-                        "got an invalid signature"
-                    );
+
+                let round_type = ParticipationRoundType::StacksSignature;
+                match multi_tx.add_signature(sig.signature) {
+                    Ok(_) => participation.responded(round_type, msg.signer_public_key),
+                    Err(error) => {
+                        tracing::warn!(
+                            %txid,
+                            %error,
+                            offending_public_key = %msg.signer_public_key,
+                            "got an invalid signature"
+                        );
+                        participation.invalid(round_type, msg.signer_public_key);
+                    }
                 }
             }
 
+            participation.complete(ParticipationRoundType::StacksSignature);
             Ok::<_, Error>(multi_tx.finalize_transaction())
         };
 
-        tokio::time::timeout(max_duration, future)
-            .await
-            .map_err(|_| Error::SignatureTimeout(txid))?
+        let result = tokio::time::timeout(max_duration, future).await;
+        self.write_participation(participation).await;
+
+        result.map_err(|_| Error::SignatureTimeout(txid))?
+    }
+
+    /// Load the coordinator state machine for the DKG shares whose
+    /// aggregate key locks a UTXO with the given x-only public key.
+    ///
+    /// This is the current aggregate key, unless the signers have rotated
+    /// keys and the UTXO has not been swept over to the new key yet. In
+    /// that case we use the signer set and threshold of the old DKG round.
+    async fn load_coordinator_state_machine(
+        &self,
+        utxo_key: &bitcoin::XOnlyPublicKey,
+        aggregate_key: &PublicKey,
+        signer_public_keys: &BTreeSet<PublicKey>,
+    ) -> Result<CoordinatorStateMachine, Error> {
+        let mut storage = self.context.get_storage_mut();
+
+        if utxo_key == &bitcoin::XOnlyPublicKey::from(aggregate_key) {
+            return CoordinatorStateMachine::load(
+                &mut storage,
+                *aggregate_key,
+                signer_public_keys.clone(),
+                self.threshold,
+                self.private_key,
+            )
+            .await;
+        }
+
+        let script_pubkey = utxo_key.signers_script_pubkey().into();
+        let shares = storage
+            .get_encrypted_dkg_shares_by_script_pubkey(&script_pubkey)
+            .await?
+            .ok_or(Error::MissingDkgSharesForUtxo(*utxo_key))?;
+
+        CoordinatorStateMachine::load(
+            &mut storage,
+            shares.aggregate_key,
+            shares.signer_set_public_keys,
+            shares.signature_share_threshold,
+            self.private_key,
+        )
+        .await
     }
 
     /// Coordinate a signing round for the given request
@@ -850,14 +1565,13 @@ where
         signer_public_keys: &BTreeSet<PublicKey>,
         transaction: &mut utxo::UnsignedTransaction<'_>,
     ) -> Result<(), Error> {
-        let mut coordinator_state_machine = CoordinatorStateMachine::load(
-            &mut self.context.get_storage_mut(),
-            *aggregate_key,
-            signer_public_keys.clone(),
-            self.threshold,
-            self.private_key,
-        )
-        .await?;
+        // The signers' UTXO is locked by the aggregate key of the DKG
+        // round that created it, which is not the current aggregate key
+        // if the signers have rotated keys since then.
+        let signer_utxo_key = transaction.signer_utxo.utxo.public_key;
+        let mut coordinator_state_machine = self
+            .load_coordinator_state_machine(&signer_utxo_key, aggregate_key, signer_public_keys)
+            .await?;
         let sighashes = transaction.construct_digests()?;
         let msg = sighashes.signers.to_raw_hash().to_byte_array();
 
@@ -880,14 +1594,13 @@ where
         for (deposit, sighash) in sighashes.deposits.into_iter() {
             let msg = sighash.to_raw_hash().to_byte_array();
 
-            let mut coordinator_state_machine = CoordinatorStateMachine::load(
-                &mut self.context.get_storage_mut(),
-                *aggregate_key,
-                signer_public_keys.clone(),
-                self.threshold,
-                self.private_key,
-            )
-            .await?;
+            let mut coordinator_state_machine = self
+                .load_coordinator_state_machine(
+                    &deposit.signers_public_key,
+                    aggregate_key,
+                    signer_public_keys,
+                )
+                .await?;
 
             let signature = self
                 .coordinate_signing_round(
@@ -923,6 +1636,7 @@ where
             .get_bitcoin_client()
             .broadcast_transaction(&transaction.tx)
             .await?;
+        metrics().sweep_fees_paid_sats.inc_by(transaction.tx_fee);
 
         // Publish the transaction to the P2P network so that peers get advance
         // knowledge of the sweep.
@@ -946,10 +1660,20 @@ where
         msg: &[u8],
         signature_type: SignatureType,
     ) -> Result<TaprootSignature, Error> {
+        let signature_type_label = match signature_type {
+            SignatureType::Frost => "frost",
+            SignatureType::Schnorr => "schnorr",
+            SignatureType::Taproot(_) => "taproot",
+        };
         let outbound = coordinator_state_machine
             .start_signing_round(msg, signature_type)
             .map_err(Error::wsts_coordinator)?;
 
+        let signers = state_machine_signers(coordinator_state_machine);
+        let mut participation = self
+            .signing_round_participation(txid, bitcoin_chain_tip, &signers)
+            .await?;
+
         // We create a signal stream before sending a message so that there
         // is no race condition with the steam and the getting a response.
         let signal_stream = self
@@ -957,7 +1681,12 @@ where
             .as_signal_stream(signed_message_filter)
             .filter_map(Self::to_signed_message);
 
-        let msg = message::WstsMessage { txid, inner: outbound.msg };
+        participation.start(ParticipationRoundType::Nonce, signers);
+        let msg = message::WstsMessage {
+            txid,
+            inner: outbound.msg,
+            dkg_participants: BTreeSet::new(),
+        };
         self.send_message(msg, bitcoin_chain_tip).await?;
 
         let max_duration = self.signing_round_max_duration;
@@ -966,11 +1695,16 @@ where
             bitcoin_chain_tip,
             coordinator_state_machine,
             txid,
+            &mut participation,
         );
 
-        let operation_result = tokio::time::timeout(max_duration, run_signing_round)
-            .await
-            .map_err(|_| Error::CoordinatorTimeout(max_duration.as_secs()))??;
+        let started_at = tokio::time::Instant::now();
+        let result = tokio::time::timeout(max_duration, run_signing_round).await;
+        record_signing_round(signature_type_label, &result, started_at.elapsed());
+        self.write_participation(participation).await;
+
+        let operation_result =
+            result.map_err(|_| Error::CoordinatorTimeout(max_duration.as_secs()))??;
 
         match operation_result {
             WstsOperationResult::SignTaproot(sig) | WstsOperationResult::SignSchnorr(sig) => {
@@ -980,27 +1714,119 @@ where
         }
     }
 
-    /// Set up a WSTS coordinator state machine and run DKG with the other
-    /// signers in the signing set.
+    /// Create the participation tracker for a WSTS signing round. Unless
+    /// disabled in the config, the nonces of signers that have been
+    /// unreliable in their recent rounds are held back in favour of the
+    /// nonces of reliable ones.
+    async fn signing_round_participation(
+        &self,
+        txid: bitcoin::Txid,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        signers: &BTreeSet<PublicKey>,
+    ) -> Result<RoundParticipation, Error> {
+        let settings = self.context.config();
+        let config = &settings.signer.participation;
+        let participation = RoundParticipation::new(txid.to_byte_array(), *bitcoin_chain_tip);
+        if config.window == 0 {
+            return Ok(participation);
+        }
+
+        let stats = self
+            .context
+            .get_storage()
+            .get_signer_participation_stats(config.window)
+            .await?;
+        let reliable = participation::reliable_signers(signers, &stats, config.max_failure_rate);
+
+        Ok(participation.with_preferred_nonce_responders(reliable))
+    }
+
+    /// Write the participation records of a finished round, logging the
+    /// signers that did not take part as expected.
+    async fn write_participation(&self, participation: RoundParticipation) {
+        let records = participation.finish();
+        for record in &records {
+            if record.outcome != model::ParticipationOutcome::Responded {
+                tracing::warn!(
+                    signer = %record.signer_public_key,
+                    round_type = %record.round_type,
+                    outcome = %record.outcome,
+                    "signer did not take part in round as expected"
+                );
+            }
+        }
+
+        let storage = self.context.get_storage_mut();
+        if let Err(error) = storage.write_signer_participation(&records).await {
+            tracing::warn!(%error, "could not write signer participation");
+        }
+    }
+
+    /// Run DKG with the other signers in the signing set, retrying rounds
+    /// that fail or time out with an exponential backoff, until a round
+    /// succeeds or we have run the configured maximum number of rounds.
     #[tracing::instrument(skip_all)]
+    async fn run_dkg(&mut self, chain_tip: &model::BitcoinBlockHash) -> Result<PublicKey, Error> {
+        let dkg_config = self.context.config().signer.dkg.clone();
+        let mut attempt = 1;
+
+        loop {
+            match self.coordinate_dkg(chain_tip, attempt).await {
+                Ok(aggregate_key) => return Ok(aggregate_key),
+                Err(error @ Error::SignerShutdown) => return Err(error),
+                Err(error) if attempt >= dkg_config.max_attempts => {
+                    tracing::error!(%error, %attempt, "DKG failed, giving up until the next bitcoin block");
+                    return Err(error);
+                }
+                Err(error) => {
+                    let delay = dkg_config.retry_delay(attempt);
+                    tracing::warn!(
+                        %error,
+                        %attempt,
+                        delay_secs = delay.as_secs(),
+                        "DKG round failed, retrying after a delay"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Set up a WSTS coordinator state machine and run a single round of
+    /// DKG with the other signers in the signing set.
+    ///
+    /// Signers that have been blamed for too many failed rounds are left
+    /// out of the round, see [`dkg_participants`], and the outcome of the
+    /// round is written to the database.
+    #[tracing::instrument(skip_all, fields(attempt = %attempt))]
     async fn coordinate_dkg(
         &mut self,
         chain_tip: &model::BitcoinBlockHash,
+        attempt: u32,
     ) -> Result<PublicKey, Error> {
         tracing::info!("Coordinating DKG");
-        // Get the current signer set for running DKG.
-        //
-        // Also, note that in order to change the signing set we must first
-        // run DKG (which the current function is doing), and DKG requires
-        // us to define signing set (which is returned in the next
-        // non-comment line). That function essentially uses the signing
-        // set of the last DKG (either through the last rotate-keys
-        // contract call or from the `dkg_shares` table) so we wind up
-        // never changing the signing set.
-        let (_, signer_set) = self.get_signer_set_and_aggregate_key(chain_tip).await?;
+        // Get the signer set for running DKG. This is the signer set
+        // approved by governance, which may differ from the signer set of
+        // the last DKG round. The other signers use the same set when they
+        // receive our `DkgBegin` message.
+        let (signer_set, threshold) = target_signer_set(&self.context).await?;
+
+        let failure_counts = self.context.get_storage().get_dkg_failure_counts().await?;
+        let exclusion_threshold = self.context.config().signer.dkg.exclusion_threshold;
+        let participants =
+            dkg_participants(&signer_set, &failure_counts, exclusion_threshold, threshold);
+
+        for excluded in signer_set.difference(&participants) {
+            tracing::warn!(
+                signer = %excluded,
+                failures = failure_counts.get(excluded).copied().unwrap_or_default(),
+                "excluding signer from DKG round because of repeated failures"
+            );
+        }
 
         let mut state_machine =
-            CoordinatorStateMachine::new(signer_set, self.threshold, self.private_key);
+            CoordinatorStateMachine::new(participants.iter().copied(), threshold, self.private_key);
 
         // Okay let's move the coordinator state machine to the beginning
         // of the DKG phase.
@@ -1015,9 +1841,30 @@ where
         // We identify the DKG round by a 32-byte hash which we throw
         // around as a bitcoin transaction ID, even when it is not one. We
         // should probably change this
-        let identifier = self.coordinator_id(chain_tip);
+        let identifier = self.dkg_id(chain_tip, attempt);
         let txid = bitcoin::Txid::from_byte_array(identifier);
-        let msg = message::WstsMessage { txid, inner: outbound.msg };
+        // The signers take part in the round with the participants that
+        // we chose, so that they do not need to agree with us on the
+        // failures recorded in their own databases.
+        let msg = message::WstsMessage {
+            txid,
+            inner: outbound.msg,
+            dkg_participants: participants.clone(),
+        };
+
+        let mut participation = RoundParticipation::new(identifier, *chain_tip);
+
+        let mut dkg_attempt = model::DkgAttempt {
+            dkg_id: txid.into(),
+            bitcoin_chain_tip: *chain_tip,
+            signer_set_public_keys: participants.iter().copied().collect(),
+            status: model::DkgAttemptStatus::Pending,
+            aggregate_key: None,
+        };
+        self.context
+            .get_storage_mut()
+            .write_dkg_attempt(&dkg_attempt)
+            .await?;
 
         // We create a signal stream before sending a message so that there
         // is no race condition with the steam and the getting a response.
@@ -1030,23 +1877,76 @@ where
         // running on the signers will pick up this message and act on it,
         // including our own. When they do they create a signing state
         // machine and begin DKG.
+        participation.start(ParticipationRoundType::Dkg, participants);
         self.send_message(msg, chain_tip).await?;
 
         // Now that DKG has "begun" we need to drive it to completion.
         let max_duration = self.dkg_max_duration;
-        let dkg_fut =
-            self.drive_wsts_state_machine(signal_stream, chain_tip, &mut state_machine, txid);
+        let dkg_fut = self.drive_wsts_state_machine(
+            signal_stream,
+            chain_tip,
+            &mut state_machine,
+            txid,
+            &mut participation,
+        );
 
-        let operation_result = tokio::time::timeout(max_duration, dkg_fut)
-            .await
-            .map_err(|_| Error::CoordinatorTimeout(max_duration.as_secs()))??;
+        let dkg_result = tokio::time::timeout(max_duration, dkg_fut).await;
+        self.write_participation(participation).await;
+
+        let (status, result) = match dkg_result {
+            Err(_) => (
+                model::DkgAttemptStatus::TimedOut,
+                Err(Error::CoordinatorTimeout(max_duration.as_secs())),
+            ),
+            Ok(Ok(WstsOperationResult::Dkg(aggregate_key))) => {
+                match PublicKey::try_from(&aggregate_key) {
+                    Ok(key) => (model::DkgAttemptStatus::Success, Ok(key)),
+                    Err(error) => (model::DkgAttemptStatus::Failed, Err(error)),
+                }
+            }
+            Ok(Ok(result)) => (
+                model::DkgAttemptStatus::Failed,
+                Err(Error::UnexpectedOperationResult(Box::new(result))),
+            ),
+            Ok(Err(error)) => (model::DkgAttemptStatus::Failed, Err(error)),
+        };
 
-        match operation_result {
-            WstsOperationResult::Dkg(aggregate_key) => PublicKey::try_from(&aggregate_key),
-            result => Err(Error::UnexpectedOperationResult(Box::new(result))),
+        dkg_attempt.status = status;
+        dkg_attempt.aggregate_key = result.as_ref().ok().copied();
+        self.context
+            .get_storage_mut()
+            .write_dkg_attempt(&dkg_attempt)
+            .await?;
+
+        // The signers record who they blame for a failed round as they
+        // process the `DkgEnd` messages, so we can report on them here.
+        if result.is_err() {
+            let failures = self
+                .context
+                .get_storage()
+                .get_dkg_failures(&dkg_attempt.dkg_id)
+                .await?;
+            for failure in failures {
+                tracing::warn!(
+                    signer = %failure.signer_public_key,
+                    reported_by = %failure.reported_by,
+                    reason = %failure.reason,
+                    "signer blamed for failed DKG round"
+                );
+            }
         }
+        tracing::info!(%status, "DKG round ended");
+
+        result
     }
 
+    /// Drive the WSTS coordinator state machine with the messages from the
+    /// signers until it produces a result, recording how each signer takes
+    /// part in the round.
+    ///
+    /// While nonces are being gathered, the nonces of signers that are
+    /// not preferred by the participation tracker are held back for up to
+    /// the configured grace period.
     #[tracing::instrument(skip_all)]
     async fn drive_wsts_state_machine<S>(
         &mut self,
@@ -1054,6 +1954,7 @@ where
         bitcoin_chain_tip: &model::BitcoinBlockHash,
         coordinator_state_machine: &mut CoordinatorStateMachine,
         txid: bitcoin::Txid,
+        participation: &mut RoundParticipation,
     ) -> Result<WstsOperationResult, Error>
     where
         S: Stream<Item = Signed<SignerMessage>>,
@@ -1065,68 +1966,187 @@ where
             .get_signer_set_and_aggregate_key(bitcoin_chain_tip)
             .await?;
 
+        let grace_period = self
+            .context
+            .config()
+            .signer
+            .participation
+            .nonce_grace_period;
+        let threshold = coordinator_state_machine.get_config().threshold as usize;
+        let mut held_back_nonces: Vec<(PublicKey, wsts::net::Packet)> = Vec::new();
+
         tokio::pin!(signal_stream);
 
         coordinator_state_machine.save();
-        // Let's get the next message from the network or the
-        // TxSignerEventLoop.
-        //
-        // If signal_stream.next() returns None then one of the underlying
-        // streams has closed. That means either the internal message
-        // channel, or the termination handler channel has closed. This is
-        // all bad, so we trigger a shutdown.
-        while let Some(msg) = signal_stream.next().await {
-            if &msg.bitcoin_chain_tip != bitcoin_chain_tip {
-                tracing::warn!(sender = %msg.signer_public_key, "concurrent WSTS activity observed");
-                continue;
-            }
-
-            let Payload::WstsMessage(wsts_msg) = msg.inner.payload else {
-                continue;
+        loop {
+            let release_deadline = participation.nonce_grace_deadline(grace_period);
+            let release_timer = async move {
+                match release_deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending().await,
+                }
             };
 
-            let packet = wsts::net::Packet {
-                msg: wsts_msg.inner,
-                sig: Vec::new(),
+            // Let's get the next message from the network or the
+            // TxSignerEventLoop, or release the nonces that we have held
+            // back once the grace period is over.
+            //
+            // If signal_stream.next() returns None then one of the
+            // underlying streams has closed. That means either the
+            // internal message channel, or the termination handler channel
+            // has closed. This is all bad, so we trigger a shutdown.
+            let maybe_msg = tokio::select! {
+                msg = signal_stream.next() => match msg {
+                    Some(msg) => Some(msg),
+                    None => break,
+                },
+                _ = release_timer, if !held_back_nonces.is_empty() => None,
             };
 
-            let msg_public_key = msg.signer_public_key;
+            let packets = match maybe_msg {
+                // The grace period is over, so we process the nonces that
+                // we have held back.
+                None => {
+                    tracing::debug!("releasing held back nonces");
+                    std::mem::take(&mut held_back_nonces)
+                }
+                Some(msg) => {
+                    if &msg.bitcoin_chain_tip != bitcoin_chain_tip {
+                        tracing::warn!(
+                            sender = %msg.signer_public_key,
+                            "concurrent WSTS activity observed"
+                        );
+                        continue;
+                    }
 
-            let sender_is_coordinator =
-                given_key_is_coordinator(msg_public_key, bitcoin_chain_tip, &signer_set);
+                    let Payload::WstsMessage(wsts_msg) = msg.inner.payload else {
+                        continue;
+                    };
 
-            let public_keys = &coordinator_state_machine.get_config().signer_public_keys;
-            let public_key_point = p256k1::point::Point::from(msg_public_key);
+                    if wsts_msg.txid != txid {
+                        tracing::debug!(
+                            sender = %msg.signer_public_key,
+                            "ignoring WSTS message for another round"
+                        );
+                        continue;
+                    }
 
-            // check that messages were signed by correct key
-            let is_authenticated = Self::authenticate_message(
-                &packet,
-                public_keys,
-                public_key_point,
-                sender_is_coordinator,
-            );
+                    let packet = wsts::net::Packet {
+                        msg: wsts_msg.inner,
+                        sig: Vec::new(),
+                    };
 
-            if !is_authenticated {
-                continue;
-            }
+                    let msg_public_key = msg.signer_public_key;
+
+                    let failover_round =
+                        coordinator_failover_round(&self.context, bitcoin_chain_tip);
+                    let sender_is_coordinator = given_key_is_coordinator(
+                        msg_public_key,
+                        bitcoin_chain_tip,
+                        &signer_set,
+                        failover_round,
+                    );
+
+                    let public_keys = &coordinator_state_machine.get_config().signer_public_keys;
+                    let public_key_point = p256k1::point::Point::from(msg_public_key);
 
-            let (outbound_packet, operation_result) =
-                match coordinator_state_machine.process_message(&packet) {
-                    Ok(val) => val,
-                    Err(err) => {
-                        tracing::warn!(?packet, reason = %err, "ignoring packet");
+                    // check that messages were signed by correct key
+                    let is_authenticated = Self::authenticate_message(
+                        &packet,
+                        public_keys,
+                        public_key_point,
+                        sender_is_coordinator,
+                    );
+
+                    let round_type = participation::wsts_round_type(&packet.msg);
+                    if !is_authenticated {
+                        if let Some(round_type) = round_type {
+                            participation.invalid(round_type, msg_public_key);
+                        }
                         continue;
                     }
-                };
 
-            if let Some(packet) = outbound_packet {
-                let msg = message::WstsMessage { txid, inner: packet.msg };
-                self.send_message(msg, bitcoin_chain_tip).await?;
-            }
+                    // A signer has taken part in DKG once it has sent its
+                    // `DkgEnd` message.
+                    match (&packet.msg, round_type) {
+                        (wsts::net::Message::DkgPublicShares(_), _)
+                        | (wsts::net::Message::DkgPrivateShares(_), _) => {}
+                        (_, Some(round_type)) => {
+                            participation.responded(round_type, msg_public_key)
+                        }
+                        (_, None) => {}
+                    }
+
+                    let is_nonce = matches!(packet.msg, wsts::net::Message::NonceResponse(_));
+                    if is_nonce
+                        && participation.should_hold_back_nonce(
+                            &msg_public_key,
+                            threshold,
+                            grace_period,
+                        )
+                    {
+                        tracing::debug!(
+                            sender = %msg_public_key,
+                            "holding back nonce from unreliable signer"
+                        );
+                        held_back_nonces.push((msg_public_key, packet));
+                        continue;
+                    }
+
+                    vec![(msg_public_key, packet)]
+                }
+            };
+
+            for (sender, packet) in packets {
+                let round_type = participation::wsts_round_type(&packet.msg);
+
+                let (outbound_packet, operation_result) =
+                    match coordinator_state_machine.process_message(&packet) {
+                        Ok(val) => val,
+                        Err(err) => {
+                            tracing::warn!(?packet, reason = %err, "ignoring packet");
+                            if let Some(round_type) = round_type {
+                                participation.invalid(round_type, sender);
+                            }
+                            continue;
+                        }
+                    };
+
+                if let Some(packet) = outbound_packet {
+                    // The signature shares are requested from the signers
+                    // whose nonces were used, so the nonce phase is over
+                    // and the nonces we held back are not needed.
+                    if let wsts::net::Message::SignatureShareRequest(request) = &packet.msg {
+                        held_back_nonces.clear();
+                        let signer_ids =
+                            request.nonce_responses.iter().map(|nonce| nonce.signer_id);
+                        let expected = signer_ids_to_keys(coordinator_state_machine, signer_ids);
+                        participation.complete(ParticipationRoundType::Nonce);
+                        participation.start(ParticipationRoundType::SignatureShare, expected);
+                    }
+                    let msg = message::WstsMessage {
+                        txid,
+                        inner: packet.msg,
+                        dkg_participants: BTreeSet::new(),
+                    };
+                    self.send_message(msg, bitcoin_chain_tip).await?;
+                }
 
-            match operation_result {
-                Some(res) => return Ok(res),
-                None => continue,
+                match operation_result {
+                    Some(WstsOperationResult::Dkg(aggregate_key)) => {
+                        participation.complete(ParticipationRoundType::Dkg);
+                        return Ok(WstsOperationResult::Dkg(aggregate_key));
+                    }
+                    Some(
+                        res @ (WstsOperationResult::SignTaproot(_)
+                        | WstsOperationResult::SignSchnorr(_)),
+                    ) => {
+                        participation.complete(ParticipationRoundType::SignatureShare);
+                        return Ok(res);
+                    }
+                    Some(res) => return Ok(res),
+                    None => continue,
+                }
             }
         }
 
@@ -1212,7 +2232,13 @@ where
         bitcoin_chain_tip: &model::BitcoinBlockHash,
         signer_public_keys: &BTreeSet<PublicKey>,
     ) -> bool {
-        given_key_is_coordinator(self.pub_key(), bitcoin_chain_tip, signer_public_keys)
+        let failover_round = coordinator_failover_round(&self.context, bitcoin_chain_tip);
+        given_key_is_coordinator(
+            self.pub_key(),
+            bitcoin_chain_tip,
+            signer_public_keys,
+            failover_round,
+        )
     }
 
     /// Constructs a new [`utxo::SignerBtcState`] based on the current market
@@ -1241,14 +2267,15 @@ where
             utxo,
             public_key: bitcoin::XOnlyPublicKey::from(aggregate_key),
             last_fees,
-            magic_bytes: [b'T', b'3'], //TODO(#472): Use the correct magic bytes.
+            magic_bytes: self.context.config().signer.magic_bytes(),
         })
     }
 
-    /// TODO(#742): This function needs to filter deposit requests based on
-    /// time as well. We need to do this because deposit requests are locked
-    /// using OP_CSV, which lock up coins based on block height or
-    /// multiples of 512 seconds measure by the median time past.
+    /// Fetch the pending deposit and withdrawal requests. Deposit
+    /// requests are locked using OP_CSV, which locks up coins based on
+    /// block height or multiples of 512 seconds measured by the
+    /// median-time-past, and the storage layer filters out deposits that
+    /// the depositor will be able to reclaim soon under either unit.
     #[tracing::instrument(skip_all)]
     async fn get_pending_requests(
         &mut self,
@@ -1360,8 +2387,29 @@ where
         }
     }
 
+    /// Check whether the signer set approved by governance in the config
+    /// differs from the signer set that took part in the latest DKG
+    /// round, see [`signer_set_changed`].
+    ///
+    /// We do not look at the `sbtc-registry` contract here, since the
+    /// signer set there is only ever updated by our own rotate-keys
+    /// transactions.
+    async fn signer_set_changed(&self) -> Result<bool, Error> {
+        let config = self.context.config();
+        let Some(target) = config.signer.signer_set.as_ref() else {
+            return Ok(false);
+        };
+        let db = self.context.get_storage();
+        let Some(shares) = db.get_latest_encrypted_dkg_shares().await? else {
+            return Ok(false);
+        };
+        let blamed_signers = db.get_dkg_blamed_signers().await?;
+
+        Ok(signer_set_changed(target, &shares, &blamed_signers))
+    }
+
     fn pub_key(&self) -> PublicKey {
-        PublicKey::from_private_key(&self.private_key)
+        self.signer_key.public_key()
     }
 
     /// This function provides a deterministic 32-byte identifier for the
@@ -1374,6 +2422,17 @@ where
             .into()
     }
 
+    /// The identifier of the given DKG round that we coordinate for the
+    /// given chain tip. Each round gets its own identifier so that the
+    /// signers start with a fresh state machine every time.
+    fn dkg_id(&self, chain_tip: &model::BitcoinBlockHash, attempt: u32) -> [u8; 32] {
+        sha2::Sha256::new_with_prefix("SIGNER_DKG_ID")
+            .chain_update(self.coordinator_id(chain_tip))
+            .chain_update(attempt.to_be_bytes())
+            .finalize()
+            .into()
+    }
+
     #[tracing::instrument(skip_all)]
     async fn send_message(
         &mut self,
@@ -1383,7 +2442,7 @@ where
         let msg = msg
             .into()
             .to_message(*bitcoin_chain_tip)
-            .sign_ecdsa(&self.private_key);
+            .sign_ecdsa_with(&*self.signer_key)?;
 
         self.network.broadcast(msg.clone()).await?;
         self.context
@@ -1527,7 +2586,7 @@ where
     }
 
     fn signer_public_key(&self) -> PublicKey {
-        PublicKey::from_private_key(&self.private_key)
+        self.signer_key.public_key()
     }
 
     /// Assesses the total fees paid for any outstanding sweep transactions in
@@ -1634,19 +2693,176 @@ where
     }
 }
 
-/// Check if the provided public key is the coordinator for the provided chain tip
+/// Check if the provided public key is the coordinator for the provided
+/// chain tip in the given failover round.
 pub fn given_key_is_coordinator(
     pub_key: PublicKey,
     bitcoin_chain_tip: &model::BitcoinBlockHash,
     signer_public_keys: &BTreeSet<PublicKey>,
+    failover_round: usize,
+) -> bool {
+    coordinator_public_key(bitcoin_chain_tip, signer_public_keys, failover_round) == Some(pub_key)
+}
+
+/// Record how long a signing round took and how it ended.
+fn record_signing_round<T, E1, E2>(
+    signature_type: &'static str,
+    result: &Result<Result<T, E1>, E2>,
+    duration: Duration,
+) {
+    let outcome = match result {
+        Ok(Ok(_)) => "success",
+        Ok(Err(_)) => "failure",
+        Err(_) => "timeout",
+    };
+    metrics()
+        .signing_round_duration_seconds
+        .get_or_create(&SigningRoundLabels { signature_type, outcome })
+        .observe(duration.as_secs_f64());
+}
+
+/// Return the public keys of the signers known to the coordinator state
+/// machine.
+fn state_machine_signers(state_machine: &CoordinatorStateMachine) -> BTreeSet<PublicKey> {
+    state_machine
+        .get_config()
+        .signer_public_keys
+        .values()
+        .filter_map(|point| PublicKey::try_from(point).ok())
+        .collect()
+}
+
+/// Return the public keys of the signers with the given WSTS signer IDs.
+fn signer_ids_to_keys(
+    state_machine: &CoordinatorStateMachine,
+    signer_ids: impl IntoIterator<Item = u32>,
+) -> BTreeSet<PublicKey> {
+    let public_keys = &state_machine.get_config().signer_public_keys;
+    signer_ids
+        .into_iter()
+        .filter_map(|signer_id| public_keys.get(&signer_id))
+        .filter_map(|point| PublicKey::try_from(point).ok())
+        .collect()
+}
+
+/// Return the failover round that is active for the given chain tip.
+///
+/// The coordinator selected by [`coordinator_public_key`] is active until
+/// we have not heard from it for `coordinator_failover_timeout`, measured
+/// from when the chain tip was observed or from its latest message. After
+/// that, the next signer in the fallback order takes over, and it stays
+/// active for as long as we keep hearing from it, and so on. A zero
+/// timeout disables failover, so the round is always zero.
+pub fn coordinator_failover_round<C: Context>(
+    ctx: &C,
+    bitcoin_chain_tip: &model::BitcoinBlockHash,
+) -> usize {
+    let timeout = ctx.config().signer.coordinator_failover_timeout;
+    if timeout.is_zero() {
+        return 0;
+    }
+    let (round, active_at) = ctx.state().coordinator_activity(bitcoin_chain_tip);
+    round.saturating_add(failover_round(active_at.elapsed(), timeout))
+}
+
+/// Record that we have just heard from the coordinator that is active for
+/// the given chain tip, which keeps it active for another
+/// `coordinator_failover_timeout`.
+///
+/// This way a coordinator that is in the middle of a long signing round
+/// is not cut off, while one that has gone quiet is replaced.
+pub fn record_coordinator_activity<C: Context>(
+    ctx: &C,
+    bitcoin_chain_tip: &model::BitcoinBlockHash,
+) {
+    let round = coordinator_failover_round(ctx, bitcoin_chain_tip);
+    ctx.state()
+        .set_coordinator_activity(bitcoin_chain_tip, round, tokio::time::Instant::now());
+}
+
+/// Return the number of full failover timeouts in the elapsed time.
+fn failover_round(elapsed: Duration, timeout: Duration) -> usize {
+    let round = elapsed.as_millis() / timeout.as_millis().max(1);
+    usize::try_from(round).unwrap_or(usize::MAX)
+}
+
+/// Return whether the signers need to run DKG for the `target` signer
+/// set, given the shares from the latest DKG round.
+///
+/// The latest DKG round may have left out signers that were blamed for
+/// failed rounds, see [`dkg_participants`]. Missing signers like these do
+/// not warrant another DKG round, otherwise we would run DKG on every
+/// bitcoin block until they behave.
+pub fn signer_set_changed(
+    target: &SignerSetConfig,
+    shares: &model::EncryptedDkgShares,
+    blamed_signers: &BTreeSet<PublicKey>,
 ) -> bool {
-    coordinator_public_key(bitcoin_chain_tip, signer_public_keys) == Some(pub_key)
+    let target_signer_set = target.public_keys();
+    let dkg_signer_set: BTreeSet<PublicKey> =
+        shares.signer_set_public_keys.iter().copied().collect();
+
+    if target.signatures_required != shares.signature_share_threshold
+        || !dkg_signer_set.is_subset(&target_signer_set)
+    {
+        return true;
+    }
+
+    target_signer_set
+        .difference(&dkg_signer_set)
+        .any(|key| !blamed_signers.contains(key))
+}
+
+/// Return the signers that should take part in the next DKG round.
+///
+/// Signers that have been blamed for at least `exclusion_threshold` failed
+/// DKG rounds are left out, starting with the ones that have failed the
+/// most, as long as at least `signatures_required` signers remain. An
+/// `exclusion_threshold` of zero leaves no one out.
+///
+/// Only the coordinator computes this, from the failures recorded in its
+/// own database, and it sends the result to the other signers in its
+/// `DkgBegin` message. The failures recorded by each signer may differ,
+/// for example when a signer missed some `DkgEnd` messages.
+pub fn dkg_participants(
+    signer_public_keys: &BTreeSet<PublicKey>,
+    failure_counts: &BTreeMap<PublicKey, u32>,
+    exclusion_threshold: u32,
+    signatures_required: u16,
+) -> BTreeSet<PublicKey> {
+    let mut participants = signer_public_keys.clone();
+    if exclusion_threshold == 0 {
+        return participants;
+    }
+
+    let mut offenders: Vec<(u32, PublicKey)> = signer_public_keys
+        .iter()
+        .filter_map(|key| Some((*failure_counts.get(key)?, *key)))
+        .filter(|(count, _)| *count >= exclusion_threshold)
+        .collect();
+    // Most failures first, with ties broken by the public key so that
+    // everyone comes up with the same set.
+    offenders.sort_by(|a, b| b.cmp(a));
+
+    for (_, key) in offenders {
+        if participants.len() <= usize::from(signatures_required) {
+            break;
+        }
+        participants.remove(&key);
+    }
+
+    participants
 }
 
-/// Find the coordinator public key
+/// Find the coordinator public key for the given failover round.
+///
+/// The coordinator for round zero is picked using the hash of the chain
+/// tip. In each later round, the signer after the previous round's
+/// coordinator takes over, wrapping around to the start of the signer set.
 pub fn coordinator_public_key(
     bitcoin_chain_tip: &model::BitcoinBlockHash,
     signer_public_keys: &BTreeSet<PublicKey>,
+    failover_round: usize,
 ) -> Option<PublicKey> {
     let mut hasher = sha2::Sha256::new();
     hasher.update(bitcoin_chain_tip.into_bytes());
@@ -1661,20 +2877,34 @@ pub fn coordinator_public_key(
     // greater than 32 bytes?
     let index = usize::from_be_bytes(*digest.first_chunk()?);
     let num_signers = signer_public_keys.len();
+    let index = (index % num_signers + failover_round % num_signers) % num_signers;
 
-    signer_public_keys.iter().nth(index % num_signers).copied()
+    signer_public_keys.iter().nth(index).copied()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+    use std::collections::BTreeSet;
+
+    use fake::Fake as _;
+
     use crate::bitcoin::MockBitcoinInteract;
+    use crate::config::SignerSetConfig;
     use crate::emily_client::MockEmilyInteract;
+    use crate::keys::PrivateKey;
+    use crate::keys::PublicKey;
     use crate::stacks::api::MockStacksInteract;
+    use crate::storage;
     use crate::storage::in_memory::SharedStore;
+    use crate::storage::model;
+    use crate::storage::DbRead as _;
+    use crate::storage::DbWrite as _;
     use crate::testing;
     use crate::testing::context::*;
     use crate::testing::transaction_coordinator::TestEnvironment;
 
+    use test_case::test_case;
     use test_log::test;
 
     fn test_environment() -> TestEnvironment<
@@ -1767,8 +2997,219 @@ mod tests {
         test_environment().assert_get_signer_utxo_unspent().await;
     }
 
+    #[tokio::test]
+    async fn should_get_signer_utxo_after_key_rotation() {
+        test_environment()
+            .assert_get_signer_utxo_follows_key_rotation()
+            .await;
+    }
+
+    #[tokio::test]
+    async fn should_keep_old_dkg_shares_until_handover_confirms() {
+        test_environment()
+            .assert_old_dkg_shares_retired_after_handover_confirms()
+            .await;
+    }
+
     #[tokio::test]
     async fn should_get_signer_utxo_donations() {
         test_environment().assert_get_signer_utxo_donations().await;
     }
+
+    #[test(tokio::test)]
+    async fn should_fail_over_when_the_coordinator_is_offline() {
+        let mut environment = test_environment();
+        environment.context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.coordinator_failover_timeout = std::time::Duration::from_secs(1);
+            })
+            .build();
+
+        environment.assert_fallback_coordinator_takes_over().await;
+    }
+
+    #[tokio::test]
+    async fn coordinator_stays_active_across_the_timeout_while_sending_messages() {
+        let timeout = std::time::Duration::from_secs(10);
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.coordinator_failover_timeout = timeout;
+            })
+            .build();
+        let chain_tip = model::BitcoinBlockHash::from([7; 32]);
+        let seconds_ago = |secs| {
+            tokio::time::Instant::now()
+                .checked_sub(std::time::Duration::from_secs(secs))
+                .unwrap()
+        };
+
+        // The signing round started more than a timeout ago, but we heard
+        // from the coordinator 8 seconds ago, so it is still active.
+        ctx.state()
+            .set_coordinator_activity(&chain_tip, 0, seconds_ago(8));
+        assert_eq!(super::coordinator_failover_round(&ctx, &chain_tip), 0);
+
+        // Each message keeps it active for another full timeout.
+        super::record_coordinator_activity(&ctx, &chain_tip);
+        let (round, active_at) = ctx.state().coordinator_activity(&chain_tip);
+        assert_eq!(round, 0);
+        assert!(active_at.elapsed() < timeout);
+
+        // Once we have not heard from it for a full timeout, the next
+        // signer in the fallback order takes over.
+        ctx.state()
+            .set_coordinator_activity(&chain_tip, 0, seconds_ago(12));
+        assert_eq!(super::coordinator_failover_round(&ctx, &chain_tip), 1);
+
+        // Messages from the new coordinator keep it active, and we do not
+        // go back to the first one.
+        super::record_coordinator_activity(&ctx, &chain_tip);
+        assert_eq!(super::coordinator_failover_round(&ctx, &chain_tip), 1);
+
+        // A new chain tip starts over with the first coordinator.
+        let new_chain_tip = model::BitcoinBlockHash::from([8; 32]);
+        assert_eq!(super::coordinator_failover_round(&ctx, &new_chain_tip), 0);
+    }
+
+    #[test_case(0, 10, 0; "before the first timeout")]
+    #[test_case(9_999, 10_000, 0; "just before the first timeout")]
+    #[test_case(10_000, 10_000, 1; "at the first timeout")]
+    #[test_case(25_000, 10_000, 2; "after the second timeout")]
+    fn failover_round_counts_elapsed_timeouts(elapsed_ms: u64, timeout_ms: u64, expected: usize) {
+        let elapsed = std::time::Duration::from_millis(elapsed_ms);
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        assert_eq!(super::failover_round(elapsed, timeout), expected);
+    }
+
+    #[test]
+    fn coordinator_public_key_falls_back_to_the_next_signer() {
+        let signer_set: BTreeSet<PublicKey> = (0..5)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng)))
+            .collect();
+        let keys: Vec<PublicKey> = signer_set.iter().copied().collect();
+        let chain_tip = model::BitcoinBlockHash::from([7; 32]);
+
+        let primary = super::coordinator_public_key(&chain_tip, &signer_set, 0).unwrap();
+        let primary_index = keys.iter().position(|key| key == &primary).unwrap();
+
+        // Each failover round moves on to the next signer, wrapping around
+        // to the start of the signer set.
+        for round in 0..2 * keys.len() {
+            let expected = keys[(primary_index + round) % keys.len()];
+            let coordinator = super::coordinator_public_key(&chain_tip, &signer_set, round);
+            assert_eq!(coordinator, Some(expected));
+
+            assert!(super::given_key_is_coordinator(
+                expected,
+                &chain_tip,
+                &signer_set,
+                round
+            ));
+            let others = signer_set.iter().filter(|key| *key != &expected);
+            for key in others {
+                assert!(!super::given_key_is_coordinator(
+                    *key,
+                    &chain_tip,
+                    &signer_set,
+                    round
+                ));
+            }
+        }
+    }
+
+    /// The failure counts are given by index into the sorted signer set.
+    #[test_case(&[], 2, 3, &[0, 1, 2, 3, 4]; "no failures")]
+    #[test_case(&[(1, 1)], 2, 3, &[0, 1, 2, 3, 4]; "below exclusion threshold")]
+    #[test_case(&[(1, 2), (3, 5)], 2, 3, &[0, 2, 4]; "exclude repeat offenders")]
+    #[test_case(&[(1, 2), (3, 5)], 0, 3, &[0, 1, 2, 3, 4]; "exclusion disabled")]
+    #[test_case(&[(0, 2), (1, 3), (3, 5)], 2, 3, &[0, 2, 4]; "threshold still holds")]
+    #[test_case(&[(0, 4), (1, 4), (3, 4)], 2, 4, &[0, 1, 2, 4]; "ties broken by public key")]
+    fn dkg_participants_excludes_failing_signers_while_threshold_holds(
+        failures: &[(usize, u32)],
+        exclusion_threshold: u32,
+        signatures_required: u16,
+        expected: &[usize],
+    ) {
+        let signer_set: BTreeSet<PublicKey> = (0..5)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng)))
+            .collect();
+        let keys: Vec<PublicKey> = signer_set.iter().copied().collect();
+
+        let failure_counts: BTreeMap<PublicKey, u32> = failures
+            .iter()
+            .map(|(index, count)| (keys[*index], *count))
+            .collect();
+
+        let participants = super::dkg_participants(
+            &signer_set,
+            &failure_counts,
+            exclusion_threshold,
+            signatures_required,
+        );
+
+        let expected: BTreeSet<PublicKey> = expected.iter().map(|index| keys[*index]).collect();
+        assert_eq!(participants, expected);
+    }
+
+    #[test(tokio::test)]
+    async fn dkg_participants_excludes_signers_blamed_for_bad_private_shares() {
+        let store = storage::in_memory::Store::new_shared();
+        let signer_set: BTreeSet<PublicKey> = (0..5)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng)))
+            .collect();
+        let keys: Vec<PublicKey> = signer_set.iter().copied().collect();
+
+        // Signer 1 sent bad private shares in two DKG rounds, and was
+        // blamed for it by two different signers in the first one.
+        let failures: Vec<model::DkgSignerFailure> = [([1; 32], 0), ([1; 32], 2), ([2; 32], 0)]
+            .into_iter()
+            .map(|(dkg_id, reporter)| model::DkgSignerFailure {
+                dkg_id: model::BitcoinTxId::from(dkg_id),
+                signer_public_key: keys[1],
+                reported_by: keys[reporter],
+                reason: model::DkgFailureReason::BadPrivateShares,
+            })
+            .collect();
+        store.write_dkg_failures(&failures).await.unwrap();
+
+        let failure_counts = store.get_dkg_failure_counts().await.unwrap();
+        assert_eq!(failure_counts.get(&keys[1]), Some(&2));
+
+        let participants = super::dkg_participants(&signer_set, &failure_counts, 2, 3);
+        let expected: BTreeSet<PublicKey> = [0, 2, 3, 4].into_iter().map(|i| keys[i]).collect();
+        assert_eq!(participants, expected);
+    }
+
+    #[test_case(&[0, 1, 2], 2, &[], false; "same signer set")]
+    #[test_case(&[0, 1, 2, 3], 2, &[], true; "added signer")]
+    #[test_case(&[0, 1, 2, 3], 2, &[3], false; "added signer was blamed before")]
+    #[test_case(&[0, 1, 2, 3, 4], 2, &[3], true; "another added signer")]
+    #[test_case(&[0, 1], 2, &[], true; "removed signer")]
+    #[test_case(&[0, 1, 2], 3, &[], true; "changed threshold")]
+    fn signer_set_changed_compares_governance_set_with_dkg_shares(
+        target: &[usize],
+        signatures_required: u16,
+        blamed: &[usize],
+        expected: bool,
+    ) {
+        let keys: Vec<PublicKey> = (0..5)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng)))
+            .collect();
+
+        let target = SignerSetConfig {
+            public_keys: target.iter().map(|index| keys[*index]).collect(),
+            signatures_required,
+        };
+        let mut shares: model::EncryptedDkgShares = fake::Faker.fake();
+        shares.signer_set_public_keys = keys[..3].to_vec();
+        shares.signature_share_threshold = 2;
+        let blamed_signers = blamed.iter().map(|index| keys[*index]).collect();
+
+        let changed = super::signer_set_changed(&target, &shares, &blamed_signers);
+        assert_eq!(changed, expected);
+    }
 }
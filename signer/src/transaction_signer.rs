@@ -7,9 +7,13 @@
 
 use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::bitcoin::validation::BitcoinSignRequestError;
 use crate::bitcoin::validation::BitcoinTxContext;
+use crate::bitcoin::validation::BitcoinTxValidationData;
+use crate::block_observer::target_signer_set;
 use crate::context::Context;
 use crate::context::P2PEvent;
 use crate::context::SignerCommand;
@@ -19,8 +23,10 @@ use crate::context::TxCoordinatorEvent;
 use crate::context::TxSignerEvent;
 use crate::ecdsa::SignEcdsa as _;
 use crate::error::Error;
+use crate::key_backend::SignerKeyBackend;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
+use crate::keys::SignerScriptPubKey as _;
 use crate::message;
 use crate::message::BitcoinPreSignAck;
 use crate::message::StacksTransactionSignRequest;
@@ -40,6 +46,7 @@ use bitcoin::hashes::Hash;
 use bitcoin::TapSighash;
 use futures::StreamExt;
 use wsts::net::DkgEnd;
+use wsts::net::DkgFailure;
 use wsts::net::DkgStatus;
 use wsts::net::Message as WstsNetMessage;
 
@@ -83,8 +90,10 @@ use wsts::net::Message as WstsNetMessage;
 ///   transaction, and if it has, send a transaction signature back over
 ///   the network.
 /// - **Bitcoin sign request**: When receiving a request to sign a bitcoin
-///   transaction, the signer must verify that it has decided to accept all
-///   requests that the transaction fulfills. Once verified, the
+///   transaction, the signer must verify that the transaction is one that
+///   it reconstructed and validated when handling the preceding bitcoin
+///   pre-sign request, and that it has decided to accept all requests
+///   that the transaction fulfills. Once verified, the
 ///   transaction signer creates a dedicated WSTS state machine to
 ///   participate in a signing round for this transaction. Thereafter, the
 ///   signer sends a bitcoin transaction sign ack message back over the
@@ -114,8 +123,12 @@ pub struct TxSignerEventLoop<Context, Network, Rng> {
     pub context: Context,
     /// Interface to the signer network.
     pub network: Network,
-    /// Private key of the signer for network communication.
+    /// Private key of the signer, used only as its network key in the
+    /// WSTS protocol, which cannot work with signatures alone.
     pub signer_private_key: PrivateKey,
+    /// The backend holding the signer's private key, used to sign the
+    /// messages sent to other signers and Stacks transactions.
+    pub signer_key: Arc<dyn SignerKeyBackend>,
     /// WSTS state machines for active signing rounds and DKG rounds
     ///
     /// - For signing rounds, the TxID is the ID of the transaction to be
@@ -133,6 +146,10 @@ pub struct TxSignerEventLoop<Context, Network, Rng> {
     /// The time the signer should pause for after receiving a DKG begin message
     /// before relaying to give the other signers time to catch up.
     pub dkg_begin_pause: Option<Duration>,
+    /// The bitcoin transactions that this signer reconstructed, validated
+    /// and acked in response to the most recent bitcoin pre-sign request.
+    /// Bitcoin transaction sign requests are checked against these.
+    pub bitcoin_presign_package: Vec<BitcoinTxValidationData>,
 }
 
 /// This function defines which messages this event loop is interested
@@ -145,6 +162,7 @@ fn run_loop_message_filter(signal: &SignerSignal) -> bool {
                 | message::Payload::SignerWithdrawalDecision(_)
                 | message::Payload::StacksTransactionSignature(_)
                 | message::Payload::BitcoinTransactionSignAck(_)
+                | message::Payload::NewDepositRequest(_)
         ),
         SignerSignal::Command(SignerCommand::Shutdown)
         | SignerSignal::Event(SignerEvent::TxCoordinator(TxCoordinatorEvent::MessageGenerated(
@@ -277,7 +295,8 @@ where
             (message::Payload::StacksTransactionSignature(_), _, _)
             | (message::Payload::BitcoinTransactionSignAck(_), _, _)
             | (message::Payload::SignerDepositDecision(_), _, _)
-            | (message::Payload::SignerWithdrawalDecision(_), _, _) => (),
+            | (message::Payload::SignerWithdrawalDecision(_), _, _)
+            | (message::Payload::NewDepositRequest(_), _, _) => (),
 
             // Any other combination should be logged
             _ => {
@@ -308,13 +327,25 @@ where
             .is_some();
         let is_canonical = msg_bitcoin_chain_tip == &chain_tip;
 
+        // Only the coordinator that is active right now, taking failover
+        // into account, may send us coordinator messages.
         let signer_set = self.get_signer_public_keys(&chain_tip).await?;
+        let failover_round =
+            crate::transaction_coordinator::coordinator_failover_round(&self.context, &chain_tip);
         let sender_is_coordinator = crate::transaction_coordinator::given_key_is_coordinator(
             msg_sender,
             &chain_tip,
             &signer_set,
+            failover_round,
         );
 
+        // Messages from the active coordinator show that it is still
+        // alive, so it stays active even if it takes a while to finish
+        // its signing rounds.
+        if sender_is_coordinator && is_canonical {
+            crate::transaction_coordinator::record_coordinator_activity(&self.context, &chain_tip);
+        }
+
         let chain_tip_status = match (is_known, is_canonical) {
             (true, true) => ChainTipStatus::Canonical,
             (true, false) => ChainTipStatus::Known,
@@ -378,6 +409,8 @@ where
         db.write_bitcoin_withdrawals_outputs(&withdrawals_outputs)
             .await?;
 
+        self.bitcoin_presign_package = sighashes;
+
         self.send_message(BitcoinPreSignAck, bitcoin_chain_tip)
             .await?;
         Ok(())
@@ -389,55 +422,56 @@ where
         request: &message::BitcoinTransactionSignRequest,
         bitcoin_chain_tip: &model::BitcoinBlockHash,
     ) -> Result<(), Error> {
-        let is_valid_sign_request = self
-            .is_valid_bitcoin_transaction_sign_request(request)
-            .await?;
+        self.assert_valid_bitcoin_transaction_sign_request(request, bitcoin_chain_tip)?;
 
-        if is_valid_sign_request {
-            let new_state_machine = SignerStateMachine::load(
-                &self.context.get_storage_mut(),
-                request.aggregate_key,
-                self.threshold,
-                self.signer_private_key,
-            )
-            .await?;
+        let new_state_machine = SignerStateMachine::load(
+            &self.context.get_storage_mut(),
+            request.aggregate_key,
+            self.threshold,
+            self.signer_private_key,
+        )
+        .await?;
 
-            let txid = request.tx.compute_txid();
+        let txid = request.tx.compute_txid();
 
-            self.wsts_state_machines.insert(txid, new_state_machine);
+        self.wsts_state_machines.insert(txid, new_state_machine);
 
-            let msg = message::BitcoinTransactionSignAck {
-                txid: request.tx.compute_txid(),
-            };
+        let msg = message::BitcoinTransactionSignAck { txid };
 
-            self.send_message(msg, bitcoin_chain_tip).await?;
-        } else {
-            tracing::warn!("received invalid sign request");
-        }
+        self.send_message(msg, bitcoin_chain_tip).await?;
 
         Ok(())
     }
 
-    async fn is_valid_bitcoin_transaction_sign_request(
+    /// Check that the transaction in the sign request is one of the
+    /// transactions that we reconstructed, validated and acked when
+    /// handling the most recent [`message::BitcoinPreSignRequest`] for
+    /// the given chain tip.
+    ///
+    /// The transaction in the package that spends the same signers' UTXO
+    /// is used to validate each of the inputs and outputs of the
+    /// transaction in the request.
+    fn assert_valid_bitcoin_transaction_sign_request(
         &self,
-        _request: &message::BitcoinTransactionSignRequest,
-    ) -> Result<bool, Error> {
-        let signer_pub_key = self.signer_public_key();
-        let _accepted_deposit_requests = self
-            .context
-            .get_storage()
-            .get_accepted_deposit_requests(&signer_pub_key)
-            .await?;
+        request: &message::BitcoinTransactionSignRequest,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<(), Error> {
+        let txid = request.tx.compute_txid();
+        let signer_outpoint = request.tx.input.first().map(|tx_in| tx_in.previous_output);
+
+        let expected = self
+            .bitcoin_presign_package
+            .iter()
+            .filter(|data| &data.chain_tip == bitcoin_chain_tip)
+            .find(|data| Some(data.reports.signer_state.utxo.outpoint) == signer_outpoint)
+            .ok_or(BitcoinSignRequestError::NotInAckedPackage(txid))
+            .map_err(Box::new)?;
 
-        // TODO(286): Validate transaction
-        // - Ensure all inputs are either accepted deposit requests
-        //    or directly spendable by the signers.
-        // - Ensure all outputs are either accepted withdrawals
-        //    or pays to an approved signer set.
-        // - Ensure the transaction fee is lower than the minimum
-        //    `max_fee` of any request.
+        expected
+            .validate_sign_request(&request.tx, &request.aggregate_key)
+            .map_err(Box::new)?;
 
-        Ok(true)
+        Ok(())
     }
 
     #[tracing::instrument(skip_all)]
@@ -460,7 +494,7 @@ where
 
         debug_assert_eq!(txid, request.txid);
 
-        let signature = crate::signature::sign_stacks_tx(multi_sig.tx(), &self.signer_private_key);
+        let signature = crate::signature::sign_stacks_tx_with(multi_sig.tx(), &*self.signer_key)?;
 
         let msg = message::StacksTransactionSignature { txid, signature };
 
@@ -512,9 +546,15 @@ where
             StacksTx::ContractCall(ContractCall::CompleteDepositV1(contract)) => {
                 contract.validate(ctx, &req_ctx).await?
             }
+            StacksTx::ContractCall(ContractCall::CompleteDepositsV1(contract)) => {
+                contract.validate(ctx, &req_ctx).await?
+            }
             StacksTx::ContractCall(ContractCall::RejectWithdrawalV1(contract)) => {
                 contract.validate(ctx, &req_ctx).await?
             }
+            StacksTx::ContractCall(ContractCall::CompleteWithdrawalsV1(contract)) => {
+                contract.validate(ctx, &req_ctx).await?
+            }
             StacksTx::ContractCall(ContractCall::RotateKeysV1(contract)) => {
                 contract.validate(ctx, &req_ctx).await?
             }
@@ -544,14 +584,43 @@ where
                     return Ok(());
                 }
 
-                let signer_public_keys = self.get_signer_public_keys(bitcoin_chain_tip).await?;
+                // DKG is run for the signer set approved by governance,
+                // which may have been changed since the last key rotation.
+                let (signer_set, threshold) = target_signer_set(&self.context).await?;
+                // The coordinator decides which signers take part in the
+                // round, since it may leave out signers that have been
+                // blamed for failed rounds. We only check that it picked
+                // enough signers from the signer set.
+                let signer_public_keys = msg.dkg_participants.clone();
+                if !signer_public_keys.is_subset(&signer_set)
+                    || signer_public_keys.len() < usize::from(threshold)
+                {
+                    tracing::warn!(
+                        num_participants = signer_public_keys.len(),
+                        %threshold,
+                        "the DKG participants are not a valid subset of the signer set"
+                    );
+                    return Ok(());
+                }
+
+                if !signer_public_keys.contains(&self.signer_public_key()) {
+                    tracing::warn!("we have been excluded from this DKG round");
+                    return Ok(());
+                }
 
                 let state_machine = SignerStateMachine::new(
                     signer_public_keys,
-                    self.threshold,
+                    u32::from(threshold),
                     self.signer_private_key,
                 )?;
                 self.wsts_state_machines.insert(msg.txid, state_machine);
+                self.write_dkg_attempt(
+                    &msg.txid,
+                    bitcoin_chain_tip,
+                    model::DkgAttemptStatus::Pending,
+                    None,
+                )
+                .await?;
 
                 if let Some(pause) = self.dkg_begin_pause {
                     // Let's give the others some slack
@@ -590,6 +659,8 @@ where
                 };
 
                 if signer_public_key != msg_public_key {
+                    self.record_dkg_bad_signature(&msg.txid, msg_public_key)
+                        .await?;
                     return Err(Error::InvalidSignature);
                 }
                 self.relay_message(msg.txid, &msg.inner, bitcoin_chain_tip)
@@ -611,6 +682,8 @@ where
                 };
 
                 if signer_public_key != msg_public_key {
+                    self.record_dkg_bad_signature(&msg.txid, msg_public_key)
+                        .await?;
                     return Err(Error::InvalidSignature);
                 }
                 self.relay_message(msg.txid, &msg.inner, bitcoin_chain_tip)
@@ -644,14 +717,14 @@ where
                 Self::validate_bitcoin_sign_request(&db, &request.message).await?;
 
                 if !self.wsts_state_machines.contains_key(&msg.txid) {
-                    let (maybe_aggregate_key, _) = self
-                        .get_signer_set_and_aggregate_key(bitcoin_chain_tip)
+                    let (aggregate_key, threshold) = self
+                        .sighash_aggregate_key(&request.message, bitcoin_chain_tip)
                         .await?;
 
                     let state_machine = SignerStateMachine::load(
                         &db,
-                        maybe_aggregate_key.ok_or(Error::NoDkgShares)?,
-                        self.threshold,
+                        aggregate_key,
+                        threshold,
                         self.signer_private_key,
                     )
                     .await?;
@@ -673,24 +746,29 @@ where
                 self.relay_message(msg.txid, &msg.inner, bitcoin_chain_tip)
                     .await?;
             }
-            WstsNetMessage::DkgEnd(dkg_end) => {
-                match &dkg_end.status {
-                    DkgStatus::Success => {
-                        tracing::info!(
-                            signer_id = %dkg_end.signer_id,
-                            "handling DkgEnd success from signer"
-                        );
-                    }
-                    DkgStatus::Failure(fail) => {
-                        // TODO(#414): handle DKG failure
-                        tracing::info!(
-                            signer_id = %dkg_end.signer_id,
-                            reason = ?fail,
-                            "handling DkgEnd failure",
-                        );
-                    }
+            WstsNetMessage::DkgEnd(dkg_end) => match &dkg_end.status {
+                DkgStatus::Success => {
+                    tracing::info!(
+                        signer_id = %dkg_end.signer_id,
+                        "handling DkgEnd success from signer"
+                    );
                 }
-            }
+                DkgStatus::Failure(fail) => {
+                    tracing::warn!(
+                        signer_id = %dkg_end.signer_id,
+                        reason = ?fail,
+                        "handling DkgEnd failure",
+                    );
+                    self.record_dkg_failure(
+                        &msg.txid,
+                        bitcoin_chain_tip,
+                        dkg_end.signer_id,
+                        msg_public_key,
+                        fail,
+                    )
+                    .await?;
+                }
+            },
             WstsNetMessage::NonceResponse(_) | WstsNetMessage::SignatureShareResponse(_) => {
                 tracing::trace!("ignoring message");
             }
@@ -725,10 +803,26 @@ where
             // emits a DkgEnd message, because that is the only way to know
             // whether it has truly received all relevant messages from its
             // peers.
-            if let WstsNetMessage::DkgEnd(DkgEnd { status: DkgStatus::Success, .. }) = outbound {
-                self.store_dkg_shares(&txid).await?;
+            match &outbound {
+                WstsNetMessage::DkgEnd(DkgEnd { status: DkgStatus::Success, .. }) => {
+                    self.store_dkg_shares(&txid, bitcoin_chain_tip).await?;
+                }
+                WstsNetMessage::DkgEnd(DkgEnd {
+                    status: DkgStatus::Failure(fail),
+                    signer_id,
+                    ..
+                }) => {
+                    let public_key = self.signer_public_key();
+                    self.record_dkg_failure(&txid, bitcoin_chain_tip, *signer_id, public_key, fail)
+                        .await?;
+                }
+                _ => {}
             }
-            let msg = message::WstsMessage { txid, inner: outbound };
+            let msg = message::WstsMessage {
+                txid,
+                inner: outbound,
+                dkg_participants: BTreeSet::new(),
+            };
 
             self.send_message(msg, bitcoin_chain_tip).await?;
         }
@@ -753,8 +847,68 @@ where
         }
     }
 
+    /// Return the aggregate key, along with the signing threshold, of the
+    /// DKG shares that lock the prevout associated with the given
+    /// sighash.
+    ///
+    /// This is the current aggregate key, unless the sighash is in the
+    /// transaction package that we acked and the prevout is locked by the
+    /// aggregate key of an earlier DKG round. This happens after the
+    /// signers rotate keys, until their UTXO is swept over to the new key.
+    async fn sighash_aggregate_key(
+        &self,
+        message: &[u8],
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<(PublicKey, u32), Error> {
+        let sighash = TapSighash::from_slice(message).map_err(Error::SigHashConversion)?;
+
+        let utxo_key = self
+            .bitcoin_presign_package
+            .iter()
+            .filter(|data| &data.chain_tip == bitcoin_chain_tip)
+            .find_map(|data| {
+                if data.signer_sighash.sighash == sighash {
+                    return Some(data.reports.signer_state.utxo.public_key);
+                }
+                let outpoint = data
+                    .deposit_sighashes
+                    .iter()
+                    .find(|deposit| deposit.sighash == sighash)?
+                    .outpoint;
+                data.reports
+                    .deposits
+                    .iter()
+                    .find(|(request, _)| request.outpoint == outpoint)
+                    .map(|(request, _)| request.signers_public_key)
+            });
+
+        let (maybe_aggregate_key, _) = self
+            .get_signer_set_and_aggregate_key(bitcoin_chain_tip)
+            .await?;
+        let aggregate_key = maybe_aggregate_key.ok_or(Error::NoDkgShares)?;
+
+        match utxo_key {
+            Some(key) if key != bitcoin::XOnlyPublicKey::from(&aggregate_key) => {
+                let script_pubkey = key.signers_script_pubkey().into();
+                let shares = self
+                    .context
+                    .get_storage()
+                    .get_encrypted_dkg_shares_by_script_pubkey(&script_pubkey)
+                    .await?
+                    .ok_or(Error::MissingDkgSharesForUtxo(key))?;
+                let threshold = u32::from(shares.signature_share_threshold);
+                Ok((shares.aggregate_key, threshold))
+            }
+            _ => Ok((aggregate_key, self.threshold)),
+        }
+    }
+
     #[tracing::instrument(skip(self))]
-    async fn store_dkg_shares(&mut self, txid: &bitcoin::Txid) -> Result<(), Error> {
+    async fn store_dkg_shares(
+        &mut self,
+        txid: &bitcoin::Txid,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<(), Error> {
         let state_machine = self
             .wsts_state_machines
             .get(txid)
@@ -768,7 +922,131 @@ where
             .write_encrypted_dkg_shares(&encrypted_dkg_shares)
             .await?;
 
-        Ok(())
+        self.write_dkg_attempt(
+            txid,
+            bitcoin_chain_tip,
+            model::DkgAttemptStatus::Success,
+            Some(encrypted_dkg_shares.aggregate_key),
+        )
+        .await
+    }
+
+    /// Write the signers blamed in a `DkgEnd` failure report to the
+    /// database, and mark the DKG round as failed.
+    #[tracing::instrument(skip(self, failure))]
+    async fn record_dkg_failure(
+        &mut self,
+        txid: &bitcoin::Txid,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        reporter_id: u32,
+        reported_by: PublicKey,
+        failure: &DkgFailure,
+    ) -> Result<(), Error> {
+        let Some(state_machine) = self.wsts_state_machines.get(txid) else {
+            tracing::warn!("missing DKG round for the failure report");
+            return Ok(());
+        };
+
+        let reporter_key = p256k1::keys::PublicKey::from(&reported_by);
+        if state_machine.public_keys.signers.get(&reporter_id) != Some(&reporter_key) {
+            tracing::warn!("DkgEnd failure report was not signed by the reporting signer");
+            return Ok(());
+        }
+
+        let failures: Vec<model::DkgSignerFailure> = dkg_failure_reasons(failure, reporter_id)
+            .into_iter()
+            .filter_map(|(signer_id, reason)| {
+                let signer_public_key = state_machine.public_keys.signers.get(&signer_id)?;
+                Some(model::DkgSignerFailure {
+                    dkg_id: (*txid).into(),
+                    signer_public_key: PublicKey::from(signer_public_key),
+                    reported_by,
+                    reason,
+                })
+            })
+            .collect();
+
+        self.context
+            .get_storage_mut()
+            .write_dkg_failures(&failures)
+            .await?;
+
+        self.write_dkg_attempt(
+            txid,
+            bitcoin_chain_tip,
+            model::DkgAttemptStatus::Failed,
+            None,
+        )
+        .await
+    }
+
+    /// Blame the sender of DKG shares that were not signed by the key of
+    /// the signer that they claim to be from.
+    async fn record_dkg_bad_signature(
+        &mut self,
+        txid: &bitcoin::Txid,
+        sender: PublicKey,
+    ) -> Result<(), Error> {
+        let Some(state_machine) = self.wsts_state_machines.get(txid) else {
+            return Ok(());
+        };
+
+        let sender_key = p256k1::keys::PublicKey::from(&sender);
+        if !state_machine
+            .public_keys
+            .signers
+            .values()
+            .any(|key| *key == sender_key)
+        {
+            return Ok(());
+        }
+
+        let failure = model::DkgSignerFailure {
+            dkg_id: (*txid).into(),
+            signer_public_key: sender,
+            reported_by: self.signer_public_key(),
+            reason: model::DkgFailureReason::BadSignature,
+        };
+
+        self.context
+            .get_storage_mut()
+            .write_dkg_failures(&[failure])
+            .await
+    }
+
+    /// Write the status of the DKG round with the given identifier to the
+    /// database.
+    async fn write_dkg_attempt(
+        &self,
+        txid: &bitcoin::Txid,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        status: model::DkgAttemptStatus,
+        aggregate_key: Option<PublicKey>,
+    ) -> Result<(), Error> {
+        let state_machine = self
+            .wsts_state_machines
+            .get(txid)
+            .ok_or(Error::MissingStateMachine)?;
+
+        let signer_set_public_keys: BTreeSet<PublicKey> = state_machine
+            .public_keys
+            .signers
+            .values()
+            .map(PublicKey::from)
+            .collect();
+
+        let attempt = model::DkgAttempt {
+            dkg_id: (*txid).into(),
+            bitcoin_chain_tip: *bitcoin_chain_tip,
+            signer_set_public_keys: signer_set_public_keys.into_iter().collect(),
+            status,
+            aggregate_key,
+        };
+
+        self.context
+            .get_storage_mut()
+            .write_dkg_attempt(&attempt)
+            .await
     }
 
     #[tracing::instrument(skip_all)]
@@ -782,7 +1060,7 @@ where
 
         let msg = payload
             .to_message(*bitcoin_chain_tip)
-            .sign_ecdsa(&self.signer_private_key);
+            .sign_ecdsa_with(&*self.signer_key)?;
 
         self.network.broadcast(msg.clone()).await?;
         self.context
@@ -859,7 +1137,41 @@ where
     }
 
     fn signer_public_key(&self) -> PublicKey {
-        PublicKey::from_private_key(&self.signer_private_key)
+        self.signer_key.public_key()
+    }
+}
+
+/// Return the WSTS signer IDs of the signers that are blamed in a
+/// `DkgEnd` failure report from the signer with the given ID, along with
+/// the reason they are blamed.
+fn dkg_failure_reasons(
+    failure: &DkgFailure,
+    reporter_id: u32,
+) -> Vec<(u32, model::DkgFailureReason)> {
+    use model::DkgFailureReason as Reason;
+
+    let blame = |signer_ids: Vec<u32>, reason: Reason| {
+        let mut signer_ids = signer_ids;
+        signer_ids.sort();
+        signer_ids
+            .into_iter()
+            .map(move |signer_id| (signer_id, reason))
+    };
+
+    match failure {
+        DkgFailure::BadState => vec![(reporter_id, Reason::BadState)],
+        DkgFailure::MissingPublicShares(ids) => {
+            blame(ids.iter().copied().collect(), Reason::MissingPublicShares).collect()
+        }
+        DkgFailure::BadPublicShares(ids) => {
+            blame(ids.iter().copied().collect(), Reason::BadPublicShares).collect()
+        }
+        DkgFailure::MissingPrivateShares(ids) => {
+            blame(ids.iter().copied().collect(), Reason::MissingPrivateShares).collect()
+        }
+        DkgFailure::BadPrivateShares(shares) => {
+            blame(shares.keys().copied().collect(), Reason::BadPrivateShares).collect()
+        }
     }
 }
 
@@ -889,6 +1201,12 @@ enum ChainTipStatus {
 
 #[cfg(test)]
 mod tests {
+    use p256k1::point::Point;
+    use p256k1::scalar::Scalar;
+    use wsts::common::TupleProof;
+    use wsts::net::BadPrivateShare;
+    use wsts::net::DkgFailure;
+
     use crate::bitcoin::MockBitcoinInteract;
     use crate::emily_client::MockEmilyInteract;
     use crate::stacks::api::MockStacksInteract;
@@ -929,9 +1247,9 @@ mod tests {
 
     #[ignore = "we have a test for this"]
     #[tokio::test]
-    async fn should_respond_to_bitcoin_transaction_sign_requests() {
+    async fn should_not_ack_unknown_bitcoin_transaction_sign_requests() {
         test_environment()
-            .assert_should_respond_to_bitcoin_transaction_sign_requests()
+            .assert_should_not_ack_unknown_bitcoin_transaction_sign_requests()
             .await;
     }
 
@@ -950,4 +1268,47 @@ mod tests {
             .assert_should_be_able_to_participate_in_signing_round()
             .await;
     }
+
+    #[test]
+    fn dkg_failure_reasons_blame_the_offending_signers() {
+        use crate::storage::model::DkgFailureReason;
+
+        let failure = DkgFailure::BadState;
+        let reasons = super::dkg_failure_reasons(&failure, 3);
+        assert_eq!(reasons, vec![(3, DkgFailureReason::BadState)]);
+
+        let failure = DkgFailure::MissingPublicShares([4, 1].into_iter().collect());
+        let reasons = super::dkg_failure_reasons(&failure, 3);
+        let expected = vec![
+            (1, DkgFailureReason::MissingPublicShares),
+            (4, DkgFailureReason::MissingPublicShares),
+        ];
+        assert_eq!(reasons, expected);
+
+        let failure = DkgFailure::BadPublicShares([0].into_iter().collect());
+        let reasons = super::dkg_failure_reasons(&failure, 3);
+        assert_eq!(reasons, vec![(0, DkgFailureReason::BadPublicShares)]);
+
+        let failure = DkgFailure::MissingPrivateShares([2].into_iter().collect());
+        let reasons = super::dkg_failure_reasons(&failure, 3);
+        assert_eq!(reasons, vec![(2, DkgFailureReason::MissingPrivateShares)]);
+
+        let bad_share = || BadPrivateShare {
+            shared_key: Point::new(),
+            tuple_proof: TupleProof {
+                R: Point::new(),
+                rB: Point::new(),
+                z: Scalar::from(1),
+            },
+        };
+        let failure = DkgFailure::BadPrivateShares(
+            [(4, bad_share()), (0, bad_share())].into_iter().collect(),
+        );
+        let reasons = super::dkg_failure_reasons(&failure, 3);
+        let expected = vec![
+            (0, DkgFailureReason::BadPrivateShares),
+            (4, DkgFailureReason::BadPrivateShares),
+        ];
+        assert_eq!(reasons, expected);
+    }
 }
@@ -13,6 +13,8 @@ use std::{
 use thiserror::Error;
 
 use crate::error::Error;
+use crate::metrics::metrics;
+use crate::metrics::FailoverLabels;
 
 /// Extension trait for `Vec`.
 pub trait CollectionExt {
@@ -184,6 +186,7 @@ impl<T> InnerApiFallbackClient<T> {
                     return Err(error.into());
                 }
 
+                record_failover::<T>(client_index);
                 self.last_client_index.store(
                     (client_index + 1) % self.inner_clients.len(),
                     Ordering::Relaxed,
@@ -199,6 +202,20 @@ impl<T> InnerApiFallbackClient<T> {
     }
 }
 
+/// Count a failover away from the endpoint at the given index of the
+/// client's list of endpoints.
+fn record_failover<T>(endpoint: usize) {
+    // The type name is the full path of the client type, we only want
+    // the last part of it.
+    let type_name = std::any::type_name::<T>();
+    let client = type_name.rsplit("::").next().unwrap_or(type_name);
+    let labels = FailoverLabels {
+        client,
+        endpoint: endpoint.to_string(),
+    };
+    metrics().api_client_failovers.get_or_create(&labels).inc();
+}
+
 impl<T> ApiFallbackClient<T> {
     /// Create a new fallback client from a list of clients.
     pub fn new(clients: Vec<T>) -> Result<Self, FallbackClientError> {
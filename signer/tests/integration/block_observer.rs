@@ -27,6 +27,7 @@ use signer::bitcoin::utxo::SbtcRequests;
 use signer::bitcoin::utxo::SignerBtcState;
 use signer::context::SbtcLimits;
 use signer::emily_client::EmilyClient;
+use signer::emily_client::PendingDeposit;
 use signer::error::Error;
 use signer::keys::SignerScriptPubKey as _;
 use signer::logging::setup_logging;
@@ -96,13 +97,19 @@ async fn load_latest_deposit_requests_persists_requests_from_past(blocks_ago: u6
     // Let's prep Emily with information about these deposits.
     ctx.with_emily_client(|client| {
         let emily_client_response = vec![
-            setup0.emily_deposit_request(),
-            setup1.emily_deposit_request(),
+            PendingDeposit {
+                request: setup0.emily_deposit_request(),
+                last_update_height: 0,
+            },
+            PendingDeposit {
+                request: setup1.emily_deposit_request(),
+                last_update_height: 0,
+            },
         ];
         client
-            .expect_get_deposits()
+            .expect_get_deposits_updated_since()
             .times(1..)
-            .returning(move || Box::pin(std::future::ready(Ok(emily_client_response.clone()))));
+            .returning(move |_| Box::pin(std::future::ready(Ok(emily_client_response.clone()))));
 
         client
             .expect_get_limits()
@@ -115,6 +122,15 @@ async fn load_latest_deposit_requests_persists_requests_from_past(blocks_ago: u6
     // information about the Stacks blockchain, so we need to prep it, even
     // though it isn't necessary for our test.
     ctx.with_stacks_client(|client| {
+        // The signers have not rotated keys according to the
+        // `sbtc-registry` contract.
+        client
+            .expect_get_current_signer_set()
+            .returning(|_| Box::pin(std::future::ready(Ok(Vec::new()))));
+        client
+            .expect_get_current_signature_threshold()
+            .returning(|_| Box::pin(std::future::ready(Ok(None))));
+
         client.expect_get_tenure_info().returning(move || {
             let response = Ok(RPCGetTenureInfo {
                 consensus_hash: ConsensusHash([0; 20]),
@@ -292,8 +308,8 @@ async fn link_blocks() {
     // No need for deposits here
     ctx.with_emily_client(|client| {
         client
-            .expect_get_deposits()
-            .returning(move || Box::pin(std::future::ready(Ok(vec![]))));
+            .expect_get_deposits_updated_since()
+            .returning(move |_| Box::pin(std::future::ready(Ok(vec![]))));
     })
     .await;
 
@@ -439,6 +455,15 @@ async fn block_observer_stores_donation_and_sbtc_utxos() {
     // up-to-date information. We don't have stacks-core running so we mock
     // these calls.
     ctx.with_stacks_client(|client| {
+        // The signers have not rotated keys according to the
+        // `sbtc-registry` contract.
+        client
+            .expect_get_current_signer_set()
+            .returning(|_| Box::pin(std::future::ready(Ok(Vec::new()))));
+        client
+            .expect_get_current_signature_threshold()
+            .returning(|_| Box::pin(std::future::ready(Ok(None))));
+
         client.expect_get_tenure_info().returning(move || {
             let response = Ok(RPCGetTenureInfo {
                 consensus_hash: ConsensusHash([0; 20]),
@@ -44,7 +44,7 @@ async fn swarm_rejects_connections_from_unknown_peers() {
         .with_in_memory_storage()
         .with_mocked_clients()
         .modify_settings(|settings| {
-            settings.signer.private_key = key1;
+            settings.signer.private_key = Some(key1);
         })
         .build();
     // Add key2 to the known signers for signer1.
@@ -58,7 +58,7 @@ async fn swarm_rejects_connections_from_unknown_peers() {
         .with_in_memory_storage()
         .with_mocked_clients()
         .modify_settings(|settings| {
-            settings.signer.private_key = key2;
+            settings.signer.private_key = Some(key2);
         })
         .build();
     // Add key1 to the known signers for signer2.
@@ -78,7 +78,7 @@ async fn swarm_rejects_connections_from_unknown_peers() {
         .with_in_memory_storage()
         .with_mocked_clients()
         .modify_settings(|settings| {
-            settings.signer.private_key = key3;
+            settings.signer.private_key = Some(key3);
         })
         .build();
     // Add key1 and key2 to the known signers for signer 3. This simulates
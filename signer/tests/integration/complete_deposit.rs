@@ -8,6 +8,8 @@ use sbtc::testing::regtest;
 use signer::error::Error;
 use signer::stacks::contracts::AsContractCall as _;
 use signer::stacks::contracts::CompleteDepositV1;
+use signer::stacks::contracts::CompleteDepositsErrorMsg;
+use signer::stacks::contracts::CompleteDepositsV1;
 use signer::stacks::contracts::DepositErrorMsg;
 use signer::stacks::contracts::ReqContext;
 use signer::storage::model::BitcoinBlockRef;
@@ -862,3 +864,233 @@ async fn complete_deposit_validation_deposit_invalid_sweep() {
 
     testing::storage::drop_db(db).await;
 }
+
+/// For this test we check that the `CompleteDepositsV1::validate` function
+/// returns okay when each of the bundled deposits passes validation.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn complete_deposits_validation_happy_path() {
+    // Normal: this generates the blockchain as well as deposit request
+    // transactions and a transaction sweeping in the deposited funds.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: this is all normal happy path things that need to happen
+    // in order to pass validation of a complete-deposit transaction.
+    setup.store_deposit_tx(&db).await;
+    setup.store_sweep_tx(&db).await;
+    setup.store_dkg_shares(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_deposit_decisions(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    setup.store_sweep_transactions(&db).await;
+
+    // Normal: create a properly formed complete-deposits transaction
+    // object and the corresponding request context.
+    let (complete_deposit_tx, req_ctx) = make_complete_deposit(&setup);
+    let complete_deposits_tx = CompleteDepositsV1 {
+        deposits: vec![complete_deposit_tx],
+        deployer: StacksAddress::burn_address(false),
+    };
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    complete_deposits_tx.validate(&ctx, &req_ctx).await.unwrap();
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `CompleteDepositsV1::validate` function
+/// returns a complete-deposits validation error with a DuplicateDeposit
+/// message when the same deposit is included more than once.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn complete_deposits_validation_duplicate_deposit() {
+    // Normal: this generates the blockchain as well as deposit request
+    // transactions and a transaction sweeping in the deposited funds.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: this is all normal happy path things that need to happen
+    // in order to pass validation of a complete-deposit transaction.
+    setup.store_deposit_tx(&db).await;
+    setup.store_sweep_tx(&db).await;
+    setup.store_dkg_shares(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_deposit_decisions(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    setup.store_sweep_transactions(&db).await;
+
+    // Different: the same deposit is included twice.
+    let (complete_deposit_tx, req_ctx) = make_complete_deposit(&setup);
+    let complete_deposits_tx = CompleteDepositsV1 {
+        deposits: vec![complete_deposit_tx.clone(), complete_deposit_tx],
+        deployer: StacksAddress::burn_address(false),
+    };
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let validate_future = complete_deposits_tx.validate(&ctx, &req_ctx);
+    match validate_future.await.unwrap_err() {
+        Error::CompleteDepositsValidation(ref err) => {
+            assert_eq!(err.error, CompleteDepositsErrorMsg::DuplicateDeposit)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `CompleteDepositsV1::validate` function
+/// returns the deposit validation error of a bundled deposit when that
+/// deposit fails validation.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn complete_deposits_validation_invalid_deposit() {
+    // Normal: this generates the blockchain as well as deposit request
+    // transactions and a transaction sweeping in the deposited funds.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: this is all normal happy path things that need to happen
+    // in order to pass validation of a complete-deposit transaction.
+    setup.store_deposit_tx(&db).await;
+    setup.store_sweep_tx(&db).await;
+    setup.store_dkg_shares(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_deposit_decisions(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    setup.store_sweep_transactions(&db).await;
+
+    // Different: the amount to mint exceeds the amount in the deposit
+    // request.
+    let (mut complete_deposit_tx, req_ctx) = make_complete_deposit(&setup);
+    complete_deposit_tx.amount = setup.deposit_request.amount + 1;
+    let complete_deposits_tx = CompleteDepositsV1 {
+        deposits: vec![complete_deposit_tx],
+        deployer: StacksAddress::burn_address(false),
+    };
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let validate_future = complete_deposits_tx.validate(&ctx, &req_ctx);
+    match validate_future.await.unwrap_err() {
+        Error::DepositValidation(ref err) => {
+            assert_eq!(err.error, DepositErrorMsg::InvalidMintAmount)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `CompleteDepositsV1::remove_invalid_deposits`
+/// function leaves out the bundled deposits that fail validation, so that
+/// the remaining deposits in the batch pass validation.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn complete_deposits_remove_invalid_deposit_from_batch() {
+    // Normal: this generates the blockchain as well as deposit request
+    // transactions and a transaction sweeping in the deposited funds.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: this is all normal happy path things that need to happen
+    // in order to pass validation of a complete-deposit transaction.
+    setup.store_deposit_tx(&db).await;
+    setup.store_sweep_tx(&db).await;
+    setup.store_dkg_shares(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_deposit_decisions(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    setup.store_sweep_transactions(&db).await;
+
+    // Different: the batch includes a deposit where the amount to mint
+    // exceeds the amount in the deposit request, next to a valid one.
+    let (complete_deposit_tx, req_ctx) = make_complete_deposit(&setup);
+    let mut invalid_deposit_tx = complete_deposit_tx.clone();
+    invalid_deposit_tx.amount = setup.deposit_request.amount + 1;
+    let mut complete_deposits_tx = CompleteDepositsV1 {
+        deposits: vec![invalid_deposit_tx.clone(), complete_deposit_tx.clone()],
+        deployer: StacksAddress::burn_address(false),
+    };
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let invalid = complete_deposits_tx
+        .remove_invalid_deposits(&ctx, &req_ctx)
+        .await
+        .unwrap();
+
+    assert_eq!(invalid.len(), 1);
+    let (deposit, error) = &invalid[0];
+    assert_eq!(deposit, &invalid_deposit_tx);
+    match error {
+        Error::DepositValidation(ref err) => {
+            assert_eq!(err.error, DepositErrorMsg::InvalidMintAmount)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    // The valid deposit remains, and the batch now passes validation.
+    assert_eq!(complete_deposits_tx.deposits, vec![complete_deposit_tx]);
+    complete_deposits_tx.validate(&ctx, &req_ctx).await.unwrap();
+
+    testing::storage::drop_db(db).await;
+}
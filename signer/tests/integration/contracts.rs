@@ -27,6 +27,9 @@ use signer::stacks::api::FeePriority;
 use signer::stacks::api::StacksClient;
 use signer::stacks::api::SubmitTxResponse;
 use signer::stacks::contracts::CompleteDepositV1;
+use signer::stacks::contracts::CompleteDepositsV1;
+use signer::stacks::contracts::CompleteWithdrawalsV1;
+use signer::stacks::contracts::WithdrawalCompletion;
 use signer::stacks::wallet::MultisigTx;
 use signer::storage::in_memory::Store;
 use signer::storage::postgres;
@@ -138,6 +141,18 @@ pub async fn deploy_smart_contracts() -> &'static SignerStxState {
     sweep_block_hash: BitcoinBlockHash::from([0; 32]),
     sweep_block_height: 7,
 }); "complete-deposit contract recipient")]
+#[test_case(ContractCallWrapper(CompleteDepositsV1 {
+    deposits: vec![CompleteDepositV1 {
+        outpoint: bitcoin::OutPoint::null(),
+        amount: 123654,
+        recipient: PrincipalData::parse("ST1RQHF4VE5CZ6EK3MZPZVQBA0JVSMM9H5PMHMS1Y").unwrap(),
+        deployer: *testing::wallet::WALLET.0.address(),
+        sweep_txid: BitcoinTxId::from([0; 32]),
+        sweep_block_hash: BitcoinBlockHash::from([0; 32]),
+        sweep_block_height: 7,
+    }],
+    deployer: *testing::wallet::WALLET.0.address(),
+}); "complete-deposits")]
 #[test_case(ContractCallWrapper(AcceptWithdrawalV1 {
     request_id: 1,
     outpoint: bitcoin::OutPoint::null(),
@@ -146,6 +161,7 @@ pub async fn deploy_smart_contracts() -> &'static SignerStxState {
     deployer: *testing::wallet::WALLET.0.address(),
     sweep_block_hash: BitcoinBlockHash::from([0; 32]),
     sweep_block_height: 7,
+    sweep_txid: BitcoinTxId::from([0; 32]),
 }); "accept-withdrawal")]
 #[test_case(ContractCallWrapper(InitiateWithdrawalRequest {
     amount: 22500,
@@ -158,6 +174,26 @@ pub async fn deploy_smart_contracts() -> &'static SignerStxState {
     signer_bitmap: BitArray::ZERO,
     deployer: *testing::wallet::WALLET.0.address(),
 }); "reject-withdrawal")]
+#[test_case(ContractCallWrapper(CompleteWithdrawalsV1 {
+    withdrawals: vec![
+        WithdrawalCompletion::Accept(AcceptWithdrawalV1 {
+            request_id: 3,
+            outpoint: bitcoin::OutPoint::null(),
+            tx_fee: 2500,
+            signer_bitmap: BitArray::ZERO,
+            deployer: *testing::wallet::WALLET.0.address(),
+            sweep_block_hash: BitcoinBlockHash::from([0; 32]),
+            sweep_block_height: 7,
+            sweep_txid: BitcoinTxId::from([0; 32]),
+        }),
+        WithdrawalCompletion::Reject(RejectWithdrawalV1 {
+            request_id: 4,
+            signer_bitmap: BitArray::ZERO,
+            deployer: *testing::wallet::WALLET.0.address(),
+        }),
+    ],
+    deployer: *testing::wallet::WALLET.0.address(),
+}); "complete-withdrawals")]
 #[test_case(ContractCallWrapper(RotateKeysV1::new(
     &testing::wallet::WALLET.0,
     *testing::wallet::WALLET.0.address(),
@@ -1,4 +1,5 @@
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use bitcoin::block::Header;
@@ -36,7 +37,6 @@ use signer::emily_client::EmilyClient;
 use signer::emily_client::EmilyInteract;
 use signer::error::Error;
 use signer::keys;
-use signer::keys::PublicKey;
 use signer::keys::SignerScriptPubKey as _;
 use signer::network;
 use signer::stacks::api::TenureBlocks;
@@ -265,6 +265,12 @@ async fn deposit_flow() {
         ],
     };
     let deposit_block_hash = deposit_block.block_hash();
+    let deposit_block_header = signer::testing::btc::block_header_result(
+        &deposit_block,
+        bitcoin_chain_tip.block_height + 1,
+        1,
+        deposit_block.header.time,
+    );
 
     // Mock required bitcoin client functions
     context
@@ -357,6 +363,19 @@ async fn deposit_flow() {
                     Box::pin(async move { res })
                 });
 
+            // Return the deposit block header, which the block observer
+            // uses to get the median-time-past of the block.
+            client
+                .expect_get_block_header()
+                .returning(move |block_hash| {
+                    let res = if *block_hash == deposit_block_hash {
+                        Ok(Some(deposit_block_header.clone()))
+                    } else {
+                        Ok(None)
+                    };
+                    Box::pin(async move { res })
+                });
+
             // Return the deposit tx block, when the block observer will query us for it
             // when processing the new block; as its parent is already in storage
             // we don't need to provide any other blocks.
@@ -377,6 +396,15 @@ async fn deposit_flow() {
     // Also mock stacks client (to return no new blocks)
     context
         .with_stacks_client(|client| {
+            // The signers have not rotated keys according to the
+            // `sbtc-registry` contract.
+            client
+                .expect_get_current_signer_set()
+                .returning(|_| Box::pin(std::future::ready(Ok(Vec::new()))));
+            client
+                .expect_get_current_signature_threshold()
+                .returning(|_| Box::pin(std::future::ready(Ok(None))));
+
             client
                 .expect_get_tenure_info()
                 .once()
@@ -439,6 +467,7 @@ async fn deposit_flow() {
         context: context.clone(),
         network: network.connect(),
         private_key,
+        signer_key: Arc::new(private_key),
         context_window,
         threshold: signing_threshold as u16,
         signing_round_max_duration: Duration::from_secs(10),
@@ -450,7 +479,7 @@ async fn deposit_flow() {
     let tx_coordinator_handle = tokio::spawn(async move { tx_coordinator.run().await });
 
     // There shouldn't be any request yet
-    let signer_public_key = PublicKey::from_private_key(&context.config().signer.private_key);
+    let signer_public_key = context.config().signer.public_key().unwrap();
     let chain_tip = bitcoin_chain_tip.block_hash;
     assert!(context
         .get_storage()
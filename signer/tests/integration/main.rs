@@ -18,6 +18,7 @@ mod transaction_coordinator;
 mod transaction_signer;
 mod utxo_construction;
 mod withdrawal_accept;
+mod withdrawal_reject;
 mod zmq;
 /// This is needed to make sure that each test has as many isolated
 /// databases as it needs.
@@ -17,6 +17,7 @@ use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 
 use signer::bitcoin::validation::DepositConfirmationStatus;
+use signer::bitcoin::validation::WithdrawalRequestStatus;
 use signer::bitcoin::MockBitcoinInteract;
 use signer::config::Settings;
 use signer::context::Context;
@@ -30,9 +31,12 @@ use signer::stacks::contracts::AcceptWithdrawalV1;
 use signer::stacks::contracts::AsContractCall;
 use signer::stacks::contracts::AsTxPayload as _;
 use signer::stacks::contracts::CompleteDepositV1;
+use signer::stacks::contracts::CompleteDepositsV1;
+use signer::stacks::contracts::CompleteWithdrawalsV1;
 use signer::stacks::contracts::RejectWithdrawalV1;
 use signer::stacks::contracts::ReqContext;
 use signer::stacks::contracts::RotateKeysV1;
+use signer::stacks::contracts::WithdrawalCompletion;
 use signer::stacks::events::CompletedDepositEvent;
 use signer::stacks::events::WithdrawalAcceptEvent;
 use signer::stacks::events::WithdrawalCreateEvent;
@@ -166,6 +170,18 @@ impl AsContractCall for InitiateWithdrawalRequest {
     sweep_block_hash: BitcoinBlockHash::from([0; 32]),
     sweep_block_height: 7,
 }); "complete-deposit contract recipient")]
+#[test_case(ContractCallWrapper(CompleteDepositsV1 {
+    deposits: vec![CompleteDepositV1 {
+        outpoint: bitcoin::OutPoint::null(),
+        amount: 123654,
+        recipient: PrincipalData::parse("ST1RQHF4VE5CZ6EK3MZPZVQBA0JVSMM9H5PMHMS1Y").unwrap(),
+        deployer: *testing::wallet::WALLET.0.address(),
+        sweep_txid: BitcoinTxId::from([0; 32]),
+        sweep_block_hash: BitcoinBlockHash::from([0; 32]),
+        sweep_block_height: 7,
+    }],
+    deployer: *testing::wallet::WALLET.0.address(),
+}); "complete-deposits")]
 #[test_case(ContractCallWrapper(AcceptWithdrawalV1 {
     request_id: 0,
     outpoint: bitcoin::OutPoint::null(),
@@ -174,12 +190,33 @@ impl AsContractCall for InitiateWithdrawalRequest {
     deployer: *testing::wallet::WALLET.0.address(),
     sweep_block_hash: BitcoinBlockHash::from([0; 32]),
     sweep_block_height: 7,
+    sweep_txid: BitcoinTxId::from([0; 32]),
 }); "accept-withdrawal")]
 #[test_case(ContractCallWrapper(RejectWithdrawalV1 {
     request_id: 0,
     signer_bitmap: BitArray::ZERO,
     deployer: *testing::wallet::WALLET.0.address(),
 }); "reject-withdrawal")]
+#[test_case(ContractCallWrapper(CompleteWithdrawalsV1 {
+    withdrawals: vec![
+        WithdrawalCompletion::Accept(AcceptWithdrawalV1 {
+            request_id: 3,
+            outpoint: bitcoin::OutPoint::null(),
+            tx_fee: 2500,
+            signer_bitmap: BitArray::ZERO,
+            deployer: *testing::wallet::WALLET.0.address(),
+            sweep_block_hash: BitcoinBlockHash::from([0; 32]),
+            sweep_block_height: 7,
+            sweep_txid: BitcoinTxId::from([0; 32]),
+        }),
+        WithdrawalCompletion::Reject(RejectWithdrawalV1 {
+            request_id: 4,
+            signer_bitmap: BitArray::ZERO,
+            deployer: *testing::wallet::WALLET.0.address(),
+        }),
+    ],
+    deployer: *testing::wallet::WALLET.0.address(),
+}); "complete-withdrawals")]
 #[test_case(ContractCallWrapper(RotateKeysV1::new(
     &testing::wallet::WALLET.0,
     *testing::wallet::WALLET.0.address(),
@@ -1117,6 +1154,7 @@ async fn writing_transactions_postgres() {
         block_hash: block_hash.into(),
         block_height: 15,
         parent_hash: parent_hash.into(),
+        median_time_past: 0,
     };
 
     // We start by writing the bitcoin block because of the foreign key
@@ -2290,227 +2328,635 @@ async fn get_swept_deposit_requests_response_tx_reorged() {
     signer::testing::storage::drop_db(db).await;
 }
 
-async fn transaction_coordinator_test_environment(
-    store: PgStore,
-) -> testing::transaction_coordinator::TestEnvironment<
-    TestContext<
-        storage::postgres::PgStore,
-        WrappedMock<MockBitcoinInteract>,
-        WrappedMock<MockStacksInteract>,
-        WrappedMock<MockEmilyInteract>,
-    >,
-> {
-    let test_model_parameters = testing::storage::model::Params {
-        num_bitcoin_blocks: 20,
-        num_stacks_blocks_per_bitcoin_block: 3,
-        num_deposit_requests_per_block: 5,
-        num_withdraw_requests_per_block: 5,
-        num_signers_per_request: 7,
-    };
+/// This tests that withdrawal requests where there is an associated sweep
+/// transaction will show up in the query results from
+/// [`DbRead::get_swept_withdrawal_requests`].
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn get_swept_withdrawal_requests_returns_swept_withdrawal_requests() {
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
 
-    let context = TestContext::builder()
-        .with_storage(store)
-        .with_mocked_clients()
-        .build();
+    let (rpc, faucet) = sbtc::testing::regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
 
-    testing::transaction_coordinator::TestEnvironment {
-        context,
-        context_window: 5,
-        num_signers: 7,
-        signing_threshold: 5,
-        test_model_parameters,
-    }
-}
+    // We need to manually update the database with new bitcoin block
+    // headers.
+    crate::setup::backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
 
-#[cfg_attr(not(feature = "integration-tests"), ignore)]
-#[tokio::test]
-async fn should_get_signer_utxo_simple() {
-    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
-    let store = testing::storage::new_test_database(db_num, true).await;
+    // The sweep transaction package references both the deposit and the
+    // withdrawal request, so both need to be in the database.
+    setup.store_deposit_tx(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    setup.store_sweep_transactions(&db).await;
+    setup.store_sweep_tx(&db).await;
 
-    transaction_coordinator_test_environment(store.clone())
+    let chain_tip = setup.sweep_block_hash.into();
+    let context_window = 20;
+
+    let mut requests = db
+        .get_swept_withdrawal_requests(&chain_tip, context_window)
         .await
-        .assert_get_signer_utxo_simple()
-        .await;
+        .unwrap();
 
-    signer::testing::storage::drop_db(store).await;
-}
+    // There should only be one withdrawal request in the database and it
+    // has a confirmed sweep transaction.
+    assert_eq!(requests.len(), 1);
 
-#[cfg_attr(not(feature = "integration-tests"), ignore)]
-#[tokio::test]
-async fn should_get_signer_utxo_fork() {
-    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
-    let store = testing::storage::new_test_database(db_num, true).await;
+    let req = requests.pop().unwrap();
 
-    transaction_coordinator_test_environment(store.clone())
-        .await
-        .assert_get_signer_utxo_fork()
-        .await;
+    assert_eq!(req.request_id, setup.withdrawal_request.request_id);
+    assert_eq!(req.txid, setup.withdrawal_request.txid);
+    assert_eq!(req.block_hash, setup.withdrawal_request.block_hash);
+    assert_eq!(req.amount, setup.withdrawal_request.amount);
+    assert_eq!(req.max_fee, setup.withdrawal_request.max_fee);
+    assert_eq!(req.recipient, setup.withdrawal_request.script_pubkey);
+    assert_eq!(req.sweep_block_hash, setup.sweep_block_hash.into());
+    assert_eq!(req.sweep_block_height, setup.sweep_block_height);
+    assert_eq!(req.sweep_txid, setup.sweep_tx_info.txid.into());
+    // The first two outputs are the signers' UTXO and the OP_RETURN
+    // output, so the withdrawal output is the third one.
+    assert_eq!(req.output_index, 2);
 
-    signer::testing::storage::drop_db(store).await;
+    let output = &setup.sweep_tx_info.tx.output[req.output_index as usize];
+    assert_eq!(output.value.to_sat(), req.amount);
+
+    signer::testing::storage::drop_db(db).await;
 }
 
+/// This function tests that withdrawal requests that do not have a
+/// confirmed sweep bitcoin transaction are not returned from
+/// [`DbRead::get_swept_withdrawal_requests`], but they are returned from
+/// [`DbRead::get_unfulfilled_withdrawal_requests`].
 #[cfg_attr(not(feature = "integration-tests"), ignore)]
 #[tokio::test]
-async fn should_get_signer_utxo_unspent() {
-    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
-    let store = testing::storage::new_test_database(db_num, true).await;
+async fn get_swept_withdrawal_requests_does_not_return_unswept_withdrawal_requests() {
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
 
-    transaction_coordinator_test_environment(store.clone())
+    let (rpc, faucet) = sbtc::testing::regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    crate::setup::backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    setup.store_deposit_tx(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    // The sweep transaction has been broadcast, but we have not observed
+    // it in a block yet since we do not call `store_sweep_tx`.
+    setup.store_sweep_transactions(&db).await;
+
+    let chain_tip = setup.sweep_block_hash.into();
+    let context_window = 20;
+
+    let requests = db
+        .get_swept_withdrawal_requests(&chain_tip, context_window)
         .await
-        .assert_get_signer_utxo_unspent()
-        .await;
+        .unwrap();
+    assert!(requests.is_empty());
 
-    signer::testing::storage::drop_db(store).await;
-}
+    // Since the request has not been swept, and there is no response on
+    // stacks, it is a candidate for rejection.
+    let requests = db
+        .get_unfulfilled_withdrawal_requests(&chain_tip, context_window)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].qualified_id(),
+        setup.withdrawal_request.qualified_id()
+    );
 
-#[cfg_attr(not(feature = "integration-tests"), ignore)]
-#[tokio::test]
-async fn should_get_signer_utxo_donations() {
-    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
-    let store = testing::storage::new_test_database(db_num, true).await;
+    // Now the sweep transaction gets confirmed, so it is no longer
+    // unfulfilled.
+    setup.store_sweep_tx(&db).await;
 
-    transaction_coordinator_test_environment(store.clone())
+    let requests = db
+        .get_unfulfilled_withdrawal_requests(&chain_tip, context_window)
         .await
-        .assert_get_signer_utxo_donations()
-        .await;
+        .unwrap();
+    assert!(requests.is_empty());
 
-    signer::testing::storage::drop_db(store).await;
+    signer::testing::storage::drop_db(db).await;
 }
 
-/// This test checks that the `get_latest_sweep_transaction_package` function
-/// returns the correct sweep transaction package for a given blockchain tip.
-///
-/// The test sets up two different sweep transactions in the database on
-/// different Bitcoin forks and then checks that the correct sweep transaction
-/// package is returned for each fork.
+/// This function tests that [`DbRead::get_unfulfilled_withdrawal_request`]
+/// and [`DbRead::get_swept_withdrawal_request`] only return the request
+/// with the given request ID, and that they follow the request from
+/// unfulfilled to swept.
 #[cfg_attr(not(feature = "integration-tests"), ignore)]
 #[tokio::test]
-async fn can_store_and_get_latest_sweep_transaction() {
-    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+async fn get_withdrawal_request_by_id_follows_the_sweep() {
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
     let db = testing::storage::new_test_database(db_num, true).await;
     let mut rng = rand::rngs::StdRng::seed_from_u64(51);
 
     let (rpc, faucet) = sbtc::testing::regtest::initialize_blockchain();
-
-    // ** TEST SETUP 1 **
-
     let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
-    crate::setup::backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
-    setup.store_deposit_tx(&db).await;
-    setup.store_deposit_request(&db).await;
-    setup.store_withdrawal_request(&db).await;
-    let first_sweep_block_hash = setup.sweep_block_hash;
-    // We'll use this to verify fork-functionality later.
-    // Store outstanding sweep transaction packages in the database and get the
-    // expected sweep package for asserts later on.
-    let expected1 = setup.store_sweep_transactions(&db).await.pop().unwrap();
 
-    // ** TEST SETUP 2 **
-
-    // Do the above all over again, but with a different sweep transaction so that
-    // we get multiple in the database.
-    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
     crate::setup::backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
     setup.store_deposit_tx(&db).await;
     setup.store_deposit_request(&db).await;
     setup.store_withdrawal_request(&db).await;
+    setup.store_sweep_transactions(&db).await;
 
-    // Make a bunch of noise on the chain.
-    for block in faucet.generate_blocks(10) {
-        let mut sweep = fake::Faker.fake_with_rng::<model::SweepTransaction, _>(&mut rng);
-        sweep.created_at_block_hash = block.into();
-        sweep.swept_deposits = vec![];
-        sweep.swept_withdrawals = vec![];
-        db.write_sweep_transaction(&sweep)
-            .await
-            .expect("failed to insert dummy sweep transaction");
-        crate::setup::backfill_bitcoin_blocks(&db, rpc, &block).await;
-    }
-
-    // Store outstanding sweep transactions in the database and get the
-    // expected sweep package for asserts later on.
-    let expected2 = setup.store_sweep_transactions(&db).await.pop().unwrap();
+    let chain_tip = setup.sweep_block_hash.into();
+    let context_window = 20;
+    let request_id = setup.withdrawal_request.request_id;
+    let other_request_id = request_id + 1;
 
-    // Make a bunch more noise on the chain.
-    for block in faucet.generate_blocks(10) {
-        let mut sweep = fake::Faker.fake_with_rng::<model::SweepTransaction, _>(&mut rng);
-        sweep.created_at_block_hash = block.into();
-        sweep.swept_deposits = vec![];
-        sweep.swept_withdrawals = vec![];
-        db.write_sweep_transaction(&sweep)
-            .await
-            .expect("failed to insert dummy sweep transaction");
-        crate::setup::backfill_bitcoin_blocks(&db, rpc, &block).await;
-    }
+    // The sweep transaction has not been confirmed yet, so the request
+    // is unfulfilled.
+    let request = db
+        .get_unfulfilled_withdrawal_request(&chain_tip, context_window, request_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        request.qualified_id(),
+        setup.withdrawal_request.qualified_id()
+    );
 
-    // ** TEST 1 **
-    // Assert that if we request the latest sweep tx for the first
-    // blockchain tip, we get the first sweep tx.
-    let tx1 = db
-        .get_latest_sweep_transaction(&first_sweep_block_hash.into(), 50)
+    let request = db
+        .get_swept_withdrawal_request(&chain_tip, context_window, request_id)
         .await
-        .expect("failed to get latest sweep transaction package (on fork)");
+        .unwrap();
+    assert!(request.is_none());
 
-    let Some(tx1) = tx1 else {
-        panic!("expected to find a sweep transaction package");
-    };
+    let request = db
+        .get_unfulfilled_withdrawal_request(&chain_tip, context_window, other_request_id)
+        .await
+        .unwrap();
+    assert!(request.is_none());
 
-    assert_eq!(tx1, expected1);
+    // Now the sweep transaction gets confirmed, so the request is swept.
+    setup.store_sweep_tx(&db).await;
 
-    // ** TEST 2 **
-    // Assert that if we request the latest sweep tx for the second
-    // blockchain tip, we get the second sweep tx.
-    //
-    // This test also verifies that the sweep tx pre-insert is identical to
-    // the sweep tx when retrieved from the database.
-    let tx2 = db
-        .get_latest_sweep_transaction(&setup.sweep_block_hash.into(), 50)
+    let request = db
+        .get_unfulfilled_withdrawal_request(&chain_tip, context_window, request_id)
         .await
-        .expect("failed to get latest sweep transaction package");
+        .unwrap();
+    assert!(request.is_none());
 
-    let Some(tx2) = tx2 else {
-        panic!("expected to find a sweep transaction package");
-    };
+    let request = db
+        .get_swept_withdrawal_request(&chain_tip, context_window, request_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(request.request_id, request_id);
+    assert_eq!(request.sweep_txid, setup.sweep_tx_info.txid.into());
 
-    assert_eq!(tx2, expected2);
+    let request = db
+        .get_swept_withdrawal_request(&chain_tip, context_window, other_request_id)
+        .await
+        .unwrap();
+    assert!(request.is_none());
 
     signer::testing::storage::drop_db(db).await;
 }
 
+/// This function tests that [`DbRead::get_swept_withdrawal_requests`]
+/// does not return requests where we have already confirmed an
+/// `accept-withdrawal-request` contract call transaction on the canonical
+/// Stacks blockchain, and that it does return them again if that
+/// transaction gets reorged.
 #[cfg_attr(not(feature = "integration-tests"), ignore)]
-#[test(tokio::test)]
-async fn can_get_latest_unconfirmed_sweep_transactions_simple() {
-    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+#[tokio::test]
+async fn get_swept_withdrawal_requests_does_not_return_withdrawal_requests_with_responses() {
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
     let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
 
-    let mut sweep_transactions: Vec<SweepTransaction> = fake::Faker.fake();
-    let txids = sweep_transactions
-        .iter()
-        .map(|tx| tx.txid)
-        .collect::<Vec<_>>();
+    let (rpc, faucet) = sbtc::testing::regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
 
-    let bitcoin_block: model::BitcoinBlock = fake::Faker.fake();
-    let utxo_tx = model::Transaction {
-        block_hash: bitcoin_block.block_hash.into_bytes(),
-        ..fake::Faker.fake()
-    };
+    let context_window = 20;
 
-    for (i, tx) in sweep_transactions.iter_mut().enumerate() {
-        if i == 0 {
-            tx.signer_prevout_txid = utxo_tx.txid.into();
-        } else {
-            tx.signer_prevout_txid = txids[i - 1];
-        }
+    // Adding a block, we will use it to store the accept withdrawal event
+    // later.
+    let chain_tip: BitcoinBlockHash = faucet.generate_blocks(1).pop().unwrap().into();
 
-        // We clear these so we don't have to mess around with writing them and
-        // their FK's etc -- that's already tested elsewhere.
-        tx.swept_deposits.clear();
-        tx.swept_withdrawals.clear();
-    }
+    crate::setup::backfill_bitcoin_blocks(&db, rpc, &chain_tip).await;
 
-    db.write_bitcoin_block(&bitcoin_block).await.unwrap();
-    db.write_transaction(&utxo_tx).await.unwrap();
+    setup.store_deposit_tx(&db).await;
+    setup.store_sweep_tx(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    setup.store_sweep_transactions(&db).await;
+
+    let requests = db
+        .get_swept_withdrawal_requests(&chain_tip, context_window)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+
+    // Now we write the event to a stacks block anchored to the chain tip.
+    let stacks_tip = db.get_stacks_chain_tip(&chain_tip).await.unwrap().unwrap();
+    let event_block = StacksBlock {
+        block_hash: fake::Faker.fake_with_rng(&mut rng),
+        block_height: stacks_tip.block_height + 1,
+        parent_hash: stacks_tip.block_hash,
+        bitcoin_anchor: chain_tip,
+    };
+    db.write_stacks_block(&event_block).await.unwrap();
+
+    let event = WithdrawalAcceptEvent {
+        txid: fake::Faker.fake_with_rng::<StacksTxId, _>(&mut rng).into(),
+        block_id: *event_block.block_hash,
+        request_id: setup.withdrawal_request.request_id,
+        signer_bitmap: BitArray::ZERO,
+        outpoint: bitcoin::OutPoint::new(setup.sweep_tx_info.txid, 2),
+        fee: 1000,
+        sweep_block_hash: setup.sweep_block_hash.into(),
+        sweep_block_height: setup.sweep_block_height,
+        sweep_txid: setup.sweep_tx_info.txid.into(),
+    };
+    db.write_withdrawal_accept_event(&event).await.unwrap();
+
+    let requests = db
+        .get_swept_withdrawal_requests(&chain_tip, context_window)
+        .await
+        .unwrap();
+    assert!(requests.is_empty());
+
+    // Now assume we have a reorg where the new bitcoin chain tip is
+    // `sweep_block_hash`, so the accept withdrawal event is no longer on
+    // the canonical stacks blockchain.
+    let requests = db
+        .get_swept_withdrawal_requests(&setup.sweep_block_hash.into(), context_window)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+/// This function tests that [`DbRead::get_swept_withdrawal_requests`]
+/// does not return requests that have been rejected on the canonical
+/// Stacks blockchain, even if they were swept.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn get_swept_withdrawal_requests_does_not_return_rejected_withdrawal_requests() {
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+
+    let (rpc, faucet) = sbtc::testing::regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    let context_window = 20;
+
+    // Adding a block, we will use it to store the reject withdrawal event
+    // later.
+    let chain_tip: BitcoinBlockHash = faucet.generate_blocks(1).pop().unwrap().into();
+
+    crate::setup::backfill_bitcoin_blocks(&db, rpc, &chain_tip).await;
+
+    setup.store_deposit_tx(&db).await;
+    setup.store_sweep_tx(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    setup.store_sweep_transactions(&db).await;
+
+    let requests = db
+        .get_swept_withdrawal_requests(&chain_tip, context_window)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+
+    // Now we write the event to a stacks block anchored to the chain tip.
+    let stacks_tip = db.get_stacks_chain_tip(&chain_tip).await.unwrap().unwrap();
+    let event_block = StacksBlock {
+        block_hash: fake::Faker.fake_with_rng(&mut rng),
+        block_height: stacks_tip.block_height + 1,
+        parent_hash: stacks_tip.block_hash,
+        bitcoin_anchor: chain_tip,
+    };
+    db.write_stacks_block(&event_block).await.unwrap();
+
+    let event = WithdrawalRejectEvent {
+        txid: fake::Faker.fake_with_rng::<StacksTxId, _>(&mut rng).into(),
+        block_id: *event_block.block_hash,
+        request_id: setup.withdrawal_request.request_id,
+        signer_bitmap: BitArray::ZERO,
+    };
+    db.write_withdrawal_reject_event(&event).await.unwrap();
+
+    let requests = db
+        .get_swept_withdrawal_requests(&chain_tip, context_window)
+        .await
+        .unwrap();
+    assert!(requests.is_empty());
+
+    // On the fork where the rejection never happened, the request is
+    // still waiting to be accepted.
+    let requests = db
+        .get_swept_withdrawal_requests(&setup.sweep_block_hash.into(), context_window)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+/// This function tests that [`DbRead::get_unfulfilled_withdrawal_requests`]
+/// does not return requests that have been rejected on the canonical
+/// Stacks blockchain.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn get_unfulfilled_withdrawal_requests_does_not_return_rejected_requests() {
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+
+    let (rpc, faucet) = sbtc::testing::regtest::initialize_blockchain();
+    let setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    let context_window = 20;
+    let chain_tip: BitcoinBlockHash = faucet.generate_blocks(1).pop().unwrap().into();
+
+    crate::setup::backfill_bitcoin_blocks(&db, rpc, &chain_tip).await;
+    setup.store_withdrawal_request(&db).await;
+
+    let requests = db
+        .get_unfulfilled_withdrawal_requests(&chain_tip, context_window)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+
+    let stacks_tip = db.get_stacks_chain_tip(&chain_tip).await.unwrap().unwrap();
+    let event_block = StacksBlock {
+        block_hash: fake::Faker.fake_with_rng(&mut rng),
+        block_height: stacks_tip.block_height + 1,
+        parent_hash: stacks_tip.block_hash,
+        bitcoin_anchor: chain_tip,
+    };
+    db.write_stacks_block(&event_block).await.unwrap();
+
+    let event = WithdrawalRejectEvent {
+        txid: fake::Faker.fake_with_rng::<StacksTxId, _>(&mut rng).into(),
+        block_id: *event_block.block_hash,
+        request_id: setup.withdrawal_request.request_id,
+        signer_bitmap: BitArray::ZERO,
+    };
+    db.write_withdrawal_reject_event(&event).await.unwrap();
+
+    let requests = db
+        .get_unfulfilled_withdrawal_requests(&chain_tip, context_window)
+        .await
+        .unwrap();
+    assert!(requests.is_empty());
+
+    // On the fork where the rejection never happened, the request is
+    // still unfulfilled.
+    let requests = db
+        .get_unfulfilled_withdrawal_requests(&setup.sweep_block_hash.into(), context_window)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+async fn transaction_coordinator_test_environment(
+    store: PgStore,
+) -> testing::transaction_coordinator::TestEnvironment<
+    TestContext<
+        storage::postgres::PgStore,
+        WrappedMock<MockBitcoinInteract>,
+        WrappedMock<MockStacksInteract>,
+        WrappedMock<MockEmilyInteract>,
+    >,
+> {
+    let test_model_parameters = testing::storage::model::Params {
+        num_bitcoin_blocks: 20,
+        num_stacks_blocks_per_bitcoin_block: 3,
+        num_deposit_requests_per_block: 5,
+        num_withdraw_requests_per_block: 5,
+        num_signers_per_request: 7,
+    };
+
+    let context = TestContext::builder()
+        .with_storage(store)
+        .with_mocked_clients()
+        .build();
+
+    testing::transaction_coordinator::TestEnvironment {
+        context,
+        context_window: 5,
+        num_signers: 7,
+        signing_threshold: 5,
+        test_model_parameters,
+    }
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn should_get_signer_utxo_simple() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let store = testing::storage::new_test_database(db_num, true).await;
+
+    transaction_coordinator_test_environment(store.clone())
+        .await
+        .assert_get_signer_utxo_simple()
+        .await;
+
+    signer::testing::storage::drop_db(store).await;
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn should_get_signer_utxo_fork() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let store = testing::storage::new_test_database(db_num, true).await;
+
+    transaction_coordinator_test_environment(store.clone())
+        .await
+        .assert_get_signer_utxo_fork()
+        .await;
+
+    signer::testing::storage::drop_db(store).await;
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn should_get_signer_utxo_unspent() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let store = testing::storage::new_test_database(db_num, true).await;
+
+    transaction_coordinator_test_environment(store.clone())
+        .await
+        .assert_get_signer_utxo_unspent()
+        .await;
+
+    signer::testing::storage::drop_db(store).await;
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn should_get_signer_utxo_after_key_rotation() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let store = testing::storage::new_test_database(db_num, true).await;
+
+    transaction_coordinator_test_environment(store.clone())
+        .await
+        .assert_get_signer_utxo_follows_key_rotation()
+        .await;
+
+    signer::testing::storage::drop_db(store).await;
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn should_get_signer_utxo_donations() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let store = testing::storage::new_test_database(db_num, true).await;
+
+    transaction_coordinator_test_environment(store.clone())
+        .await
+        .assert_get_signer_utxo_donations()
+        .await;
+
+    signer::testing::storage::drop_db(store).await;
+}
+
+/// This test checks that the `get_latest_sweep_transaction_package` function
+/// returns the correct sweep transaction package for a given blockchain tip.
+///
+/// The test sets up two different sweep transactions in the database on
+/// different Bitcoin forks and then checks that the correct sweep transaction
+/// package is returned for each fork.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn can_store_and_get_latest_sweep_transaction() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+
+    let (rpc, faucet) = sbtc::testing::regtest::initialize_blockchain();
+
+    // ** TEST SETUP 1 **
+
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+    crate::setup::backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+    setup.store_deposit_tx(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    let first_sweep_block_hash = setup.sweep_block_hash;
+    // We'll use this to verify fork-functionality later.
+    // Store outstanding sweep transaction packages in the database and get the
+    // expected sweep package for asserts later on.
+    let expected1 = setup.store_sweep_transactions(&db).await.pop().unwrap();
+
+    // ** TEST SETUP 2 **
+
+    // Do the above all over again, but with a different sweep transaction so that
+    // we get multiple in the database.
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+    crate::setup::backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+    setup.store_deposit_tx(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_withdrawal_request(&db).await;
+
+    // Make a bunch of noise on the chain.
+    for block in faucet.generate_blocks(10) {
+        let mut sweep = fake::Faker.fake_with_rng::<model::SweepTransaction, _>(&mut rng);
+        sweep.created_at_block_hash = block.into();
+        sweep.swept_deposits = vec![];
+        sweep.swept_withdrawals = vec![];
+        db.write_sweep_transaction(&sweep)
+            .await
+            .expect("failed to insert dummy sweep transaction");
+        crate::setup::backfill_bitcoin_blocks(&db, rpc, &block).await;
+    }
+
+    // Store outstanding sweep transactions in the database and get the
+    // expected sweep package for asserts later on.
+    let expected2 = setup.store_sweep_transactions(&db).await.pop().unwrap();
+
+    // Make a bunch more noise on the chain.
+    for block in faucet.generate_blocks(10) {
+        let mut sweep = fake::Faker.fake_with_rng::<model::SweepTransaction, _>(&mut rng);
+        sweep.created_at_block_hash = block.into();
+        sweep.swept_deposits = vec![];
+        sweep.swept_withdrawals = vec![];
+        db.write_sweep_transaction(&sweep)
+            .await
+            .expect("failed to insert dummy sweep transaction");
+        crate::setup::backfill_bitcoin_blocks(&db, rpc, &block).await;
+    }
+
+    // ** TEST 1 **
+    // Assert that if we request the latest sweep tx for the first
+    // blockchain tip, we get the first sweep tx.
+    let tx1 = db
+        .get_latest_sweep_transaction(&first_sweep_block_hash.into(), 50)
+        .await
+        .expect("failed to get latest sweep transaction package (on fork)");
+
+    let Some(tx1) = tx1 else {
+        panic!("expected to find a sweep transaction package");
+    };
+
+    assert_eq!(tx1, expected1);
+
+    // ** TEST 2 **
+    // Assert that if we request the latest sweep tx for the second
+    // blockchain tip, we get the second sweep tx.
+    //
+    // This test also verifies that the sweep tx pre-insert is identical to
+    // the sweep tx when retrieved from the database.
+    let tx2 = db
+        .get_latest_sweep_transaction(&setup.sweep_block_hash.into(), 50)
+        .await
+        .expect("failed to get latest sweep transaction package");
+
+    let Some(tx2) = tx2 else {
+        panic!("expected to find a sweep transaction package");
+    };
+
+    assert_eq!(tx2, expected2);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[test(tokio::test)]
+async fn can_get_latest_unconfirmed_sweep_transactions_simple() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+
+    let mut sweep_transactions: Vec<SweepTransaction> = fake::Faker.fake();
+    let txids = sweep_transactions
+        .iter()
+        .map(|tx| tx.txid)
+        .collect::<Vec<_>>();
+
+    let bitcoin_block: model::BitcoinBlock = fake::Faker.fake();
+    let utxo_tx = model::Transaction {
+        block_hash: bitcoin_block.block_hash.into_bytes(),
+        ..fake::Faker.fake()
+    };
+
+    for (i, tx) in sweep_transactions.iter_mut().enumerate() {
+        if i == 0 {
+            tx.signer_prevout_txid = utxo_tx.txid.into();
+        } else {
+            tx.signer_prevout_txid = txids[i - 1];
+        }
+
+        // We clear these so we don't have to mess around with writing them and
+        // their FK's etc -- that's already tested elsewhere.
+        tx.swept_deposits.clear();
+        tx.swept_withdrawals.clear();
+    }
+
+    db.write_bitcoin_block(&bitcoin_block).await.unwrap();
+    db.write_transaction(&utxo_tx).await.unwrap();
     db.write_bitcoin_transaction(&model::BitcoinTxRef {
         txid: utxo_tx.txid.into(),
         block_hash: utxo_tx.block_hash.into(),
@@ -2801,9 +3247,193 @@ async fn deposit_report_with_only_deposit_request() {
     db.write_transaction(&tx).await.unwrap();
     db.write_bitcoin_transaction(&tx_ref).await.unwrap();
 
-    // The result shouldn't be Ok(None), since we have a deposit request,
-    // but only the amount and locktime should be present, everything else
-    // should be None.
+    // The result shouldn't be Ok(None), since we have a deposit request,
+    // but only the amount and locktime should be present, everything else
+    // should be None.
+    let report = db
+        .get_deposit_request_report(&chain_tip, txid, output_index, signer_public_key)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let report_lock_time = report.lock_time.to_consensus_u32();
+
+    assert_eq!(report.amount, deposit_request.amount);
+    assert_eq!(report_lock_time, deposit_request.lock_time);
+    assert_eq!(report.max_fee, deposit_request.max_fee);
+    assert!(report.can_accept.is_none());
+    assert!(report.can_sign.is_none());
+    // The transaction is not on the canonical bitcoin blockchain, so it
+    // shows up as unconfirmed.
+    assert_eq!(report.status, DepositConfirmationStatus::Unconfirmed);
+
+    testing::storage::drop_db(db).await;
+}
+
+/// Check that if the deposit has been confirmed on a block that is not on
+/// the canonical bitcoin blockchain then the deposit reports the status as
+/// unconfirmed. We also check that if this signer has voted on the request
+/// that the votes are accurately reflected in the report.
+///
+/// The difference between this test and
+/// [`deposit_report_with_only_deposit_request`] is that we write the
+/// signer decision to the database here and check that it gets reproduced
+/// in the report.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn deposit_report_with_deposit_request_reorged() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(21);
+
+    // We only want the blockchain to be generated
+    let num_signers = 3;
+    let test_params = testing::storage::model::Params {
+        num_bitcoin_blocks: 10,
+        num_stacks_blocks_per_bitcoin_block: 1,
+        num_deposit_requests_per_block: 0,
+        num_withdraw_requests_per_block: 0,
+        num_signers_per_request: num_signers,
+    };
+
+    let signer_set = testing::wsts::generate_signer_set_public_keys(&mut rng, num_signers);
+    let test_data = TestData::generate(&mut rng, &signer_set, &test_params);
+    test_data.write_to(&db).await;
+
+    // Let's write the deposit request and associated transaction to our
+    // database. The deposit transaction will be confirmed, but on a block
+    // that is not on the canonical bitcoin blockchain.
+    let deposit_request: model::DepositRequest = fake::Faker.fake_with_rng(&mut rng);
+    let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
+    let txid = &deposit_request.txid;
+    let output_index = deposit_request.output_index;
+    let signer_public_key = &signer_set[0];
+
+    let random_block: model::BitcoinBlock = fake::Faker.fake_with_rng(&mut rng);
+    let tx = model::Transaction {
+        txid: deposit_request.txid.into_bytes(),
+        tx: Vec::new(),
+        tx_type: model::TransactionType::DepositRequest,
+        block_hash: random_block.block_hash.into_bytes(),
+    };
+    let tx_ref = model::BitcoinTxRef {
+        txid: deposit_request.txid,
+        block_hash: random_block.block_hash,
+    };
+
+    db.write_deposit_request(&deposit_request).await.unwrap();
+    db.write_bitcoin_block(&random_block).await.unwrap();
+    db.write_transaction(&tx).await.unwrap();
+    db.write_bitcoin_transaction(&tx_ref).await.unwrap();
+
+    // Time to record the signers' vote.
+    let mut decision: model::DepositSigner = fake::Faker.fake_with_rng(&mut rng);
+    decision.output_index = deposit_request.output_index;
+    decision.txid = deposit_request.txid;
+    decision.signer_pub_key = *signer_public_key;
+
+    db.write_deposit_signer_decision(&decision).await.unwrap();
+
+    let report = db
+        .get_deposit_request_report(&chain_tip, txid, output_index, signer_public_key)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let report_lock_time = report.lock_time.to_consensus_u32();
+
+    assert_eq!(report.amount, deposit_request.amount);
+    assert_eq!(report_lock_time, deposit_request.lock_time);
+    assert_eq!(report.max_fee, deposit_request.max_fee);
+    assert_eq!(report.can_accept, Some(decision.can_accept));
+    assert_eq!(report.can_sign, Some(decision.can_sign));
+    assert_eq!(report.status, DepositConfirmationStatus::Unconfirmed);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+/// Check that if the deposit has been included in a sweep transaction
+/// then the deposit report states that the deposit has been spent in the
+/// status.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn deposit_report_with_deposit_request_spent() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(22);
+
+    // We only want the blockchain to be generated
+    let num_signers = 3;
+    let test_params = testing::storage::model::Params {
+        num_bitcoin_blocks: 10,
+        num_stacks_blocks_per_bitcoin_block: 1,
+        num_deposit_requests_per_block: 0,
+        num_withdraw_requests_per_block: 0,
+        num_signers_per_request: num_signers,
+    };
+
+    let signer_set = testing::wsts::generate_signer_set_public_keys(&mut rng, num_signers);
+    let test_data = TestData::generate(&mut rng, &signer_set, &test_params);
+    test_data.write_to(&db).await;
+
+    // Let's write the deposit request and associated trasnaction to the
+    // database. Here the deposit transaction will be confirmed on the
+    // canonical bitcoin blockchain.
+    let deposit_request: model::DepositRequest = fake::Faker.fake_with_rng(&mut rng);
+    let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
+    let txid = &deposit_request.txid;
+    let output_index = deposit_request.output_index;
+    let signer_public_key = &signer_set[0];
+
+    let tx = model::Transaction {
+        txid: deposit_request.txid.into_bytes(),
+        tx: Vec::new(),
+        tx_type: model::TransactionType::DepositRequest,
+        block_hash: chain_tip.into_bytes(),
+    };
+    let tx_ref = model::BitcoinTxRef {
+        txid: deposit_request.txid,
+        block_hash: chain_tip,
+    };
+
+    db.write_deposit_request(&deposit_request).await.unwrap();
+    db.write_transaction(&tx).await.unwrap();
+    db.write_bitcoin_transaction(&tx_ref).await.unwrap();
+
+    // Write the decision to the database
+    let mut decision: model::DepositSigner = fake::Faker.fake_with_rng(&mut rng);
+    decision.output_index = deposit_request.output_index;
+    decision.txid = deposit_request.txid;
+    decision.signer_pub_key = *signer_public_key;
+
+    db.write_deposit_signer_decision(&decision).await.unwrap();
+
+    // Okay now let's pretend that the deposit has been swept. For that we
+    // need a row in the `sweep_*` tables, and records in the `transactions`
+    // and `bitcoin_transactions` tables.
+    let mut sweep_tx: model::SweepTransaction = fake::Faker.fake_with_rng(&mut rng);
+    sweep_tx.created_at_block_hash = chain_tip;
+    sweep_tx.swept_withdrawals = Vec::new();
+    sweep_tx.swept_deposits = vec![model::SweptDeposit {
+        input_index: 1,
+        deposit_request_output_index: deposit_request.output_index,
+        deposit_request_txid: deposit_request.txid,
+    }];
+
+    let sweep_tx_model = model::Transaction {
+        tx_type: model::TransactionType::SbtcTransaction,
+        txid: sweep_tx.txid.to_byte_array(),
+        tx: Vec::new(),
+        block_hash: chain_tip.to_byte_array(),
+    };
+    let sweep_tx_ref = model::BitcoinTxRef {
+        txid: sweep_tx.txid,
+        block_hash: chain_tip,
+    };
+    db.write_transaction(&sweep_tx_model).await.unwrap();
+    db.write_bitcoin_transaction(&sweep_tx_ref).await.unwrap();
+    db.write_sweep_transaction(&sweep_tx).await.unwrap();
+
     let report = db
         .get_deposit_request_report(&chain_tip, txid, output_index, signer_public_key)
         .await
@@ -2815,30 +3445,25 @@ async fn deposit_report_with_only_deposit_request() {
     assert_eq!(report.amount, deposit_request.amount);
     assert_eq!(report_lock_time, deposit_request.lock_time);
     assert_eq!(report.max_fee, deposit_request.max_fee);
-    assert!(report.can_accept.is_none());
-    assert!(report.can_sign.is_none());
-    // The transaction is not on the canonical bitcoin blockchain, so it
-    // shows up as unconfirmed.
-    assert_eq!(report.status, DepositConfirmationStatus::Unconfirmed);
+    assert_eq!(report.can_accept, Some(decision.can_accept));
+    assert_eq!(report.can_sign, Some(decision.can_sign));
+    assert_eq!(
+        report.status,
+        DepositConfirmationStatus::Spent(sweep_tx.txid)
+    );
 
-    testing::storage::drop_db(db).await;
+    signer::testing::storage::drop_db(db).await;
 }
 
-/// Check that if the deposit has been confirmed on a block that is not on
-/// the canonical bitcoin blockchain then the deposit reports the status as
-/// unconfirmed. We also check that if this signer has voted on the request
-/// that the votes are accurately reflected in the report.
-///
-/// The difference between this test and
-/// [`deposit_report_with_only_deposit_request`] is that we write the
-/// signer decision to the database here and check that it gets reproduced
-/// in the report.
+/// Check that if the deposit has been included in a sweep transaction
+/// that gets reorged, then the deposit report states that the deposit is
+/// confirmed and not spent.
 #[cfg_attr(not(feature = "integration-tests"), ignore)]
 #[tokio::test]
-async fn deposit_report_with_deposit_request_reorged() {
+async fn deposit_report_with_deposit_request_swept_but_swept_reorged() {
     let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
     let db = testing::storage::new_test_database(db_num, true).await;
-    let mut rng = rand::rngs::StdRng::seed_from_u64(21);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(23);
 
     // We only want the blockchain to be generated
     let num_signers = 3;
@@ -2854,33 +3479,34 @@ async fn deposit_report_with_deposit_request_reorged() {
     let test_data = TestData::generate(&mut rng, &signer_set, &test_params);
     test_data.write_to(&db).await;
 
-    // Let's write the deposit request and associated transaction to our
-    // database. The deposit transaction will be confirmed, but on a block
-    // that is not on the canonical bitcoin blockchain.
+    // Let's write the deposit request and associated trasnaction to the
+    // database. Here the deposit transaction will be confirmed on the
+    // canonical bitcoin blockchain.
     let deposit_request: model::DepositRequest = fake::Faker.fake_with_rng(&mut rng);
     let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
+    let chain_tip_block = db.get_bitcoin_block(&chain_tip).await.unwrap().unwrap();
     let txid = &deposit_request.txid;
     let output_index = deposit_request.output_index;
     let signer_public_key = &signer_set[0];
 
-    let random_block: model::BitcoinBlock = fake::Faker.fake_with_rng(&mut rng);
+    // We confirm it on the parent block of the chain tip because later we
+    // change the chain tip and test certain conditions.
     let tx = model::Transaction {
         txid: deposit_request.txid.into_bytes(),
         tx: Vec::new(),
         tx_type: model::TransactionType::DepositRequest,
-        block_hash: random_block.block_hash.into_bytes(),
+        block_hash: chain_tip_block.parent_hash.into_bytes(),
     };
     let tx_ref = model::BitcoinTxRef {
         txid: deposit_request.txid,
-        block_hash: random_block.block_hash,
+        block_hash: chain_tip_block.parent_hash,
     };
 
     db.write_deposit_request(&deposit_request).await.unwrap();
-    db.write_bitcoin_block(&random_block).await.unwrap();
     db.write_transaction(&tx).await.unwrap();
     db.write_bitcoin_transaction(&tx_ref).await.unwrap();
 
-    // Time to record the signers' vote.
+    // Write the decision to the database
     let mut decision: model::DepositSigner = fake::Faker.fake_with_rng(&mut rng);
     decision.output_index = deposit_request.output_index;
     decision.txid = deposit_request.txid;
@@ -2888,6 +3514,40 @@ async fn deposit_report_with_deposit_request_reorged() {
 
     db.write_deposit_signer_decision(&decision).await.unwrap();
 
+    // Okay now let's pretend that the deposit has been swept, but the
+    // sweep gets reorged. For that we need a row in the `sweep_*` tables,
+    // and records in the `transactions` and `bitcoin_transactions` tables,
+    // but we'll use a random block that appears at the same height as the
+    // current chain tip for what confirms the sweep transaction. This way
+    // it is not on the canonical bitcoin blockchain identified by the
+    // chain tip.
+    let mut sweep_tx: model::SweepTransaction = fake::Faker.fake_with_rng(&mut rng);
+    let mut alt_chain_tip_block: model::BitcoinBlock = chain_tip_block.clone();
+    alt_chain_tip_block.block_hash = fake::Faker.fake_with_rng(&mut rng);
+
+    sweep_tx.created_at_block_hash = chain_tip;
+    sweep_tx.swept_withdrawals = Vec::new();
+    sweep_tx.swept_deposits = vec![model::SweptDeposit {
+        input_index: 1,
+        deposit_request_output_index: deposit_request.output_index,
+        deposit_request_txid: deposit_request.txid,
+    }];
+
+    let sweep_tx_model = model::Transaction {
+        tx_type: model::TransactionType::SbtcTransaction,
+        txid: sweep_tx.txid.to_byte_array(),
+        tx: Vec::new(),
+        block_hash: alt_chain_tip_block.block_hash.to_byte_array(),
+    };
+    let sweep_tx_ref = model::BitcoinTxRef {
+        txid: sweep_tx.txid,
+        block_hash: alt_chain_tip_block.block_hash,
+    };
+    db.write_bitcoin_block(&alt_chain_tip_block).await.unwrap();
+    db.write_transaction(&sweep_tx_model).await.unwrap();
+    db.write_bitcoin_transaction(&sweep_tx_ref).await.unwrap();
+    db.write_sweep_transaction(&sweep_tx).await.unwrap();
+
     let report = db
         .get_deposit_request_report(&chain_tip, txid, output_index, signer_public_key)
         .await
@@ -2901,20 +3561,45 @@ async fn deposit_report_with_deposit_request_reorged() {
     assert_eq!(report.max_fee, deposit_request.max_fee);
     assert_eq!(report.can_accept, Some(decision.can_accept));
     assert_eq!(report.can_sign, Some(decision.can_sign));
-    assert_eq!(report.status, DepositConfirmationStatus::Unconfirmed);
+
+    let confirmed_height = chain_tip_block.block_height - 1;
+    let confirmed_block_hash = chain_tip_block.parent_hash;
+    let expected_status =
+        DepositConfirmationStatus::Confirmed(confirmed_height, confirmed_block_hash);
+    assert_eq!(report.status, expected_status);
+
+    // If we use the chain tip that confirms the sweep transaction, then we
+    // see that the report tells us that it is now spent.
+    let alt_chain_tip = alt_chain_tip_block.block_hash;
+    let report = db
+        .get_deposit_request_report(&alt_chain_tip, txid, output_index, signer_public_key)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let report_lock_time = report.lock_time.to_consensus_u32();
+
+    assert_eq!(report.amount, deposit_request.amount);
+    assert_eq!(report_lock_time, deposit_request.lock_time);
+    assert_eq!(report.max_fee, deposit_request.max_fee);
+    assert_eq!(report.can_accept, Some(decision.can_accept));
+    assert_eq!(report.can_sign, Some(decision.can_sign));
+
+    let expected_status = DepositConfirmationStatus::Spent(sweep_tx.txid);
+    assert_eq!(report.status, expected_status);
 
     signer::testing::storage::drop_db(db).await;
 }
 
-/// Check that if the deposit has been included in a sweep transaction
-/// then the deposit report states that the deposit has been spent in the
-/// status.
+/// Check when we have a deposit that has been confirmed on the canonical
+/// bitcoin and hasn't been spent, that the deposit report has the
+/// appropriate "Confirmed" status.
 #[cfg_attr(not(feature = "integration-tests"), ignore)]
 #[tokio::test]
-async fn deposit_report_with_deposit_request_spent() {
+async fn deposit_report_with_deposit_request_confirmed() {
     let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
     let db = testing::storage::new_test_database(db_num, true).await;
-    let mut rng = rand::rngs::StdRng::seed_from_u64(22);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(24);
 
     // We only want the blockchain to be generated
     let num_signers = 3;
@@ -2930,9 +3615,9 @@ async fn deposit_report_with_deposit_request_spent() {
     let test_data = TestData::generate(&mut rng, &signer_set, &test_params);
     test_data.write_to(&db).await;
 
-    // Let's write the deposit request and associated trasnaction to the
-    // database. Here the deposit transaction will be confirmed on the
-    // canonical bitcoin blockchain.
+    // Let's write the deposit request and associated transaction to the
+    // database. The transaction will be on the canonical bitcoin
+    // blockchain.
     let deposit_request: model::DepositRequest = fake::Faker.fake_with_rng(&mut rng);
     let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
     let txid = &deposit_request.txid;
@@ -2954,7 +3639,7 @@ async fn deposit_report_with_deposit_request_spent() {
     db.write_transaction(&tx).await.unwrap();
     db.write_bitcoin_transaction(&tx_ref).await.unwrap();
 
-    // Write the decision to the database
+    // Write this signer's vote to the database.
     let mut decision: model::DepositSigner = fake::Faker.fake_with_rng(&mut rng);
     decision.output_index = deposit_request.output_index;
     decision.txid = deposit_request.txid;
@@ -2962,32 +3647,6 @@ async fn deposit_report_with_deposit_request_spent() {
 
     db.write_deposit_signer_decision(&decision).await.unwrap();
 
-    // Okay now let's pretend that the deposit has been swept. For that we
-    // need a row in the `sweep_*` tables, and records in the `transactions`
-    // and `bitcoin_transactions` tables.
-    let mut sweep_tx: model::SweepTransaction = fake::Faker.fake_with_rng(&mut rng);
-    sweep_tx.created_at_block_hash = chain_tip;
-    sweep_tx.swept_withdrawals = Vec::new();
-    sweep_tx.swept_deposits = vec![model::SweptDeposit {
-        input_index: 1,
-        deposit_request_output_index: deposit_request.output_index,
-        deposit_request_txid: deposit_request.txid,
-    }];
-
-    let sweep_tx_model = model::Transaction {
-        tx_type: model::TransactionType::SbtcTransaction,
-        txid: sweep_tx.txid.to_byte_array(),
-        tx: Vec::new(),
-        block_hash: chain_tip.to_byte_array(),
-    };
-    let sweep_tx_ref = model::BitcoinTxRef {
-        txid: sweep_tx.txid,
-        block_hash: chain_tip,
-    };
-    db.write_transaction(&sweep_tx_model).await.unwrap();
-    db.write_bitcoin_transaction(&sweep_tx_ref).await.unwrap();
-    db.write_sweep_transaction(&sweep_tx).await.unwrap();
-
     let report = db
         .get_deposit_request_report(&chain_tip, txid, output_index, signer_public_key)
         .await
@@ -2996,28 +3655,189 @@ async fn deposit_report_with_deposit_request_spent() {
 
     let report_lock_time = report.lock_time.to_consensus_u32();
 
+    // This is all happy path stuff, with fields filled in and a confirmed
+    // status.
     assert_eq!(report.amount, deposit_request.amount);
     assert_eq!(report_lock_time, deposit_request.lock_time);
     assert_eq!(report.max_fee, deposit_request.max_fee);
     assert_eq!(report.can_accept, Some(decision.can_accept));
     assert_eq!(report.can_sign, Some(decision.can_sign));
+
+    let block = db.get_bitcoin_block(&chain_tip).await.unwrap().unwrap();
+    let expected_status =
+        DepositConfirmationStatus::Confirmed(block.block_height, block.block_hash);
+    assert_eq!(report.status, expected_status);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+/// The following tests check the [`DbRead::get_withdrawal_request_report`]
+/// function and follow the same pattern as the deposit report tests:
+/// 1. Generate a random blockchain and write it to the database.
+/// 2. Generate a random withdrawal request and write it to the database,
+///    sometimes in a stacks block on the canonical stacks blockchain,
+///    sometimes not.
+/// 3. Maybe generate a random withdrawal vote for the current signer and
+///    store that in the database.
+/// 4. Maybe generate a sweep transaction or a rejection event and put that
+///    in our database.
+/// 5. Check that the report comes out right depending on where the various
+///    transactions are confirmed.
+
+/// Check that the report is `None` when we do not have a record of the
+/// withdrawal request, and that the request shows up as unconfirmed when
+/// it is in a stacks block that is not on the canonical stacks
+/// blockchain.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn withdrawal_report_with_withdrawal_request_on_a_fork() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(60);
+
+    // We only want the blockchain to be generated
+    let num_signers = 3;
+    let test_params = testing::storage::model::Params {
+        num_bitcoin_blocks: 10,
+        num_stacks_blocks_per_bitcoin_block: 1,
+        num_deposit_requests_per_block: 0,
+        num_withdraw_requests_per_block: 0,
+        num_signers_per_request: num_signers,
+    };
+
+    let signer_set = testing::wsts::generate_signer_set_public_keys(&mut rng, num_signers);
+    let test_data = TestData::generate(&mut rng, &signer_set, &test_params);
+    test_data.write_to(&db).await;
+
+    let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
+    let stacks_tip = db.get_stacks_chain_tip(&chain_tip).await.unwrap().unwrap();
+    let signer_public_key = &signer_set[0];
+
+    // The withdrawal request is in a stacks block that forks off of the
+    // canonical stacks blockchain, and is anchored to a bitcoin block
+    // that is not on the canonical bitcoin blockchain.
+    let fork_block = StacksBlock {
+        block_hash: fake::Faker.fake_with_rng(&mut rng),
+        block_height: stacks_tip.block_height,
+        parent_hash: stacks_tip.parent_hash,
+        bitcoin_anchor: fake::Faker.fake_with_rng(&mut rng),
+    };
+    let mut withdrawal_request: model::WithdrawalRequest = fake::Faker.fake_with_rng(&mut rng);
+    withdrawal_request.block_hash = fork_block.block_hash;
+    let id = withdrawal_request.qualified_id();
+
+    // The withdrawal request is not in our database, so we should get
+    // None here.
+    let report = db
+        .get_withdrawal_request_report(&chain_tip, &id, signer_public_key)
+        .await
+        .unwrap();
+
+    assert!(report.is_none());
+
+    db.write_stacks_block(&fork_block).await.unwrap();
+    db.write_withdrawal_request(&withdrawal_request)
+        .await
+        .unwrap();
+
+    let report = db
+        .get_withdrawal_request_report(&chain_tip, &id, signer_public_key)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(report.id, id);
+    assert_eq!(report.amount, withdrawal_request.amount);
+    assert_eq!(report.max_fee, withdrawal_request.max_fee);
     assert_eq!(
-        report.status,
-        DepositConfirmationStatus::Spent(sweep_tx.txid)
+        report.script_pubkey,
+        bitcoin::ScriptBuf::from(withdrawal_request.recipient.clone())
     );
+    assert!(report.is_accepted.is_none());
+    assert_eq!(report.status, WithdrawalRequestStatus::Unconfirmed);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+/// Check that a withdrawal request in a stacks block on the canonical
+/// stacks blockchain shows up as confirmed, along with the bitcoin block
+/// anchoring that stacks block and this signer's vote.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn withdrawal_report_with_withdrawal_request_confirmed() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(61);
+
+    // We only want the blockchain to be generated
+    let num_signers = 3;
+    let test_params = testing::storage::model::Params {
+        num_bitcoin_blocks: 10,
+        num_stacks_blocks_per_bitcoin_block: 1,
+        num_deposit_requests_per_block: 0,
+        num_withdraw_requests_per_block: 0,
+        num_signers_per_request: num_signers,
+    };
+
+    let signer_set = testing::wsts::generate_signer_set_public_keys(&mut rng, num_signers);
+    let test_data = TestData::generate(&mut rng, &signer_set, &test_params);
+    test_data.write_to(&db).await;
+
+    let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
+    let stacks_tip = db.get_stacks_chain_tip(&chain_tip).await.unwrap().unwrap();
+    let signer_public_key = &signer_set[0];
+
+    let mut withdrawal_request: model::WithdrawalRequest = fake::Faker.fake_with_rng(&mut rng);
+    withdrawal_request.block_hash = stacks_tip.block_hash;
+    let id = withdrawal_request.qualified_id();
+
+    db.write_withdrawal_request(&withdrawal_request)
+        .await
+        .unwrap();
+
+    // Write this signer's vote to the database.
+    let mut decision: WithdrawalSigner = fake::Faker.fake_with_rng(&mut rng);
+    decision.request_id = withdrawal_request.request_id;
+    decision.block_hash = withdrawal_request.block_hash;
+    decision.txid = withdrawal_request.txid;
+    decision.signer_pub_key = *signer_public_key;
+
+    db.write_withdrawal_signer_decision(&decision)
+        .await
+        .unwrap();
+
+    let report = db
+        .get_withdrawal_request_report(&chain_tip, &id, signer_public_key)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(report.id, id);
+    assert_eq!(report.amount, withdrawal_request.amount);
+    assert_eq!(report.max_fee, withdrawal_request.max_fee);
+    assert_eq!(report.is_accepted, Some(decision.is_accepted));
+
+    let anchor = db
+        .get_bitcoin_block(&stacks_tip.bitcoin_anchor)
+        .await
+        .unwrap()
+        .unwrap();
+    let expected_status =
+        WithdrawalRequestStatus::Confirmed(anchor.block_height, anchor.block_hash);
+    assert_eq!(report.status, expected_status);
 
     signer::testing::storage::drop_db(db).await;
 }
 
-/// Check that if the deposit has been included in a sweep transaction
-/// that gets reorged, then the deposit report states that the deposit is
-/// confirmed and not spent.
+/// Check that a withdrawal request that has been included in a sweep
+/// transaction shows up as fulfilled, but only when the sweep transaction
+/// is confirmed on the canonical bitcoin blockchain.
 #[cfg_attr(not(feature = "integration-tests"), ignore)]
 #[tokio::test]
-async fn deposit_report_with_deposit_request_swept_but_swept_reorged() {
+async fn withdrawal_report_with_withdrawal_request_fulfilled() {
     let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
     let db = testing::storage::new_test_database(db_num, true).await;
-    let mut rng = rand::rngs::StdRng::seed_from_u64(23);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(62);
 
     // We only want the blockchain to be generated
     let num_signers = 3;
@@ -3033,127 +3853,97 @@ async fn deposit_report_with_deposit_request_swept_but_swept_reorged() {
     let test_data = TestData::generate(&mut rng, &signer_set, &test_params);
     test_data.write_to(&db).await;
 
-    // Let's write the deposit request and associated trasnaction to the
-    // database. Here the deposit transaction will be confirmed on the
-    // canonical bitcoin blockchain.
-    let deposit_request: model::DepositRequest = fake::Faker.fake_with_rng(&mut rng);
     let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
-    let chain_tip_block = db.get_bitcoin_block(&chain_tip).await.unwrap().unwrap();
-    let txid = &deposit_request.txid;
-    let output_index = deposit_request.output_index;
+    let stacks_tip = db.get_stacks_chain_tip(&chain_tip).await.unwrap().unwrap();
     let signer_public_key = &signer_set[0];
 
-    // We confirm it on the parent block of the chain tip because later we
-    // change the chain tip and test certain conditions.
-    let tx = model::Transaction {
-        txid: deposit_request.txid.into_bytes(),
-        tx: Vec::new(),
-        tx_type: model::TransactionType::DepositRequest,
-        block_hash: chain_tip_block.parent_hash.into_bytes(),
-    };
-    let tx_ref = model::BitcoinTxRef {
-        txid: deposit_request.txid,
-        block_hash: chain_tip_block.parent_hash,
-    };
-
-    db.write_deposit_request(&deposit_request).await.unwrap();
-    db.write_transaction(&tx).await.unwrap();
-    db.write_bitcoin_transaction(&tx_ref).await.unwrap();
-
-    // Write the decision to the database
-    let mut decision: model::DepositSigner = fake::Faker.fake_with_rng(&mut rng);
-    decision.output_index = deposit_request.output_index;
-    decision.txid = deposit_request.txid;
-    decision.signer_pub_key = *signer_public_key;
-
-    db.write_deposit_signer_decision(&decision).await.unwrap();
+    let mut withdrawal_request: model::WithdrawalRequest = fake::Faker.fake_with_rng(&mut rng);
+    withdrawal_request.block_hash = stacks_tip.block_hash;
+    let id = withdrawal_request.qualified_id();
 
-    // Okay now let's pretend that the deposit has been swept, but the
-    // sweep gets reorged. For that we need a row in the `sweep_*` tables,
-    // and records in the `transactions` and `bitcoin_transactions` tables,
-    // but we'll use a random block that appears at the same height as the
-    // current chain tip for what confirms the sweep transaction. This way
-    // it is not on the canonical bitcoin blockchain identified by the
-    // chain tip.
-    let mut sweep_tx: model::SweepTransaction = fake::Faker.fake_with_rng(&mut rng);
-    let mut alt_chain_tip_block: model::BitcoinBlock = chain_tip_block.clone();
-    alt_chain_tip_block.block_hash = fake::Faker.fake_with_rng(&mut rng);
+    db.write_withdrawal_request(&withdrawal_request)
+        .await
+        .unwrap();
 
+    // Okay now let's pretend that the withdrawal has been swept out. For
+    // that we need a row in the `sweep_*` tables, and records in the
+    // `transactions` and `bitcoin_transactions` tables. We start with the
+    // sweep transaction confirmed on a block that is not on the canonical
+    // bitcoin blockchain.
+    let mut sweep_tx: SweepTransaction = fake::Faker.fake_with_rng(&mut rng);
     sweep_tx.created_at_block_hash = chain_tip;
-    sweep_tx.swept_withdrawals = Vec::new();
-    sweep_tx.swept_deposits = vec![model::SweptDeposit {
-        input_index: 1,
-        deposit_request_output_index: deposit_request.output_index,
-        deposit_request_txid: deposit_request.txid,
+    sweep_tx.swept_deposits = Vec::new();
+    sweep_tx.swept_withdrawals = vec![model::SweptWithdrawal {
+        output_index: 2,
+        withdrawal_request_id: withdrawal_request.request_id,
+        withdrawal_request_block_hash: withdrawal_request.block_hash,
     }];
 
+    let random_block: model::BitcoinBlock = fake::Faker.fake_with_rng(&mut rng);
     let sweep_tx_model = model::Transaction {
         tx_type: model::TransactionType::SbtcTransaction,
         txid: sweep_tx.txid.to_byte_array(),
         tx: Vec::new(),
-        block_hash: alt_chain_tip_block.block_hash.to_byte_array(),
+        block_hash: random_block.block_hash.to_byte_array(),
     };
     let sweep_tx_ref = model::BitcoinTxRef {
         txid: sweep_tx.txid,
-        block_hash: alt_chain_tip_block.block_hash,
+        block_hash: random_block.block_hash,
     };
-    db.write_bitcoin_block(&alt_chain_tip_block).await.unwrap();
+    db.write_bitcoin_block(&random_block).await.unwrap();
     db.write_transaction(&sweep_tx_model).await.unwrap();
     db.write_bitcoin_transaction(&sweep_tx_ref).await.unwrap();
     db.write_sweep_transaction(&sweep_tx).await.unwrap();
 
-    let report = db
-        .get_deposit_request_report(&chain_tip, txid, output_index, signer_public_key)
+    let anchor = db
+        .get_bitcoin_block(&stacks_tip.bitcoin_anchor)
         .await
         .unwrap()
         .unwrap();
 
-    let report_lock_time = report.lock_time.to_consensus_u32();
-
-    assert_eq!(report.amount, deposit_request.amount);
-    assert_eq!(report_lock_time, deposit_request.lock_time);
-    assert_eq!(report.max_fee, deposit_request.max_fee);
-    assert_eq!(report.can_accept, Some(decision.can_accept));
-    assert_eq!(report.can_sign, Some(decision.can_sign));
+    let report = db
+        .get_withdrawal_request_report(&chain_tip, &id, signer_public_key)
+        .await
+        .unwrap()
+        .unwrap();
 
-    let confirmed_height = chain_tip_block.block_height - 1;
-    let confirmed_block_hash = chain_tip_block.parent_hash;
     let expected_status =
-        DepositConfirmationStatus::Confirmed(confirmed_height, confirmed_block_hash);
+        WithdrawalRequestStatus::Confirmed(anchor.block_height, anchor.block_hash);
     assert_eq!(report.status, expected_status);
 
-    // If we use the chain tip that confirms the sweep transaction, then we
-    // see that the report tells us that it is now spent.
-    let alt_chain_tip = alt_chain_tip_block.block_hash;
+    // Now the sweep transaction is confirmed on the canonical bitcoin
+    // blockchain too.
+    let sweep_tx_ref = model::BitcoinTxRef {
+        txid: sweep_tx.txid,
+        block_hash: chain_tip,
+    };
+    db.write_bitcoin_transaction(&sweep_tx_ref).await.unwrap();
+
     let report = db
-        .get_deposit_request_report(&alt_chain_tip, txid, output_index, signer_public_key)
+        .get_withdrawal_request_report(&chain_tip, &id, signer_public_key)
         .await
         .unwrap()
         .unwrap();
 
-    let report_lock_time = report.lock_time.to_consensus_u32();
-
-    assert_eq!(report.amount, deposit_request.amount);
-    assert_eq!(report_lock_time, deposit_request.lock_time);
-    assert_eq!(report.max_fee, deposit_request.max_fee);
-    assert_eq!(report.can_accept, Some(decision.can_accept));
-    assert_eq!(report.can_sign, Some(decision.can_sign));
-
-    let expected_status = DepositConfirmationStatus::Spent(sweep_tx.txid);
-    assert_eq!(report.status, expected_status);
+    assert_eq!(report.amount, withdrawal_request.amount);
+    assert_eq!(report.max_fee, withdrawal_request.max_fee);
+    assert!(report.is_accepted.is_none());
+    assert_eq!(
+        report.status,
+        WithdrawalRequestStatus::Fulfilled(sweep_tx.txid)
+    );
 
     signer::testing::storage::drop_db(db).await;
 }
 
-/// Check when we have a deposit that has been confirmed on the canonical
-/// bitcoin and hasn't been spent, that the deposit report has the
-/// appropriate "Confirmed" status.
+/// Check that a withdrawal request shows up as rejected once there is a
+/// `withdrawal-reject` event for it on the canonical stacks blockchain.
 #[cfg_attr(not(feature = "integration-tests"), ignore)]
 #[tokio::test]
-async fn deposit_report_with_deposit_request_confirmed() {
+async fn withdrawal_report_with_withdrawal_request_rejected() {
     let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
     let db = testing::storage::new_test_database(db_num, true).await;
-    let mut rng = rand::rngs::StdRng::seed_from_u64(24);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(63);
 
     // We only want the blockchain to be generated
     let num_signers = 3;
@@ -3169,58 +3959,45 @@ async fn deposit_report_with_deposit_request_confirmed() {
     let test_data = TestData::generate(&mut rng, &signer_set, &test_params);
     test_data.write_to(&db).await;
 
-    // Let's write the deposit request and associated transaction to the
-    // database. The transaction will be on the canonical bitcoin
-    // blockchain.
-    let deposit_request: model::DepositRequest = fake::Faker.fake_with_rng(&mut rng);
     let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
-    let txid = &deposit_request.txid;
-    let output_index = deposit_request.output_index;
+    let stacks_tip = db.get_stacks_chain_tip(&chain_tip).await.unwrap().unwrap();
     let signer_public_key = &signer_set[0];
 
-    let tx = model::Transaction {
-        txid: deposit_request.txid.into_bytes(),
-        tx: Vec::new(),
-        tx_type: model::TransactionType::DepositRequest,
-        block_hash: chain_tip.into_bytes(),
-    };
-    let tx_ref = model::BitcoinTxRef {
-        txid: deposit_request.txid,
-        block_hash: chain_tip,
-    };
+    let mut withdrawal_request: model::WithdrawalRequest = fake::Faker.fake_with_rng(&mut rng);
+    withdrawal_request.block_hash = stacks_tip.block_hash;
+    let id = withdrawal_request.qualified_id();
 
-    db.write_deposit_request(&deposit_request).await.unwrap();
-    db.write_transaction(&tx).await.unwrap();
-    db.write_bitcoin_transaction(&tx_ref).await.unwrap();
+    db.write_withdrawal_request(&withdrawal_request)
+        .await
+        .unwrap();
 
-    // Write this signer's vote to the database.
-    let mut decision: model::DepositSigner = fake::Faker.fake_with_rng(&mut rng);
-    decision.output_index = deposit_request.output_index;
-    decision.txid = deposit_request.txid;
-    decision.signer_pub_key = *signer_public_key;
+    // Now we write the rejection event to a stacks block on top of the
+    // one with the withdrawal request.
+    let event_block = StacksBlock {
+        block_hash: fake::Faker.fake_with_rng(&mut rng),
+        block_height: stacks_tip.block_height + 1,
+        parent_hash: stacks_tip.block_hash,
+        bitcoin_anchor: chain_tip,
+    };
+    db.write_stacks_block(&event_block).await.unwrap();
 
-    db.write_deposit_signer_decision(&decision).await.unwrap();
+    let event = WithdrawalRejectEvent {
+        txid: fake::Faker.fake_with_rng::<StacksTxId, _>(&mut rng).into(),
+        block_id: *event_block.block_hash,
+        request_id: withdrawal_request.request_id,
+        signer_bitmap: BitArray::ZERO,
+    };
+    db.write_withdrawal_reject_event(&event).await.unwrap();
 
     let report = db
-        .get_deposit_request_report(&chain_tip, txid, output_index, signer_public_key)
+        .get_withdrawal_request_report(&chain_tip, &id, signer_public_key)
         .await
         .unwrap()
         .unwrap();
 
-    let report_lock_time = report.lock_time.to_consensus_u32();
-
-    // This is all happy path stuff, with fields filled in and a confirmed
-    // status.
-    assert_eq!(report.amount, deposit_request.amount);
-    assert_eq!(report_lock_time, deposit_request.lock_time);
-    assert_eq!(report.max_fee, deposit_request.max_fee);
-    assert_eq!(report.can_accept, Some(decision.can_accept));
-    assert_eq!(report.can_sign, Some(decision.can_sign));
-
-    let block = db.get_bitcoin_block(&chain_tip).await.unwrap().unwrap();
-    let expected_status =
-        DepositConfirmationStatus::Confirmed(block.block_height, block.block_hash);
-    assert_eq!(report.status, expected_status);
+    assert_eq!(report.amount, withdrawal_request.amount);
+    assert_eq!(report.max_fee, withdrawal_request.max_fee);
+    assert_eq!(report.status, WithdrawalRequestStatus::Rejected);
 
     signer::testing::storage::drop_db(db).await;
 }
@@ -3313,3 +4090,259 @@ async fn get_deposit_request_returns_returns_inserted_deposit_request() {
 
     signer::testing::storage::drop_db(db).await;
 }
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn sweep_magic_bytes_are_persisted_once() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+
+    assert!(db.get_sweep_magic_bytes().await.unwrap().is_empty());
+
+    // Writing the same magic bytes more than once is a no-op.
+    db.write_sweep_magic_bytes([b'T', b'3']).await.unwrap();
+    db.write_sweep_magic_bytes([b'T', b'3']).await.unwrap();
+    db.write_sweep_magic_bytes([b'X', b'2']).await.unwrap();
+
+    let mut magic_bytes = db.get_sweep_magic_bytes().await.unwrap();
+    magic_bytes.sort();
+    assert_eq!(magic_bytes, vec![[b'T', b'3'], [b'X', b'2']]);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn migration_status_lists_the_applied_migrations() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+
+    let status = db.migration_status().await.unwrap();
+    assert!(!status.is_empty());
+    assert!(status.iter().all(|(_, is_applied)| *is_applied));
+    assert!(status.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(status[0].0, "0001__create_schema.sql");
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn dkg_attempts_and_failures_are_persisted() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+
+    assert!(db.get_latest_dkg_attempt().await.unwrap().is_none());
+    assert!(db.get_dkg_failure_counts().await.unwrap().is_empty());
+
+    let mut attempt = model::DkgAttempt {
+        status: model::DkgAttemptStatus::Pending,
+        aggregate_key: None,
+        ..fake::Faker.fake_with_rng(&mut rng)
+    };
+    db.write_dkg_attempt(&attempt).await.unwrap();
+    let latest = db.get_latest_dkg_attempt().await.unwrap();
+    assert_eq!(latest.as_ref(), Some(&attempt));
+
+    // Writing the same round again updates its status.
+    attempt.status = model::DkgAttemptStatus::Failed;
+    db.write_dkg_attempt(&attempt).await.unwrap();
+    let latest = db.get_latest_dkg_attempt().await.unwrap();
+    assert_eq!(latest, Some(attempt.clone()));
+
+    // Two reporters blaming the same signer for the same round count as
+    // one failed round.
+    let offender: PublicKey = fake::Faker.fake_with_rng(&mut rng);
+    let failures: Vec<model::DkgSignerFailure> = (0..2)
+        .map(|_| model::DkgSignerFailure {
+            dkg_id: attempt.dkg_id,
+            signer_public_key: offender,
+            reported_by: fake::Faker.fake_with_rng(&mut rng),
+            reason: model::DkgFailureReason::MissingPublicShares,
+        })
+        .collect();
+    db.write_dkg_failures(&failures).await.unwrap();
+    db.write_dkg_failures(&failures).await.unwrap();
+
+    let mut expected = failures.clone();
+    expected.sort();
+    let mut stored = db.get_dkg_failures(&attempt.dkg_id).await.unwrap();
+    stored.sort();
+    assert_eq!(stored, expected);
+
+    let another_round: BitcoinTxId = fake::Faker.fake_with_rng(&mut rng);
+    let failure = model::DkgSignerFailure {
+        dkg_id: another_round,
+        ..failures[0].clone()
+    };
+    db.write_dkg_failures(&[failure]).await.unwrap();
+
+    let counts = db.get_dkg_failure_counts().await.unwrap();
+    assert_eq!(counts, BTreeMap::from([(offender, 2)]));
+
+    // Failures from before the latest successful DKG do not count.
+    let shares: EncryptedDkgShares = fake::Faker.fake_with_rng(&mut rng);
+    db.write_encrypted_dkg_shares(&shares).await.unwrap();
+    assert!(db.get_dkg_failure_counts().await.unwrap().is_empty());
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+async fn assert_signer_participation_is_summarized<S>(db: &S, rng: &mut StdRng)
+where
+    S: DbRead + DbWrite,
+{
+    use model::ParticipationOutcome::*;
+
+    assert!(db
+        .get_signer_participation_stats(10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let reliable: PublicKey = fake::Faker.fake_with_rng(rng);
+    let flaky: PublicKey = fake::Faker.fake_with_rng(rng);
+
+    // The flaky signer missed the oldest two rounds and sent invalid data
+    // in the most recent one.
+    let rounds = [
+        (Missing, Responded),
+        (Missing, Responded),
+        (Responded, Responded),
+        (Invalid, Responded),
+    ];
+    let mut records = Vec::new();
+    for (index, (flaky_outcome, reliable_outcome)) in rounds.into_iter().enumerate() {
+        let round = model::SignerParticipation {
+            round_id: [index as u8; 32],
+            round_type: model::ParticipationRoundType::Nonce,
+            ..fake::Faker.fake_with_rng(rng)
+        };
+        for (signer_public_key, outcome) in [(flaky, flaky_outcome), (reliable, reliable_outcome)] {
+            let response_time_ms = (outcome == Responded).then_some(100 * (index as i64 + 1));
+            records.push(model::SignerParticipation {
+                signer_public_key,
+                outcome,
+                response_time_ms,
+                ..round.clone()
+            });
+        }
+    }
+    db.write_signer_participation(&records[..4]).await.unwrap();
+    db.write_signer_participation(&records[4..]).await.unwrap();
+
+    let stored = db.get_signer_participation(&[3; 32]).await.unwrap();
+    assert_eq!(stored, records[6..].to_vec());
+
+    let stats = db.get_signer_participation_stats(10).await.unwrap();
+    let stats: BTreeMap<PublicKey, model::SignerParticipationStats> = stats
+        .into_iter()
+        .map(|stats| (stats.signer_public_key, stats))
+        .collect();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[&reliable].responded, 4);
+    assert_eq!(stats[&reliable].avg_response_time_ms, Some(250));
+    assert_eq!(stats[&flaky].responded, 1);
+    assert_eq!(stats[&flaky].invalid, 1);
+    assert_eq!(stats[&flaky].missing, 2);
+    assert_eq!(stats[&flaky].avg_response_time_ms, Some(300));
+
+    // Only the most recent rounds of each signer are summarized.
+    let stats = db.get_signer_participation_stats(2).await.unwrap();
+    let flaky_stats = stats
+        .iter()
+        .find(|stats| stats.signer_public_key == flaky)
+        .unwrap();
+    assert_eq!(flaky_stats.responded, 1);
+    assert_eq!(flaky_stats.invalid, 1);
+    assert_eq!(flaky_stats.missing, 0);
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn signer_participation_is_persisted_and_summarized() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(52);
+
+    assert_signer_participation_is_summarized(&db, &mut rng).await;
+    let in_memory = storage::in_memory::Store::new_shared();
+    assert_signer_participation_is_summarized(&in_memory, &mut rng).await;
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+/// Check that the Emily deposit sync height starts out unset and that
+/// writing it replaces the previous value.
+async fn assert_emily_deposit_sync_height_is_replaced<S>(db: &S)
+where
+    S: DbRead + DbWrite,
+{
+    assert_eq!(db.get_emily_deposit_sync_height().await.unwrap(), None);
+
+    db.set_emily_deposit_sync_height(12).await.unwrap();
+    assert_eq!(db.get_emily_deposit_sync_height().await.unwrap(), Some(12));
+
+    db.set_emily_deposit_sync_height(7).await.unwrap();
+    assert_eq!(db.get_emily_deposit_sync_height().await.unwrap(), Some(7));
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn emily_deposit_sync_height_is_persisted() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+
+    assert_emily_deposit_sync_height_is_replaced(&db).await;
+    let in_memory = storage::in_memory::Store::new_shared();
+    assert_emily_deposit_sync_height_is_replaced(&in_memory).await;
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+/// Check that the Emily deposit sync retries are only written once and
+/// that they can be deleted again.
+async fn assert_emily_deposit_sync_retries_are_written_and_deleted<S>(db: &S, rng: &mut StdRng)
+where
+    S: DbRead + DbWrite,
+{
+    assert!(db
+        .get_emily_deposit_sync_retries()
+        .await
+        .unwrap()
+        .is_empty());
+
+    let retry1: model::EmilyDepositSyncRetry = fake::Faker.fake_with_rng(rng);
+    let retry2: model::EmilyDepositSyncRetry = fake::Faker.fake_with_rng(rng);
+    db.write_emily_deposit_sync_retry(&retry1).await.unwrap();
+    db.write_emily_deposit_sync_retry(&retry2).await.unwrap();
+    // Writing the same deposit request again does not add another row.
+    db.write_emily_deposit_sync_retry(&retry1).await.unwrap();
+
+    let mut retries = db.get_emily_deposit_sync_retries().await.unwrap();
+    retries.sort();
+    let mut expected = vec![retry1.clone(), retry2.clone()];
+    expected.sort();
+    assert_eq!(retries, expected);
+
+    db.delete_emily_deposit_sync_retry(&retry1.txid, retry1.output_index)
+        .await
+        .unwrap();
+    let retries = db.get_emily_deposit_sync_retries().await.unwrap();
+    assert_eq!(retries, vec![retry2]);
+}
+
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn emily_deposit_sync_retries_are_persisted() {
+    let db_num = testing::storage::DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = StdRng::seed_from_u64(51);
+
+    assert_emily_deposit_sync_retries_are_written_and_deleted(&db, &mut rng).await;
+    let in_memory = storage::in_memory::Store::new_shared();
+    assert_emily_deposit_sync_retries_are_written_and_deleted(&in_memory, &mut rng).await;
+
+    signer::testing::storage::drop_db(db).await;
+}
@@ -1,4 +1,5 @@
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use emily_client::apis::deposit_api;
 use emily_client::apis::testing_api;
@@ -176,7 +177,9 @@ async fn handle_pending_deposit_request_address_script_pub_key() {
         context: ctx.clone(),
         context_window: 10000,
         blocklist_checker: Some(()),
-        signer_private_key: setup.aggregated_signer.keypair.secret_key().into(),
+        signer_key: Arc::new(PrivateKey::from(
+            setup.aggregated_signer.keypair.secret_key(),
+        )),
     };
 
     // We need this so that there is a live "network". Otherwise,
@@ -264,7 +267,7 @@ async fn handle_pending_deposit_request_not_in_signing_set() {
         blocklist_checker: Some(()),
         // We generate a new private key here so that we know (with very
         // high probability) that this signer is not in the signer set.
-        signer_private_key: PrivateKey::new(&mut rng),
+        signer_key: Arc::new(PrivateKey::new(&mut rng)),
     };
 
     // We need this so that there is a live "network". Otherwise,
@@ -342,7 +345,7 @@ async fn persist_received_deposit_decision_fetches_missing_deposit_requests() {
         context: ctx.clone(),
         context_window: 10000,
         blocklist_checker: Some(()),
-        signer_private_key: PrivateKey::new(&mut rng),
+        signer_key: Arc::new(PrivateKey::new(&mut rng)),
     };
     let txid = setup.deposit_request.outpoint.txid.into();
     let output_index = setup.deposit_request.outpoint.vout;
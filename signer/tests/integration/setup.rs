@@ -396,6 +396,7 @@ pub async fn backfill_bitcoin_blocks(db: &PgStore, rpc: &Client, chain_tip: &bit
             block_hash: block_header.hash.into(),
             block_height: block_header.height as u64,
             parent_hash: parent_header_hash.into(),
+            median_time_past: block_header.median_time.unwrap_or_default() as u64,
         };
 
         db.write_bitcoin_block(&bitcoin_block).await.unwrap();
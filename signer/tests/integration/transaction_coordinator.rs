@@ -553,6 +553,7 @@ async fn process_complete_deposit() {
         context: context.clone(),
         network: network.connect(),
         private_key,
+        signer_key: Arc::new(private_key),
         context_window,
         threshold: signing_threshold as u16,
         signing_round_max_duration: Duration::from_secs(10),
@@ -701,7 +702,7 @@ async fn deploy_smart_contracts_coordinator<F>(
     // Note that cloning the context will `Arc::clone` the stacks client, so we are
     // instantiating a new one instead
     let mut tx_coordinator_context = TestContext::new(
-        context.config().clone(),
+        (*context.config()).clone(),
         context.storage.clone(),
         context.bitcoin_client.clone(),
         WrappedMock::default(),
@@ -720,6 +721,7 @@ async fn deploy_smart_contracts_coordinator<F>(
         context: tx_coordinator_context.clone(),
         network: network.connect(),
         private_key,
+        signer_key: Arc::new(private_key),
         context_window,
         threshold: signing_threshold as u16,
         signing_round_max_duration: Duration::from_secs(10),
@@ -816,7 +818,8 @@ async fn get_signer_public_keys_and_aggregate_key_falls_back() {
         network: network.connect(),
         context: ctx.clone(),
         context_window: 10000,
-        private_key: ctx.config().signer.private_key,
+        private_key: ctx.config().signer.private_key.unwrap(),
+        signer_key: ctx.config().signer.key_backend().unwrap(),
         signing_round_max_duration: Duration::from_secs(10),
         bitcoin_presign_request_max_duration: Duration::from_secs(10),
         threshold: 2,
@@ -1029,6 +1032,7 @@ async fn run_dkg_from_scratch() {
             context: ctx.clone(),
             context_window: 10000,
             private_key: kp.secret_key().into(),
+            signer_key: Arc::new(PrivateKey::from(kp.secret_key())),
             signing_round_max_duration: Duration::from_secs(10),
             bitcoin_presign_request_max_duration: Duration::from_secs(10),
             threshold: ctx.config().signer.bootstrap_signatures_required,
@@ -1046,8 +1050,10 @@ async fn run_dkg_from_scratch() {
             context_window: 10000,
             wsts_state_machines: HashMap::new(),
             signer_private_key: kp.secret_key().into(),
+            signer_key: Arc::new(PrivateKey::from(kp.secret_key())),
             rng: rand::rngs::OsRng,
             dkg_begin_pause: None,
+            bitcoin_presign_package: Vec::new(),
         });
 
     // We only proceed with the test after all processes have started, and
@@ -1230,6 +1236,15 @@ async fn sign_bitcoin_transaction() {
         let broadcast_stacks_tx = broadcast_stacks_tx.clone();
 
         ctx.with_stacks_client(|client| {
+            // The signers have not rotated keys according to the
+            // `sbtc-registry` contract.
+            client
+                .expect_get_current_signer_set()
+                .returning(|_| Box::pin(std::future::ready(Ok(Vec::new()))));
+            client
+                .expect_get_current_signature_threshold()
+                .returning(|_| Box::pin(std::future::ready(Ok(None))));
+
             client.expect_get_tenure_info().returning(move || {
                 let response = Ok(RPCGetTenureInfo {
                     consensus_hash: ConsensusHash([0; 20]),
@@ -1343,6 +1358,7 @@ async fn sign_bitcoin_transaction() {
             context: ctx.clone(),
             context_window: 10000,
             private_key: kp.secret_key().into(),
+            signer_key: Arc::new(PrivateKey::from(kp.secret_key())),
             signing_round_max_duration: Duration::from_secs(10),
             bitcoin_presign_request_max_duration: Duration::from_secs(10),
             threshold: ctx.config().signer.bootstrap_signatures_required,
@@ -1363,8 +1379,10 @@ async fn sign_bitcoin_transaction() {
             context_window: 10000,
             wsts_state_machines: HashMap::new(),
             signer_private_key: kp.secret_key().into(),
+            signer_key: Arc::new(PrivateKey::from(kp.secret_key())),
             rng: rand::rngs::OsRng,
             dkg_begin_pause: None,
+            bitcoin_presign_package: Vec::new(),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -1639,6 +1657,15 @@ async fn skip_smart_contract_deployment_and_key_rotation_if_up_to_date() {
     for (ctx, db, _, _) in signers.iter_mut() {
         let db = db.clone();
         ctx.with_stacks_client(|client| {
+            // The signers have not rotated keys according to the
+            // `sbtc-registry` contract.
+            client
+                .expect_get_current_signer_set()
+                .returning(|_| Box::pin(std::future::ready(Ok(Vec::new()))));
+            client
+                .expect_get_current_signature_threshold()
+                .returning(|_| Box::pin(std::future::ready(Ok(None))));
+
             client.expect_get_tenure_info().returning(move || {
                 let response = Ok(RPCGetTenureInfo {
                     consensus_hash: ConsensusHash([0; 20]),
@@ -1742,6 +1769,7 @@ async fn skip_smart_contract_deployment_and_key_rotation_if_up_to_date() {
             context: ctx.clone(),
             context_window: 10000,
             private_key: kp.secret_key().into(),
+            signer_key: Arc::new(PrivateKey::from(kp.secret_key())),
             signing_round_max_duration: Duration::from_secs(10),
             bitcoin_presign_request_max_duration: Duration::from_secs(10),
             threshold: ctx.config().signer.bootstrap_signatures_required,
@@ -1762,8 +1790,10 @@ async fn skip_smart_contract_deployment_and_key_rotation_if_up_to_date() {
             context_window: 10000,
             wsts_state_machines: HashMap::new(),
             signer_private_key: kp.secret_key().into(),
+            signer_key: Arc::new(PrivateKey::from(kp.secret_key())),
             rng: rand::rngs::OsRng,
             dkg_begin_pause: None,
+            bitcoin_presign_package: Vec::new(),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -1934,9 +1964,11 @@ async fn test_get_btc_state_with_no_available_sweep_transactions() {
         })
         .await;
 
+    let private_key = PrivateKey::new(&mut rng);
     let mut coord = TxCoordinatorEventLoop {
         context,
-        private_key: PrivateKey::new(&mut rng),
+        private_key,
+        signer_key: Arc::new(private_key),
         network: network.spawn(),
         threshold: 5,
         context_window: 5,
@@ -1962,6 +1994,8 @@ async fn test_get_btc_state_with_no_available_sweep_transactions() {
         block_height: 1,
         block_hash: Faker.fake_with_rng(&mut rng),
         parent_hash: Faker.fake_with_rng(&mut rng),
+
+        median_time_past: 0,
     };
 
     // Create a Bitcoin transaction simulating holding a simulated signer
@@ -2071,9 +2105,11 @@ async fn test_get_btc_state_with_available_sweep_transactions_and_rbf() {
         .build();
     let network = SignerNetwork::single(&context);
 
+    let private_key = PrivateKey::new(&mut rng);
     let mut coord = TxCoordinatorEventLoop {
         context,
-        private_key: PrivateKey::new(&mut rng),
+        private_key,
+        signer_key: Arc::new(private_key),
         network: network.spawn(),
         threshold: 5,
         context_window: 5,
@@ -2127,6 +2163,7 @@ async fn test_get_btc_state_with_available_sweep_transactions_and_rbf() {
         block_height: 1,
         block_hash: signer_utxo_block_hash.into(),
         parent_hash: BlockHash::all_zeros().into(),
+        median_time_past: 0,
     })
     .await
     .unwrap();
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use fake::Fake as _;
@@ -131,7 +132,7 @@ fn sweep_transaction_info<R: rand::RngCore>(
 
 #[ignore = "we have a test for this"]
 #[tokio::test]
-async fn should_respond_to_bitcoin_transaction_sign_request() {
+async fn should_not_ack_unknown_bitcoin_transaction_sign_request() {
     let num_signers = 3;
     let signing_threshold = 2;
 
@@ -140,7 +141,7 @@ async fn should_respond_to_bitcoin_transaction_sign_request() {
     // databases later.
     test_environment(db.clone(), signing_threshold, num_signers)
         .await
-        .assert_should_respond_to_bitcoin_transaction_sign_requests()
+        .assert_should_not_ack_unknown_bitcoin_transaction_sign_requests()
         .await;
 
     // Now drop the database that we just created.
@@ -197,7 +198,7 @@ async fn should_store_sweep_transaction_info_from_other_signers() {
             .with_storage(db.clone())
             .with_mocked_clients()
             .modify_settings(|settings| {
-                settings.signer.private_key = private_key;
+                settings.signer.private_key = Some(private_key);
                 settings.signer.bootstrap_signing_set = signer_key_pairs
                     .iter()
                     .map(|kp| kp.public_key().into())
@@ -213,9 +214,11 @@ async fn should_store_sweep_transaction_info_from_other_signers() {
             context_window: 10000,
             wsts_state_machines: HashMap::new(),
             signer_private_key: private_key,
+            signer_key: Arc::new(private_key),
             threshold: 2,
             rng: rand::rngs::StdRng::seed_from_u64(51),
             dkg_begin_pause: None,
+            bitcoin_presign_package: Vec::new(),
         };
 
         contexts.push((ctx, db, net));
@@ -258,6 +261,7 @@ async fn should_store_sweep_transaction_info_from_other_signers() {
                 pk,
                 &bitcoin_chain_tip,
                 &signer_set_pubkeys,
+                0,
             )
         })
         .expect("could not determine coordinator");
@@ -361,10 +365,12 @@ async fn get_signer_public_keys_and_aggregate_key_falls_back() {
         context: ctx.clone(),
         context_window: 10000,
         wsts_state_machines: HashMap::new(),
-        signer_private_key: ctx.config().signer.private_key,
+        signer_private_key: ctx.config().signer.private_key.unwrap(),
+        signer_key: ctx.config().signer.key_backend().unwrap(),
         threshold: 2,
         rng: rand::rngs::StdRng::seed_from_u64(51),
         dkg_begin_pause: None,
+        bitcoin_presign_package: Vec::new(),
     };
 
     // We need stacks blocks for the rotate-keys transactions.
@@ -473,9 +479,13 @@ async fn signing_set_validation_check_for_stacks_transactions() {
         context_window: 10000,
         wsts_state_machines: HashMap::new(),
         signer_private_key: setup.aggregated_signer.keypair.secret_key().into(),
+        signer_key: Arc::new(PrivateKey::from(
+            setup.aggregated_signer.keypair.secret_key(),
+        )),
         threshold: 2,
         rng: rand::rngs::StdRng::seed_from_u64(51),
         dkg_begin_pause: None,
+        bitcoin_presign_package: Vec::new(),
     };
 
     // Let's create a proper sign request.
@@ -501,7 +511,9 @@ async fn signing_set_validation_check_for_stacks_transactions() {
 
     // Now we make sure that the current signer is not in the current
     // signing set.
-    tx_signer.signer_private_key = PrivateKey::new(&mut rng);
+    let private_key = PrivateKey::new(&mut rng);
+    tx_signer.signer_private_key = private_key;
+    tx_signer.signer_key = Arc::new(private_key);
 
     // Okay now that we have changed the fact that we are not in the
     // signing set, we should get an error now.
@@ -560,9 +572,13 @@ pub async fn assert_should_be_able_to_handle_sbtc_requests() {
         context_window: 10000,
         wsts_state_machines: HashMap::new(),
         signer_private_key: setup.aggregated_signer.keypair.secret_key().into(),
+        signer_key: Arc::new(PrivateKey::from(
+            setup.aggregated_signer.keypair.secret_key(),
+        )),
         threshold: 2,
         rng: rand::rngs::StdRng::seed_from_u64(51),
         dkg_begin_pause: None,
+        bitcoin_presign_package: Vec::new(),
     };
 
     let sbtc_requests: TxRequestIds = TxRequestIds {
@@ -7,7 +7,9 @@ use sbtc::testing::regtest;
 use signer::error::Error;
 use signer::stacks::contracts::AcceptWithdrawalV1;
 use signer::stacks::contracts::AsContractCall as _;
+use signer::stacks::contracts::CompleteWithdrawalsV1;
 use signer::stacks::contracts::ReqContext;
+use signer::stacks::contracts::WithdrawalCompletion;
 use signer::stacks::contracts::WithdrawalErrorMsg;
 use signer::storage::model::BitcoinBlockRef;
 use signer::storage::model::BitcoinTxId;
@@ -54,6 +56,8 @@ fn make_withdrawal_accept(data: &TestSweepSetup) -> (AcceptWithdrawalV1, ReqCont
         sweep_block_hash: data.sweep_block_hash.into(),
         // This must be the height of the above block.
         sweep_block_height: data.sweep_block_height,
+        // This must be the txid of the above outpoint.
+        sweep_txid: data.sweep_tx_info.txid.into(),
     };
 
     // This is what the current signer thinks is the state of things.
@@ -800,3 +804,82 @@ async fn accept_withdrawal_validation_withdrawal_invalid_sweep() {
 
     testing::storage::drop_db(db).await;
 }
+
+/// For this test we check that the
+/// `CompleteWithdrawalsV1::remove_invalid_withdrawals` function leaves out
+/// the bundled withdrawals that fail validation, so that the remaining
+/// withdrawals in the batch pass validation.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn complete_withdrawals_remove_invalid_withdrawal_from_batch() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: this is all normal happy path things that need to happen
+    // in order to pass validation of an accept-withdrawal transaction.
+    setup.store_sweep_tx(&db).await;
+    setup.store_dkg_shares(&db).await;
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Different: the batch includes a withdrawal with a request_id that
+    // does not exist in our database, next to a valid one.
+    let (accept_withdrawal_tx, req_ctx) = make_withdrawal_accept(&setup);
+    let mut invalid_withdrawal_tx = accept_withdrawal_tx.clone();
+    invalid_withdrawal_tx.request_id = u64::MAX;
+    let mut complete_withdrawals_tx = CompleteWithdrawalsV1 {
+        withdrawals: vec![
+            WithdrawalCompletion::Accept(invalid_withdrawal_tx.clone()),
+            WithdrawalCompletion::Accept(accept_withdrawal_tx.clone()),
+        ],
+        deployer: StacksAddress::burn_address(false),
+    };
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let invalid = complete_withdrawals_tx
+        .remove_invalid_withdrawals(&ctx, &req_ctx)
+        .await
+        .unwrap();
+
+    assert_eq!(invalid.len(), 1);
+    let (withdrawal, error) = &invalid[0];
+    assert_eq!(
+        withdrawal,
+        &WithdrawalCompletion::Accept(invalid_withdrawal_tx)
+    );
+    match error {
+        Error::WithdrawalAcceptValidation(ref err) => {
+            assert_eq!(err.error, WithdrawalErrorMsg::RequestMissing)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    // The valid withdrawal remains, and the batch now passes validation.
+    assert_eq!(
+        complete_withdrawals_tx.withdrawals,
+        vec![WithdrawalCompletion::Accept(accept_withdrawal_tx)]
+    );
+    complete_withdrawals_tx
+        .validate(&ctx, &req_ctx)
+        .await
+        .unwrap();
+
+    testing::storage::drop_db(db).await;
+}
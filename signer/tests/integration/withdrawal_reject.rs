@@ -0,0 +1,523 @@
+use std::sync::atomic::Ordering;
+
+use blockstack_lib::types::chainstate::StacksAddress;
+use rand::rngs::OsRng;
+use sbtc::testing::regtest;
+use signer::error::Error;
+use signer::stacks::contracts::AsContractCall as _;
+use signer::stacks::contracts::RejectWithdrawalV1;
+use signer::stacks::contracts::ReqContext;
+use signer::stacks::contracts::WithdrawalRejectErrorMsg;
+use signer::storage::model::BitcoinBlockHash;
+use signer::storage::model::BitcoinBlockRef;
+use signer::storage::DbRead as _;
+use signer::storage::DbWrite as _;
+use signer::testing;
+use signer::WITHDRAWAL_BLOCKS_EXPIRY;
+
+use fake::Fake;
+use rand::SeedableRng;
+use signer::testing::context::*;
+
+use crate::setup::backfill_bitcoin_blocks;
+use crate::setup::fill_signers_utxo;
+use crate::setup::TestSweepSetup;
+use crate::DATABASE_NUM;
+
+/// Create a "proper" [`RejectWithdrawalV1`] object and context with the
+/// given information. If the information here is correct then the returned
+/// [`RejectWithdrawalV1`] object will pass validation with the given
+/// context, so long as the signers have rejected the withdrawal request
+/// or the request has expired.
+fn make_withdrawal_reject(data: &TestSweepSetup) -> (RejectWithdrawalV1, ReqContext) {
+    let reject_withdrawal_tx = RejectWithdrawalV1 {
+        // This points to the withdrawal request transaction.
+        request_id: data.withdrawal_request.request_id,
+        // This must match how the signers voted.
+        signer_bitmap: data.withdrawal_request.signer_bitmap,
+        // The deployer must match what is in the signers' context.
+        deployer: StacksAddress::burn_address(false),
+    };
+
+    // This is what the current signer thinks is the state of things.
+    let req_ctx = ReqContext {
+        chain_tip: BitcoinBlockRef {
+            block_hash: data.sweep_block_hash.into(),
+            block_height: data.sweep_block_height,
+        },
+        // This value means that the signer will go back 20 blocks when
+        // looking for unfulfilled withdrawal requests.
+        context_window: 20,
+        // The value here doesn't matter.
+        origin: fake::Faker.fake_with_rng(&mut OsRng),
+        // This is the aggregate key of the signing set that voted on the
+        // withdrawal request.
+        aggregate_key: data.aggregated_signer.keypair.public_key().into(),
+        // This value affects whether a withdrawal request can still be
+        // accepted. A signer will only sign a rejection if there are not
+        // enough signers left who could accept the request.
+        signatures_required: 2,
+        // This is who the current signer thinks deployed the sBTC
+        // contracts.
+        deployer: StacksAddress::burn_address(false),
+    };
+
+    (reject_withdrawal_tx, req_ctx)
+}
+
+/// Update the signer bitmap in the withdrawal request so that all but one
+/// of the signers voted against the withdrawal request. With two
+/// signatures required, such a request can never be accepted.
+fn set_rejecting_votes(setup: &mut TestSweepSetup) {
+    let num_signers = setup.signer_keys.len();
+    for index in 0..num_signers - 1 {
+        setup.withdrawal_request.signer_bitmap.set(index, true);
+    }
+}
+
+/// For this test we check that the `RejectWithdrawalV1::validate` function
+/// returns okay when enough signers have rejected the withdrawal request.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn reject_withdrawal_validation_happy_path() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: we need to store a row in the dkg_shares table so that we
+    // have a record of the signing set.
+    setup.store_dkg_shares(&db).await;
+
+    // Normal: the request and how the signers voted needs to be added to
+    // the database. Here the bitmap in the withdrawal request object
+    // corresponds to how the signers voted, and enough of them voted
+    // against the request for it to be rejected.
+    set_rejecting_votes(&mut setup);
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Generate the transaction and corresponding request context.
+    let (reject_withdrawal_tx, req_ctx) = make_withdrawal_reject(&setup);
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    reject_withdrawal_tx.validate(&ctx, &req_ctx).await.unwrap();
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `RejectWithdrawalV1::validate` function
+/// returns okay when the withdrawal request has expired, even if the
+/// signers voted to accept it.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn reject_withdrawal_validation_expired_request() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: we need to store a row in the dkg_shares table so that we
+    // have a record of the signing set.
+    setup.store_dkg_shares(&db).await;
+
+    // Different: the signers all voted to accept the withdrawal request.
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Generate the transaction and corresponding request context.
+    let (reject_withdrawal_tx, mut req_ctx) = make_withdrawal_reject(&setup);
+    // Different: the chain tip is far enough ahead of the bitcoin block
+    // anchoring the withdrawal request for the request to have expired.
+    req_ctx.chain_tip.block_height += WITHDRAWAL_BLOCKS_EXPIRY;
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    reject_withdrawal_tx.validate(&ctx, &req_ctx).await.unwrap();
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `RejectWithdrawalV1::validate` function
+/// returns a withdrawal reject validation error with a
+/// RequestNotRejectable message when the request has not expired and the
+/// signers voted to accept it.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn reject_withdrawal_validation_request_not_rejectable() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: we need to store a row in the dkg_shares table so that we
+    // have a record of the signing set.
+    setup.store_dkg_shares(&db).await;
+
+    // Different: the signers all voted to accept the withdrawal request,
+    // and the request has not expired.
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Generate the transaction and corresponding request context.
+    let (reject_withdrawal_tx, mut req_ctx) = make_withdrawal_reject(&setup);
+    // This is the last block before the request expires.
+    req_ctx.chain_tip.block_height += WITHDRAWAL_BLOCKS_EXPIRY - 1;
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let validation_result = reject_withdrawal_tx.validate(&ctx, &req_ctx).await;
+    match validation_result.unwrap_err() {
+        Error::WithdrawalRejectValidation(ref err) => {
+            assert_eq!(err.error, WithdrawalRejectErrorMsg::RequestNotRejectable)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `RejectWithdrawalV1::validate` function
+/// returns a withdrawal reject validation error with a DeployerMismatch
+/// message when the deployer doesn't match but everything else is okay.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn reject_withdrawal_validation_deployer_mismatch() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: we need to store a row in the dkg_shares table so that we
+    // have a record of the signing set.
+    setup.store_dkg_shares(&db).await;
+
+    // Normal: the request and how the signers voted needs to be added to
+    // the database. Here the bitmap in the withdrawal request object
+    // corresponds to how the signers voted, and enough of them voted
+    // against the request for it to be rejected.
+    set_rejecting_votes(&mut setup);
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Generate the transaction and corresponding request context.
+    let (mut reject_withdrawal_tx, mut req_ctx) = make_withdrawal_reject(&setup);
+    // Different: Okay, let's make sure the deployers do not match.
+    reject_withdrawal_tx.deployer = StacksAddress::p2pkh(false, &setup.signer_keys[0].into());
+    req_ctx.deployer = StacksAddress::p2pkh(false, &setup.signer_keys[1].into());
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let validation_result = reject_withdrawal_tx.validate(&ctx, &req_ctx).await;
+    match validation_result.unwrap_err() {
+        Error::WithdrawalRejectValidation(ref err) => {
+            assert_eq!(err.error, WithdrawalRejectErrorMsg::DeployerMismatch)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `RejectWithdrawalV1::validate` function
+/// returns a withdrawal reject validation error with a RequestMissing
+/// message when the signer does not have a record of the withdrawal
+/// request.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn reject_withdrawal_validation_missing_withdrawal_request() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: we need to store a row in the dkg_shares table so that we
+    // have a record of the signing set.
+    setup.store_dkg_shares(&db).await;
+
+    // Normal: the request and how the signers voted needs to be added to
+    // the database. Here the bitmap in the withdrawal request object
+    // corresponds to how the signers voted, and enough of them voted
+    // against the request for it to be rejected.
+    set_rejecting_votes(&mut setup);
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Generate the transaction and corresponding request context.
+    let (mut reject_withdrawal_tx, req_ctx) = make_withdrawal_reject(&setup);
+    // Different: Let's use a request_id that does not exist in our
+    // database. In these tests, the withdrawal id starts at 0 and
+    // increments by 1 for each withdrawal request generated.
+    reject_withdrawal_tx.request_id = u64::MAX;
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let validation_result = reject_withdrawal_tx.validate(&ctx, &req_ctx).await;
+    match validation_result.unwrap_err() {
+        Error::WithdrawalRejectValidation(ref err) => {
+            assert_eq!(err.error, WithdrawalRejectErrorMsg::RequestMissing)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `RejectWithdrawalV1::validate` function
+/// returns a withdrawal reject validation error with a BitmapMismatch
+/// message when the bitmap in the transaction does not match how the
+/// signers voted.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn reject_withdrawal_validation_bitmap_mismatch() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: we need to store a row in the dkg_shares table so that we
+    // have a record of the signing set.
+    setup.store_dkg_shares(&db).await;
+
+    // Normal: the request and how the signers voted needs to be added to
+    // the database. Here the bitmap in the withdrawal request object
+    // corresponds to how the signers voted, and enough of them voted
+    // against the request for it to be rejected.
+    set_rejecting_votes(&mut setup);
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Generate the transaction and corresponding request context.
+    let (mut reject_withdrawal_tx, req_ctx) = make_withdrawal_reject(&setup);
+    // Different: the bitmap in the transaction claims that every signer
+    // voted against the request, which does not match our records.
+    reject_withdrawal_tx.signer_bitmap.fill(true);
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let validation_result = reject_withdrawal_tx.validate(&ctx, &req_ctx).await;
+    match validation_result.unwrap_err() {
+        Error::WithdrawalRejectValidation(ref err) => {
+            assert_eq!(err.error, WithdrawalRejectErrorMsg::BitmapMismatch)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `RejectWithdrawalV1::validate` function
+/// returns a withdrawal reject validation error with a RequestFulfilled
+/// message when a sweep transaction fulfilling the request has been
+/// confirmed on the canonical bitcoin blockchain.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn reject_withdrawal_validation_request_fulfilled() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: we need to store a row in the dkg_shares table so that we
+    // have a record of the signing set.
+    setup.store_dkg_shares(&db).await;
+
+    // Normal: the request and how the signers voted needs to be added to
+    // the database. Here the bitmap in the withdrawal request object
+    // corresponds to how the signers voted, and enough of them voted
+    // against the request for it to be rejected.
+    set_rejecting_votes(&mut setup);
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Different: the sweep transaction, which fulfills the withdrawal
+    // request, has been confirmed.
+    setup.store_sweep_transactions(&db).await;
+    setup.store_sweep_tx(&db).await;
+
+    // Generate the transaction and corresponding request context.
+    let (reject_withdrawal_tx, req_ctx) = make_withdrawal_reject(&setup);
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let validation_result = reject_withdrawal_tx.validate(&ctx, &req_ctx).await;
+    match validation_result.unwrap_err() {
+        Error::WithdrawalRejectValidation(ref err) => {
+            assert_eq!(err.error, WithdrawalRejectErrorMsg::RequestFulfilled)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    testing::storage::drop_db(db).await;
+}
+
+/// For this test we check that the `RejectWithdrawalV1::validate` function
+/// returns a withdrawal reject validation error with a
+/// RequestBeingFulfilled message when a sweep transaction fulfilling the
+/// request is in the mempool.
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+#[tokio::test]
+async fn reject_withdrawal_validation_request_being_fulfilled() {
+    // Normal: this generates the blockchain as well as a transaction
+    // sweeping out the funds for a withdrawal request.
+    let db_num = DATABASE_NUM.fetch_add(1, Ordering::SeqCst);
+    let db = testing::storage::new_test_database(db_num, true).await;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let mut setup = TestSweepSetup::new_setup(&rpc, &faucet, 1_000_000, &mut rng);
+
+    // Normal: the signer follows the bitcoin blockchain and event observer
+    // should be getting new block events from bitcoin-core. We haven't
+    // hooked up our block observer, so we need to manually update the
+    // database with new bitcoin block headers.
+    backfill_bitcoin_blocks(&db, rpc, &setup.sweep_block_hash).await;
+
+    // Normal: we need to store a row in the dkg_shares table so that we
+    // have a record of the signing set.
+    setup.store_dkg_shares(&db).await;
+
+    // Normal: the request and how the signers voted needs to be added to
+    // the database. Here the bitmap in the withdrawal request object
+    // corresponds to how the signers voted, and enough of them voted
+    // against the request for it to be rejected.
+    set_rejecting_votes(&mut setup);
+    setup.store_withdrawal_request(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    // Different: the signers have a UTXO, and a sweep transaction that
+    // spends it and fulfills the withdrawal request is in the mempool.
+    let chain_tip: BitcoinBlockHash = setup.sweep_block_hash.into();
+    let bitcoin_block = db.get_bitcoin_block(&chain_tip).await.unwrap().unwrap();
+    let aggregate_key = setup.aggregated_signer.keypair.public_key().into();
+    fill_signers_utxo(&db, bitcoin_block, &aggregate_key, &mut rng).await;
+    let signer_utxo = db.get_signer_utxo(&chain_tip, 20).await.unwrap().unwrap();
+
+    // Validation only asks bitcoin-core whether the sweep transaction is
+    // in its mempool, so any unconfirmed transaction will do for the
+    // sweep's txid.
+    let mempool_outpoint = faucet.send_to(10_000, &setup.aggregated_signer.address);
+    let mut sweep = setup.sweep_transactions.pop().unwrap();
+    sweep.txid = mempool_outpoint.txid.into();
+    sweep.signer_prevout_txid = signer_utxo.outpoint.txid.into();
+    sweep.signer_prevout_output_index = signer_utxo.outpoint.vout;
+    db.write_sweep_transaction(&sweep).await.unwrap();
+
+    // Generate the transaction and corresponding request context.
+    let (reject_withdrawal_tx, req_ctx) = make_withdrawal_reject(&setup);
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_first_bitcoin_core_client()
+        .with_mocked_stacks_client()
+        .with_mocked_emily_client()
+        .build();
+
+    let validation_result = reject_withdrawal_tx.validate(&ctx, &req_ctx).await;
+    match validation_result.unwrap_err() {
+        Error::WithdrawalRejectValidation(ref err) => {
+            assert_eq!(err.error, WithdrawalRejectErrorMsg::RequestBeingFulfilled)
+        }
+        err => panic!("unexpected error during validation {err}"),
+    }
+
+    testing::storage::drop_db(db).await;
+}
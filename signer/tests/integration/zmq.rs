@@ -1,8 +1,14 @@
+use std::time::Duration;
+
 use bitcoin::Block;
 use bitcoin::BlockHash;
+use bitcoincore_rpc::RpcApi as _;
 use futures::StreamExt;
 use sbtc::testing::regtest;
+use signer::bitcoin::rpc::BitcoinCoreClient;
 use signer::bitcoin::zmq::BitcoinCoreMessageStream;
+use signer::bitcoin::zmq::FailoverBlockHashStream;
+use url::Url;
 
 pub const BITCOIN_CORE_ZMQ_ENDPOINT: &str = "tcp://localhost:28332";
 
@@ -111,3 +117,43 @@ async fn block_hash_stream_streams_block_hashes() {
     assert_eq!(block_hashes.len(), 1);
     assert_eq!(block_hashes[0], item.unwrap());
 }
+
+/// This tests that the failover block hash stream skips over endpoints
+/// that it cannot connect to, sends the current chain tip once it has
+/// connected, and then streams new block hashes as they arrive.
+#[tokio::test]
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+async fn failover_block_hash_stream_skips_unavailable_endpoints() {
+    let (rpc, faucet) = regtest::initialize_blockchain();
+    let client = BitcoinCoreClient::new(
+        "http://localhost:18443",
+        regtest::BITCOIN_CORE_RPC_USERNAME.to_string(),
+        regtest::BITCOIN_CORE_RPC_PASSWORD.to_string(),
+    )
+    .unwrap();
+
+    // Nothing is listening on the first endpoint, so the stream needs to
+    // fail over to the second one.
+    let endpoints = vec![
+        Url::parse("tcp://localhost:28399").unwrap(),
+        Url::parse(BITCOIN_CORE_ZMQ_ENDPOINT).unwrap(),
+    ];
+    let mut stream = FailoverBlockHashStream::new(endpoints, client).unwrap();
+
+    // Once connected, the stream sends the current chain tip so that we
+    // catch up on any blocks that we may have missed.
+    let chain_tip = rpc.get_best_block_hash().unwrap();
+    let item = tokio::time::timeout(Duration::from_secs(30), stream.next())
+        .await
+        .unwrap();
+    assert_eq!(item.unwrap().unwrap(), chain_tip);
+
+    // New blocks come in through the ZeroMQ endpoint.
+    let block_hashes = faucet.generate_blocks(1);
+    let item = tokio::time::timeout(Duration::from_secs(10), stream.next())
+        .await
+        .unwrap();
+
+    assert_eq!(block_hashes.len(), 1);
+    assert_eq!(item.unwrap().unwrap(), block_hashes[0]);
+}